@@ -1,22 +1,29 @@
-use anyhow::Result;
 use aws_config::BehaviorVersion;
 use aws_sdk_elasticache::Client as ElastiCacheClient;
 use aws_sdk_s3::Client as S3Client;
+use chrono::NaiveDate;
 use std::time::Instant;
-use tracing::{info, info_span};
+use tracing::{Instrument, info, info_span, warn};
 
 use crate::cli::Args;
+use crate::error::StepError;
 use crate::export;
 use crate::retention;
 use crate::snapshot;
-use crate::types::StepTimings;
+use crate::types::{BackupOutcome, FailedStep, RegionCopy, SnapshotSource, Step, StepTimings};
 
-/// Run the complete backup workflow
+/// Run the complete backup workflow for one snapshot source.
+///
+/// `job_date` is the start date of the invocation that `--s3-prefix` is
+/// expanded with, shared by every source of a multi-cluster run. An error
+/// names the step the workflow stopped at.
 pub async fn run(
     args: &Args,
+    source: &SnapshotSource,
+    job_date: NaiveDate,
     step_timings: &mut StepTimings,
     snapshot_name_out: &mut Option<String>,
-) -> Result<(String, String, usize)> {
+) -> Result<BackupOutcome, StepError> {
     // Initialize AWS SDK
     let config = aws_config::defaults(BehaviorVersion::latest())
         .region(aws_config::Region::new(args.region.clone()))
@@ -25,11 +32,24 @@ pub async fn run(
 
     let elasticache_client = ElastiCacheClient::new(&config);
     let s3_client = S3Client::new(&config);
+    let copy_s3_client = match &args.copy_to_region {
+        Some(region) => {
+            let copy_config = aws_config::defaults(BehaviorVersion::latest())
+                .region(aws_config::Region::new(region.clone()))
+                .load()
+                .await;
+            Some(S3Client::new(&copy_config))
+        }
+        None => None,
+    };
 
     run_with_clients(
         &elasticache_client,
         &s3_client,
+        copy_s3_client.as_ref(),
         args,
+        source,
+        job_date,
         step_timings,
         snapshot_name_out,
     )
@@ -40,168 +60,453 @@ pub async fn run(
 ///
 /// This is the dependency-injected core of [`run`]; it contains the full
 /// orchestration logic minus AWS client construction so it can be driven with
-/// mock clients in tests.
+/// mock clients in tests. `copy_s3_client` targets `--copy-to-region` and is
+/// only set when that option is.
 pub(crate) async fn run_with_clients(
     elasticache_client: &ElastiCacheClient,
     s3_client: &S3Client,
+    copy_s3_client: Option<&S3Client>,
     args: &Args,
+    source: &SnapshotSource,
+    job_date: NaiveDate,
     step_timings: &mut StepTimings,
     snapshot_name_out: &mut Option<String>,
-) -> Result<(String, String, usize)> {
-    // Step 1: Create snapshot
-    let _span = info_span!("step_1_snapshot_creation").entered();
-    info!("Creating ElastiCache snapshot");
-    let step1_start = Instant::now();
-    let snapshot_name =
-        snapshot::create_snapshot(elasticache_client, &args.cache_cluster_id).await?;
+) -> Result<BackupOutcome, StepError> {
+    // Step spans are attached with `instrument` rather than entered, so the
+    // future stays `Send` and concurrent cluster backups keep their own spans.
+
+    // A retried run of the same day reuses today's snapshot and export
+    // instead of producing duplicates.
+    let snapshot_name = snapshot::daily_snapshot_name(source);
     *snapshot_name_out = Some(snapshot_name.clone());
-    step_timings.snapshot_creation = step1_start.elapsed().as_secs_f64();
-    info!(
-        duration_seconds = step_timings.snapshot_creation,
-        snapshot_name = %snapshot_name,
-        "Snapshot creation completed"
-    );
-    drop(_span);
-
-    // Step 2: Wait for snapshot completion
-    let _span = info_span!("step_2_snapshot_wait", snapshot_name = %snapshot_name).entered();
-    info!("Waiting for snapshot completion");
-    let step2_start = Instant::now();
-    snapshot::wait_for_completion(
-        elasticache_client,
-        &snapshot_name,
-        args.snapshot_timeout,
-        args.check_interval,
-    )
-    .await?;
-    step_timings.snapshot_wait = step2_start.elapsed().as_secs_f64();
-    info!(
-        duration_seconds = step_timings.snapshot_wait,
-        "Snapshot wait completed"
-    );
-    drop(_span);
-
-    // Step 3: Export to S3
-    let _span = info_span!("step_3_s3_export", snapshot_name = %snapshot_name).entered();
-    info!("Copying snapshot to S3");
-    let step3_start = Instant::now();
-    let (target_snapshot_name, s3_location) =
-        export::export_to_s3(elasticache_client, &snapshot_name, &args.s3_bucket_name).await?;
-    step_timings.s3_export = step3_start.elapsed().as_secs_f64();
-    info!(
-        duration_seconds = step_timings.s3_export,
-        target_snapshot_name = %target_snapshot_name,
-        s3_location = %s3_location,
-        "S3 export completed"
-    );
-    drop(_span);
-
-    // Step 4: Wait for export completion
-    let _span = info_span!("step_4_export_wait", snapshot_name = %snapshot_name).entered();
-    info!("Waiting for S3 export completion");
-    let step4_start = Instant::now();
-    export::wait_for_completion(
-        elasticache_client,
-        &snapshot_name,
-        args.export_timeout,
-        args.check_interval,
-    )
-    .await?;
-    step_timings.export_wait = step4_start.elapsed().as_secs_f64();
-    info!(
-        duration_seconds = step_timings.export_wait,
-        "Export wait completed"
-    );
-    drop(_span);
-
-    // Step 5: Cleanup
-    let _span = info_span!("step_5_cleanup", snapshot_name = %snapshot_name).entered();
-    info!("Cleaning up source snapshot");
-    let step5_start = Instant::now();
-    snapshot::cleanup(elasticache_client, &snapshot_name).await;
-    step_timings.cleanup = step5_start.elapsed().as_secs_f64();
-    info!(duration_seconds = step_timings.cleanup, "Cleanup completed");
-    drop(_span);
+    let expected_target = export::target_snapshot_name(&snapshot_name);
+    // Exported files end up under the expanded `--s3-prefix`
+    let key_prefix = args.s3_prefix.expand(source.id(), job_date);
+    let object_prefix = format!("{}{}", key_prefix, expected_target);
+    // Failures of these lookups are reported as part of snapshot creation
+    let already_exported = !args.force
+        && export::export_exists(s3_client, &args.s3_bucket_name, &object_prefix)
+            .await
+            .map_err(StepError::at(FailedStep::SnapshotCreation))?;
+    let existing_snapshot = snapshot::find_existing(elasticache_client, source, &snapshot_name)
+        .await
+        .map_err(StepError::at(FailedStep::SnapshotCreation))?;
+    let reusable_snapshot = existing_snapshot
+        .as_ref()
+        .filter(|s| matches!(s.snapshot_status(), Some("available" | "creating")));
+    let mut skipped_steps = Vec::new();
 
-    // Step 6: Retention cleanup
-    let _span = info_span!("step_6_retention_cleanup").entered();
-    let step6_start = Instant::now();
-    let deleted_count = if args.retention_count > 0 {
+    let (target_snapshot_name, s3_location, shards) = if already_exported {
         info!(
-            retention_count = args.retention_count,
-            "Starting retention cleanup"
+            target_snapshot_name = %expected_target,
+            object_prefix = %object_prefix,
+            "S3 export of today's snapshot already exists, skipping snapshot and export (use --force to export again)"
         );
-        match retention::cleanup_old_snapshots(
+        skipped_steps.extend([
+            Step::SnapshotCreation,
+            Step::SnapshotWait,
+            Step::S3Export,
+            Step::ExportWait,
+        ]);
+        let s3_location = match source {
+            SnapshotSource::CacheCluster(_) => {
+                format!("s3://{}/{}", args.s3_bucket_name, object_prefix)
+            }
+            SnapshotSource::ReplicationGroup(_) => {
+                format!("s3://{}/{}/", args.s3_bucket_name, object_prefix)
+            }
+        };
+        (expected_target, s3_location, Vec::new())
+    } else {
+        // Step 1: Create snapshot
+        async {
+            if let Some(existing) = reusable_snapshot {
+                info!(
+                    snapshot_name = %snapshot_name,
+                    snapshot_status = existing.snapshot_status().unwrap_or("Unknown"),
+                    "Reusing today's existing snapshot"
+                );
+                skipped_steps.push(Step::SnapshotCreation);
+                return anyhow::Ok(());
+            }
+            info!("Creating ElastiCache snapshot");
+            let step1_start = Instant::now();
+            snapshot::create_snapshot(elasticache_client, source, &snapshot_name).await?;
+            step_timings.snapshot_creation = step1_start.elapsed().as_secs_f64();
+            info!(
+                duration_seconds = step_timings.snapshot_creation,
+                snapshot_name = %snapshot_name,
+                "Snapshot creation completed"
+            );
+            Ok(())
+        }
+        .instrument(info_span!("step_1_snapshot_creation"))
+        .await
+        .map_err(StepError::at(FailedStep::SnapshotCreation))?;
+
+        // Step 2: Wait for snapshot completion
+        let source_snapshot = async {
+            if let Some(existing) =
+                reusable_snapshot.filter(|s| s.snapshot_status() == Some("available"))
+            {
+                info!("Reused snapshot is already available, skipping wait");
+                skipped_steps.push(Step::SnapshotWait);
+                return anyhow::Ok(existing.clone());
+            }
+            info!("Waiting for snapshot completion");
+            let step2_start = Instant::now();
+            let source_snapshot = snapshot::wait_for_completion(
+                elasticache_client,
+                &snapshot_name,
+                args.snapshot_wait_timeout(),
+                args.poll_interval(),
+            )
+            .await?;
+            step_timings.snapshot_wait = step2_start.elapsed().as_secs_f64();
+            info!(
+                duration_seconds = step_timings.snapshot_wait,
+                "Snapshot wait completed"
+            );
+            Ok(source_snapshot)
+        }
+        .instrument(info_span!("step_2_snapshot_wait", snapshot_name = %snapshot_name))
+        .await
+        .map_err(StepError::at(FailedStep::SnapshotWait))?;
+
+        // Step 3: Export to S3
+        let (target_snapshot_name, mut s3_location) = async {
+            info!("Copying snapshot to S3");
+            let step3_start = Instant::now();
+            let (target_snapshot_name, s3_location) =
+                export::export_to_s3(elasticache_client, &snapshot_name, &args.s3_bucket_name)
+                    .await?;
+            step_timings.s3_export = step3_start.elapsed().as_secs_f64();
+            info!(
+                duration_seconds = step_timings.s3_export,
+                target_snapshot_name = %target_snapshot_name,
+                s3_location = %s3_location,
+                "S3 export completed"
+            );
+            anyhow::Ok((target_snapshot_name, s3_location))
+        }
+        .instrument(info_span!("step_3_s3_export", snapshot_name = %snapshot_name))
+        .await
+        .map_err(StepError::at(FailedStep::S3Export))?;
+
+        // Step 4: Wait for export completion
+        let shards = async {
+            info!("Waiting for S3 export completion");
+            let step4_start = Instant::now();
+            export::wait_for_completion(
+                elasticache_client,
+                &snapshot_name,
+                args.export_wait_timeout(),
+                args.poll_interval(),
+            )
+            .await?;
+            if !key_prefix.is_empty() {
+                export::move_under_prefix(
+                    s3_client,
+                    &args.s3_bucket_name,
+                    &target_snapshot_name,
+                    &key_prefix,
+                )
+                .await?;
+                s3_location = format!("s3://{}/{}", args.s3_bucket_name, object_prefix);
+            }
+            let shards = if let SnapshotSource::ReplicationGroup(_) = source {
+                let node_group_ids: Vec<String> = source_snapshot
+                    .node_snapshots()
+                    .iter()
+                    .filter_map(|node| node.node_group_id())
+                    .map(str::to_string)
+                    .collect();
+                let shards = export::split_shards(
+                    s3_client,
+                    &args.s3_bucket_name,
+                    &object_prefix,
+                    &node_group_ids,
+                )
+                .await?;
+                s3_location = format!("s3://{}/{}/", args.s3_bucket_name, object_prefix);
+                shards
+            } else {
+                Vec::new()
+            };
+            if !args.s3_tags.is_empty() || args.kms_key_id.is_some() {
+                export::apply_object_settings(
+                    s3_client,
+                    &args.s3_bucket_name,
+                    &object_prefix,
+                    &args.s3_tags,
+                    args.kms_key_id.as_deref(),
+                )
+                .await?;
+            }
+            step_timings.export_wait = step4_start.elapsed().as_secs_f64();
+            info!(
+                duration_seconds = step_timings.export_wait,
+                shard_count = shards.len(),
+                "Export wait completed"
+            );
+            anyhow::Ok(shards)
+        }
+        .instrument(info_span!("step_4_export_wait", snapshot_name = %snapshot_name))
+        .await
+        .map_err(StepError::at(FailedStep::ExportWait))?;
+
+        (target_snapshot_name, s3_location, shards)
+    };
+
+    // Verify the exported objects, including an export found from an
+    // earlier run, before the source snapshot is removed
+    let verified_objects = async {
+        info!("Verifying exported S3 objects");
+        let bucket_prefix = format!("s3://{}/", args.s3_bucket_name);
+        let shard_keys: Vec<String> = shards
+            .iter()
+            .filter_map(|shard| shard.s3_location.strip_prefix(&bucket_prefix))
+            .map(str::to_string)
+            .collect();
+        export::verify_export(
             s3_client,
             &args.s3_bucket_name,
-            &args.cache_cluster_id,
-            args.retention_count,
+            &object_prefix,
+            &shard_keys,
+            args.min_export_bytes,
         )
         .await
-        {
-            Ok(count) => {
-                step_timings.retention = step6_start.elapsed().as_secs_f64();
-                info!(
-                    deleted_count = count,
-                    duration_seconds = step_timings.retention,
-                    "Retention cleanup completed"
-                );
-                count
+    }
+    .instrument(info_span!("export_verify", object_prefix = %object_prefix))
+    .await
+    .map_err(StepError::at(FailedStep::ExportVerify))?;
+
+    // Off-region copy of the verified files for disaster recovery. ElastiCache
+    // cannot copy snapshots across regions, so the exported files are copied.
+    let region_copy = match (&args.copy_to_region, &args.copy_bucket_name, copy_s3_client) {
+        (Some(region), Some(copy_bucket_name), Some(copy_s3_client)) => Some(
+            async {
+                info!("Copying exported S3 objects to the copy region");
+                let copy_start = Instant::now();
+                let bucket_prefix = format!("s3://{}/", args.s3_bucket_name);
+                let keys: Vec<String> = verified_objects
+                    .iter()
+                    .filter_map(|object| object.s3_location.strip_prefix(&bucket_prefix))
+                    .map(str::to_string)
+                    .collect();
+                let result = export::copy_to_bucket(
+                    copy_s3_client,
+                    &args.s3_bucket_name,
+                    &keys,
+                    copy_bucket_name,
+                    args.copy_kms_key_id.as_deref(),
+                )
+                .await;
+                step_timings.region_copy = copy_start.elapsed().as_secs_f64();
+
+                match result {
+                    Ok(s3_locations) => {
+                        info!(
+                            duration_seconds = step_timings.region_copy,
+                            object_count = s3_locations.len(),
+                            "Copy to the copy region completed"
+                        );
+                        Ok(RegionCopy {
+                            region: region.clone(),
+                            status: "Success".to_string(),
+                            s3_locations,
+                            error: None,
+                        })
+                    }
+                    Err(e) if args.copy_required => Err(e),
+                    Err(e) => {
+                        warn!(
+                            error = %format!("{e:#}"),
+                            duration_seconds = step_timings.region_copy,
+                            "Copy to the copy region failed, continuing (use --copy-required to fail the run)"
+                        );
+                        Ok(RegionCopy {
+                            region: region.clone(),
+                            status: "Failed".to_string(),
+                            s3_locations: Vec::new(),
+                            error: Some(format!("{e:#}")),
+                        })
+                    }
+                }
             }
-            Err(e) => {
-                step_timings.retention = step6_start.elapsed().as_secs_f64();
-                info!(
-                    error = %e,
-                    duration_seconds = step_timings.retention,
-                    "Retention cleanup failed, continuing"
-                );
-                0
+            .instrument(info_span!("region_copy", copy_to_region = %region))
+            .await
+            .map_err(StepError::at(FailedStep::RegionCopy))?,
+        ),
+        _ => None,
+    };
+
+    // Step 5: Cleanup (nothing to do when the export was found and no
+    // snapshot of today is left over)
+    if !already_exported || existing_snapshot.is_some() {
+        async {
+            info!("Cleaning up source snapshot");
+            let step5_start = Instant::now();
+            snapshot::cleanup(elasticache_client, &snapshot_name).await;
+            step_timings.cleanup = step5_start.elapsed().as_secs_f64();
+            info!(duration_seconds = step_timings.cleanup, "Cleanup completed");
+        }
+        .instrument(info_span!("step_5_cleanup", snapshot_name = %snapshot_name))
+        .await;
+    }
+
+    // Step 6: Retention cleanup
+    let deleted_count = async {
+        let step6_start = Instant::now();
+        if args.retention_count > 0 {
+            info!(
+                retention_count = args.retention_count,
+                "Starting retention cleanup"
+            );
+            match retention::cleanup_old_snapshots(
+                s3_client,
+                &args.s3_bucket_name,
+                source.id(),
+                &args.s3_prefix,
+                args.retention_count,
+            )
+            .await
+            {
+                Ok(count) => {
+                    step_timings.retention = step6_start.elapsed().as_secs_f64();
+                    info!(
+                        deleted_count = count,
+                        duration_seconds = step_timings.retention,
+                        "Retention cleanup completed"
+                    );
+                    count
+                }
+                Err(e) => {
+                    step_timings.retention = step6_start.elapsed().as_secs_f64();
+                    info!(
+                        error = %e,
+                        duration_seconds = step_timings.retention,
+                        "Retention cleanup failed, continuing"
+                    );
+                    0
+                }
             }
+        } else {
+            info!(
+                retention_count = 0,
+                "Retention cleanup disabled, unlimited retention"
+            );
+            0
         }
-    } else {
-        info!(
-            retention_count = 0,
-            "Retention cleanup disabled, unlimited retention"
-        );
-        0
-    };
-    drop(_span);
+    }
+    .instrument(info_span!("step_6_retention_cleanup"))
+    .await;
 
-    Ok((target_snapshot_name, s3_location, deleted_count))
+    Ok(BackupOutcome {
+        target_snapshot_name,
+        s3_location,
+        shards,
+        verified_objects,
+        deleted_count,
+        skipped_steps,
+        region_copy,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metrics::MetricsFormat;
+    use crate::prefix::{DEFAULT_S3_PREFIX, S3Prefix};
     use aws_sdk_elasticache::Client as EcClient;
-    use aws_sdk_elasticache::operation::copy_snapshot::CopySnapshotOutput;
-    use aws_sdk_elasticache::operation::create_snapshot::CreateSnapshotOutput;
+    use aws_sdk_elasticache::operation::copy_snapshot::{CopySnapshotError, CopySnapshotOutput};
+    use aws_sdk_elasticache::operation::create_snapshot::{
+        CreateSnapshotError, CreateSnapshotOutput,
+    };
     use aws_sdk_elasticache::operation::delete_snapshot::DeleteSnapshotOutput;
     use aws_sdk_elasticache::operation::describe_snapshots::DescribeSnapshotsOutput;
-    use aws_sdk_elasticache::types::Snapshot;
+    use aws_sdk_elasticache::types::error::{
+        InvalidSnapshotStateFault, SnapshotQuotaExceededFault,
+    };
+    use aws_sdk_elasticache::types::{NodeSnapshot, Snapshot};
     use aws_sdk_s3::Client as S3MockClient;
+    use aws_sdk_s3::operation::copy_object::{CopyObjectError, CopyObjectOutput};
     use aws_sdk_s3::operation::delete_object::DeleteObjectOutput;
+    use aws_sdk_s3::operation::head_object::HeadObjectOutput;
     use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Output;
     use aws_sdk_s3::types::Object;
-    use aws_smithy_mocks::{RuleMode, mock, mock_client};
+    use aws_sdk_s3::types::error::ObjectNotInActiveTierError;
+    use aws_smithy_mocks::{Rule, RuleMode, mock, mock_client};
     use aws_smithy_types::DateTime;
 
     fn test_args(retention_count: u32) -> Args {
         Args {
-            cache_cluster_id: "cluster".to_string(),
+            cache_cluster_id: vec!["cluster".to_string()],
+            replication_group_id: None,
             s3_bucket_name: "bucket".to_string(),
+            s3_prefix: S3Prefix::parse("").unwrap(),
             region: "ap-northeast-2".to_string(),
-            snapshot_timeout: 30,
-            export_timeout: 30,
-            check_interval: 1,
+            snapshot_wait_timeout_minutes: 1,
+            export_wait_timeout_minutes: 1,
+            poll_interval_seconds: 1,
+            min_export_bytes: 1,
             retention_count,
+            s3_tags: Vec::new(),
+            kms_key_id: None,
+            emit_emf: false,
+            emit_metrics: false,
+            metrics_format: MetricsFormat::Api,
+            metrics_namespace: "ElastiCacheBackup".to_string(),
+            parallel: 1,
+            fail_fast: false,
+            force: false,
+            copy_to_region: None,
+            copy_bucket_name: None,
+            copy_kms_key_id: None,
+            copy_required: false,
         }
     }
 
+    fn job_date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
     fn available() -> Snapshot {
         Snapshot::builder().snapshot_status("available").build()
     }
 
+    /// Idempotency probe of `export_exists` finding nothing; listed first so
+    /// it wins over the client's other `list_objects_v2` rules.
+    fn no_existing_export() -> Rule {
+        mock!(S3MockClient::list_objects_v2)
+            .match_requests(|req| req.max_keys() == Some(1))
+            .then_output(|| ListObjectsV2Output::builder().build())
+    }
+
+    /// Listing of the finished export for verification: one RDB file under
+    /// the target snapshot name
+    fn exported_object() -> Rule {
+        mock!(S3MockClient::list_objects_v2)
+            .match_requests(|req| {
+                req.max_keys().is_none() && req.prefix().is_some_and(|p| p.ends_with("-s3-export"))
+            })
+            .then_compute_output(|req| {
+                let target = req.prefix().unwrap_or_default();
+                ListObjectsV2Output::builder()
+                    .contents(Object::builder().key(format!("{target}-0001.rdb")).build())
+                    .build()
+            })
+    }
+
+    fn head_exported_object() -> Rule {
+        mock!(S3MockClient::head_object).then_output(|| {
+            HeadObjectOutput::builder()
+                .content_length(10)
+                .last_modified(DateTime::from_secs(100))
+                .build()
+        })
+    }
+
     #[tokio::test]
     async fn test_run_with_clients_happy_path_with_retention() {
         let create = mock!(EcClient::create_snapshot).then_output(|| {
@@ -245,18 +550,39 @@ mod tests {
         });
         let delete_obj = mock!(S3MockClient::delete_object)
             .then_output(|| DeleteObjectOutput::builder().build());
-        let s3_client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &delete_obj]);
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &exported_object(),
+                &list,
+                &delete_obj,
+                &head_exported_object()
+            ]
+        );
 
         let args = test_args(1);
         let mut timings = StepTimings::default();
         let mut name = None;
-        let (target, location, deleted) =
-            run_with_clients(&ec_client, &s3_client, &args, &mut timings, &mut name)
-                .await
-                .unwrap();
-        assert!(target.ends_with("-s3-export"));
-        assert!(location.starts_with("s3://bucket/"));
-        assert_eq!(deleted, 1);
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        assert!(outcome.target_snapshot_name.ends_with("-s3-export"));
+        assert!(outcome.s3_location.starts_with("s3://bucket/"));
+        assert!(outcome.shards.is_empty());
+        assert_eq!(outcome.verified_objects.len(), 1);
+        assert_eq!(outcome.verified_objects[0].size_bytes, 10);
+        assert_eq!(outcome.deleted_count, 1);
         assert!(name.is_some());
     }
 
@@ -281,17 +607,34 @@ mod tests {
             RuleMode::MatchAny,
             &[&create, &describe, &copy, &delete_snap]
         );
-        // retention_count = 0 -> S3 client never used.
-        let s3_client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[]);
+        // retention_count = 0 -> S3 is only used to check for an existing
+        // export and to verify the new one.
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &exported_object(),
+                &head_exported_object()
+            ]
+        );
 
         let args = test_args(0);
         let mut timings = StepTimings::default();
         let mut name = None;
-        let (_, _, deleted) =
-            run_with_clients(&ec_client, &s3_client, &args, &mut timings, &mut name)
-                .await
-                .unwrap();
-        assert_eq!(deleted, 0);
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.deleted_count, 0);
     }
 
     #[tokio::test]
@@ -320,15 +663,519 @@ mod tests {
             .sequence()
             .http_status(500, None)
             .build();
-        let s3_client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list]);
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &exported_object(),
+                &list,
+                &head_exported_object()
+            ]
+        );
 
         let args = test_args(2);
         let mut timings = StepTimings::default();
         let mut name = None;
-        let (_, _, deleted) =
-            run_with_clients(&ec_client, &s3_client, &args, &mut timings, &mut name)
-                .await
-                .unwrap();
-        assert_eq!(deleted, 0);
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome.deleted_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_clients_replication_group_shards() {
+        let create = mock!(EcClient::create_snapshot)
+            .match_requests(|req| req.replication_group_id() == Some("rg"))
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let describe = mock!(EcClient::describe_snapshots).then_output(|| {
+            DescribeSnapshotsOutput::builder()
+                .snapshots(
+                    Snapshot::builder()
+                        .snapshot_status("available")
+                        .node_snapshots(NodeSnapshot::builder().node_group_id("0001").build())
+                        .node_snapshots(NodeSnapshot::builder().node_group_id("0002").build())
+                        .build(),
+                )
+                .build()
+        });
+        let copy = mock!(EcClient::copy_snapshot)
+            .then_output(|| CopySnapshotOutput::builder().snapshot(available()).build());
+        let delete_snap = mock!(EcClient::delete_snapshot)
+            .then_output(|| DeleteSnapshotOutput::builder().build());
+        let ec_client = mock_client!(
+            aws_sdk_elasticache,
+            RuleMode::MatchAny,
+            &[&create, &describe, &copy, &delete_snap]
+        );
+
+        // Every shard file sits next to the others under the export target
+        let list = mock!(S3MockClient::list_objects_v2).then_compute_output(|req| {
+            let target = req.prefix().unwrap_or_default();
+            ListObjectsV2Output::builder()
+                .contents(Object::builder().key(format!("{target}-0001.rdb")).build())
+                .contents(Object::builder().key(format!("{target}-0002.rdb")).build())
+                .build()
+        });
+        let copy_obj =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let delete_obj = mock!(S3MockClient::delete_object)
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &list,
+                &copy_obj,
+                &delete_obj,
+                &head_exported_object()
+            ]
+        );
+
+        let mut args = test_args(0);
+        args.cache_cluster_id.clear();
+        args.replication_group_id = Some("rg".to_string());
+        let mut timings = StepTimings::default();
+        let mut name = None;
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        let target = &outcome.target_snapshot_name;
+        assert!(target.starts_with("rg-"));
+        assert_eq!(outcome.s3_location, format!("s3://bucket/{target}/"));
+        let locations: Vec<&str> = outcome
+            .shards
+            .iter()
+            .map(|shard| shard.s3_location.as_str())
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                format!("s3://bucket/{target}/0001/{target}-0001.rdb"),
+                format!("s3://bucket/{target}/0002/{target}-0002.rdb"),
+            ]
+        );
+        // Each shard file is checked at its final location
+        let verified: Vec<&str> = outcome
+            .verified_objects
+            .iter()
+            .map(|object| object.s3_location.as_str())
+            .collect();
+        assert_eq!(verified, locations);
+        assert_eq!(copy_obj.num_calls(), 2);
+        assert_eq!(delete_obj.num_calls(), 2);
+        assert_eq!(create.num_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_clients_moves_export_under_dated_prefix() {
+        let create = mock!(EcClient::create_snapshot)
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let describe = mock!(EcClient::describe_snapshots).then_output(|| {
+            DescribeSnapshotsOutput::builder()
+                .snapshots(available())
+                .build()
+        });
+        let copy = mock!(EcClient::copy_snapshot)
+            .then_output(|| CopySnapshotOutput::builder().snapshot(available()).build());
+        let delete_snap = mock!(EcClient::delete_snapshot)
+            .then_output(|| DeleteSnapshotOutput::builder().build());
+        let ec_client = mock_client!(
+            aws_sdk_elasticache,
+            RuleMode::MatchAny,
+            &[&create, &describe, &copy, &delete_snap]
+        );
+
+        let copy_obj = mock!(S3MockClient::copy_object)
+            .match_requests(|req| {
+                req.key()
+                    .is_some_and(|k| k.starts_with("elasticache/cluster/2025/01/cluster-"))
+            })
+            .then_output(|| CopyObjectOutput::builder().build());
+        let delete_obj = mock!(S3MockClient::delete_object)
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &exported_object(),
+                &copy_obj,
+                &delete_obj,
+                &head_exported_object()
+            ]
+        );
+
+        let mut args = test_args(0);
+        args.s3_prefix = S3Prefix::parse(DEFAULT_S3_PREFIX).unwrap();
+        let mut timings = StepTimings::default();
+        let mut name = None;
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        let target = &outcome.target_snapshot_name;
+        assert_eq!(
+            outcome.s3_location,
+            format!("s3://bucket/elasticache/cluster/2025/01/{target}")
+        );
+        assert_eq!(copy_obj.num_calls(), 1);
+        assert_eq!(delete_obj.num_calls(), 1);
+        // The moved file is verified under the dated prefix
+        assert_eq!(
+            outcome.verified_objects[0].s3_location,
+            format!("s3://bucket/elasticache/cluster/2025/01/{target}-0001.rdb")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_with_clients_copies_export_to_another_region() {
+        let describe = mock!(EcClient::describe_snapshots).then_output(|| {
+            DescribeSnapshotsOutput::builder()
+                .snapshots(available())
+                .build()
+        });
+        let create = mock!(EcClient::create_snapshot)
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let copy = mock!(EcClient::copy_snapshot)
+            .then_output(|| CopySnapshotOutput::builder().snapshot(available()).build());
+        let delete_snap = mock!(EcClient::delete_snapshot)
+            .then_output(|| DeleteSnapshotOutput::builder().build());
+        let ec_client = mock_client!(
+            aws_sdk_elasticache,
+            RuleMode::MatchAny,
+            &[&create, &describe, &copy, &delete_snap]
+        );
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &exported_object(),
+                &head_exported_object()
+            ]
+        );
+        let copy_ok = mock!(S3MockClient::copy_object)
+            .match_requests(|req| req.bucket() == Some("dr-bucket"))
+            .then_output(|| CopyObjectOutput::builder().build());
+        let copy_client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&copy_ok]);
+        let copy_denied = mock!(S3MockClient::copy_object).then_error(|| {
+            CopyObjectError::ObjectNotInActiveTierError(
+                ObjectNotInActiveTierError::builder().build(),
+            )
+        });
+        let failing_copy_client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&copy_denied]);
+
+        let mut args = test_args(0);
+        args.copy_to_region = Some("us-west-2".to_string());
+        args.copy_bucket_name = Some("dr-bucket".to_string());
+        let run = async |copy_client: &S3MockClient, args: &Args| {
+            let mut timings = StepTimings::default();
+            let mut name = None;
+            run_with_clients(
+                &ec_client,
+                &s3_client,
+                Some(copy_client),
+                args,
+                &args.sources()[0],
+                job_date(),
+                &mut timings,
+                &mut name,
+            )
+            .await
+        };
+
+        let region_copy = run(&copy_client, &args).await.unwrap().region_copy.unwrap();
+        assert_eq!(region_copy.status, "Success");
+        assert_eq!(region_copy.region, "us-west-2");
+        assert_eq!(region_copy.s3_locations.len(), 1);
+        assert!(region_copy.s3_locations[0].starts_with("s3://dr-bucket/cluster-"));
+
+        // A failed copy is reported while the backup itself succeeds...
+        let region_copy = run(&failing_copy_client, &args)
+            .await
+            .unwrap()
+            .region_copy
+            .unwrap();
+        assert_eq!(region_copy.status, "Failed");
+        assert!(region_copy.error.is_some());
+
+        // ...unless the copy is required
+        args.copy_required = true;
+        let error = run(&failing_copy_client, &args).await.unwrap_err();
+        assert_eq!(error.step, FailedStep::RegionCopy);
+    }
+
+    /// Run a backup whose clients try `ec_rules` and `s3_rules` before the
+    /// rules of a successful run, and return the step it failed in
+    async fn failed_step(ec_rules: &[&Rule], s3_rules: &[&Rule]) -> FailedStep {
+        let create = mock!(EcClient::create_snapshot)
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let describe = mock!(EcClient::describe_snapshots).then_output(|| {
+            DescribeSnapshotsOutput::builder()
+                .snapshots(available())
+                .build()
+        });
+        let copy = mock!(EcClient::copy_snapshot)
+            .then_output(|| CopySnapshotOutput::builder().snapshot(available()).build());
+        let delete_snap = mock!(EcClient::delete_snapshot)
+            .then_output(|| DeleteSnapshotOutput::builder().build());
+        let ec_rules: Vec<&Rule> = ec_rules
+            .iter()
+            .copied()
+            .chain([&create, &describe, &copy, &delete_snap])
+            .collect();
+        let ec_client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &ec_rules);
+
+        let (no_export, exported, head) = (
+            no_existing_export(),
+            exported_object(),
+            head_exported_object(),
+        );
+        let s3_rules: Vec<&Rule> = s3_rules
+            .iter()
+            .copied()
+            .chain([&no_export, &exported, &head])
+            .collect();
+        let s3_client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &s3_rules);
+
+        let args = test_args(0);
+        let mut timings = StepTimings::default();
+        let mut name = None;
+        run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .expect_err("backup should fail")
+        .step
+    }
+
+    #[tokio::test]
+    async fn test_run_with_clients_reports_failed_step() {
+        let create_fails = mock!(EcClient::create_snapshot).then_error(|| {
+            CreateSnapshotError::SnapshotQuotaExceededFault(
+                SnapshotQuotaExceededFault::builder().build(),
+            )
+        });
+        assert_eq!(
+            failed_step(&[&create_fails], &[]).await,
+            FailedStep::SnapshotCreation
+        );
+
+        // The wait describes the snapshot by name, the lookup before creation
+        // by cluster
+        let snapshot_fails = mock!(EcClient::describe_snapshots)
+            .match_requests(|req| req.snapshot_name().is_some())
+            .then_output(|| {
+                DescribeSnapshotsOutput::builder()
+                    .snapshots(Snapshot::builder().snapshot_status("failed").build())
+                    .build()
+            });
+        assert_eq!(
+            failed_step(&[&snapshot_fails], &[]).await,
+            FailedStep::SnapshotWait
+        );
+
+        let copy_fails = mock!(EcClient::copy_snapshot).then_error(|| {
+            CopySnapshotError::InvalidSnapshotStateFault(
+                InvalidSnapshotStateFault::builder().build(),
+            )
+        });
+        assert_eq!(failed_step(&[&copy_fails], &[]).await, FailedStep::S3Export);
+
+        // Today's snapshot is reused, so only the export wait describes it
+        // by name
+        let reused = mock!(EcClient::describe_snapshots)
+            .match_requests(|req| req.cache_cluster_id().is_some())
+            .then_compute_output(|req| {
+                let name = snapshot::daily_snapshot_name(&SnapshotSource::CacheCluster(
+                    req.cache_cluster_id().unwrap_or_default().to_string(),
+                ));
+                DescribeSnapshotsOutput::builder()
+                    .snapshots(
+                        Snapshot::builder()
+                            .snapshot_name(name)
+                            .snapshot_status("available")
+                            .build(),
+                    )
+                    .build()
+            });
+        assert_eq!(
+            failed_step(&[&reused, &snapshot_fails], &[]).await,
+            FailedStep::ExportWait
+        );
+
+        let empty_export = mock!(S3MockClient::head_object)
+            .then_output(|| HeadObjectOutput::builder().content_length(0).build());
+        assert_eq!(
+            failed_step(&[], &[&empty_export]).await,
+            FailedStep::ExportVerify
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_with_clients_reuses_available_snapshot() {
+        // A retried run finds today's snapshot already available
+        let create = mock!(EcClient::create_snapshot)
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let describe = mock!(EcClient::describe_snapshots).then_compute_output(|req| {
+            let name = snapshot::daily_snapshot_name(&SnapshotSource::CacheCluster(
+                req.cache_cluster_id().unwrap_or_default().to_string(),
+            ));
+            DescribeSnapshotsOutput::builder()
+                .snapshots(
+                    Snapshot::builder()
+                        .snapshot_name(name)
+                        .snapshot_status("available")
+                        .build(),
+                )
+                .build()
+        });
+        let copy = mock!(EcClient::copy_snapshot)
+            .then_output(|| CopySnapshotOutput::builder().snapshot(available()).build());
+        let delete_snap = mock!(EcClient::delete_snapshot)
+            .then_output(|| DeleteSnapshotOutput::builder().build());
+        let ec_client = mock_client!(
+            aws_sdk_elasticache,
+            RuleMode::MatchAny,
+            &[&create, &describe, &copy, &delete_snap]
+        );
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[
+                &no_existing_export(),
+                &exported_object(),
+                &head_exported_object()
+            ]
+        );
+
+        let args = test_args(0);
+        let mut timings = StepTimings::default();
+        let mut name = None;
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        assert_eq!(create.num_calls(), 0);
+        assert_eq!(copy.num_calls(), 1);
+        assert_eq!(
+            outcome.skipped_steps,
+            vec![Step::SnapshotCreation, Step::SnapshotWait]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_with_clients_skips_existing_export_unless_forced() {
+        let create = mock!(EcClient::create_snapshot)
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let describe = mock!(EcClient::describe_snapshots).then_output(|| {
+            DescribeSnapshotsOutput::builder()
+                .snapshots(available())
+                .build()
+        });
+        let copy = mock!(EcClient::copy_snapshot)
+            .then_output(|| CopySnapshotOutput::builder().snapshot(available()).build());
+        let delete_snap = mock!(EcClient::delete_snapshot)
+            .then_output(|| DeleteSnapshotOutput::builder().build());
+        let ec_client = mock_client!(
+            aws_sdk_elasticache,
+            RuleMode::MatchAny,
+            &[&create, &describe, &copy, &delete_snap]
+        );
+        let existing_export = mock!(S3MockClient::list_objects_v2).then_compute_output(|req| {
+            ListObjectsV2Output::builder()
+                .contents(
+                    Object::builder()
+                        .key(req.prefix().unwrap_or_default())
+                        .build(),
+                )
+                .build()
+        });
+        let s3_client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[&existing_export, &head_exported_object()]
+        );
+
+        let mut args = test_args(0);
+        let mut timings = StepTimings::default();
+        let mut name = None;
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        assert_eq!(create.num_calls(), 0);
+        assert_eq!(copy.num_calls(), 0);
+        assert_eq!(outcome.skipped_steps.len(), 4);
+        assert!(outcome.s3_location.ends_with("-s3-export"));
+
+        // --force exports again despite the existing object
+        args.force = true;
+        let outcome = run_with_clients(
+            &ec_client,
+            &s3_client,
+            None,
+            &args,
+            &args.sources()[0],
+            job_date(),
+            &mut timings,
+            &mut name,
+        )
+        .await
+        .unwrap();
+        assert_eq!(create.num_calls(), 1);
+        assert_eq!(copy.num_calls(), 1);
+        assert!(outcome.skipped_steps.is_empty());
     }
 }
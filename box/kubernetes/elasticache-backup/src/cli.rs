@@ -1,34 +1,430 @@
-use clap::Parser;
+use std::time::Duration;
+
+use clap::{ArgGroup, Parser};
+
+use crate::error::BackupError;
+use crate::metrics::MetricsFormat;
+use crate::prefix::{DEFAULT_S3_PREFIX, S3Prefix};
+use crate::types::{S3Tag, SnapshotSource};
 
 /// ElastiCache snapshot backup to S3 automation
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
+#[command(group(
+    ArgGroup::new("source")
+        .required(true)
+        .args(["cache_cluster_id", "replication_group_id"])
+))]
 pub struct Args {
-    /// ElastiCache cluster ID (read replica node)
-    #[arg(long, env = "CACHE_CLUSTER_ID")]
-    pub cache_cluster_id: String,
+    /// ElastiCache cluster ID (read replica node); repeat the flag or pass a
+    /// comma-separated list to back up several clusters in one run
+    #[arg(long, env = "CACHE_CLUSTER_ID", value_delimiter = ',')]
+    pub cache_cluster_id: Vec<String>,
+
+    /// ElastiCache replication group ID; each shard is exported separately
+    /// in cluster mode enabled groups
+    #[arg(long, env = "REPLICATION_GROUP_ID")]
+    pub replication_group_id: Option<String>,
 
     /// S3 bucket name for storing RDB files
     #[arg(long, env = "S3_BUCKET_NAME")]
     pub s3_bucket_name: String,
 
+    /// Key prefix of the exported RDB files; `{cluster}`, `{yyyy}`, `{mm}`,
+    /// and `{dd}` are replaced with the source ID and the job's start date
+    /// (`TZ_OFFSET_HOURS`). An empty value writes to the bucket root.
+    #[arg(long, env = "S3_PREFIX", default_value = DEFAULT_S3_PREFIX, value_parser = S3Prefix::parse)]
+    pub s3_prefix: S3Prefix,
+
     /// AWS region
     #[arg(long, env = "AWS_REGION", default_value = "ap-northeast-2")]
     pub region: String,
 
-    /// Maximum wait time for snapshot completion in seconds
-    #[arg(long, default_value = "1800")]
-    pub snapshot_timeout: u64,
+    /// Maximum wait for the snapshot to become available, in minutes
+    #[arg(
+        long,
+        env = "SNAPSHOT_WAIT_TIMEOUT_MINUTES",
+        default_value = "30",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub snapshot_wait_timeout_minutes: u64,
 
-    /// Maximum wait time for S3 export completion in seconds
-    #[arg(long, default_value = "300")]
-    pub export_timeout: u64,
+    /// Maximum wait for the S3 export to complete, in minutes
+    #[arg(
+        long,
+        env = "EXPORT_WAIT_TIMEOUT_MINUTES",
+        default_value = "5",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub export_wait_timeout_minutes: u64,
 
-    /// Snapshot status check interval in seconds
-    #[arg(long, default_value = "30")]
-    pub check_interval: u64,
+    /// Interval between status checks while waiting, in seconds
+    #[arg(
+        long,
+        env = "POLL_INTERVAL_SECONDS",
+        default_value = "30",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub poll_interval_seconds: u64,
+
+    /// Smallest accepted size of each exported RDB file in bytes; a smaller
+    /// or missing object fails the run
+    #[arg(long, env = "MIN_EXPORT_BYTES", default_value = "1")]
+    pub min_export_bytes: u64,
 
     /// Number of snapshots to retain in S3 (0 = unlimited)
     #[arg(long, env = "RETENTION_COUNT", default_value = "0")]
     pub retention_count: u32,
+
+    /// Tags applied to the exported S3 objects (Key=Value,...)
+    #[arg(long, env = "S3_TAGS", value_delimiter = ',', value_parser = parse_s3_tag)]
+    pub s3_tags: Vec<S3Tag>,
+
+    /// KMS key ID or ARN used to encrypt the exported S3 objects (SSE-KMS)
+    #[arg(long, env = "KMS_KEY_ID")]
+    pub kms_key_id: Option<String>,
+
+    /// Region whose `--copy-bucket-name` bucket receives a copy of every
+    /// exported RDB file for disaster recovery
+    #[arg(long, env = "COPY_TO_REGION", requires = "copy_bucket_name")]
+    pub copy_to_region: Option<String>,
+
+    /// Bucket in `--copy-to-region` that the exported RDB files are copied to
+    #[arg(long, env = "COPY_BUCKET_NAME", requires = "copy_to_region")]
+    pub copy_bucket_name: Option<String>,
+
+    /// KMS key ID or ARN in `--copy-to-region` used to encrypt the copies
+    /// (SSE-KMS)
+    #[arg(long, env = "COPY_KMS_KEY_ID", requires = "copy_to_region")]
+    pub copy_kms_key_id: Option<String>,
+
+    /// Fail the run when the copy to `--copy-to-region` fails; otherwise the
+    /// failure is only reported in the summary
+    #[arg(long, env = "COPY_REQUIRED", requires = "copy_to_region")]
+    pub copy_required: bool,
+
+    /// Write a CloudWatch Embedded Metric Format line with the run's timings
+    #[arg(long, env = "EMIT_EMF")]
+    pub emit_emf: bool,
+
+    /// Publish BackupSuccess and duration metrics for each backup; failing
+    /// to publish them only logs a warning
+    #[arg(long, env = "EMIT_METRICS")]
+    pub emit_metrics: bool,
+
+    /// How `--emit-metrics` reports: `api` calls PutMetricData, `emf` writes
+    /// Embedded Metric Format lines to stderr (Lambda)
+    #[arg(long, env = "METRICS_FORMAT", value_enum, default_value = "api")]
+    pub metrics_format: MetricsFormat,
+
+    /// CloudWatch namespace of the `--emit-metrics` metrics
+    #[arg(long, env = "METRICS_NAMESPACE", default_value = "ElastiCacheBackup")]
+    pub metrics_namespace: String,
+
+    /// Number of cache clusters backed up concurrently
+    #[arg(
+        long,
+        env = "PARALLEL",
+        default_value = "1",
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub parallel: u16,
+
+    /// Stop starting new cluster backups after the first failure
+    #[arg(long, env = "FAIL_FAST")]
+    pub fail_fast: bool,
+
+    /// Export again even when today's export already exists in S3
+    #[arg(long, env = "FORCE")]
+    pub force: bool,
+}
+
+impl Args {
+    /// The clusters or the replication group to snapshot, in the given order.
+    ///
+    /// The `source` argument group guarantees that either cache cluster IDs or
+    /// a replication group ID is set. Blank and repeated cluster IDs are
+    /// dropped.
+    pub fn sources(&self) -> Vec<SnapshotSource> {
+        if let Some(id) = &self.replication_group_id {
+            return vec![SnapshotSource::ReplicationGroup(id.clone())];
+        }
+
+        let mut sources: Vec<SnapshotSource> = Vec::new();
+        for id in self.cache_cluster_id.iter().map(|id| id.trim()) {
+            let source = SnapshotSource::CacheCluster(id.to_string());
+            if !id.is_empty() && !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        sources
+    }
+
+    pub fn snapshot_wait_timeout(&self) -> Duration {
+        Duration::from_secs(self.snapshot_wait_timeout_minutes * 60)
+    }
+
+    pub fn export_wait_timeout(&self) -> Duration {
+        Duration::from_secs(self.export_wait_timeout_minutes * 60)
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_seconds)
+    }
+}
+
+/// Parse a single `Key=Value` S3 object tag.
+///
+/// Enforces the S3 tag limits (key up to 128 characters, value up to 256)
+/// and rejects the reserved `aws:` prefix, so a bad `--s3-tags` value fails
+/// at startup instead of after the snapshot has been taken.
+pub fn parse_s3_tag(s: &str) -> Result<S3Tag, BackupError> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| BackupError::InvalidTag(format!("'{s}' is not in Key=Value format")))?;
+    let key = key.trim();
+    let value = value.trim();
+
+    if key.is_empty() {
+        return Err(BackupError::InvalidTag(format!("'{s}' has an empty key")));
+    }
+    if key.chars().count() > 128 {
+        return Err(BackupError::InvalidTag(format!(
+            "key '{key}' exceeds 128 characters"
+        )));
+    }
+    if value.chars().count() > 256 {
+        return Err(BackupError::InvalidTag(format!(
+            "value of '{key}' exceeds 256 characters"
+        )));
+    }
+    if key.to_ascii_lowercase().starts_with("aws:") {
+        return Err(BackupError::InvalidTag(format!(
+            "key '{key}' uses the reserved aws: prefix"
+        )));
+    }
+    let allowed = |c: char| c.is_alphanumeric() || " +-=._:/@".contains(c);
+    if let Some(c) = key.chars().chain(value.chars()).find(|&c| !allowed(c)) {
+        return Err(BackupError::InvalidTag(format!(
+            "'{s}' contains unsupported character '{c}'"
+        )));
+    }
+
+    Ok(S3Tag {
+        key: key.to_string(),
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(extra: &[&str]) -> Result<Args, clap::Error> {
+        let base = [
+            "elasticache-backup",
+            "--cache-cluster-id",
+            "cluster",
+            "--s3-bucket-name",
+            "bucket",
+        ];
+        Args::try_parse_from(base.iter().chain(extra))
+    }
+
+    #[test]
+    fn test_args_source() {
+        assert_eq!(
+            parse(&[]).unwrap().sources(),
+            vec![SnapshotSource::CacheCluster("cluster".to_string())]
+        );
+
+        let args = Args::try_parse_from([
+            "elasticache-backup",
+            "--replication-group-id",
+            "rg",
+            "--s3-bucket-name",
+            "bucket",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.sources(),
+            vec![SnapshotSource::ReplicationGroup("rg".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_args_multiple_cache_clusters() {
+        let args = parse(&[
+            "--cache-cluster-id",
+            "redis-b,redis-c",
+            "--cache-cluster-id",
+            "cluster",
+            "--parallel",
+            "4",
+            "--fail-fast",
+        ])
+        .unwrap();
+        let ids: Vec<String> = args
+            .sources()
+            .iter()
+            .map(|source| source.id().to_string())
+            .collect();
+        // Order is kept and the repeated "cluster" is backed up once
+        assert_eq!(ids, vec!["cluster", "redis-b", "redis-c"]);
+        assert_eq!(args.parallel, 4);
+        assert!(args.fail_fast);
+
+        let err = parse(&["--parallel", "0"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn test_args_wait_settings() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.snapshot_wait_timeout(), Duration::from_secs(30 * 60));
+        assert_eq!(args.export_wait_timeout(), Duration::from_secs(5 * 60));
+        assert_eq!(args.poll_interval(), Duration::from_secs(30));
+
+        let args = parse(&[
+            "--snapshot-wait-timeout-minutes",
+            "60",
+            "--poll-interval-seconds",
+            "90",
+        ])
+        .unwrap();
+        assert_eq!(args.snapshot_wait_timeout(), Duration::from_secs(3600));
+        assert_eq!(args.poll_interval(), Duration::from_secs(90));
+
+        for flag in [
+            "--snapshot-wait-timeout-minutes",
+            "--export-wait-timeout-minutes",
+            "--poll-interval-seconds",
+        ] {
+            let err = parse(&[flag, "0"]).unwrap_err();
+            assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+        }
+    }
+
+    #[test]
+    fn test_args_source_exclusive_and_required() {
+        let err = parse(&["--replication-group-id", "rg"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+
+        let err =
+            Args::try_parse_from(["elasticache-backup", "--s3-bucket-name", "bucket"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_args_s3_prefix() {
+        assert_eq!(parse(&[]).unwrap().s3_prefix.as_str(), DEFAULT_S3_PREFIX);
+        // An empty prefix keeps the exports at the bucket root
+        assert_eq!(parse(&["--s3-prefix="]).unwrap().s3_prefix.as_str(), "");
+
+        let err = parse(&["--s3-prefix", "backups/{date}/"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn test_args_metrics() {
+        let args = parse(&[]).unwrap();
+        assert!(!args.emit_metrics);
+        assert_eq!(args.metrics_format, MetricsFormat::Api);
+        assert_eq!(args.metrics_namespace, "ElastiCacheBackup");
+
+        let args = parse(&[
+            "--emit-metrics",
+            "--metrics-format",
+            "emf",
+            "--metrics-namespace",
+            "Custom/Backups",
+        ])
+        .unwrap();
+        assert!(args.emit_metrics);
+        assert_eq!(args.metrics_format, MetricsFormat::Emf);
+        assert_eq!(args.metrics_namespace, "Custom/Backups");
+
+        let err = parse(&["--metrics-format", "statsd"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn test_args_copy_to_region() {
+        let args = parse(&[
+            "--copy-to-region",
+            "us-west-2",
+            "--copy-bucket-name",
+            "dr-bucket",
+            "--copy-required",
+        ])
+        .unwrap();
+        assert_eq!(args.copy_to_region.as_deref(), Some("us-west-2"));
+        assert_eq!(args.copy_bucket_name.as_deref(), Some("dr-bucket"));
+        assert!(args.copy_required);
+
+        // The region and the bucket are only meaningful together
+        for incomplete in [
+            &["--copy-to-region", "us-west-2"][..],
+            &["--copy-bucket-name", "dr-bucket"][..],
+            &["--copy-required"][..],
+        ] {
+            let err = parse(incomplete).unwrap_err();
+            assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+        }
+    }
+
+    #[test]
+    fn test_parse_s3_tag() {
+        let tag = parse_s3_tag("team=data-platform").unwrap();
+        assert_eq!(tag.key, "team");
+        assert_eq!(tag.value, "data-platform");
+
+        // Empty values are allowed by S3
+        assert_eq!(parse_s3_tag("backup=").unwrap().value, "");
+        // Only the first '=' separates key and value
+        assert_eq!(parse_s3_tag("expr=a=b").unwrap().value, "a=b");
+    }
+
+    #[test]
+    fn test_parse_s3_tag_rejects_invalid() {
+        for invalid in ["team", "=data", "aws:createdBy=me", "team=data;drop", " =x"] {
+            assert!(
+                parse_s3_tag(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+        assert!(parse_s3_tag(&format!("{}=v", "k".repeat(129))).is_err());
+        assert!(parse_s3_tag(&format!("k={}", "v".repeat(257))).is_err());
+    }
+
+    #[test]
+    fn test_args_s3_tags_list() {
+        let args = parse(&[
+            "--s3-tags",
+            "team=data,env=prod",
+            "--kms-key-id",
+            "alias/backup",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.s3_tags,
+            vec![
+                S3Tag {
+                    key: "team".to_string(),
+                    value: "data".to_string(),
+                },
+                S3Tag {
+                    key: "env".to_string(),
+                    value: "prod".to_string(),
+                },
+            ]
+        );
+        assert_eq!(args.kms_key_id.as_deref(), Some("alias/backup"));
+    }
+
+    #[test]
+    fn test_args_invalid_s3_tags_rejected_preflight() {
+        let err = parse(&["--s3-tags", "team=data,invalid"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
 }
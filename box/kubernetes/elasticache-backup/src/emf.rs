@@ -0,0 +1,216 @@
+//! CloudWatch Embedded Metric Format (EMF) output
+//!
+//! With `--emit-emf`, every run writes one EMF log line to stderr, next to the
+//! logs. CloudWatch Logs extracts the metrics from it, so backup durations can
+//! be charted and alarmed on without publishing metrics through the API.
+//! Stdout is kept for the execution summary alone.
+
+use serde_json::{Map, Value, json};
+
+use crate::metrics::{Metric, Unit};
+use crate::types::{SnapshotSource, Step, StepTimings};
+
+/// CloudWatch namespace of the emitted metrics
+pub const NAMESPACE: &str = "ElastiCacheBackup";
+
+/// Build the EMF line for one run.
+///
+/// Step durations are `Seconds` metrics and `BackupSuccess` is a `Count` of 1
+/// or 0, all dimensioned by the cache cluster or replication group ID. Steps
+/// skipped because their work already existed are left out rather than
+/// reported as zero-second durations.
+pub fn metrics_line(
+    source: &SnapshotSource,
+    timings: &StepTimings,
+    skipped: &[Step],
+    total_seconds: f64,
+    success: bool,
+    timestamp_ms: i64,
+) -> String {
+    let steps = [
+        (Step::SnapshotCreation, timings.snapshot_creation),
+        (Step::SnapshotWait, timings.snapshot_wait),
+        (Step::S3Export, timings.s3_export),
+        (Step::ExportWait, timings.export_wait),
+    ];
+    let mut metrics: Vec<Metric> = steps
+        .into_iter()
+        .filter(|(step, _)| !skipped.contains(step))
+        .map(|(step, value)| Metric::seconds(step.metric_name(), value))
+        .collect();
+    metrics.extend([
+        Metric::seconds("CleanupSeconds", timings.cleanup),
+        Metric::seconds("RetentionSeconds", timings.retention),
+        Metric::seconds("TotalExecutionSeconds", total_seconds),
+        Metric::count("BackupSuccess", u8::from(success).into()),
+    ]);
+
+    render(NAMESPACE, source, &metrics, timestamp_ms)
+}
+
+/// Write an EMF line to the log stream (stderr)
+pub fn write(line: &str) {
+    eprintln!("{line}");
+}
+
+/// Dimension the metrics of a source are reported under
+pub fn dimension_name(source: &SnapshotSource) -> &'static str {
+    match source {
+        SnapshotSource::CacheCluster(_) => "CacheClusterId",
+        SnapshotSource::ReplicationGroup(_) => "ReplicationGroupId",
+    }
+}
+
+/// Single-line EMF record of `metrics` in `namespace`, dimensioned by the
+/// source ID
+pub fn render(
+    namespace: &str,
+    source: &SnapshotSource,
+    metrics: &[Metric],
+    timestamp_ms: i64,
+) -> String {
+    let dimension = dimension_name(source);
+    let definitions: Vec<Value> = metrics
+        .iter()
+        .map(|metric| json!({ "Name": metric.name, "Unit": metric.unit.as_str() }))
+        .collect();
+
+    let mut line = Map::new();
+    line.insert(
+        "_aws".to_string(),
+        json!({
+            "Timestamp": timestamp_ms,
+            "CloudWatchMetrics": [{
+                "Namespace": namespace,
+                "Dimensions": [[dimension]],
+                "Metrics": definitions,
+            }],
+        }),
+    );
+    line.insert(dimension.to_string(), json!(source.id()));
+    for metric in metrics {
+        let value = match metric.unit {
+            // Counts are whole numbers, so they are written as integers
+            Unit::Count => json!(metric.value as i64),
+            Unit::Seconds => json!(metric.value),
+        };
+        line.insert(metric.name.to_string(), value);
+    }
+
+    Value::Object(line).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_line_schema() {
+        let timings = StepTimings {
+            snapshot_wait: 120.5,
+            s3_export: 3.0,
+            ..Default::default()
+        };
+        let line = metrics_line(
+            &SnapshotSource::CacheCluster("redis-002".to_string()),
+            &timings,
+            &[],
+            130.0,
+            true,
+            1_700_000_000_000,
+        );
+        assert!(!line.contains('\n'), "EMF record must be a single line");
+
+        let value: Value = serde_json::from_str(&line).unwrap();
+        let metadata = &value["_aws"];
+        assert_eq!(metadata["Timestamp"], 1_700_000_000_000_i64);
+        let directive = &metadata["CloudWatchMetrics"][0];
+        assert_eq!(directive["Namespace"], NAMESPACE);
+        assert_eq!(directive["Dimensions"], json!([["CacheClusterId"]]));
+
+        let names: Vec<&str> = directive["Metrics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["Name"].as_str().unwrap())
+            .collect();
+        for name in [
+            "SnapshotWaitSeconds",
+            "S3ExportSeconds",
+            "TotalExecutionSeconds",
+            "BackupSuccess",
+        ] {
+            assert!(names.contains(&name), "missing metric {name}");
+            // Every declared metric needs a value on the root object
+            assert!(value.get(name).is_some(), "missing value for {name}");
+        }
+
+        assert_eq!(value["CacheClusterId"], "redis-002");
+        assert_eq!(value["SnapshotWaitSeconds"], 120.5);
+        assert_eq!(value["TotalExecutionSeconds"], 130.0);
+        assert_eq!(value["BackupSuccess"], 1);
+    }
+
+    #[test]
+    fn test_metrics_line_failure_for_replication_group() {
+        let line = metrics_line(
+            &SnapshotSource::ReplicationGroup("rg".to_string()),
+            &StepTimings::default(),
+            &[],
+            4.0,
+            false,
+            0,
+        );
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            value["_aws"]["CloudWatchMetrics"][0]["Dimensions"],
+            json!([["ReplicationGroupId"]])
+        );
+        assert_eq!(value["ReplicationGroupId"], "rg");
+        assert_eq!(value["BackupSuccess"], 0);
+    }
+
+    #[test]
+    fn test_metrics_line_omits_skipped_steps() {
+        let line = metrics_line(
+            &SnapshotSource::CacheCluster("redis-002".to_string()),
+            &StepTimings::default(),
+            &[Step::SnapshotCreation, Step::SnapshotWait],
+            2.0,
+            true,
+            0,
+        );
+        let value: Value = serde_json::from_str(&line).unwrap();
+        let names: Vec<&str> = value["_aws"]["CloudWatchMetrics"][0]["Metrics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["Name"].as_str().unwrap())
+            .collect();
+        assert!(!names.contains(&"SnapshotCreationSeconds"));
+        assert!(!names.contains(&"SnapshotWaitSeconds"));
+        assert!(value.get("SnapshotWaitSeconds").is_none());
+        assert!(names.contains(&"S3ExportSeconds"));
+    }
+
+    #[test]
+    fn test_emf_line_uses_configured_namespace() {
+        let line = render(
+            "Custom/Backups",
+            &SnapshotSource::CacheCluster("redis-002".to_string()),
+            &[
+                Metric::count("BackupSuccess", 1.0),
+                Metric::seconds("TotalDurationSeconds", 4.5),
+            ],
+            0,
+        );
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            value["_aws"]["CloudWatchMetrics"][0]["Namespace"],
+            "Custom/Backups"
+        );
+        assert_eq!(value["CacheClusterId"], "redis-002");
+        assert_eq!(value["BackupSuccess"], 1);
+        assert_eq!(value["TotalDurationSeconds"], 4.5);
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::types::FailedStep;
+
 #[derive(Error, Debug)]
 pub enum BackupError {
     #[error("Snapshot not found: {0}")]
@@ -11,6 +13,48 @@ pub enum BackupError {
     #[error("S3 export failed: {0}")]
     ExportFailed(String),
 
-    #[error("Operation timed out: {0}")]
-    Timeout(String),
+    #[error("Timed out waiting for snapshot {snapshot_name} after {elapsed_seconds:.1}s")]
+    SnapshotWaitTimeout {
+        snapshot_name: String,
+        elapsed_seconds: f64,
+    },
+
+    #[error("Timed out waiting for S3 export of {snapshot_name} after {elapsed_seconds:.1}s")]
+    ExportWaitTimeout {
+        snapshot_name: String,
+        elapsed_seconds: f64,
+    },
+
+    #[error("Exported object {s3_location} is missing")]
+    ExportObjectMissing { s3_location: String },
+
+    #[error(
+        "Exported object {s3_location} is {size_bytes} bytes, below the {min_bytes}-byte minimum"
+    )]
+    ExportObjectTooSmall {
+        s3_location: String,
+        size_bytes: i64,
+        min_bytes: u64,
+    },
+
+    #[error("Invalid S3 tag: {0}")]
+    InvalidTag(String),
+
+    #[error("Invalid S3 prefix: {0}")]
+    InvalidS3Prefix(String),
+}
+
+/// Error of the backup workflow together with the step it failed in
+#[derive(Error, Debug)]
+#[error("{error:#}")]
+pub struct StepError {
+    pub step: FailedStep,
+    pub error: anyhow::Error,
+}
+
+impl StepError {
+    /// Attach the failed step to an error, for use with `map_err`
+    pub fn at(step: FailedStep) -> impl FnOnce(anyhow::Error) -> Self {
+        move |error| Self { step, error }
+    }
 }
@@ -1,9 +1,18 @@
 use anyhow::{Context, Result};
 use aws_sdk_elasticache::Client as ElastiCacheClient;
+use aws_sdk_s3::Client as S3Client;
+use aws_sdk_s3::types::{MetadataDirective, ServerSideEncryption, Tag, Tagging, TaggingDirective};
+use aws_smithy_types::date_time::Format as DateTimeFormat;
 use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 use crate::error::BackupError;
+use crate::types::{S3Tag, ShardExport, VerifiedObject};
+
+/// Name the S3 export of a snapshot is written under
+pub fn target_snapshot_name(snapshot_name: &str) -> String {
+    format!("{}-s3-export", snapshot_name)
+}
 
 /// Export snapshot to S3 bucket
 pub async fn export_to_s3(
@@ -37,8 +46,7 @@ pub async fn export_to_s3(
         }
     }
 
-    // Generate target snapshot name for S3 export
-    let target_snapshot_name = format!("{}-s3-export", snapshot_name);
+    let target_snapshot_name = target_snapshot_name(snapshot_name);
 
     info!(
         source_snapshot_name = %snapshot_name,
@@ -70,29 +78,49 @@ pub async fn export_to_s3(
     Ok((target_snapshot_name, s3_location))
 }
 
+/// Whether an earlier run already exported the target snapshot to S3.
+///
+/// Any object under the target name counts, including shard files of a
+/// replication group that were already moved under their per-shard prefix.
+pub async fn export_exists(
+    s3_client: &S3Client,
+    s3_bucket_name: &str,
+    target_snapshot_name: &str,
+) -> Result<bool> {
+    let response = s3_client
+        .list_objects_v2()
+        .bucket(s3_bucket_name)
+        .prefix(target_snapshot_name)
+        .max_keys(1)
+        .send()
+        .await
+        .context("Failed to check for an existing S3 export")?;
+    Ok(!response.contents().is_empty())
+}
+
 /// Wait for S3 export to complete
 pub async fn wait_for_completion(
     client: &ElastiCacheClient,
     source_snapshot_name: &str,
-    max_wait_time: u64,
-    check_interval: u64,
+    timeout: Duration,
+    poll_interval: Duration,
 ) -> Result<()> {
     let wait_start_time = Instant::now();
     let mut checks_performed = 0;
 
     info!(
         source_snapshot_name = %source_snapshot_name,
-        max_wait_time_seconds = max_wait_time,
-        check_interval_seconds = check_interval,
+        timeout_seconds = timeout.as_secs(),
+        poll_interval_seconds = poll_interval.as_secs(),
         "Waiting for S3 export completion"
     );
 
     loop {
-        if wait_start_time.elapsed().as_secs() >= max_wait_time {
-            return Err(BackupError::Timeout(format!(
-                "S3 export completion timeout after {:.1}s",
-                wait_start_time.elapsed().as_secs_f64()
-            ))
+        if wait_start_time.elapsed() >= timeout {
+            return Err(BackupError::ExportWaitTimeout {
+                snapshot_name: source_snapshot_name.to_string(),
+                elapsed_seconds: wait_start_time.elapsed().as_secs_f64(),
+            }
             .into());
         }
 
@@ -157,8 +185,400 @@ pub async fn wait_for_completion(
             );
         }
 
-        tokio::time::sleep(Duration::from_secs(check_interval)).await;
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Move the exported RDB files under the `--s3-prefix` key prefix.
+///
+/// ElastiCache always writes an export to the root of the bucket, so every
+/// object under the target snapshot name is copied to `<key_prefix><key>`
+/// and the original deleted. Returns the number of objects moved.
+pub async fn move_under_prefix(
+    s3_client: &S3Client,
+    s3_bucket_name: &str,
+    target_snapshot_name: &str,
+    key_prefix: &str,
+) -> Result<usize> {
+    let response = s3_client
+        .list_objects_v2()
+        .bucket(s3_bucket_name)
+        .prefix(target_snapshot_name)
+        .send()
+        .await
+        .context("Failed to list exported S3 objects")?;
+
+    let keys: Vec<&str> = response.contents().iter().filter_map(|o| o.key()).collect();
+    if keys.is_empty() {
+        return Err(BackupError::NotFound(format!(
+            "No exported objects found under s3://{}/{}",
+            s3_bucket_name, target_snapshot_name
+        ))
+        .into());
+    }
+
+    for key in &keys {
+        let prefixed_key = format!("{}{}", key_prefix, key);
+        s3_client
+            .copy_object()
+            .bucket(s3_bucket_name)
+            .key(&prefixed_key)
+            .copy_source(format!("{}/{}", s3_bucket_name, url_encode(key)))
+            .send()
+            .await
+            .with_context(|| format!("Failed to copy S3 object {} to {}", key, prefixed_key))?;
+        s3_client
+            .delete_object()
+            .bucket(s3_bucket_name)
+            .key(*key)
+            .send()
+            .await
+            .with_context(|| format!("Failed to delete S3 object {}", key))?;
+        debug!(key = %key, prefixed_key = %prefixed_key, "Exported S3 object moved");
+    }
+
+    info!(
+        object_count = keys.len(),
+        key_prefix = %key_prefix,
+        "Exported S3 objects moved under key prefix"
+    );
+    Ok(keys.len())
+}
+
+/// Move each shard's RDB file of a replication group export under a
+/// per-shard prefix.
+///
+/// ElastiCache writes every shard next to each other as
+/// `<target>-<node_group_id>.rdb`; each file is copied to
+/// `<target>/<node_group_id>/<file>` and the original deleted, so one shard
+/// can be restored without sorting through the others. A single-shard group
+/// may be exported as `<target>.rdb`, which is moved under its only node
+/// group. Returns the exported location and move time of every shard.
+pub async fn split_shards(
+    s3_client: &S3Client,
+    s3_bucket_name: &str,
+    target_snapshot_name: &str,
+    node_group_ids: &[String],
+) -> Result<Vec<ShardExport>> {
+    let mut node_group_ids = node_group_ids.to_vec();
+    node_group_ids.sort();
+    node_group_ids.dedup();
+
+    let response = s3_client
+        .list_objects_v2()
+        .bucket(s3_bucket_name)
+        .prefix(target_snapshot_name)
+        .send()
+        .await
+        .context("Failed to list exported S3 objects")?;
+
+    // Skip files already moved by an earlier run
+    let nested_prefix = format!("{}/", target_snapshot_name);
+    let objects: Vec<(&str, Option<i64>)> = response
+        .contents()
+        .iter()
+        .filter_map(|o| o.key().map(|key| (key, o.size())))
+        .filter(|(key, _)| !key.starts_with(&nested_prefix))
+        .collect();
+
+    let mut shards = Vec::with_capacity(node_group_ids.len());
+    for node_group_id in &node_group_ids {
+        let shard_start = Instant::now();
+        let shard_prefix = format!("{}-{}", target_snapshot_name, node_group_id);
+        let (key, size_bytes) = objects
+            .iter()
+            .find(|(key, _)| key.starts_with(&shard_prefix))
+            .or_else(|| match (node_group_ids.len(), objects.as_slice()) {
+                (1, [only]) => Some(only),
+                _ => None,
+            })
+            .copied()
+            .ok_or_else(|| {
+                BackupError::NotFound(format!(
+                    "No exported object found for shard {} under s3://{}/{}",
+                    node_group_id, s3_bucket_name, target_snapshot_name
+                ))
+            })?;
+
+        let file_name = key.rsplit('/').next().unwrap_or(key);
+        let shard_key = format!("{}{}/{}", nested_prefix, node_group_id, file_name);
+        s3_client
+            .copy_object()
+            .bucket(s3_bucket_name)
+            .key(&shard_key)
+            .copy_source(format!("{}/{}", s3_bucket_name, url_encode(key)))
+            .send()
+            .await
+            .with_context(|| format!("Failed to copy shard object {} to {}", key, shard_key))?;
+        s3_client
+            .delete_object()
+            .bucket(s3_bucket_name)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("Failed to delete shard object {}", key))?;
+
+        let shard = ShardExport {
+            node_group_id: node_group_id.clone(),
+            s3_location: format!("s3://{}/{}", s3_bucket_name, shard_key),
+            size_bytes,
+            duration_seconds: shard_start.elapsed().as_secs_f64(),
+        };
+        info!(
+            node_group_id = %shard.node_group_id,
+            s3_location = %shard.s3_location,
+            size_bytes = shard.size_bytes.unwrap_or(0),
+            duration_seconds = shard.duration_seconds,
+            "Shard export moved under per-shard prefix"
+        );
+        shards.push(shard);
+    }
+
+    Ok(shards)
+}
+
+/// Apply object tags and SSE-KMS encryption to the exported RDB files.
+///
+/// ElastiCache writes one object per shard under the target snapshot name
+/// (e.g. `<target>-0001.rdb`) and offers no tagging or CMK option on export,
+/// so the objects are updated in place once the export is complete. With a
+/// KMS key each object is copied onto itself with `aws:kms` encryption and
+/// the tags set in the same request; without one the tags are written with
+/// `PutObjectTagging`. Returns the number of objects updated.
+pub async fn apply_object_settings(
+    s3_client: &S3Client,
+    s3_bucket_name: &str,
+    target_snapshot_name: &str,
+    tags: &[S3Tag],
+    kms_key_id: Option<&str>,
+) -> Result<usize> {
+    let response = s3_client
+        .list_objects_v2()
+        .bucket(s3_bucket_name)
+        .prefix(target_snapshot_name)
+        .send()
+        .await
+        .context("Failed to list exported S3 objects")?;
+
+    let keys: Vec<&str> = response.contents().iter().filter_map(|o| o.key()).collect();
+    if keys.is_empty() {
+        return Err(BackupError::NotFound(format!(
+            "No exported objects found under s3://{}/{}",
+            s3_bucket_name, target_snapshot_name
+        ))
+        .into());
+    }
+
+    for key in &keys {
+        if let Some(kms_key_id) = kms_key_id {
+            let mut request = s3_client
+                .copy_object()
+                .bucket(s3_bucket_name)
+                .key(*key)
+                .copy_source(format!("{}/{}", s3_bucket_name, url_encode(key)))
+                .metadata_directive(MetadataDirective::Copy)
+                .server_side_encryption(ServerSideEncryption::AwsKms)
+                .ssekms_key_id(kms_key_id);
+            if !tags.is_empty() {
+                request = request
+                    .tagging(tagging_query(tags))
+                    .tagging_directive(TaggingDirective::Replace);
+            }
+            request
+                .send()
+                .await
+                .with_context(|| format!("Failed to encrypt S3 object {}", key))?;
+        } else {
+            let tag_set = tags
+                .iter()
+                .map(|t| Tag::builder().key(&t.key).value(&t.value).build())
+                .collect::<Result<Vec<_>, _>>()?;
+            s3_client
+                .put_object_tagging()
+                .bucket(s3_bucket_name)
+                .key(*key)
+                .tagging(Tagging::builder().set_tag_set(Some(tag_set)).build()?)
+                .send()
+                .await
+                .with_context(|| format!("Failed to tag S3 object {}", key))?;
+        }
+
+        debug!(
+            key = %key,
+            tag_count = tags.len(),
+            kms_key_id = kms_key_id.unwrap_or(""),
+            "S3 object settings applied"
+        );
+    }
+
+    info!(
+        object_count = keys.len(),
+        tag_count = tags.len(),
+        kms_encrypted = kms_key_id.is_some(),
+        "Tags and encryption applied to exported S3 objects"
+    );
+    Ok(keys.len())
+}
+
+/// Check the exported RDB files with `HeadObject` once the export completed.
+///
+/// ElastiCache can report an export as complete while the object is empty,
+/// so every file must exist and hold at least `min_bytes`. `keys` are the
+/// expected object keys (the per-shard files of a replication group); when
+/// empty, every object under the target snapshot name is checked. Returns the
+/// size and modification time of each file.
+pub async fn verify_export(
+    s3_client: &S3Client,
+    s3_bucket_name: &str,
+    target_snapshot_name: &str,
+    keys: &[String],
+    min_bytes: u64,
+) -> Result<Vec<VerifiedObject>> {
+    let keys = if keys.is_empty() {
+        let response = s3_client
+            .list_objects_v2()
+            .bucket(s3_bucket_name)
+            .prefix(target_snapshot_name)
+            .send()
+            .await
+            .context("Failed to list exported S3 objects")?;
+        response
+            .contents()
+            .iter()
+            .filter_map(|o| o.key())
+            .map(str::to_string)
+            .collect()
+    } else {
+        keys.to_vec()
+    };
+    if keys.is_empty() {
+        return Err(BackupError::ExportObjectMissing {
+            s3_location: format!("s3://{}/{}", s3_bucket_name, target_snapshot_name),
+        }
+        .into());
+    }
+
+    let mut verified = Vec::with_capacity(keys.len());
+    for key in &keys {
+        let s3_location = format!("s3://{}/{}", s3_bucket_name, key);
+        let response = match s3_client
+            .head_object()
+            .bucket(s3_bucket_name)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_not_found()) => {
+                return Err(BackupError::ExportObjectMissing { s3_location }.into());
+            }
+            Err(e) => {
+                return Err(anyhow::Error::new(e)
+                    .context(format!("Failed to check exported S3 object {}", key)));
+            }
+        };
+
+        let size_bytes = response.content_length().unwrap_or(0);
+        if u64::try_from(size_bytes).unwrap_or(0) < min_bytes {
+            return Err(BackupError::ExportObjectTooSmall {
+                s3_location,
+                size_bytes,
+                min_bytes,
+            }
+            .into());
+        }
+
+        let object = VerifiedObject {
+            s3_location,
+            size_bytes,
+            last_modified: response
+                .last_modified()
+                .and_then(|t| t.fmt(DateTimeFormat::DateTime).ok()),
+        };
+        debug!(
+            s3_location = %object.s3_location,
+            size_bytes = object.size_bytes,
+            last_modified = object.last_modified.as_deref().unwrap_or(""),
+            "Exported S3 object verified"
+        );
+        verified.push(object);
+    }
+
+    info!(
+        object_count = verified.len(),
+        total_size_bytes = verified.iter().map(|o| o.size_bytes).sum::<i64>(),
+        min_export_bytes = min_bytes,
+        "Exported S3 objects verified"
+    );
+    Ok(verified)
+}
+
+/// Copy the exported RDB files into a bucket of another region.
+///
+/// ElastiCache cannot copy a snapshot across regions, so the off-region copy
+/// is made of the exported files. `s3_client` must be configured for the
+/// region of `copy_bucket_name`; each key is copied under the same name,
+/// keeping its tags, and encrypted with `kms_key_id` when given. Returns the
+/// location of every copy.
+pub async fn copy_to_bucket(
+    s3_client: &S3Client,
+    s3_bucket_name: &str,
+    keys: &[String],
+    copy_bucket_name: &str,
+    kms_key_id: Option<&str>,
+) -> Result<Vec<String>> {
+    let mut locations = Vec::with_capacity(keys.len());
+    for key in keys {
+        let mut request = s3_client
+            .copy_object()
+            .bucket(copy_bucket_name)
+            .key(key)
+            .copy_source(format!("{}/{}", s3_bucket_name, url_encode(key)));
+        if let Some(kms_key_id) = kms_key_id {
+            request = request
+                .server_side_encryption(ServerSideEncryption::AwsKms)
+                .ssekms_key_id(kms_key_id);
+        }
+        request.send().await.with_context(|| {
+            format!(
+                "Failed to copy s3://{}/{} to s3://{}/{}",
+                s3_bucket_name, key, copy_bucket_name, key
+            )
+        })?;
+
+        let location = format!("s3://{}/{}", copy_bucket_name, key);
+        debug!(s3_location = %location, "Exported S3 object copied");
+        locations.push(location);
+    }
+
+    info!(
+        object_count = locations.len(),
+        copy_bucket_name = %copy_bucket_name,
+        kms_encrypted = kms_key_id.is_some(),
+        "Exported S3 objects copied to the copy bucket"
+    );
+    Ok(locations)
+}
+
+/// URL query form of the tag set, as expected by the `x-amz-tagging` header
+fn tagging_query(tags: &[S3Tag]) -> String {
+    tags.iter()
+        .map(|t| format!("{}={}", url_encode(&t.key), url_encode(&t.value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters
+fn url_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
     }
+    out
 }
 
 #[cfg(test)]
@@ -169,7 +589,30 @@ mod tests {
     use aws_sdk_elasticache::operation::describe_snapshots::DescribeSnapshotsOutput;
     use aws_sdk_elasticache::types::Snapshot;
     use aws_sdk_elasticache::types::error::SnapshotNotFoundFault;
+    use aws_sdk_s3::Client as S3MockClient;
+    use aws_sdk_s3::operation::copy_object::CopyObjectOutput;
+    use aws_sdk_s3::operation::delete_object::DeleteObjectOutput;
+    use aws_sdk_s3::operation::head_object::{HeadObjectError, HeadObjectOutput};
+    use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Output;
+    use aws_sdk_s3::operation::put_object_tagging::PutObjectTaggingOutput;
+    use aws_sdk_s3::types::Object;
+    use aws_sdk_s3::types::error::NotFound;
     use aws_smithy_mocks::{RuleMode, mock, mock_client};
+    use aws_smithy_types::DateTime;
+
+    fn tag(key: &str, value: &str) -> S3Tag {
+        S3Tag {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn exported_objects() -> ListObjectsV2Output {
+        ListObjectsV2Output::builder()
+            .contents(Object::builder().key("snap-s3-export-0001.rdb").build())
+            .contents(Object::builder().key("snap-s3-export-0002.rdb").build())
+            .build()
+    }
 
     fn snap(status: &str) -> Snapshot {
         Snapshot::builder()
@@ -222,6 +665,21 @@ mod tests {
         assert!(export_to_s3(&client, "snap", "b").await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_export_exists() {
+        let list = mock!(S3MockClient::list_objects_v2)
+            .match_requests(|req| {
+                req.prefix() == Some("snap-s3-export") && req.max_keys() == Some(1)
+            })
+            .then_output(exported_objects);
+        let empty = mock!(S3MockClient::list_objects_v2)
+            .then_output(|| ListObjectsV2Output::builder().build());
+        let s3 = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &empty]);
+
+        assert!(export_exists(&s3, "b", "snap-s3-export").await.unwrap());
+        assert!(!export_exists(&s3, "b", "other-s3-export").await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_wait_for_completion_available() {
         let rule = mock!(Client::describe_snapshots).then_output(|| {
@@ -230,7 +688,14 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        wait_for_completion(&client, "snap", 30, 1).await.unwrap();
+        wait_for_completion(
+            &client,
+            "snap",
+            Duration::from_secs(30),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -241,7 +706,16 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        assert!(wait_for_completion(&client, "snap", 30, 1).await.is_err());
+        assert!(
+            wait_for_completion(
+                &client,
+                "snap",
+                Duration::from_secs(30),
+                Duration::from_secs(1)
+            )
+            .await
+            .is_err()
+        );
     }
 
     #[tokio::test]
@@ -249,7 +723,16 @@ mod tests {
         let rule = mock!(Client::describe_snapshots)
             .then_output(|| DescribeSnapshotsOutput::builder().build());
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        assert!(wait_for_completion(&client, "snap", 30, 1).await.is_err());
+        assert!(
+            wait_for_completion(
+                &client,
+                "snap",
+                Duration::from_secs(30),
+                Duration::from_secs(1)
+            )
+            .await
+            .is_err()
+        );
     }
 
     #[tokio::test]
@@ -260,9 +743,367 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        let err = wait_for_completion(&client, "snap", 0, 1)
+        let err = wait_for_completion(&client, "snap", Duration::ZERO, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BackupError>(),
+            Some(BackupError::ExportWaitTimeout { .. })
+        ));
+        assert!(err.to_string().starts_with("Timed out waiting for"));
+    }
+
+    #[test]
+    fn test_tagging_query_encodes_values() {
+        let tags = [tag("team", "data platform"), tag("path", "a/b=c")];
+        assert_eq!(tagging_query(&tags), "team=data%20platform&path=a%2Fb%3Dc");
+    }
+
+    #[tokio::test]
+    async fn test_apply_object_settings_tags_only() {
+        let list = mock!(S3MockClient::list_objects_v2)
+            .match_requests(|req| req.prefix() == Some("snap-s3-export"))
+            .then_output(exported_objects);
+        let tagging = mock!(S3MockClient::put_object_tagging)
+            .match_requests(|req| {
+                req.tagging()
+                    .is_some_and(|t| t.tag_set().len() == 1 && t.tag_set()[0].key() == "team")
+            })
+            .then_output(|| PutObjectTaggingOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &tagging]);
+
+        let count =
+            apply_object_settings(&client, "b", "snap-s3-export", &[tag("team", "data")], None)
+                .await
+                .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(tagging.num_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_apply_object_settings_kms() {
+        let list = mock!(S3MockClient::list_objects_v2).then_output(exported_objects);
+        let copy = mock!(S3MockClient::copy_object)
+            .match_requests(|req| {
+                req.server_side_encryption() == Some(&ServerSideEncryption::AwsKms)
+                    && req.ssekms_key_id() == Some("alias/backup")
+                    && req.tagging() == Some("team=data")
+                    && req.copy_source() == Some("b/snap-s3-export-0001.rdb")
+            })
+            .then_output(|| CopyObjectOutput::builder().build());
+        let copy_second =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[&list, &copy, &copy_second]
+        );
+
+        let count = apply_object_settings(
+            &client,
+            "b",
+            "snap-s3-export",
+            &[tag("team", "data")],
+            Some("alias/backup"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(copy.num_calls(), 1);
+    }
+
+    fn node_groups(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_split_shards_moves_each_shard() {
+        let list = mock!(S3MockClient::list_objects_v2).then_output(|| {
+            ListObjectsV2Output::builder()
+                .contents(
+                    Object::builder()
+                        .key("snap-s3-export-0001.rdb")
+                        .size(10)
+                        .build(),
+                )
+                .contents(
+                    Object::builder()
+                        .key("snap-s3-export-0002.rdb")
+                        .size(20)
+                        .build(),
+                )
+                .build()
+        });
+        let copy = mock!(S3MockClient::copy_object)
+            .match_requests(|req| {
+                req.key() == Some("snap-s3-export/0002/snap-s3-export-0002.rdb")
+                    && req.copy_source() == Some("b/snap-s3-export-0002.rdb")
+            })
+            .then_output(|| CopyObjectOutput::builder().build());
+        let copy_other =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let delete = mock!(S3MockClient::delete_object)
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[&list, &copy, &copy_other, &delete]
+        );
+
+        // Node groups come from every node snapshot, so duplicates are expected
+        let shards = split_shards(
+            &client,
+            "b",
+            "snap-s3-export",
+            &node_groups(&["0002", "0001", "0002"]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(shards.len(), 2);
+        assert_eq!(shards[0].node_group_id, "0001");
+        assert_eq!(
+            shards[0].s3_location,
+            "s3://b/snap-s3-export/0001/snap-s3-export-0001.rdb"
+        );
+        assert_eq!(shards[1].size_bytes, Some(20));
+        assert_eq!(copy.num_calls(), 1);
+        assert_eq!(delete.num_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_copy_to_bucket_with_kms() {
+        let copy = mock!(S3MockClient::copy_object)
+            .match_requests(|req| {
+                req.bucket() == Some("dr-bucket")
+                    && req.copy_source() == Some("b/p/snap-s3-export-0001.rdb")
+                    && req.server_side_encryption() == Some(&ServerSideEncryption::AwsKms)
+                    && req.ssekms_key_id() == Some("alias/dr")
+            })
+            .then_output(|| CopyObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&copy]);
+
+        let locations = copy_to_bucket(
+            &client,
+            "b",
+            &["p/snap-s3-export-0001.rdb".to_string()],
+            "dr-bucket",
+            Some("alias/dr"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(locations, vec!["s3://dr-bucket/p/snap-s3-export-0001.rdb"]);
+        assert_eq!(copy.num_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_copy_to_bucket_error() {
+        let copy = mock!(S3MockClient::copy_object)
+            .sequence()
+            .http_status(403, None)
+            .build();
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&copy]);
+
+        let err = copy_to_bucket(&client, "b", &["k.rdb".to_string()], "dr-bucket", None)
+            .await
+            .unwrap_err();
+        assert!(format!("{err}").contains("s3://dr-bucket/k.rdb"));
+    }
+
+    #[tokio::test]
+    async fn test_move_under_prefix() {
+        let list = mock!(S3MockClient::list_objects_v2)
+            .match_requests(|req| req.prefix() == Some("snap-s3-export"))
+            .then_output(exported_objects);
+        let copy = mock!(S3MockClient::copy_object)
+            .match_requests(|req| {
+                req.key() == Some("elasticache/snap/2025/01/snap-s3-export-0001.rdb")
+                    && req.copy_source() == Some("b/snap-s3-export-0001.rdb")
+            })
+            .then_output(|| CopyObjectOutput::builder().build());
+        let copy_other =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let delete = mock!(S3MockClient::delete_object)
+            .match_requests(|req| req.key().is_some_and(|k| !k.contains('/')))
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[&list, &copy, &copy_other, &delete]
+        );
+
+        let moved = move_under_prefix(&client, "b", "snap-s3-export", "elasticache/snap/2025/01/")
+            .await
+            .unwrap();
+        assert_eq!(moved, 2);
+        assert_eq!(copy.num_calls(), 1);
+        assert_eq!(delete.num_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_split_shards_under_key_prefix() {
+        let list = mock!(S3MockClient::list_objects_v2).then_output(|| {
+            ListObjectsV2Output::builder()
+                .contents(
+                    Object::builder()
+                        .key("p/2025/snap-s3-export-0001.rdb")
+                        .build(),
+                )
+                .build()
+        });
+        let copy =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let delete = mock!(S3MockClient::delete_object)
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &copy, &delete]);
+
+        let shards = split_shards(
+            &client,
+            "b",
+            "p/2025/snap-s3-export",
+            &node_groups(&["0001"]),
+        )
+        .await
+        .unwrap();
+        // Only the file name is repeated under the per-shard prefix
+        assert_eq!(
+            shards[0].s3_location,
+            "s3://b/p/2025/snap-s3-export/0001/snap-s3-export-0001.rdb"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_split_shards_single_unsuffixed_file() {
+        let list = mock!(S3MockClient::list_objects_v2).then_output(|| {
+            ListObjectsV2Output::builder()
+                .contents(Object::builder().key("snap-s3-export.rdb").build())
+                .build()
+        });
+        let copy =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let delete = mock!(S3MockClient::delete_object)
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &copy, &delete]);
+
+        let shards = split_shards(&client, "b", "snap-s3-export", &node_groups(&["0001"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            shards[0].s3_location,
+            "s3://b/snap-s3-export/0001/snap-s3-export.rdb"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_split_shards_missing_shard() {
+        let list = mock!(S3MockClient::list_objects_v2).then_output(exported_objects);
+        let copy =
+            mock!(S3MockClient::copy_object).then_output(|| CopyObjectOutput::builder().build());
+        let delete = mock!(S3MockClient::delete_object)
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &copy, &delete]);
+
+        let err = split_shards(
+            &client,
+            "b",
+            "snap-s3-export",
+            &node_groups(&["0001", "0003"]),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("shard 0003"));
+    }
+
+    #[tokio::test]
+    async fn test_apply_object_settings_no_objects() {
+        let list = mock!(S3MockClient::list_objects_v2)
+            .then_output(|| ListObjectsV2Output::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list]);
+        assert!(
+            apply_object_settings(&client, "b", "snap-s3-export", &[], Some("k"))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_export_lists_and_heads_objects() {
+        let list = mock!(S3MockClient::list_objects_v2)
+            .match_requests(|req| req.prefix() == Some("snap-s3-export"))
+            .then_output(exported_objects);
+        let head = mock!(S3MockClient::head_object).then_compute_output(|req| {
+            let size = if req.key() == Some("snap-s3-export-0001.rdb") {
+                100
+            } else {
+                200
+            };
+            HeadObjectOutput::builder()
+                .content_length(size)
+                .last_modified(DateTime::from_secs(0))
+                .build()
+        });
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &head]);
+
+        let verified = verify_export(&client, "bucket", "snap-s3-export", &[], 1)
+            .await
+            .unwrap();
+        assert_eq!(verified.len(), 2);
+        assert_eq!(
+            verified[0].s3_location,
+            "s3://bucket/snap-s3-export-0001.rdb"
+        );
+        assert_eq!(verified[0].size_bytes, 100);
+        assert_eq!(verified[1].size_bytes, 200);
+        assert_eq!(
+            verified[0].last_modified.as_deref(),
+            Some("1970-01-01T00:00:00Z")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_export_rejects_small_object() {
+        let head = mock!(S3MockClient::head_object)
+            .then_output(|| HeadObjectOutput::builder().content_length(0).build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&head]);
+
+        let keys = ["t/0001/t-0001.rdb".to_string()];
+        let err = verify_export(&client, "bucket", "t", &keys, 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BackupError>(),
+            Some(BackupError::ExportObjectTooSmall {
+                size_bytes: 0,
+                min_bytes: 1,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_export_missing_object() {
+        // A shard file that is gone
+        let head = mock!(S3MockClient::head_object)
+            .then_error(|| HeadObjectError::NotFound(NotFound::builder().build()));
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&head]);
+        let keys = ["t/0002/t-0002.rdb".to_string()];
+        let err = verify_export(&client, "bucket", "t", &keys, 1)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Exported object s3://bucket/t/0002/t-0002.rdb is missing"
+        );
+
+        // Nothing exported under the target at all
+        let list = mock!(S3MockClient::list_objects_v2)
+            .then_output(|| ListObjectsV2Output::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list]);
+        let err = verify_export(&client, "bucket", "t", &[], 1)
             .await
             .unwrap_err();
-        assert!(err.to_string().contains("timeout"));
+        assert!(matches!(
+            err.downcast_ref::<BackupError>(),
+            Some(BackupError::ExportObjectMissing { .. })
+        ));
     }
 }
@@ -0,0 +1,254 @@
+//! Native AWS Lambda entry point (`lambda` feature).
+//!
+//! The handler takes the CLI options as an event JSON, runs the same backup
+//! workflow as the CLI, and returns the summary it would print as the
+//! function response, or as the error of a failed invocation. Event fields are turned into command-line arguments and parsed
+//! by `Args`, so defaults, tag validation, and the snapshot source rules are
+//! identical in both entry points.
+
+use anyhow::Result;
+use clap::Parser;
+use lambda_runtime::{LambdaEvent, service_fn};
+use serde::Deserialize;
+use tracing::error;
+
+use crate::Report;
+use crate::cli::Args;
+
+/// Environment variable the Lambda runtime sets for its Runtime API endpoint
+const RUNTIME_API_ENV: &str = "AWS_LAMBDA_RUNTIME_API";
+
+/// Invocation payload; each field mirrors the CLI option of the same name
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BackupEvent {
+    pub cache_cluster_id: Option<String>,
+    pub replication_group_id: Option<String>,
+    pub s3_bucket_name: Option<String>,
+    pub s3_prefix: Option<String>,
+    pub region: Option<String>,
+    pub retention_count: Option<u32>,
+    pub snapshot_wait_timeout_minutes: Option<u64>,
+    pub export_wait_timeout_minutes: Option<u64>,
+    pub poll_interval_seconds: Option<u64>,
+    pub min_export_bytes: Option<u64>,
+    #[serde(default)]
+    pub s3_tags: Vec<String>,
+    pub kms_key_id: Option<String>,
+    pub emit_emf: Option<bool>,
+    pub emit_metrics: Option<bool>,
+    pub metrics_format: Option<String>,
+    pub metrics_namespace: Option<String>,
+    pub parallel: Option<u16>,
+    pub fail_fast: Option<bool>,
+    pub force: Option<bool>,
+    pub copy_to_region: Option<String>,
+    pub copy_bucket_name: Option<String>,
+    pub copy_kms_key_id: Option<String>,
+    pub copy_required: Option<bool>,
+}
+
+impl BackupEvent {
+    /// Parse the event through the CLI definition.
+    ///
+    /// Fields missing from the event fall back to the function's environment
+    /// variables and then to the CLI defaults, exactly as for the binary.
+    pub fn into_args(self) -> Result<Args, clap::Error> {
+        let mut argv = vec!["elasticache-backup".to_string()];
+        let mut push = |flag: &str, value: Option<String>| {
+            if let Some(value) = value {
+                argv.push(format!("--{flag}={value}"));
+            }
+        };
+
+        push("cache-cluster-id", self.cache_cluster_id);
+        push("replication-group-id", self.replication_group_id);
+        push("s3-bucket-name", self.s3_bucket_name);
+        push("s3-prefix", self.s3_prefix);
+        push("region", self.region);
+        push(
+            "retention-count",
+            self.retention_count.map(|n| n.to_string()),
+        );
+        push(
+            "snapshot-wait-timeout-minutes",
+            self.snapshot_wait_timeout_minutes.map(|n| n.to_string()),
+        );
+        push(
+            "export-wait-timeout-minutes",
+            self.export_wait_timeout_minutes.map(|n| n.to_string()),
+        );
+        push(
+            "poll-interval-seconds",
+            self.poll_interval_seconds.map(|n| n.to_string()),
+        );
+        push(
+            "min-export-bytes",
+            self.min_export_bytes.map(|n| n.to_string()),
+        );
+        if !self.s3_tags.is_empty() {
+            push("s3-tags", Some(self.s3_tags.join(",")));
+        }
+        push("kms-key-id", self.kms_key_id);
+        push("parallel", self.parallel.map(|n| n.to_string()));
+        push("copy-to-region", self.copy_to_region);
+        push("copy-bucket-name", self.copy_bucket_name);
+        push("copy-kms-key-id", self.copy_kms_key_id);
+        push("metrics-format", self.metrics_format);
+        push("metrics-namespace", self.metrics_namespace);
+        if self.emit_emf == Some(true) {
+            argv.push("--emit-emf".to_string());
+        }
+        if self.emit_metrics == Some(true) {
+            argv.push("--emit-metrics".to_string());
+        }
+        if self.fail_fast == Some(true) {
+            argv.push("--fail-fast".to_string());
+        }
+        if self.force == Some(true) {
+            argv.push("--force".to_string());
+        }
+        if self.copy_required == Some(true) {
+            argv.push("--copy-required".to_string());
+        }
+
+        Args::try_parse_from(argv)
+    }
+}
+
+/// Whether the process was started by the Lambda runtime
+pub fn is_lambda_runtime() -> bool {
+    std::env::var_os(RUNTIME_API_ENV).is_some()
+}
+
+/// Serve invocations until the runtime shuts the function down
+pub async fn run() -> Result<()> {
+    lambda_runtime::run(service_fn(handler))
+        .await
+        .map_err(|e| anyhow::anyhow!(e))
+}
+
+async fn handler(event: LambdaEvent<BackupEvent>) -> Result<Report, lambda_runtime::Error> {
+    let args = event.payload.into_args()?;
+    let report = crate::execute_all(&args).await?;
+    into_response(report)
+}
+
+/// A backup that did not succeed fails the invocation, so Lambda's `Errors`
+/// metric, async retries, and on-failure destinations see it, the same way
+/// the CLI exits non-zero. The error message is the serialized summary, so
+/// `failed_step`, the step timings, and per-cluster results are kept.
+fn into_response(report: Report) -> Result<Report, lambda_runtime::Error> {
+    let Some(reason) = report.failure() else {
+        return Ok(report);
+    };
+    error!(reason = %reason, "Backup did not succeed");
+    Err(serde_json::to_string(&report)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::StepError;
+    use crate::types::{FailedStep, SnapshotSource, StepTimings};
+
+    fn event(json: serde_json::Value) -> BackupEvent {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_event_into_args() {
+        let args = event(serde_json::json!({
+            "cache_cluster_id": "redis-002",
+            "s3_bucket_name": "backups",
+            "region": "us-east-1",
+            "retention_count": 7,
+            "s3_tags": ["Team=platform", "Env=prod"]
+        }))
+        .into_args()
+        .unwrap();
+
+        assert_eq!(
+            args.sources(),
+            vec![SnapshotSource::CacheCluster("redis-002".to_string())]
+        );
+        assert_eq!(args.s3_bucket_name, "backups");
+        assert_eq!(args.region, "us-east-1");
+        assert_eq!(args.retention_count, 7);
+        assert_eq!(args.s3_tags.len(), 2);
+        // Unset fields keep the CLI defaults
+        assert_eq!(args.snapshot_wait_timeout_minutes, 30);
+        assert_eq!(args.poll_interval_seconds, 30);
+        assert_eq!(args.parallel, 1);
+    }
+
+    #[test]
+    fn test_event_multiple_clusters() {
+        let args = event(serde_json::json!({
+            "cache_cluster_id": "redis-002,redis-003",
+            "s3_bucket_name": "backups",
+            "parallel": 2,
+            "fail_fast": true
+        }))
+        .into_args()
+        .unwrap();
+
+        assert_eq!(args.sources().len(), 2);
+        assert_eq!(args.parallel, 2);
+        assert!(args.fail_fast);
+    }
+
+    #[test]
+    fn test_event_rejects_invalid_input() {
+        let both = BackupEvent {
+            cache_cluster_id: Some("redis-002".to_string()),
+            replication_group_id: Some("rg".to_string()),
+            s3_bucket_name: Some("backups".to_string()),
+            ..Default::default()
+        };
+        assert!(both.into_args().is_err());
+
+        let bad_tag = BackupEvent {
+            replication_group_id: Some("rg".to_string()),
+            s3_bucket_name: Some("backups".to_string()),
+            s3_tags: vec!["aws:owner=me".to_string()],
+            ..Default::default()
+        };
+        assert!(bad_tag.into_args().is_err());
+
+        assert!(
+            serde_json::from_value::<BackupEvent>(serde_json::json!({"bucket": "typo"})).is_err()
+        );
+    }
+
+    #[test]
+    fn test_failed_backup_fails_invocation_with_summary() {
+        let args = event(serde_json::json!({
+            "cache_cluster_id": "redis-002",
+            "s3_bucket_name": "backups"
+        }))
+        .into_args()
+        .unwrap();
+        let source = SnapshotSource::CacheCluster("redis-002".to_string());
+
+        let success = crate::base_summary(&args, &source, "Success", String::new());
+        assert!(into_response(Report::Single(success)).is_ok());
+
+        let error = StepError {
+            step: FailedStep::S3Export,
+            error: anyhow::anyhow!("AccessDenied"),
+        };
+        let timings = StepTimings {
+            snapshot_creation: 1.5,
+            ..Default::default()
+        };
+        let summary = crate::failure_summary(&args, &source, timings, None, &error, 2.0);
+        let err = into_response(Report::Single(summary)).unwrap_err();
+
+        let json: serde_json::Value = serde_json::from_str(&err.to_string()).unwrap();
+        assert_eq!(json["status"], "Failed");
+        assert_eq!(json["failed_step"], "s3_export");
+        assert_eq!(json["step_timings"]["snapshot_creation"], 1.5);
+        assert_eq!(json["total_execution_time_seconds"], 2.0);
+    }
+}
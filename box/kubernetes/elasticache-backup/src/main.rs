@@ -1,18 +1,101 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
 use clap::Parser;
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Instant;
-use tracing::{error, info, info_span};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{Instrument, error, info, info_span, warn};
 
 mod backup;
 mod cli;
+mod emf;
 mod error;
 mod export;
+#[cfg(feature = "lambda")]
+mod lambda;
+mod metrics;
+mod prefix;
 mod retention;
 mod snapshot;
 mod types;
 
 use cli::Args;
-use types::{ExecutionSummary, RetentionInfo, StepTimings};
+use error::StepError;
+use types::{
+    BackupOutcome, BatchSummary, ExecutionSummary, RetentionInfo, SnapshotSource, StepTimings,
+};
+
+/// Output of one invocation: a single summary for one source, or the
+/// aggregate when several cache clusters are backed up
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Report {
+    Single(ExecutionSummary),
+    Batch(BatchSummary),
+}
+
+impl Report {
+    /// Why the invocation did not succeed; the report is printed either way
+    fn failure(&self) -> Option<String> {
+        match self {
+            Self::Single(summary) if summary.status == "Failed" => {
+                let step = summary
+                    .failed_step
+                    .map_or("unknown step", |step| step.as_str());
+                Some(format!("Backup failed at {step}: {}", summary.message))
+            }
+            Self::Batch(batch) if !batch.is_success() => Some(format!(
+                "{} of {} cache cluster backups did not succeed",
+                batch.failed + batch.skipped,
+                batch.results.len()
+            )),
+            Self::Single(_) | Self::Batch(_) => None,
+        }
+    }
+}
+
+/// A failed backup, carrying the summary of how far it got
+struct BackupFailure {
+    summary: ExecutionSummary,
+}
+
+/// Summary fields shared by every outcome of one source
+fn base_summary(
+    args: &Args,
+    source: &SnapshotSource,
+    status: &str,
+    message: String,
+) -> ExecutionSummary {
+    let (cache_cluster, replication_group) = match source {
+        SnapshotSource::CacheCluster(id) => (Some(id.clone()), None),
+        SnapshotSource::ReplicationGroup(id) => (None, Some(id.clone())),
+    };
+
+    ExecutionSummary {
+        status: status.to_string(),
+        message,
+        failed_step: None,
+        total_execution_time_seconds: 0.0,
+        step_timings: StepTimings::default(),
+        cache_cluster,
+        replication_group,
+        snapshot_name: None,
+        target_snapshot_name: None,
+        s3_location: None,
+        s3_locations: Vec::new(),
+        shards: Vec::new(),
+        verified_objects: Vec::new(),
+        s3_bucket: args.s3_bucket_name.clone(),
+        retention_info: None,
+        s3_tags: args.s3_tags.clone(),
+        kms_key_id: args.kms_key_id.clone(),
+        skipped_steps: Vec::new(),
+        region_copy: None,
+    }
+}
 
 /// Build the execution summary from a successful backup run.
 ///
@@ -20,41 +103,101 @@ use types::{ExecutionSummary, RetentionInfo, StepTimings};
 /// retention-info gating on `retention_count`) is unit-testable without AWS I/O.
 fn build_summary(
     args: &Args,
+    source: &SnapshotSource,
     step_timings: StepTimings,
     snapshot_name: Option<String>,
-    target_snapshot: String,
-    s3_location: String,
-    deleted_count: usize,
+    outcome: BackupOutcome,
     total_time: f64,
 ) -> ExecutionSummary {
     let retention_info = if args.retention_count > 0 {
         Some(RetentionInfo {
             enabled: true,
             retention_count: args.retention_count,
-            deleted_count,
+            deleted_count: outcome.deleted_count,
         })
     } else {
         None
     };
 
+    let mut message = "ElastiCache snapshot backup completed successfully".to_string();
+    if !outcome.verified_objects.is_empty() {
+        let total_bytes: i64 = outcome.verified_objects.iter().map(|o| o.size_bytes).sum();
+        message.push_str(&format!(
+            ", {} exported object(s) verified ({} bytes)",
+            outcome.verified_objects.len(),
+            total_bytes
+        ));
+    }
+    // A failed optional copy leaves the backup successful but must not go
+    // unnoticed
+    match &outcome.region_copy {
+        Some(copy) if copy.status == "Success" => {
+            message.push_str(&format!(", copied to {}", copy.region));
+        }
+        Some(copy) => message.push_str(&format!(", copy to {} failed", copy.region)),
+        None => {}
+    }
+    // Steps skipped because today's snapshot or export already existed are
+    // named, so their zero durations are not mistaken for measurements.
+    if !outcome.skipped_steps.is_empty() {
+        let skipped: Vec<&str> = outcome.skipped_steps.iter().map(|s| s.as_str()).collect();
+        message.push_str(&format!(
+            " (skipped, already done today: {})",
+            skipped.join(", ")
+        ));
+    }
+
+    let s3_locations = if outcome.shards.is_empty() {
+        vec![outcome.s3_location.clone()]
+    } else {
+        outcome
+            .shards
+            .iter()
+            .map(|shard| shard.s3_location.clone())
+            .collect()
+    };
+
     ExecutionSummary {
-        status: "Success".to_string(),
-        message: "ElastiCache snapshot backup completed successfully".to_string(),
         total_execution_time_seconds: total_time,
         step_timings,
-        cache_cluster: args.cache_cluster_id.clone(),
         snapshot_name,
-        target_snapshot_name: Some(target_snapshot),
-        s3_location: Some(s3_location),
-        s3_bucket: args.s3_bucket_name.clone(),
+        target_snapshot_name: Some(outcome.target_snapshot_name),
+        s3_location: Some(outcome.s3_location),
+        s3_locations,
+        shards: outcome.shards,
+        verified_objects: outcome.verified_objects,
         retention_info,
+        skipped_steps: outcome.skipped_steps,
+        region_copy: outcome.region_copy,
+        ..base_summary(args, source, "Success", message)
+    }
+}
+
+/// Build the execution summary of a backup that failed at `error.step`.
+///
+/// The step timings cover the steps that ran before the failure.
+fn failure_summary(
+    args: &Args,
+    source: &SnapshotSource,
+    step_timings: StepTimings,
+    snapshot_name: Option<String>,
+    error: &StepError,
+    total_time: f64,
+) -> ExecutionSummary {
+    ExecutionSummary {
+        failed_step: Some(error.step),
+        total_execution_time_seconds: total_time,
+        step_timings,
+        snapshot_name,
+        ..base_summary(args, source, "Failed", error.to_string())
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize tracing with configurable format
-    // Use JSON format if LOG_FORMAT=json, otherwise use pretty format
+    // Use JSON format if LOG_FORMAT=json, otherwise use pretty format.
+    // Logs and EMF lines go to stderr so stdout only carries the summary.
     let log_format = std::env::var("LOG_FORMAT").unwrap_or_else(|_| "pretty".to_string());
     let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
 
@@ -62,6 +205,7 @@ async fn main() -> Result<()> {
         "json" => {
             tracing_subscriber::fmt()
                 .with_env_filter(tracing_subscriber::EnvFilter::new(&log_level))
+                .with_writer(std::io::stderr)
                 .json()
                 .with_current_span(true)
                 .with_span_list(true)
@@ -70,6 +214,7 @@ async fn main() -> Result<()> {
         _ => {
             tracing_subscriber::fmt()
                 .with_env_filter(tracing_subscriber::EnvFilter::new(&log_level))
+                .with_writer(std::io::stderr)
                 .with_target(false)
                 .with_thread_ids(false)
                 .with_file(false)
@@ -78,104 +223,299 @@ async fn main() -> Result<()> {
         }
     }
 
+    #[cfg(feature = "lambda")]
+    if lambda::is_lambda_runtime() {
+        return lambda::run().await;
+    }
+
     let args = Args::parse();
+    let report = execute_all(&args).await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if let Some(reason) = report.failure() {
+        bail!(reason);
+    }
+    Ok(())
+}
 
-    let _span = info_span!(
-        "elasticache_backup",
-        cache_cluster_id = %args.cache_cluster_id,
-        s3_bucket_name = %args.s3_bucket_name,
-        region = %args.region
-    )
-    .entered();
+/// Back up every source of the invocation.
+///
+/// Shared by the CLI and the Lambda handler. A single source is reported by
+/// its `ExecutionSummary`, which names the failed step when the backup
+/// failed; several cache clusters are aggregated into a `BatchSummary` whose
+/// status tells whether any of them failed.
+async fn execute_all(args: &Args) -> Result<Report> {
+    info!(
+        snapshot_wait_timeout_minutes = args.snapshot_wait_timeout_minutes,
+        export_wait_timeout_minutes = args.export_wait_timeout_minutes,
+        poll_interval_seconds = args.poll_interval_seconds,
+        "Effective wait settings"
+    );
+    // Every source of the run writes under the same dated prefix, even when
+    // a backup finishes after midnight
+    let job_date = chrono::Utc::now()
+        .with_timezone(&snapshot::local_offset())
+        .date_naive();
+    info!(
+        s3_prefix = %args.s3_prefix.as_str(),
+        job_date = %job_date,
+        "Effective S3 prefix template"
+    );
+    match args.sources().as_slice() {
+        [] => bail!("No cache cluster ID given"),
+        [source] => match execute(args, source, job_date).await {
+            Ok(summary) | Err(BackupFailure { summary }) => Ok(Report::Single(summary)),
+        },
+        sources => Ok(Report::Batch(execute_batch(args, sources, job_date).await)),
+    }
+}
 
+/// Back up several cache clusters, at most `--parallel` at a time.
+///
+/// A failed cluster does not stop the others unless `--fail-fast` is set, in
+/// which case clusters not yet started are reported as `Skipped`. Backups
+/// already in flight always run to completion so no snapshot is left behind.
+async fn execute_batch(
+    args: &Args,
+    sources: &[SnapshotSource],
+    job_date: NaiveDate,
+) -> BatchSummary {
+    let start = Instant::now();
+    info!(
+        cluster_count = sources.len(),
+        parallel = args.parallel,
+        fail_fast = args.fail_fast,
+        "Backing up multiple cache clusters"
+    );
+
+    let args = Arc::new(args.clone());
+    let permits = Arc::new(Semaphore::new(usize::from(args.parallel)));
+    let failed = Arc::new(AtomicBool::new(false));
+    let mut tasks = JoinSet::new();
+    for (index, source) in sources.iter().cloned().enumerate() {
+        let (args, permits, failed) =
+            (Arc::clone(&args), Arc::clone(&permits), Arc::clone(&failed));
+        tasks.spawn(async move {
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .expect("semaphore is never closed");
+            if args.fail_fast && failed.load(Ordering::SeqCst) {
+                warn!(source_id = %source.id(), "Skipping backup after an earlier failure");
+                let message = "Skipped after an earlier backup failed (--fail-fast)".to_string();
+                return (index, base_summary(&args, &source, "Skipped", message));
+            }
+            match execute(&args, &source, job_date).await {
+                Ok(summary) => (index, summary),
+                Err(failure) => {
+                    failed.store(true, Ordering::SeqCst);
+                    (index, failure.summary)
+                }
+            }
+        });
+    }
+
+    let mut results: Vec<(usize, ExecutionSummary)> = Vec::with_capacity(sources.len());
+    while let Some(joined) = tasks.join_next().await {
+        results.push(joined.expect("backup task panicked"));
+    }
+    results.sort_by_key(|(index, _)| *index);
+
+    let batch = BatchSummary::new(
+        results.into_iter().map(|(_, summary)| summary).collect(),
+        start.elapsed().as_secs_f64(),
+    );
     info!(
-        cache_cluster_id = %args.cache_cluster_id,
+        status = %batch.status,
+        succeeded = batch.succeeded,
+        failed = batch.failed,
+        skipped = batch.skipped,
+        total_execution_seconds = batch.total_execution_time_seconds,
+        "Multi-cluster backup finished"
+    );
+    batch
+}
+
+/// Run one backup and shape its result into the execution summary.
+///
+/// Step timings are tracked per source, and a failure carries the summary of
+/// the steps that did run so a batch can still report it.
+async fn execute(
+    args: &Args,
+    source: &SnapshotSource,
+    job_date: NaiveDate,
+) -> std::result::Result<ExecutionSummary, BackupFailure> {
+    let span = info_span!(
+        "elasticache_backup",
+        source_id = %source.id(),
         s3_bucket_name = %args.s3_bucket_name,
-        region = %args.region,
-        "ElastiCache snapshot backup started"
+        region = %args.region
     );
 
-    let lambda_start_time = Instant::now();
-    let mut step_timings = StepTimings::default();
-    let mut snapshot_name: Option<String> = None;
-
-    match backup::run(&args, &mut step_timings, &mut snapshot_name).await {
-        Ok((target_snapshot, s3_location, deleted_count)) => {
-            let total_time = lambda_start_time.elapsed().as_secs_f64();
-
-            let summary = build_summary(
-                &args,
-                step_timings,
-                snapshot_name.clone(),
-                target_snapshot.clone(),
-                s3_location.clone(),
-                deleted_count,
-                total_time,
-            );
+    let (cache_cluster_id, replication_group_id) = match source {
+        SnapshotSource::CacheCluster(id) => (id.as_str(), ""),
+        SnapshotSource::ReplicationGroup(id) => ("", id.as_str()),
+    };
 
-            info!(
-                snapshot_creation_seconds = summary.step_timings.snapshot_creation,
-                snapshot_wait_seconds = summary.step_timings.snapshot_wait,
-                s3_export_seconds = summary.step_timings.s3_export,
-                export_wait_seconds = summary.step_timings.export_wait,
-                cleanup_seconds = summary.step_timings.cleanup,
-                retention_seconds = summary.step_timings.retention,
-                total_execution_seconds = total_time,
-                "Execution timing summary"
-            );
+    async {
+        info!(
+            cache_cluster_id,
+            replication_group_id,
+            s3_bucket_name = %args.s3_bucket_name,
+            region = %args.region,
+            "ElastiCache snapshot backup started"
+        );
 
-            info!(
-                status = "success",
-                snapshot_name = snapshot_name.as_deref().unwrap_or(""),
-                target_snapshot_name = %target_snapshot,
-                s3_location = %s3_location,
-                total_execution_seconds = total_time,
-                "Backup execution completed successfully"
-            );
+        let lambda_start_time = Instant::now();
+        let mut step_timings = StepTimings::default();
+        let mut snapshot_name: Option<String> = None;
 
-            println!("{}", serde_json::to_string_pretty(&summary)?);
-            Ok(())
-        }
-        Err(e) => {
-            let total_time = lambda_start_time.elapsed().as_secs_f64();
-
-            error!(
-                snapshot_creation_seconds = step_timings.snapshot_creation,
-                snapshot_wait_seconds = step_timings.snapshot_wait,
-                s3_export_seconds = step_timings.s3_export,
-                export_wait_seconds = step_timings.export_wait,
-                cleanup_seconds = step_timings.cleanup,
-                retention_seconds = step_timings.retention,
-                total_execution_seconds = total_time,
-                "Execution timing summary (error)"
+        let result = backup::run(
+            args,
+            source,
+            job_date,
+            &mut step_timings,
+            &mut snapshot_name,
+        )
+        .await;
+        if args.emit_emf {
+            let line = emf::metrics_line(
+                source,
+                &step_timings,
+                result
+                    .as_ref()
+                    .map_or(&[][..], |outcome| &outcome.skipped_steps),
+                lambda_start_time.elapsed().as_secs_f64(),
+                result.is_ok(),
+                chrono::Utc::now().timestamp_millis(),
             );
+            emf::write(&line);
+        }
+        if args.emit_metrics {
+            let run = metrics::RunMetrics {
+                timings: &step_timings,
+                skipped: result
+                    .as_ref()
+                    .map_or(&[][..], |outcome| &outcome.skipped_steps),
+                total_seconds: lambda_start_time.elapsed().as_secs_f64(),
+                success: result.is_ok(),
+                deleted_count: result.as_ref().ok().map(|outcome| outcome.deleted_count),
+            };
+            metrics::emit(args, source, &run).await;
+        }
 
-            error!(
-                status = "failed",
-                error = %e,
-                snapshot_name = snapshot_name.as_deref().unwrap_or(""),
-                total_execution_seconds = total_time,
-                "Backup execution failed"
-            );
+        match result {
+            Ok(outcome) => {
+                let total_time = lambda_start_time.elapsed().as_secs_f64();
+
+                let summary = build_summary(
+                    args,
+                    source,
+                    step_timings,
+                    snapshot_name.clone(),
+                    outcome,
+                    total_time,
+                );
 
-            Err(e)
+                info!(
+                    snapshot_creation_seconds = summary.step_timings.snapshot_creation,
+                    snapshot_wait_seconds = summary.step_timings.snapshot_wait,
+                    s3_export_seconds = summary.step_timings.s3_export,
+                    export_wait_seconds = summary.step_timings.export_wait,
+                    cleanup_seconds = summary.step_timings.cleanup,
+                    retention_seconds = summary.step_timings.retention,
+                    region_copy_seconds = summary.step_timings.region_copy,
+                    total_execution_seconds = total_time,
+                    "Execution timing summary"
+                );
+
+                info!(
+                    status = "success",
+                    snapshot_name = snapshot_name.as_deref().unwrap_or(""),
+                    target_snapshot_name = summary.target_snapshot_name.as_deref().unwrap_or(""),
+                    s3_location = summary.s3_location.as_deref().unwrap_or(""),
+                    shard_count = summary.shards.len(),
+                    total_execution_seconds = total_time,
+                    "Backup execution completed successfully"
+                );
+
+                Ok(summary)
+            }
+            Err(e) => {
+                let total_time = lambda_start_time.elapsed().as_secs_f64();
+
+                error!(
+                    snapshot_creation_seconds = step_timings.snapshot_creation,
+                    snapshot_wait_seconds = step_timings.snapshot_wait,
+                    s3_export_seconds = step_timings.s3_export,
+                    export_wait_seconds = step_timings.export_wait,
+                    cleanup_seconds = step_timings.cleanup,
+                    retention_seconds = step_timings.retention,
+                    region_copy_seconds = step_timings.region_copy,
+                    total_execution_seconds = total_time,
+                    "Execution timing summary (error)"
+                );
+
+                error!(
+                    status = "failed",
+                    failed_step = e.step.as_str(),
+                    error = %e,
+                    snapshot_name = snapshot_name.as_deref().unwrap_or(""),
+                    total_execution_seconds = total_time,
+                    "Backup execution failed"
+                );
+
+                let summary =
+                    failure_summary(args, source, step_timings, snapshot_name, &e, total_time);
+                Err(BackupFailure { summary })
+            }
         }
     }
+    .instrument(span)
+    .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use types::ShardExport;
 
     fn args(retention_count: u32) -> Args {
         Args {
-            cache_cluster_id: "cluster".to_string(),
+            cache_cluster_id: vec!["cluster".to_string()],
+            replication_group_id: None,
             s3_bucket_name: "bucket".to_string(),
+            s3_prefix: prefix::S3Prefix::parse(prefix::DEFAULT_S3_PREFIX).unwrap(),
             region: "ap-northeast-2".to_string(),
-            snapshot_timeout: 1800,
-            export_timeout: 300,
-            check_interval: 30,
+            snapshot_wait_timeout_minutes: 30,
+            export_wait_timeout_minutes: 5,
+            poll_interval_seconds: 30,
+            min_export_bytes: 1,
             retention_count,
+            s3_tags: Vec::new(),
+            kms_key_id: None,
+            emit_emf: false,
+            emit_metrics: false,
+            metrics_format: metrics::MetricsFormat::Api,
+            metrics_namespace: "ElastiCacheBackup".to_string(),
+            parallel: 1,
+            fail_fast: false,
+            force: false,
+            copy_to_region: None,
+            copy_bucket_name: None,
+            copy_kms_key_id: None,
+            copy_required: false,
+        }
+    }
+
+    fn outcome(target: &str, shards: Vec<ShardExport>, deleted_count: usize) -> BackupOutcome {
+        BackupOutcome {
+            target_snapshot_name: target.to_string(),
+            s3_location: format!("s3://bucket/{target}"),
+            shards,
+            verified_objects: Vec::new(),
+            deleted_count,
+            skipped_steps: Vec::new(),
+            region_copy: None,
         }
     }
 
@@ -183,15 +523,16 @@ mod tests {
     fn test_build_summary_without_retention() {
         let summary = build_summary(
             &args(0),
+            &SnapshotSource::CacheCluster("cluster".to_string()),
             StepTimings::default(),
             Some("snap".to_string()),
-            "snap-s3-export".to_string(),
-            "s3://bucket/snap-s3-export".to_string(),
-            0,
+            outcome("snap-s3-export", Vec::new(), 0),
             12.5,
         );
         assert_eq!(summary.status, "Success");
-        assert_eq!(summary.cache_cluster, "cluster");
+        assert_eq!(summary.cache_cluster.as_deref(), Some("cluster"));
+        assert!(summary.replication_group.is_none());
+        assert_eq!(summary.s3_locations, vec!["s3://bucket/snap-s3-export"]);
         assert_eq!(summary.s3_bucket, "bucket");
         assert_eq!(summary.snapshot_name.as_deref(), Some("snap"));
         assert_eq!(
@@ -206,11 +547,10 @@ mod tests {
     fn test_build_summary_with_retention() {
         let summary = build_summary(
             &args(5),
+            &SnapshotSource::CacheCluster("cluster".to_string()),
             StepTimings::default(),
             None,
-            "t".to_string(),
-            "s3://bucket/t".to_string(),
-            3,
+            outcome("t", Vec::new(), 3),
             0.0,
         );
         let info = summary.retention_info.expect("retention info present");
@@ -218,4 +558,180 @@ mod tests {
         assert_eq!(info.retention_count, 5);
         assert_eq!(info.deleted_count, 3);
     }
+
+    #[test]
+    fn test_build_summary_message_names_verified_and_skipped() {
+        let mut outcome = outcome("snap-s3-export", Vec::new(), 0);
+        outcome.skipped_steps = vec![types::Step::SnapshotCreation, types::Step::SnapshotWait];
+        outcome.verified_objects = [10, 20]
+            .into_iter()
+            .map(|size_bytes| types::VerifiedObject {
+                s3_location: format!("s3://bucket/snap-s3-export-{size_bytes}.rdb"),
+                size_bytes,
+                last_modified: None,
+            })
+            .collect();
+        let summary = build_summary(
+            &args(0),
+            &SnapshotSource::CacheCluster("cluster".to_string()),
+            StepTimings::default(),
+            Some("snap".to_string()),
+            outcome,
+            1.0,
+        );
+        assert_eq!(summary.status, "Success");
+        assert_eq!(summary.skipped_steps.len(), 2);
+        assert_eq!(summary.verified_objects.len(), 2);
+        assert!(
+            summary
+                .message
+                .contains(", 2 exported object(s) verified (30 bytes)")
+        );
+        assert!(
+            summary
+                .message
+                .ends_with("(skipped, already done today: snapshot_creation, snapshot_wait)")
+        );
+    }
+
+    #[test]
+    fn test_build_summary_reports_failed_region_copy() {
+        let mut outcome = outcome("snap-s3-export", Vec::new(), 0);
+        outcome.region_copy = Some(types::RegionCopy {
+            region: "us-west-2".to_string(),
+            status: "Failed".to_string(),
+            s3_locations: Vec::new(),
+            error: Some("access denied".to_string()),
+        });
+        let summary = build_summary(
+            &args(0),
+            &SnapshotSource::CacheCluster("cluster".to_string()),
+            StepTimings::default(),
+            None,
+            outcome,
+            1.0,
+        );
+        // The backup itself still succeeded
+        assert_eq!(summary.status, "Success");
+        assert!(summary.message.ends_with(", copy to us-west-2 failed"));
+        let copy = summary.region_copy.expect("region copy present");
+        assert_eq!(copy.error.as_deref(), Some("access denied"));
+    }
+
+    #[test]
+    fn test_build_summary_replication_group() {
+        let mut args = args(0);
+        args.cache_cluster_id.clear();
+        args.replication_group_id = Some("rg".to_string());
+        let shard = |id: &str| ShardExport {
+            node_group_id: id.to_string(),
+            s3_location: format!("s3://bucket/t/{id}/t-{id}.rdb"),
+            size_bytes: None,
+            duration_seconds: 0.5,
+        };
+
+        let summary = build_summary(
+            &args,
+            &args.sources()[0],
+            StepTimings::default(),
+            None,
+            outcome("t", vec![shard("0001"), shard("0002")], 0),
+            0.0,
+        );
+        assert!(summary.cache_cluster.is_none());
+        assert_eq!(summary.replication_group.as_deref(), Some("rg"));
+        assert_eq!(
+            summary.s3_locations,
+            vec![
+                "s3://bucket/t/0001/t-0001.rdb",
+                "s3://bucket/t/0002/t-0002.rdb"
+            ]
+        );
+        assert_eq!(summary.shards.len(), 2);
+    }
+
+    #[test]
+    fn test_failure_summary_json_names_failed_step() {
+        let steps = [
+            (types::FailedStep::SnapshotCreation, "snapshot_creation"),
+            (types::FailedStep::SnapshotWait, "snapshot_wait"),
+            (types::FailedStep::S3Export, "s3_export"),
+            (types::FailedStep::ExportWait, "export_wait"),
+            (types::FailedStep::ExportVerify, "export_verify"),
+            (types::FailedStep::RegionCopy, "region_copy"),
+        ];
+        for (step, name) in steps {
+            let error = StepError {
+                step,
+                error: anyhow::anyhow!("AccessDenied").context("Failed to copy snapshot"),
+            };
+            let timings = StepTimings {
+                snapshot_creation: 1.5,
+                ..Default::default()
+            };
+            let summary = failure_summary(
+                &args(3),
+                &SnapshotSource::CacheCluster("cluster".to_string()),
+                timings,
+                Some("snap".to_string()),
+                &error,
+                4.0,
+            );
+
+            let json = serde_json::to_value(Report::Single(summary)).unwrap();
+            assert_eq!(json["status"], "Failed");
+            assert_eq!(json["failed_step"], name);
+            assert_eq!(json["message"], "Failed to copy snapshot: AccessDenied");
+            assert_eq!(json["step_timings"]["snapshot_creation"], 1.5);
+            assert_eq!(json["step_timings"]["export_wait"], 0.0);
+            assert_eq!(json["total_execution_time_seconds"], 4.0);
+            assert_eq!(json["snapshot_name"], "snap");
+            assert_eq!(json["cache_cluster"], "cluster");
+            assert!(json.get("retention_info").is_none());
+        }
+    }
+
+    #[test]
+    fn test_report_failure() {
+        let source = SnapshotSource::CacheCluster("cluster".to_string());
+        let success = build_summary(
+            &args(0),
+            &source,
+            StepTimings::default(),
+            None,
+            outcome("t", Vec::new(), 0),
+            1.0,
+        );
+        assert!(Report::Single(success).failure().is_none());
+
+        let error = StepError {
+            step: types::FailedStep::SnapshotWait,
+            error: anyhow::anyhow!("Timed out"),
+        };
+        let failed = failure_summary(&args(0), &source, StepTimings::default(), None, &error, 1.0);
+        assert_eq!(
+            Report::Single(failed).failure().as_deref(),
+            Some("Backup failed at snapshot_wait: Timed out")
+        );
+
+        // Summaries of other outcomes leave out failed_step
+        let json = serde_json::to_value(base_summary(&args(0), &source, "Skipped", String::new()))
+            .unwrap();
+        assert!(json.get("failed_step").is_none());
+    }
+
+    #[test]
+    fn test_base_summary_for_unfinished_backup() {
+        let source = SnapshotSource::CacheCluster("redis-b".to_string());
+        let summary = base_summary(&args(3), &source, "Skipped", "skipped".to_string());
+        assert_eq!(summary.status, "Skipped");
+        assert_eq!(summary.cache_cluster.as_deref(), Some("redis-b"));
+        assert!(summary.target_snapshot_name.is_none());
+        assert!(summary.retention_info.is_none());
+
+        let json = serde_json::to_value(Report::Single(summary)).unwrap();
+        // The single-source report keeps the plain ExecutionSummary shape
+        assert_eq!(json["status"], "Skipped");
+        assert!(json.get("results").is_none());
+    }
 }
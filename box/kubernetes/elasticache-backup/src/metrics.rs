@@ -0,0 +1,314 @@
+//! Backup health metrics (`--emit-metrics`)
+//!
+//! Each run reports whether it succeeded and how long it took, dimensioned
+//! by the cache cluster or replication group ID. The metrics are published
+//! with `PutMetricData`, or written as an EMF line to the log stream (stderr)
+//! with `--metrics-format emf` where CloudWatch Logs picks them up (Lambda).
+
+use anyhow::{Context, Result};
+use aws_config::BehaviorVersion;
+use aws_sdk_cloudwatch::Client as CloudWatchClient;
+use aws_sdk_cloudwatch::primitives::DateTime;
+use aws_sdk_cloudwatch::types::{Dimension, MetricDatum, StandardUnit};
+use clap::ValueEnum;
+use tracing::{info, warn};
+
+use crate::cli::Args;
+use crate::emf;
+use crate::types::{SnapshotSource, Step, StepTimings};
+
+/// Where `--emit-metrics` sends the metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MetricsFormat {
+    /// Publish with the CloudWatch `PutMetricData` API
+    Api,
+    /// Write an Embedded Metric Format line to stderr
+    Emf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Seconds,
+    Count,
+}
+
+impl Unit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Seconds => "Seconds",
+            Self::Count => "Count",
+        }
+    }
+}
+
+/// One metric value of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    pub name: &'static str,
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Metric {
+    pub fn seconds(name: &'static str, value: f64) -> Self {
+        Self {
+            name,
+            value,
+            unit: Unit::Seconds,
+        }
+    }
+
+    pub fn count(name: &'static str, value: f64) -> Self {
+        Self {
+            name,
+            value,
+            unit: Unit::Count,
+        }
+    }
+}
+
+/// Result of one backup run as reported by `--emit-metrics`
+pub struct RunMetrics<'a> {
+    pub timings: &'a StepTimings,
+    pub skipped: &'a [Step],
+    pub total_seconds: f64,
+    pub success: bool,
+    /// S3 objects removed by retention; `None` when the run failed before it
+    pub deleted_count: Option<usize>,
+}
+
+impl RunMetrics<'_> {
+    /// Metrics of the run.
+    ///
+    /// The snapshot duration covers creation and wait, the export duration
+    /// covers the copy and its wait. A duration whose steps were all skipped
+    /// by an idempotent rerun is left out rather than reported as zero.
+    pub fn metrics(&self) -> Vec<Metric> {
+        let ran = |steps: &[Step]| steps.iter().any(|step| !self.skipped.contains(step));
+
+        let mut metrics = vec![Metric::count(
+            "BackupSuccess",
+            u8::from(self.success).into(),
+        )];
+        if ran(&[Step::SnapshotCreation, Step::SnapshotWait]) {
+            metrics.push(Metric::seconds(
+                "SnapshotDurationSeconds",
+                self.timings.snapshot_creation + self.timings.snapshot_wait,
+            ));
+        }
+        if ran(&[Step::S3Export, Step::ExportWait]) {
+            metrics.push(Metric::seconds(
+                "ExportDurationSeconds",
+                self.timings.s3_export + self.timings.export_wait,
+            ));
+        }
+        metrics.push(Metric::seconds("TotalDurationSeconds", self.total_seconds));
+        if let Some(deleted_count) = self.deleted_count {
+            metrics.push(Metric::count("DeletedSnapshots", deleted_count as f64));
+        }
+        metrics
+    }
+}
+
+/// Publish the metrics with `PutMetricData`
+pub async fn publish(
+    client: &CloudWatchClient,
+    namespace: &str,
+    source: &SnapshotSource,
+    metrics: &[Metric],
+    timestamp_ms: i64,
+) -> Result<()> {
+    let dimension = Dimension::builder()
+        .name(emf::dimension_name(source))
+        .value(source.id())
+        .build();
+    let data = metrics
+        .iter()
+        .map(|metric| {
+            MetricDatum::builder()
+                .metric_name(metric.name)
+                .value(metric.value)
+                .unit(match metric.unit {
+                    Unit::Seconds => StandardUnit::Seconds,
+                    Unit::Count => StandardUnit::Count,
+                })
+                .timestamp(DateTime::from_millis(timestamp_ms))
+                .dimensions(dimension.clone())
+                .build()
+        })
+        .collect();
+
+    client
+        .put_metric_data()
+        .namespace(namespace)
+        .set_metric_data(Some(data))
+        .send()
+        .await
+        .context("Failed to publish CloudWatch metrics")?;
+    Ok(())
+}
+
+/// Report the run's metrics in the configured format.
+///
+/// Metrics never fail the backup; a publishing error is logged as a warning.
+pub async fn emit(args: &Args, source: &SnapshotSource, run: &RunMetrics<'_>) {
+    let metrics = run.metrics();
+    let timestamp_ms = chrono::Utc::now().timestamp_millis();
+
+    match args.metrics_format {
+        MetricsFormat::Emf => {
+            let line = emf::render(&args.metrics_namespace, source, &metrics, timestamp_ms);
+            emf::write(&line);
+        }
+        MetricsFormat::Api => {
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(aws_config::Region::new(args.region.clone()))
+                .load()
+                .await;
+            let client = CloudWatchClient::new(&config);
+            match publish(
+                &client,
+                &args.metrics_namespace,
+                source,
+                &metrics,
+                timestamp_ms,
+            )
+            .await
+            {
+                Ok(()) => info!(
+                    namespace = %args.metrics_namespace,
+                    metric_count = metrics.len(),
+                    "CloudWatch metrics published"
+                ),
+                Err(e) => warn!(
+                    namespace = %args.metrics_namespace,
+                    error = %format!("{e:#}"),
+                    "Failed to publish CloudWatch metrics, continuing"
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_cloudwatch::Client;
+    use aws_sdk_cloudwatch::operation::put_metric_data::PutMetricDataOutput;
+    use aws_smithy_mocks::{RuleMode, mock, mock_client};
+
+    fn names(metrics: &[Metric]) -> Vec<&str> {
+        metrics.iter().map(|m| m.name).collect()
+    }
+
+    #[test]
+    fn test_run_metrics_of_successful_run() {
+        let timings = StepTimings {
+            snapshot_creation: 1.0,
+            snapshot_wait: 120.0,
+            s3_export: 2.0,
+            export_wait: 30.0,
+            ..Default::default()
+        };
+        let metrics = RunMetrics {
+            timings: &timings,
+            skipped: &[],
+            total_seconds: 160.0,
+            success: true,
+            deleted_count: Some(2),
+        }
+        .metrics();
+
+        assert_eq!(
+            metrics,
+            vec![
+                Metric::count("BackupSuccess", 1.0),
+                Metric::seconds("SnapshotDurationSeconds", 121.0),
+                Metric::seconds("ExportDurationSeconds", 32.0),
+                Metric::seconds("TotalDurationSeconds", 160.0),
+                Metric::count("DeletedSnapshots", 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_metrics_omit_skipped_and_unknown_values() {
+        let timings = StepTimings::default();
+        let rerun = RunMetrics {
+            timings: &timings,
+            skipped: &[
+                Step::SnapshotCreation,
+                Step::SnapshotWait,
+                Step::S3Export,
+                Step::ExportWait,
+            ],
+            total_seconds: 3.0,
+            success: true,
+            deleted_count: Some(0),
+        };
+        assert_eq!(
+            names(&rerun.metrics()),
+            vec!["BackupSuccess", "TotalDurationSeconds", "DeletedSnapshots"]
+        );
+
+        let failed = RunMetrics {
+            timings: &timings,
+            skipped: &[],
+            total_seconds: 3.0,
+            success: false,
+            deleted_count: None,
+        };
+        let metrics = failed.metrics();
+        assert_eq!(metrics[0], Metric::count("BackupSuccess", 0.0));
+        assert!(!names(&metrics).contains(&"DeletedSnapshots"));
+    }
+
+    #[tokio::test]
+    async fn test_publish_sends_dimensioned_metrics() {
+        let put = mock!(Client::put_metric_data)
+            .match_requests(|req| {
+                req.namespace() == Some("ElastiCacheBackup")
+                    && req.metric_data().len() == 2
+                    && req.metric_data().iter().all(|datum| {
+                        datum.dimensions().first().is_some_and(|d| {
+                            d.name() == Some("ReplicationGroupId") && d.value() == Some("rg")
+                        })
+                    })
+            })
+            .then_output(|| PutMetricDataOutput::builder().build());
+        let client = mock_client!(aws_sdk_cloudwatch, RuleMode::MatchAny, &[&put]);
+
+        publish(
+            &client,
+            "ElastiCacheBackup",
+            &SnapshotSource::ReplicationGroup("rg".to_string()),
+            &[
+                Metric::count("BackupSuccess", 1.0),
+                Metric::seconds("TotalDurationSeconds", 4.5),
+            ],
+            1_700_000_000_000,
+        )
+        .await
+        .unwrap();
+        assert_eq!(put.num_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_error_is_returned() {
+        let put = mock!(Client::put_metric_data)
+            .sequence()
+            .http_status(500, None)
+            .build();
+        let client = mock_client!(aws_sdk_cloudwatch, RuleMode::MatchAny, &[&put]);
+
+        let result = publish(
+            &client,
+            "ElastiCacheBackup",
+            &SnapshotSource::CacheCluster("redis".to_string()),
+            &[Metric::count("BackupSuccess", 0.0)],
+            0,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+}
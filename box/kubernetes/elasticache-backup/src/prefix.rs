@@ -0,0 +1,230 @@
+//! S3 key prefix template of the exported RDB files (`--s3-prefix`)
+//!
+//! The template is expanded with the job's start date, so exports land in
+//! dated folders such as `elasticache/redis-001/2025/01/`. Retention lists
+//! the part of the template that does not depend on the date and matches
+//! keys against the rest, which finds backups of every earlier day.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::error::BackupError;
+
+/// Template used when `--s3-prefix` is not given
+pub const DEFAULT_S3_PREFIX: &str = "elasticache/{cluster}/{yyyy}/{mm}/";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Cluster,
+    Year,
+    Month,
+    Day,
+}
+
+/// Parsed `--s3-prefix` template with `{cluster}`, `{yyyy}`, `{mm}`, and
+/// `{dd}` placeholders. An empty template writes to the bucket root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Prefix {
+    template: String,
+    segments: Vec<Segment>,
+}
+
+impl S3Prefix {
+    /// Parse a template, rejecting unknown or unclosed placeholders so a
+    /// typo fails at startup instead of producing a literal `{...}` key.
+    pub fn parse(template: &str) -> Result<Self, BackupError> {
+        if template.starts_with('/') {
+            return Err(BackupError::InvalidS3Prefix(format!(
+                "'{template}' must not start with '/'"
+            )));
+        }
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = template;
+        while let Some(c) = rest.chars().next() {
+            match c {
+                '{' => {
+                    let end = rest.find('}').ok_or_else(|| {
+                        BackupError::InvalidS3Prefix(format!(
+                            "'{template}' has an unclosed placeholder"
+                        ))
+                    })?;
+                    let segment = match &rest[1..end] {
+                        "cluster" => Segment::Cluster,
+                        "yyyy" => Segment::Year,
+                        "mm" => Segment::Month,
+                        "dd" => Segment::Day,
+                        other => {
+                            return Err(BackupError::InvalidS3Prefix(format!(
+                                "unknown placeholder '{{{other}}}', expected {{cluster}}, {{yyyy}}, {{mm}}, or {{dd}}"
+                            )));
+                        }
+                    };
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(segment);
+                    rest = &rest[end + 1..];
+                }
+                '}' => {
+                    return Err(BackupError::InvalidS3Prefix(format!(
+                        "'{template}' has an unmatched '}}'"
+                    )));
+                }
+                _ => {
+                    literal.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+            segments,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Key prefix of the exports of `cluster` taken on `date`
+    pub fn expand(&self, cluster: &str, date: NaiveDate) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(s) => s.clone(),
+                Segment::Cluster => cluster.to_string(),
+                Segment::Year => format!("{:04}", date.year()),
+                Segment::Month => format!("{:02}", date.month()),
+                Segment::Day => format!("{:02}", date.day()),
+            })
+            .collect()
+    }
+
+    /// Prefix to list when looking for every backup of `cluster`.
+    ///
+    /// This is the template up to its first date placeholder. A template
+    /// without one is followed by the snapshot name, which starts with the
+    /// cluster ID.
+    pub fn list_prefix(&self, cluster: &str) -> String {
+        let mut prefix = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(s) => prefix.push_str(s),
+                Segment::Cluster => prefix.push_str(cluster),
+                Segment::Year | Segment::Month | Segment::Day => return prefix,
+            }
+        }
+        prefix.push_str(cluster);
+        prefix.push('-');
+        prefix
+    }
+
+    /// Whether `key` is an export of `cluster` written under this template
+    /// on any date
+    pub fn matches(&self, key: &str, cluster: &str) -> bool {
+        let mut rest = key;
+        for segment in &self.segments {
+            let next = match segment {
+                Segment::Literal(s) => rest.strip_prefix(s.as_str()),
+                Segment::Cluster => rest.strip_prefix(cluster),
+                Segment::Year => strip_digits(rest, 4),
+                Segment::Month | Segment::Day => strip_digits(rest, 2),
+            };
+            match next {
+                Some(next) => rest = next,
+                None => return false,
+            }
+        }
+        rest.strip_prefix(cluster)
+            .is_some_and(|name| name.starts_with('-'))
+    }
+}
+
+fn strip_digits(s: &str, count: usize) -> Option<&str> {
+    let digits = s.get(..count)?;
+    digits
+        .bytes()
+        .all(|b| b.is_ascii_digit())
+        .then_some(&s[count..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_expand_default_template() {
+        let prefix = S3Prefix::parse(DEFAULT_S3_PREFIX).unwrap();
+        assert_eq!(
+            prefix.expand("redis-001", date(2025, 1, 5)),
+            "elasticache/redis-001/2025/01/"
+        );
+
+        let daily = S3Prefix::parse("backups/{yyyy}-{mm}-{dd}/{cluster}/").unwrap();
+        assert_eq!(
+            daily.expand("rg", date(2024, 12, 31)),
+            "backups/2024-12-31/rg/"
+        );
+        assert_eq!(
+            S3Prefix::parse("").unwrap().expand("rg", date(2025, 1, 1)),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        for invalid in ["/elasticache/", "{cluster", "{yy}/", "a}b", "{Cluster}"] {
+            assert!(
+                S3Prefix::parse(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_prefix_stops_at_first_date() {
+        let prefix = S3Prefix::parse(DEFAULT_S3_PREFIX).unwrap();
+        assert_eq!(prefix.list_prefix("redis"), "elasticache/redis/");
+
+        let dated_first = S3Prefix::parse("{yyyy}/{cluster}/").unwrap();
+        assert_eq!(dated_first.list_prefix("redis"), "");
+
+        let flat = S3Prefix::parse("").unwrap();
+        assert_eq!(flat.list_prefix("redis"), "redis-");
+        let static_folder = S3Prefix::parse("backups/{cluster}/").unwrap();
+        assert_eq!(static_folder.list_prefix("redis"), "backups/redis/redis-");
+    }
+
+    #[test]
+    fn test_matches_keys_of_any_date() {
+        let prefix = S3Prefix::parse(DEFAULT_S3_PREFIX).unwrap();
+        for key in [
+            "elasticache/redis/2024/12/redis-20241231-s3-export-0001.rdb",
+            "elasticache/redis/2025/01/redis-20250101-s3-export/0001/redis-20250101-s3-export-0001.rdb",
+        ] {
+            assert!(prefix.matches(key, "redis"), "{key} should match");
+        }
+        for key in [
+            // Another cluster whose ID starts with this one
+            "elasticache/redis-2/2025/01/redis-2-20250101-s3-export-0001.rdb",
+            "elasticache/redis/2025/1/redis-20250101-s3-export-0001.rdb",
+            "elasticache/redis/2025/01/notes.txt",
+            "redis-20250101-s3-export-0001.rdb",
+        ] {
+            assert!(!prefix.matches(key, "redis"), "{key} should not match");
+        }
+
+        let flat = S3Prefix::parse("").unwrap();
+        assert!(flat.matches("redis-20250101-s3-export-0001.rdb", "redis"));
+    }
+}
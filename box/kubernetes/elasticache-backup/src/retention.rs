@@ -1,9 +1,12 @@
 use anyhow::{Context, Result};
 use aws_sdk_s3::Client as S3Client;
 use chrono::{DateTime, Utc};
+use std::collections::HashSet;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
+use crate::prefix::S3Prefix;
+
 /// S3 object metadata for retention management
 #[derive(Debug, Clone)]
 struct S3Object {
@@ -12,11 +15,29 @@ struct S3Object {
     size: i64,
 }
 
-/// Clean up old snapshots in S3 based on retention policy
+/// Export target a key belongs to.
+///
+/// A replication group export writes one file per shard, either as
+/// `<target>-<shard>.rdb` or moved under `<target>/<shard>/`, and all of
+/// them count as one backup. Keys without the export suffix stand alone.
+fn backup_name(key: &str) -> &str {
+    const EXPORT_SUFFIX: &str = "-s3-export";
+    key.find(EXPORT_SUFFIX)
+        .map(|i| &key[..i + EXPORT_SUFFIX.len()])
+        .unwrap_or(key)
+}
+
+/// Clean up old snapshots in S3 based on retention policy.
+///
+/// `retention_count` is the number of backups kept; every shard file of a
+/// kept backup is kept with it. Backups are looked up with the same
+/// `--s3-prefix` template they were written with, so objects in the dated
+/// folders of earlier days are found as well.
 pub async fn cleanup_old_snapshots(
     s3_client: &S3Client,
     bucket_name: &str,
     cache_cluster_id: &str,
+    s3_prefix: &S3Prefix,
     retention_count: u32,
 ) -> Result<usize> {
     if retention_count == 0 {
@@ -35,10 +56,11 @@ pub async fn cleanup_old_snapshots(
         "Starting S3 snapshot retention cleanup"
     );
 
-    // List all objects with the cache cluster prefix
-    let prefix = format!("{}-", cache_cluster_id);
+    // List everything under the date-independent part of the template
+    let prefix = s3_prefix.list_prefix(cache_cluster_id);
     debug!(
         prefix = %prefix,
+        s3_prefix = %s3_prefix.as_str(),
         "Listing S3 objects with prefix"
     );
 
@@ -67,6 +89,7 @@ pub async fn cleanup_old_snapshots(
         for obj in contents {
             if let (Some(key), Some(last_modified), Some(size)) =
                 (obj.key(), obj.last_modified(), obj.size())
+                && s3_prefix.matches(key, cache_cluster_id)
             {
                 objects.push(S3Object {
                     key: key.to_string(),
@@ -102,6 +125,15 @@ pub async fn cleanup_old_snapshots(
     // Sort by last modified date (newest first)
     objects.sort_by_key(|o| std::cmp::Reverse(o.last_modified));
 
+    // Keep the newest backups, each with all of its shard files
+    let mut kept_backups = HashSet::new();
+    for obj in &objects {
+        if kept_backups.len() == retention_count as usize {
+            break;
+        }
+        kept_backups.insert(backup_name(&obj.key));
+    }
+
     // Log the snapshots we found
     for (idx, obj) in objects.iter().enumerate() {
         debug!(
@@ -109,13 +141,16 @@ pub async fn cleanup_old_snapshots(
             key = %obj.key,
             last_modified = %obj.last_modified.format("%Y-%m-%d %H:%M:%S UTC"),
             size_bytes = obj.size,
-            status = if idx < retention_count as usize { "KEEP" } else { "DELETE" },
+            status = if kept_backups.contains(backup_name(&obj.key)) { "KEEP" } else { "DELETE" },
             "Snapshot status"
         );
     }
 
     // Determine which objects to delete
-    let objects_to_delete: Vec<_> = objects.iter().skip(retention_count as usize).collect();
+    let objects_to_delete: Vec<_> = objects
+        .iter()
+        .filter(|obj| !kept_backups.contains(backup_name(&obj.key)))
+        .collect();
 
     let delete_count = objects_to_delete.len();
     let keep_count = total_objects - delete_count;
@@ -225,10 +260,15 @@ mod tests {
             .build()
     }
 
+    /// Objects written to the bucket root, as before `--s3-prefix`
+    fn flat() -> S3Prefix {
+        S3Prefix::parse("").unwrap()
+    }
+
     #[tokio::test]
     async fn test_retention_zero_early_return() {
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[]);
-        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", 0)
+        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 0)
             .await
             .unwrap();
         assert_eq!(deleted, 0);
@@ -239,7 +279,7 @@ mod tests {
         let list = mock!(Client::list_objects_v2)
             .then_output(|| ListObjectsV2Output::builder().is_truncated(false).build());
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list]);
-        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", 3)
+        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 3)
             .await
             .unwrap();
         assert_eq!(deleted, 0);
@@ -255,7 +295,7 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list]);
-        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", 5)
+        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 5)
             .await
             .unwrap();
         assert_eq!(deleted, 0);
@@ -275,10 +315,97 @@ mod tests {
         let delete =
             mock!(Client::delete_object).then_output(|| DeleteObjectOutput::builder().build());
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &delete]);
-        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", 1)
+        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 1)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 2);
+    }
+
+    #[test]
+    fn test_backup_name_groups_shards() {
+        assert_eq!(
+            backup_name("rg-20250101-s3-export-0001.rdb"),
+            "rg-20250101-s3-export"
+        );
+        assert_eq!(
+            backup_name("rg-20250101-s3-export/0002/rg-20250101-s3-export-0002.rdb"),
+            "rg-20250101-s3-export"
+        );
+        assert_eq!(backup_name("cluster-1"), "cluster-1");
+    }
+
+    #[tokio::test]
+    async fn test_retention_keeps_every_shard_of_kept_backups() {
+        let list = mock!(Client::list_objects_v2).then_output(|| {
+            ListObjectsV2Output::builder()
+                .contents(obj(
+                    "rg-20250101-s3-export/0001/rg-20250101-s3-export-0001.rdb",
+                    100,
+                ))
+                .contents(obj(
+                    "rg-20250101-s3-export/0002/rg-20250101-s3-export-0002.rdb",
+                    110,
+                ))
+                .contents(obj(
+                    "rg-20250102-s3-export/0001/rg-20250102-s3-export-0001.rdb",
+                    200,
+                ))
+                .contents(obj(
+                    "rg-20250102-s3-export/0002/rg-20250102-s3-export-0002.rdb",
+                    210,
+                ))
+                .is_truncated(false)
+                .build()
+        });
+        let delete = mock!(Client::delete_object)
+            .match_requests(|req| req.key().is_some_and(|k| k.starts_with("rg-20250101-")))
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &delete]);
+        // retention 1 => both shards of the older backup deleted
+        let deleted = cleanup_old_snapshots(&client, "bucket", "rg", &flat(), 1)
             .await
             .unwrap();
         assert_eq!(deleted, 2);
+        assert_eq!(delete.num_calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retention_finds_backups_under_dated_prefixes() {
+        let s3_prefix = S3Prefix::parse(crate::prefix::DEFAULT_S3_PREFIX).unwrap();
+        let list = mock!(Client::list_objects_v2)
+            .match_requests(|req| req.prefix() == Some("elasticache/redis/"))
+            .then_output(|| {
+                ListObjectsV2Output::builder()
+                    .contents(obj(
+                        "elasticache/redis/2024/12/redis-20241231-s3-export-0001.rdb",
+                        100,
+                    ))
+                    .contents(obj(
+                        "elasticache/redis/2025/01/redis-20250101-s3-export-0001.rdb",
+                        200,
+                    ))
+                    .contents(obj(
+                        "elasticache/redis/2025/01/redis-20250102-s3-export-0001.rdb",
+                        300,
+                    ))
+                    // Not written by this tool, never deleted
+                    .contents(obj("elasticache/redis/README.txt", 50))
+                    .is_truncated(false)
+                    .build()
+            });
+        let delete = mock!(Client::delete_object)
+            .match_requests(|req| {
+                req.key() == Some("elasticache/redis/2024/12/redis-20241231-s3-export-0001.rdb")
+            })
+            .then_output(|| DeleteObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &delete]);
+
+        // retention 2 => only the December backup is deleted
+        let deleted = cleanup_old_snapshots(&client, "bucket", "redis", &s3_prefix, 2)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 1);
+        assert_eq!(delete.num_calls(), 1);
     }
 
     #[tokio::test]
@@ -306,7 +433,7 @@ mod tests {
             mock!(Client::delete_object).then_output(|| DeleteObjectOutput::builder().build());
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&page1, &page2, &delete]);
         // 3 total, retention 1 => 2 deleted
-        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", 1)
+        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 1)
             .await
             .unwrap();
         assert_eq!(deleted, 2);
@@ -327,7 +454,7 @@ mod tests {
             .http_status(500, None)
             .build();
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list, &delete]);
-        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", 1)
+        let deleted = cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 1)
             .await
             .unwrap();
         assert_eq!(deleted, 0);
@@ -341,7 +468,7 @@ mod tests {
             .build();
         let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&list]);
         assert!(
-            cleanup_old_snapshots(&client, "bucket", "cluster", 1)
+            cleanup_old_snapshots(&client, "bucket", "cluster", &flat(), 1)
                 .await
                 .is_err()
         );
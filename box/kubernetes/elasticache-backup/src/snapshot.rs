@@ -6,36 +6,97 @@ use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 use crate::error::BackupError;
+use crate::types::SnapshotSource;
 
-/// Create an ElastiCache snapshot
-pub async fn create_snapshot(client: &ElastiCacheClient, cache_cluster_id: &str) -> Result<String> {
-    let snapshot_start_time = Instant::now();
+/// Name of today's snapshot of a cache cluster or replication group
+pub fn daily_snapshot_name(source: &SnapshotSource) -> String {
+    // Generate snapshot name with cluster or replication group ID and date
+    let date_str = Utc::now()
+        .with_timezone(&local_offset())
+        .format("%Y%m%d")
+        .to_string();
+    format!("{}-{}", source.id(), date_str)
+}
 
-    // Generate snapshot name with cluster ID and date
-    // Use TZ environment variable to determine timezone offset (default: UTC+9 for Asia/Seoul)
+/// Timezone of snapshot names and S3 prefix dates, from `TZ_OFFSET_HOURS`
+/// (default: UTC+9 for Asia/Seoul)
+pub fn local_offset() -> FixedOffset {
     let tz_offset = std::env::var("TZ_OFFSET_HOURS")
         .ok()
         .and_then(|v| v.parse::<i32>().ok())
-        .unwrap_or(9); // Default to UTC+9 (Asia/Seoul)
+        .unwrap_or(9);
 
-    let timezone =
-        FixedOffset::east_opt(tz_offset * 3600).expect("Failed to create timezone offset");
-    let date_str = Utc::now()
-        .with_timezone(&timezone)
-        .format("%Y%m%d")
-        .to_string();
-    let snapshot_name = format!("{}-{}", cache_cluster_id, date_str);
+    FixedOffset::east_opt(tz_offset * 3600).expect("Failed to create timezone offset")
+}
 
-    info!(
-        cache_cluster_id = %cache_cluster_id,
-        snapshot_name = %snapshot_name,
-        "Creating ElastiCache snapshot"
-    );
+/// Find a snapshot of the source with the given name.
+///
+/// Lists the source's snapshots rather than describing the name directly,
+/// because `DescribeSnapshots` fails with `SnapshotNotFoundFault` for an
+/// unknown name.
+pub async fn find_existing(
+    client: &ElastiCacheClient,
+    source: &SnapshotSource,
+    snapshot_name: &str,
+) -> Result<Option<Snapshot>> {
+    let mut marker: Option<String> = None;
+    loop {
+        let request = match source {
+            SnapshotSource::CacheCluster(id) => client.describe_snapshots().cache_cluster_id(id),
+            SnapshotSource::ReplicationGroup(id) => {
+                client.describe_snapshots().replication_group_id(id)
+            }
+        };
+        let response = request
+            .set_marker(marker.take())
+            .send()
+            .await
+            .context("Failed to list existing snapshots")?;
+
+        if let Some(snapshot) = response
+            .snapshots()
+            .iter()
+            .find(|s| s.snapshot_name() == Some(snapshot_name))
+        {
+            return Ok(Some(snapshot.clone()));
+        }
+
+        match response.marker() {
+            Some(next) if !next.is_empty() => marker = Some(next.to_string()),
+            _ => return Ok(None),
+        }
+    }
+}
 
-    let response = client
-        .create_snapshot()
-        .cache_cluster_id(cache_cluster_id)
-        .snapshot_name(&snapshot_name)
+/// Create an ElastiCache snapshot of a cache cluster or replication group
+pub async fn create_snapshot(
+    client: &ElastiCacheClient,
+    source: &SnapshotSource,
+    snapshot_name: &str,
+) -> Result<()> {
+    let snapshot_start_time = Instant::now();
+
+    let request = match source {
+        SnapshotSource::CacheCluster(id) => {
+            info!(
+                cache_cluster_id = %id,
+                snapshot_name = %snapshot_name,
+                "Creating ElastiCache snapshot"
+            );
+            client.create_snapshot().cache_cluster_id(id)
+        }
+        SnapshotSource::ReplicationGroup(id) => {
+            info!(
+                replication_group_id = %id,
+                snapshot_name = %snapshot_name,
+                "Creating ElastiCache replication group snapshot"
+            );
+            client.create_snapshot().replication_group_id(id)
+        }
+    };
+
+    let response = request
+        .snapshot_name(snapshot_name)
         .send()
         .await
         .context("Failed to create ElastiCache snapshot")?;
@@ -51,32 +112,32 @@ pub async fn create_snapshot(client: &ElastiCacheClient, cache_cluster_id: &str)
         );
     }
 
-    Ok(snapshot_name)
+    Ok(())
 }
 
 /// Wait for snapshot to become available
 pub async fn wait_for_completion(
     client: &ElastiCacheClient,
     snapshot_name: &str,
-    max_wait_time: u64,
-    check_interval: u64,
+    timeout: Duration,
+    poll_interval: Duration,
 ) -> Result<Snapshot> {
     let wait_start_time = Instant::now();
     let mut checks_performed = 0;
 
     info!(
         snapshot_name = %snapshot_name,
-        max_wait_time_seconds = max_wait_time,
-        check_interval_seconds = check_interval,
+        timeout_seconds = timeout.as_secs(),
+        poll_interval_seconds = poll_interval.as_secs(),
         "Waiting for snapshot completion"
     );
 
     loop {
-        if wait_start_time.elapsed().as_secs() >= max_wait_time {
-            return Err(BackupError::Timeout(format!(
-                "Snapshot completion timeout after {:.1}s",
-                wait_start_time.elapsed().as_secs_f64()
-            ))
+        if wait_start_time.elapsed() >= timeout {
+            return Err(BackupError::SnapshotWaitTimeout {
+                snapshot_name: snapshot_name.to_string(),
+                elapsed_seconds: wait_start_time.elapsed().as_secs_f64(),
+            }
             .into());
         }
 
@@ -138,7 +199,7 @@ pub async fn wait_for_completion(
             );
         }
 
-        tokio::time::sleep(Duration::from_secs(check_interval)).await;
+        tokio::time::sleep(poll_interval).await;
     }
 }
 
@@ -243,6 +304,10 @@ mod tests {
     use aws_sdk_elasticache::types::error::{CacheClusterNotFoundFault, SnapshotNotFoundFault};
     use aws_smithy_mocks::{RuleMode, mock, mock_client};
 
+    fn cluster(id: &str) -> SnapshotSource {
+        SnapshotSource::CacheCluster(id.to_string())
+    }
+
     fn snap(status: &str) -> Snapshot {
         Snapshot::builder()
             .snapshot_status(status)
@@ -261,25 +326,76 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        let name = create_snapshot(&client, "my-cluster").await.unwrap();
-        assert!(name.starts_with("my-cluster-"));
+        create_snapshot(&client, &cluster("my-cluster"), "my-cluster-20261016")
+            .await
+            .unwrap();
+        assert_eq!(rule.num_calls(), 1);
     }
 
     #[tokio::test]
-    async fn test_create_snapshot_with_tz_env() {
+    async fn test_create_snapshot_replication_group() {
+        let rule = mock!(Client::create_snapshot)
+            .match_requests(|req| {
+                req.replication_group_id() == Some("my-rg") && req.cache_cluster_id().is_none()
+            })
+            .then_output(|| CreateSnapshotOutput::builder().build());
+        let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
+        let source = SnapshotSource::ReplicationGroup("my-rg".to_string());
+        create_snapshot(&client, &source, "my-rg-20261016")
+            .await
+            .unwrap();
+        assert_eq!(rule.num_calls(), 1);
+    }
+
+    #[test]
+    fn test_daily_snapshot_name_with_tz_env() {
         unsafe {
             std::env::set_var("TZ_OFFSET_HOURS", "0");
         }
-        let rule =
-            mock!(Client::create_snapshot).then_output(|| CreateSnapshotOutput::builder().build());
-        let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        let name = create_snapshot(&client, "c").await.unwrap();
-        assert!(name.starts_with("c-"));
+        let name = daily_snapshot_name(&cluster("c"));
+        assert_eq!(name, format!("c-{}", Utc::now().format("%Y%m%d")));
         unsafe {
             std::env::remove_var("TZ_OFFSET_HOURS");
         }
     }
 
+    #[tokio::test]
+    async fn test_find_existing_pages_by_source() {
+        let page1 = mock!(Client::describe_snapshots)
+            .match_requests(|req| req.cache_cluster_id() == Some("c") && req.marker().is_none())
+            .then_output(|| {
+                DescribeSnapshotsOutput::builder()
+                    .snapshots(Snapshot::builder().snapshot_name("c-20261015").build())
+                    .marker("next")
+                    .build()
+            });
+        let page2 = mock!(Client::describe_snapshots)
+            .match_requests(|req| req.marker() == Some("next"))
+            .then_output(|| {
+                DescribeSnapshotsOutput::builder()
+                    .snapshots(
+                        Snapshot::builder()
+                            .snapshot_name("c-20261016")
+                            .snapshot_status("available")
+                            .build(),
+                    )
+                    .build()
+            });
+        let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&page1, &page2]);
+
+        let found = find_existing(&client, &cluster("c"), "c-20261016")
+            .await
+            .unwrap()
+            .expect("snapshot on the second page");
+        assert_eq!(found.snapshot_status(), Some("available"));
+        assert!(
+            find_existing(&client, &cluster("c"), "c-20261017")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_create_snapshot_error() {
         let rule = mock!(Client::create_snapshot).then_error(|| {
@@ -288,7 +404,11 @@ mod tests {
             )
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        assert!(create_snapshot(&client, "c").await.is_err());
+        assert!(
+            create_snapshot(&client, &cluster("c"), "c-20261016")
+                .await
+                .is_err()
+        );
     }
 
     #[tokio::test]
@@ -299,7 +419,14 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        let s = wait_for_completion(&client, "snap", 30, 1).await.unwrap();
+        let s = wait_for_completion(
+            &client,
+            "snap",
+            Duration::from_secs(30),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
         assert_eq!(s.snapshot_status(), Some("available"));
     }
 
@@ -311,7 +438,16 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        assert!(wait_for_completion(&client, "snap", 30, 1).await.is_err());
+        assert!(
+            wait_for_completion(
+                &client,
+                "snap",
+                Duration::from_secs(30),
+                Duration::from_secs(1)
+            )
+            .await
+            .is_err()
+        );
     }
 
     #[tokio::test]
@@ -319,7 +455,16 @@ mod tests {
         let rule = mock!(Client::describe_snapshots)
             .then_output(|| DescribeSnapshotsOutput::builder().build());
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        assert!(wait_for_completion(&client, "snap", 30, 1).await.is_err());
+        assert!(
+            wait_for_completion(
+                &client,
+                "snap",
+                Duration::from_secs(30),
+                Duration::from_secs(1)
+            )
+            .await
+            .is_err()
+        );
     }
 
     #[tokio::test]
@@ -330,10 +475,14 @@ mod tests {
                 .build()
         });
         let client = mock_client!(aws_sdk_elasticache, RuleMode::MatchAny, &[&rule]);
-        let err = wait_for_completion(&client, "snap", 0, 1)
+        let err = wait_for_completion(&client, "snap", Duration::ZERO, Duration::from_secs(1))
             .await
             .unwrap_err();
-        assert!(err.to_string().contains("timeout"));
+        assert!(matches!(
+            err.downcast_ref::<BackupError>(),
+            Some(BackupError::SnapshotWaitTimeout { .. })
+        ));
+        assert!(err.to_string().starts_with("Timed out waiting for"));
     }
 
     #[tokio::test]
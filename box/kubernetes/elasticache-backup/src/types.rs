@@ -1,18 +1,101 @@
 use serde::Serialize;
 
+/// What the snapshot is taken from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// Single node or a read replica of a cluster-mode-disabled deployment
+    CacheCluster(String),
+    /// Replication group; a cluster-mode-enabled group has one node group per shard
+    ReplicationGroup(String),
+}
+
+impl SnapshotSource {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::CacheCluster(id) | Self::ReplicationGroup(id) => id,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct ExecutionSummary {
     pub status: String,
     pub message: String,
+    /// Step a `Failed` backup stopped at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_step: Option<FailedStep>,
     pub total_execution_time_seconds: f64,
     pub step_timings: StepTimings,
-    pub cache_cluster: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_cluster: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication_group: Option<String>,
     pub snapshot_name: Option<String>,
     pub target_snapshot_name: Option<String>,
     pub s3_location: Option<String>,
+    /// Every exported RDB file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub s3_locations: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub shards: Vec<ShardExport>,
+    /// Exported RDB files with the size and modification time S3 reported
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub verified_objects: Vec<VerifiedObject>,
     pub s3_bucket: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retention_info: Option<RetentionInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub s3_tags: Vec<S3Tag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kms_key_id: Option<String>,
+    /// Steps an earlier run had already done; their timings stay zero
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_steps: Vec<Step>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region_copy: Option<RegionCopy>,
+}
+
+/// Aggregate result of backing up several cache clusters in one run
+#[derive(Debug, Serialize)]
+pub struct BatchSummary {
+    /// `Success`, `PartialFailure`, or `Failure`
+    pub status: String,
+    pub total_execution_time_seconds: f64,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Clusters not started because of `--fail-fast`
+    pub skipped: usize,
+    /// One summary per cluster, in the order the clusters were given
+    pub results: Vec<ExecutionSummary>,
+}
+
+impl BatchSummary {
+    pub fn new(results: Vec<ExecutionSummary>, total_execution_time_seconds: f64) -> Self {
+        let count = |status: &str| results.iter().filter(|r| r.status == status).count();
+        let succeeded = count("Success");
+        let failed = count("Failed");
+        let skipped = count("Skipped");
+        let status = if succeeded == results.len() {
+            "Success"
+        } else if succeeded == 0 {
+            "Failure"
+        } else {
+            "PartialFailure"
+        };
+
+        Self {
+            status: status.to_string(),
+            total_execution_time_seconds,
+            succeeded,
+            failed,
+            skipped,
+            results,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.status == "Success"
+    }
 }
 
 #[derive(Debug, Serialize, Default)]
@@ -23,6 +106,116 @@ pub struct StepTimings {
     pub export_wait: f64,
     pub cleanup: f64,
     pub retention: f64,
+    /// Copy of the exported files to `--copy-to-region`
+    pub region_copy: f64,
+}
+
+/// Workflow step that can be skipped because an earlier run of the same day
+/// already did its work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    SnapshotCreation,
+    SnapshotWait,
+    S3Export,
+    ExportWait,
+}
+
+impl Step {
+    /// Name used for the step in `skipped_steps`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SnapshotCreation => "snapshot_creation",
+            Self::SnapshotWait => "snapshot_wait",
+            Self::S3Export => "s3_export",
+            Self::ExportWait => "export_wait",
+        }
+    }
+
+    /// Name of the step's duration metric in the EMF output
+    pub fn metric_name(self) -> &'static str {
+        match self {
+            Self::SnapshotCreation => "SnapshotCreationSeconds",
+            Self::SnapshotWait => "SnapshotWaitSeconds",
+            Self::S3Export => "S3ExportSeconds",
+            Self::ExportWait => "ExportWaitSeconds",
+        }
+    }
+}
+
+/// Workflow step a backup failed in, reported as `failed_step`.
+///
+/// Cleanup and retention only log their errors, so they never fail a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailedStep {
+    /// Includes the lookup of today's snapshot and export that precedes it
+    SnapshotCreation,
+    SnapshotWait,
+    S3Export,
+    /// Includes moving, splitting, and tagging the exported files
+    ExportWait,
+    ExportVerify,
+    /// Only with `--copy-required`
+    RegionCopy,
+}
+
+impl FailedStep {
+    /// Name used for the step in `failed_step`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SnapshotCreation => "snapshot_creation",
+            Self::SnapshotWait => "snapshot_wait",
+            Self::S3Export => "s3_export",
+            Self::ExportWait => "export_wait",
+            Self::ExportVerify => "export_verify",
+            Self::RegionCopy => "region_copy",
+        }
+    }
+}
+
+/// Exported RDB file of one replication group shard
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ShardExport {
+    pub node_group_id: String,
+    pub s3_location: String,
+    pub size_bytes: Option<i64>,
+    /// Time spent moving the shard file under its per-shard prefix
+    pub duration_seconds: f64,
+}
+
+/// Exported RDB file checked with `HeadObject` once the export completed
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifiedObject {
+    pub s3_location: String,
+    pub size_bytes: i64,
+    /// RFC 3339 timestamp reported by S3
+    pub last_modified: Option<String>,
+}
+
+/// Off-region copy of the exported RDB files (`--copy-to-region`)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegionCopy {
+    pub region: String,
+    /// `Success` or `Failed`; a failure only fails the run with `--copy-required`
+    pub status: String,
+    /// Copied RDB files in the copy bucket
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub s3_locations: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of a successful backup workflow
+#[derive(Debug)]
+pub struct BackupOutcome {
+    pub target_snapshot_name: String,
+    pub s3_location: String,
+    pub shards: Vec<ShardExport>,
+    pub verified_objects: Vec<VerifiedObject>,
+    pub deleted_count: usize,
+    pub skipped_steps: Vec<Step>,
+    pub region_copy: Option<RegionCopy>,
 }
 
 #[derive(Debug, Serialize)]
@@ -32,6 +225,13 @@ pub struct RetentionInfo {
     pub deleted_count: usize,
 }
 
+/// Tag applied to the exported S3 objects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct S3Tag {
+    pub key: String,
+    pub value: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,41 +248,177 @@ mod tests {
         let summary = ExecutionSummary {
             status: "Success".to_string(),
             message: "ok".to_string(),
+            failed_step: None,
             total_execution_time_seconds: 1.5,
             step_timings: StepTimings::default(),
-            cache_cluster: "cluster".to_string(),
+            cache_cluster: Some("cluster".to_string()),
+            replication_group: None,
             snapshot_name: Some("snap".to_string()),
             target_snapshot_name: Some("snap-s3-export".to_string()),
             s3_location: Some("s3://b/k".to_string()),
+            s3_locations: vec!["s3://b/k".to_string()],
+            shards: Vec::new(),
+            verified_objects: Vec::new(),
             s3_bucket: "b".to_string(),
             retention_info: None,
+            s3_tags: Vec::new(),
+            kms_key_id: None,
+            skipped_steps: Vec::new(),
+            region_copy: None,
         };
         let json = serde_json::to_string(&summary).unwrap();
-        // retention_info is skipped when None.
+        // retention_info, s3_tags and kms_key_id are skipped when unset.
         assert!(!json.contains("retention_info"));
+        assert!(!json.contains("s3_tags"));
+        assert!(!json.contains("kms_key_id"));
+        assert!(!json.contains("replication_group"));
+        assert!(!json.contains("shards"));
+        assert!(!json.contains("verified_objects"));
+        assert!(!json.contains("skipped_steps"));
+        // step_timings always has a region_copy duration, the summary field is skipped
+        assert!(!json.contains("\"region_copy\":{"));
         assert!(json.contains("\"status\":\"Success\""));
     }
 
+    #[test]
+    fn test_summary_serializes_replication_group_shards() {
+        let summary = ExecutionSummary {
+            status: "Success".to_string(),
+            message: "ok".to_string(),
+            failed_step: None,
+            total_execution_time_seconds: 0.0,
+            step_timings: StepTimings::default(),
+            cache_cluster: None,
+            replication_group: Some("rg".to_string()),
+            snapshot_name: None,
+            target_snapshot_name: None,
+            s3_location: None,
+            s3_locations: vec!["s3://b/t/0001/t-0001.rdb".to_string()],
+            shards: vec![ShardExport {
+                node_group_id: "0001".to_string(),
+                s3_location: "s3://b/t/0001/t-0001.rdb".to_string(),
+                size_bytes: Some(1024),
+                duration_seconds: 1.5,
+            }],
+            verified_objects: vec![VerifiedObject {
+                s3_location: "s3://b/t/0001/t-0001.rdb".to_string(),
+                size_bytes: 1024,
+                last_modified: Some("2025-01-01T00:00:00Z".to_string()),
+            }],
+            s3_bucket: "b".to_string(),
+            retention_info: None,
+            s3_tags: Vec::new(),
+            kms_key_id: None,
+            skipped_steps: Vec::new(),
+            region_copy: None,
+        };
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.contains("cache_cluster"));
+        assert!(json.contains("\"replication_group\":\"rg\""));
+        assert!(json.contains("\"node_group_id\":\"0001\""));
+        assert!(json.contains("\"duration_seconds\":1.5"));
+        assert!(json.contains("\"verified_objects\":[{\"s3_location\":\"s3://b/t/0001/t-0001.rdb\",\"size_bytes\":1024,\"last_modified\":\"2025-01-01T00:00:00Z\"}]"));
+    }
+
+    #[test]
+    fn test_snapshot_source_id() {
+        assert_eq!(SnapshotSource::CacheCluster("c".to_string()).id(), "c");
+        assert_eq!(
+            SnapshotSource::ReplicationGroup("rg".to_string()).id(),
+            "rg"
+        );
+    }
+
     #[test]
     fn test_summary_serializes_with_retention() {
         let summary = ExecutionSummary {
             status: "Success".to_string(),
             message: "ok".to_string(),
+            failed_step: None,
             total_execution_time_seconds: 0.0,
             step_timings: StepTimings::default(),
-            cache_cluster: "c".to_string(),
+            cache_cluster: Some("c".to_string()),
+            replication_group: None,
             snapshot_name: None,
             target_snapshot_name: None,
             s3_location: None,
+            s3_locations: Vec::new(),
+            shards: Vec::new(),
+            verified_objects: Vec::new(),
             s3_bucket: "b".to_string(),
             retention_info: Some(RetentionInfo {
                 enabled: true,
                 retention_count: 3,
                 deleted_count: 2,
             }),
+            s3_tags: vec![S3Tag {
+                key: "team".to_string(),
+                value: "data".to_string(),
+            }],
+            kms_key_id: Some("alias/backup".to_string()),
+            skipped_steps: vec![Step::SnapshotCreation, Step::S3Export],
+            region_copy: None,
         };
         let json = serde_json::to_string(&summary).unwrap();
         assert!(json.contains("retention_info"));
         assert!(json.contains("\"deleted_count\":2"));
+        assert!(json.contains("\"s3_tags\":[{\"key\":\"team\",\"value\":\"data\"}]"));
+        assert!(json.contains("\"kms_key_id\":\"alias/backup\""));
+        assert!(json.contains("\"skipped_steps\":[\"snapshot_creation\",\"s3_export\"]"));
+    }
+
+    fn cluster_summary(cluster: &str, status: &str) -> ExecutionSummary {
+        ExecutionSummary {
+            status: status.to_string(),
+            message: String::new(),
+            failed_step: None,
+            total_execution_time_seconds: 0.0,
+            step_timings: StepTimings::default(),
+            cache_cluster: Some(cluster.to_string()),
+            replication_group: None,
+            snapshot_name: None,
+            target_snapshot_name: None,
+            s3_location: None,
+            s3_locations: Vec::new(),
+            shards: Vec::new(),
+            verified_objects: Vec::new(),
+            s3_bucket: "b".to_string(),
+            retention_info: None,
+            s3_tags: Vec::new(),
+            kms_key_id: None,
+            skipped_steps: Vec::new(),
+            region_copy: None,
+        }
+    }
+
+    #[test]
+    fn test_batch_summary_status() {
+        let batch = BatchSummary::new(
+            vec![
+                cluster_summary("a", "Success"),
+                cluster_summary("b", "Success"),
+            ],
+            1.0,
+        );
+        assert_eq!(batch.status, "Success");
+        assert!(batch.is_success());
+
+        let batch = BatchSummary::new(
+            vec![
+                cluster_summary("a", "Success"),
+                cluster_summary("b", "Failed"),
+                cluster_summary("c", "Skipped"),
+            ],
+            1.0,
+        );
+        assert_eq!(batch.status, "PartialFailure");
+        assert_eq!((batch.succeeded, batch.failed, batch.skipped), (1, 1, 1));
+
+        let batch = BatchSummary::new(vec![cluster_summary("a", "Failed")], 1.0);
+        assert_eq!(batch.status, "Failure");
+        assert!(!batch.is_success());
+
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!(json["results"][0]["cache_cluster"], "a");
     }
 }
@@ -0,0 +1,399 @@
+use anyhow::{Context, Result};
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::Client as S3Client;
+use aws_sdk_s3::primitives::{ByteStream, Length};
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use std::path::Path;
+use sysinfo::System;
+use tracing::warn;
+
+/// Files larger than this are uploaded in parts. A single PutObject is
+/// limited to 5 GiB.
+const MULTIPART_THRESHOLD: u64 = 100 * 1024 * 1024;
+
+/// Size of each part of a multipart upload, raised for files that would
+/// otherwise need more than `MAX_PARTS` parts
+const PART_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Most parts S3 accepts in one multipart upload
+const MAX_PARTS: u64 = 10_000;
+
+/// Uploads files to S3 before they are deleted (`--archive-s3-bucket`)
+///
+/// Each file is stored under `<prefix>/<hostname>/<original-path>`. In
+/// Kubernetes the hostname is the pod name, so pods sharing a bucket do not
+/// overwrite each other's archives. Files over `MULTIPART_THRESHOLD` are
+/// uploaded with a multipart upload.
+pub struct S3Archiver {
+    client: S3Client,
+    bucket: String,
+    prefix: String,
+    hostname: String,
+    multipart_threshold: u64,
+    part_size: u64,
+}
+
+/// Archive counters of a single cleanup operation
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ArchiveResult {
+    /// Bytes uploaded to S3
+    pub uploaded_bytes: u64,
+    /// Files whose upload failed; they are kept on disk
+    pub upload_failures: usize,
+    /// Files deleted after a successful upload
+    pub deleted_count: usize,
+}
+
+impl S3Archiver {
+    /// Create an archiver using the default AWS credential and region chain
+    pub async fn from_env(bucket: &str, prefix: &str) -> Self {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        Self::new(S3Client::new(&config), bucket, prefix, &hostname())
+    }
+
+    pub fn new(client: S3Client, bucket: &str, prefix: &str, hostname: &str) -> Self {
+        Self {
+            client,
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+            hostname: hostname.to_string(),
+            multipart_threshold: MULTIPART_THRESHOLD,
+            part_size: PART_SIZE,
+        }
+    }
+
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    pub fn hostname(&self) -> &str {
+        &self.hostname
+    }
+
+    /// S3 key of a local file
+    pub fn key_for(&self, path: &Path) -> String {
+        let path = path.to_string_lossy();
+        let path = path.trim_start_matches('/');
+
+        if self.prefix.is_empty() {
+            format!("{}/{}", self.hostname, path)
+        } else {
+            format!("{}/{}/{}", self.prefix, self.hostname, path)
+        }
+    }
+
+    /// Upload a file and return its S3 key
+    pub async fn upload(&self, path: &Path) -> Result<String> {
+        let key = self.key_for(path);
+        let size = tokio::fs::metadata(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .len();
+
+        if size > self.multipart_threshold {
+            self.upload_multipart(path, &key, size).await?;
+            return Ok(key);
+        }
+
+        let body = ByteStream::from_path(path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to upload to s3://{}/{}", self.bucket, key))?;
+
+        Ok(key)
+    }
+
+    /// Upload a file in parts, aborting the upload if any part fails so S3
+    /// does not keep the uploaded parts
+    async fn upload_multipart(&self, path: &Path, key: &str, size: u64) -> Result<()> {
+        let created = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to start multipart upload to s3://{}/{}",
+                    self.bucket, key
+                )
+            })?;
+        let upload_id = created
+            .upload_id()
+            .context("CreateMultipartUpload returned no upload ID")?;
+
+        let result = self.upload_parts(path, key, upload_id, size).await;
+        if result.is_err()
+            && let Err(e) = self
+                .client
+                .abort_multipart_upload()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .send()
+                .await
+        {
+            warn!(
+                key = %key,
+                upload_id = %upload_id,
+                error = %e,
+                "Failed to abort multipart upload"
+            );
+        }
+        result
+    }
+
+    async fn upload_parts(&self, path: &Path, key: &str, upload_id: &str, size: u64) -> Result<()> {
+        let part_size = part_size(size, self.part_size);
+        let mut parts = Vec::new();
+        let mut offset = 0;
+        let mut part_number = 1;
+
+        while offset < size {
+            let length = part_size.min(size - offset);
+            let body = ByteStream::read_from()
+                .path(path)
+                .offset(offset)
+                .length(Length::Exact(length))
+                .build()
+                .await
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+
+            let uploaded = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(body)
+                .send()
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to upload part {} to s3://{}/{}",
+                        part_number, self.bucket, key
+                    )
+                })?;
+
+            parts.push(
+                CompletedPart::builder()
+                    .part_number(part_number)
+                    .set_e_tag(uploaded.e_tag().map(str::to_string))
+                    .build(),
+            );
+            offset += length;
+            part_number += 1;
+        }
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to complete multipart upload to s3://{}/{}",
+                    self.bucket, key
+                )
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Part size for a file of `size` bytes: at least `min_part_size`, and large
+/// enough that the file fits in `MAX_PARTS` parts
+fn part_size(size: u64, min_part_size: u64) -> u64 {
+    min_part_size.max(size.div_ceil(MAX_PARTS))
+}
+
+/// Pod name from `HOSTNAME`, falling back to the kernel hostname
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .or_else(System::host_name)
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::operation::abort_multipart_upload::AbortMultipartUploadOutput;
+    use aws_sdk_s3::operation::complete_multipart_upload::CompleteMultipartUploadOutput;
+    use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadOutput;
+    use aws_sdk_s3::operation::put_object::PutObjectOutput;
+    use aws_sdk_s3::operation::upload_part::{UploadPartError, UploadPartOutput};
+    use aws_smithy_mocks::{RuleMode, mock, mock_client};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_for_keeps_original_path() {
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[]);
+        let archiver = S3Archiver::new(client, "archive", "/ci-cache/", "runner-0");
+        assert_eq!(
+            archiver.key_for(Path::new("/home/runner/_work/app/build.tar")),
+            "ci-cache/runner-0/home/runner/_work/app/build.tar"
+        );
+
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[]);
+        let archiver = S3Archiver::new(client, "archive", "", "runner-0");
+        assert_eq!(
+            archiver.key_for(Path::new("/tmp/a.bin")),
+            "runner-0/tmp/a.bin"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_puts_file_under_key() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("cache.bin");
+        fs::write(&path, b"cache").unwrap();
+        let expected_key = format!(
+            "archive/runner-0/{}",
+            path.to_string_lossy().trim_start_matches('/')
+        );
+
+        let put = mock!(aws_sdk_s3::Client::put_object)
+            .match_requests(move |req| {
+                req.bucket() == Some("bucket") && req.key() == Some(expected_key.as_str())
+            })
+            .then_output(|| PutObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&put]);
+        let archiver = S3Archiver::new(client, "bucket", "archive", "runner-0");
+
+        let key = archiver.upload(&path).await.unwrap();
+        assert!(key.ends_with("/cache.bin"));
+        assert_eq!(put.num_calls(), 1);
+    }
+
+    fn create_multipart() -> aws_smithy_mocks::Rule {
+        mock!(aws_sdk_s3::Client::create_multipart_upload).then_output(|| {
+            CreateMultipartUploadOutput::builder()
+                .upload_id("up-1")
+                .build()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_upload_over_threshold_uses_multipart() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("cache.bin");
+        fs::write(&path, b"0123456789ab").unwrap();
+
+        let put = mock!(aws_sdk_s3::Client::put_object)
+            .then_output(|| PutObjectOutput::builder().build());
+        let create = create_multipart();
+        let part = mock!(aws_sdk_s3::Client::upload_part)
+            .match_requests(|req| req.upload_id() == Some("up-1"))
+            .then_output(|| UploadPartOutput::builder().e_tag("etag").build());
+        let complete = mock!(aws_sdk_s3::Client::complete_multipart_upload)
+            .match_requests(|req| {
+                let parts = req
+                    .multipart_upload()
+                    .map(|u| u.parts())
+                    .unwrap_or_default();
+                let numbers: Vec<_> = parts.iter().filter_map(|p| p.part_number()).collect();
+                req.upload_id() == Some("up-1") && numbers == [1, 2, 3]
+            })
+            .then_output(|| CompleteMultipartUploadOutput::builder().build());
+        let client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[&put, &create, &part, &complete]
+        );
+        let mut archiver = S3Archiver::new(client, "bucket", "archive", "runner-0");
+        archiver.multipart_threshold = 8;
+        archiver.part_size = 5;
+
+        archiver.upload(&path).await.unwrap();
+        assert_eq!(put.num_calls(), 0);
+        assert_eq!(create.num_calls(), 1);
+        assert_eq!(part.num_calls(), 3);
+        assert_eq!(complete.num_calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_upload_at_threshold_uses_put_object() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("cache.bin");
+        fs::write(&path, b"01234567").unwrap();
+
+        let put = mock!(aws_sdk_s3::Client::put_object)
+            .then_output(|| PutObjectOutput::builder().build());
+        let create = create_multipart();
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&put, &create]);
+        let mut archiver = S3Archiver::new(client, "bucket", "archive", "runner-0");
+        archiver.multipart_threshold = 8;
+
+        archiver.upload(&path).await.unwrap();
+        assert_eq!(put.num_calls(), 1);
+        assert_eq!(create.num_calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_part_aborts_multipart_upload() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("cache.bin");
+        fs::write(&path, b"0123456789ab").unwrap();
+
+        let create = create_multipart();
+        let part = mock!(aws_sdk_s3::Client::upload_part)
+            .then_error(|| UploadPartError::unhandled("connection reset"));
+        let complete = mock!(aws_sdk_s3::Client::complete_multipart_upload)
+            .then_output(|| CompleteMultipartUploadOutput::builder().build());
+        let abort = mock!(aws_sdk_s3::Client::abort_multipart_upload)
+            .match_requests(|req| req.upload_id() == Some("up-1"))
+            .then_output(|| AbortMultipartUploadOutput::builder().build());
+        let client = mock_client!(
+            aws_sdk_s3,
+            RuleMode::MatchAny,
+            &[&create, &part, &complete, &abort]
+        );
+        let mut archiver = S3Archiver::new(client, "bucket", "archive", "runner-0");
+        archiver.multipart_threshold = 8;
+        archiver.part_size = 5;
+
+        assert!(archiver.upload(&path).await.is_err());
+        assert_eq!(complete.num_calls(), 0);
+        assert_eq!(abort.num_calls(), 1);
+    }
+
+    #[test]
+    fn test_part_size_stays_within_part_limit() {
+        assert_eq!(part_size(1024, PART_SIZE), PART_SIZE);
+        assert_eq!(part_size(PART_SIZE * MAX_PARTS, PART_SIZE), PART_SIZE);
+        let huge = PART_SIZE * MAX_PARTS + 1;
+        assert!(huge.div_ceil(part_size(huge, PART_SIZE)) <= MAX_PARTS);
+    }
+
+    #[tokio::test]
+    async fn test_upload_missing_file_fails() {
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[]);
+        let archiver = S3Archiver::new(client, "bucket", "archive", "runner-0");
+        assert!(
+            archiver
+                .upload(Path::new("/does/not/exist/zzzz-test"))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use bytesize::ByteSize;
+use nix::sys::statvfs::statvfs;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
@@ -9,8 +10,10 @@ use sysinfo::Disks;
 use tokio::time;
 use tracing::{error, info, warn};
 
+use crate::archiver::{ArchiveResult, S3Archiver};
 use crate::config::{Args, CleanupMode};
 use crate::matcher::PatternMatcher;
+use crate::open_files::OpenFiles;
 use crate::scanner::FileScanner;
 
 /// Filesystem cleaner orchestrator
@@ -23,9 +26,19 @@ use crate::scanner::FileScanner;
 pub struct Cleaner {
     config: Args,
     matcher: PatternMatcher,
+    archiver: Option<S3Archiver>,
     stopped: Arc<AtomicBool>,
 }
 
+/// Result of cleaning a single target path
+#[derive(Debug, Default, PartialEq)]
+pub struct CleanupStats {
+    pub deleted_count: usize,
+    pub freed_space: u64,
+    /// Set when files are uploaded to S3 before deletion
+    pub archive: Option<ArchiveResult>,
+}
+
 impl Cleaner {
     /// Create a new cleaner with the given configuration
     pub fn new(config: Args) -> Result<Self> {
@@ -34,10 +47,17 @@ impl Cleaner {
         Ok(Self {
             config,
             matcher,
+            archiver: None,
             stopped: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Upload each file to S3 before deleting it
+    pub fn with_archiver(mut self, archiver: S3Archiver) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+
     /// Run the cleaner based on configured mode (once or interval)
     pub async fn run(&self) -> Result<()> {
         match self.config.cleanup_mode {
@@ -88,22 +108,41 @@ impl Cleaner {
 
         for path in &self.config.target_paths {
             let usage = self.get_disk_usage_percent(path);
+            let inode_usage = self.get_inode_usage_percent(path);
+            let inode_exceeded = self
+                .config
+                .inode_threshold_percent
+                .is_some_and(|threshold| inode_usage > threshold as f64);
 
             if usage > self.config.usage_threshold_percent as f64 {
                 warn!(
                     path = %path.display(),
                     usage = usage,
                     threshold = self.config.usage_threshold_percent,
+                    inode_usage_percent = inode_usage,
                     cleanup_mode = %self.config.cleanup_mode,
                     dry_run = self.config.dry_run,
                     "Disk usage exceeds threshold, starting cleanup"
                 );
                 self.clean_path(path).await;
+            } else if inode_exceeded {
+                warn!(
+                    path = %path.display(),
+                    usage = usage,
+                    inode_usage_percent = inode_usage,
+                    inode_threshold = ?self.config.inode_threshold_percent,
+                    cleanup_mode = %self.config.cleanup_mode,
+                    dry_run = self.config.dry_run,
+                    "Inode usage exceeds threshold, starting cleanup"
+                );
+                self.clean_path(path).await;
             } else {
                 info!(
                     path = %path.display(),
                     usage = usage,
                     threshold = self.config.usage_threshold_percent,
+                    inode_usage_percent = inode_usage,
+                    inode_threshold = ?self.config.inode_threshold_percent,
                     cleanup_mode = %self.config.cleanup_mode,
                     "Disk usage is below threshold, skipping cleanup"
                 );
@@ -151,18 +190,58 @@ impl Cleaner {
         }
     }
 
+    /// Get inode usage percentage of the filesystem containing a given path
+    ///
+    /// Filesystems without a fixed inode count (e.g. btrfs) report zero
+    /// inodes and are treated as 0% used.
+    fn get_inode_usage_percent(&self, path: &Path) -> f64 {
+        let stat = match statvfs(path) {
+            Ok(stat) => stat,
+            Err(e) => {
+                error!(path = %path.display(), error = %e, "Failed to get inode usage");
+                return 0.0;
+            }
+        };
+
+        let total = stat.files() as u64;
+        let free = stat.files_free() as u64;
+        if total == 0 {
+            return 0.0;
+        }
+
+        let used = total.saturating_sub(free);
+        (used as f64 / total as f64) * 100.0
+    }
+
     /// Clean files in the given path
-    async fn clean_path(&self, base_path: &Path) {
+    async fn clean_path(&self, base_path: &Path) -> CleanupStats {
         if !base_path.exists() {
             error!(path = %base_path.display(), "Path does not exist");
-            return;
+            return CleanupStats::default();
         }
 
         let initial_usage = self.get_disk_usage_percent(base_path);
+        let initial_inode_usage = self.get_inode_usage_percent(base_path);
 
         // Use FileScanner to collect files
         let scanner = FileScanner::new(&self.matcher);
-        let files = scanner.scan(base_path);
+        let mut files = scanner.scan(base_path);
+
+        if self.config.skip_open_files {
+            let open_files = OpenFiles::snapshot();
+            files.retain(|file| match open_files.holder(&file.path) {
+                Some(pid) => {
+                    info!(
+                        file = %file.path.display(),
+                        pid = pid,
+                        size = %ByteSize::b(file.size),
+                        "Skipping file held open by a process"
+                    );
+                    false
+                }
+                None => true,
+            });
+        }
 
         if files.is_empty() {
             info!(
@@ -170,7 +249,7 @@ impl Cleaner {
                 initial_usage_percent = initial_usage,
                 "No files to clean"
             );
-            return;
+            return CleanupStats::default();
         }
 
         let total_size: u64 = files.iter().map(|f| f.size).sum();
@@ -183,8 +262,8 @@ impl Cleaner {
             "Starting cleanup operation"
         );
 
-        let mut deleted_count = 0;
-        let mut freed_space = 0u64;
+        let mut stats = CleanupStats::default();
+        let mut archive = ArchiveResult::default();
         let file_count = files.len();
 
         for file in &files {
@@ -194,35 +273,75 @@ impl Cleaner {
             }
 
             if self.config.dry_run {
-                info!(
-                    file = %file.path.display(),
-                    size = %ByteSize::b(file.size),
-                    "[DRY-RUN] Would delete file"
-                );
-            } else {
-                match fs::remove_file(&file.path) {
-                    Ok(_) => {
+                match &self.archiver {
+                    Some(archiver) => info!(
+                        file = %file.path.display(),
+                        size = %ByteSize::b(file.size),
+                        destination = %format!("s3://{}/{}", archiver.bucket(), archiver.key_for(&file.path)),
+                        "[DRY-RUN] Would upload and delete file"
+                    ),
+                    None => info!(
+                        file = %file.path.display(),
+                        size = %ByteSize::b(file.size),
+                        "[DRY-RUN] Would delete file"
+                    ),
+                }
+                continue;
+            }
+
+            if let Some(archiver) = &self.archiver {
+                match archiver.upload(&file.path).await {
+                    Ok(key) => {
                         info!(
                             file = %file.path.display(),
+                            key = %key,
                             size = %ByteSize::b(file.size),
-                            "File deleted successfully"
+                            "File uploaded to S3"
                         );
-                        deleted_count += 1;
-                        freed_space += file.size;
+                        archive.uploaded_bytes += file.size;
                     }
                     Err(e) => {
                         error!(
                             file = %file.path.display(),
-                            error = %e,
-                            "Failed to delete file"
+                            error = %format!("{e:#}"),
+                            "Failed to upload file, keeping it"
                         );
+                        archive.upload_failures += 1;
+                        continue;
+                    }
+                }
+            }
+
+            match fs::remove_file(&file.path) {
+                Ok(_) => {
+                    info!(
+                        file = %file.path.display(),
+                        size = %ByteSize::b(file.size),
+                        "File deleted successfully"
+                    );
+                    stats.deleted_count += 1;
+                    stats.freed_space += file.size;
+                    if self.archiver.is_some() {
+                        archive.deleted_count += 1;
                     }
                 }
+                Err(e) => {
+                    error!(
+                        file = %file.path.display(),
+                        error = %e,
+                        "Failed to delete file"
+                    );
+                }
             }
         }
 
+        if self.archiver.is_some() {
+            stats.archive = Some(archive);
+        }
+
         let final_usage = self.get_disk_usage_percent(base_path);
         let usage_reduction = initial_usage - final_usage;
+        let final_inode_usage = self.get_inode_usage_percent(base_path);
 
         if self.config.dry_run {
             info!(
@@ -230,6 +349,8 @@ impl Cleaner {
                 initial_usage_percent = initial_usage,
                 final_usage_percent = final_usage,
                 usage_reduction = usage_reduction,
+                initial_inode_usage_percent = initial_inode_usage,
+                final_inode_usage_percent = final_inode_usage,
                 would_delete = file_count,
                 "Cleanup completed (DRY-RUN)"
             );
@@ -239,11 +360,24 @@ impl Cleaner {
                 initial_usage_percent = initial_usage,
                 final_usage_percent = final_usage,
                 usage_reduction = usage_reduction,
-                deleted_count = deleted_count,
-                freed_space = %ByteSize::b(freed_space),
+                initial_inode_usage_percent = initial_inode_usage,
+                final_inode_usage_percent = final_inode_usage,
+                deleted_count = stats.deleted_count,
+                freed_space = %ByteSize::b(stats.freed_space),
                 "Cleanup completed successfully"
             );
+            if let Some(archive) = &stats.archive {
+                info!(
+                    path = %base_path.display(),
+                    uploaded = %ByteSize::b(archive.uploaded_bytes),
+                    upload_failures = archive.upload_failures,
+                    deleted_count = archive.deleted_count,
+                    "Archive to S3 completed"
+                );
+            }
         }
+
+        stats
     }
 }
 
@@ -251,6 +385,9 @@ impl Cleaner {
 mod tests {
     use super::*;
     use crate::config::{Args, CleanupMode};
+    use aws_sdk_s3::Client as S3Client;
+    use aws_sdk_s3::operation::put_object::PutObjectOutput;
+    use aws_smithy_mocks::{RuleMode, mock, mock_client};
     use std::fs::File;
     use std::io::Write;
     use std::path::PathBuf;
@@ -265,11 +402,15 @@ mod tests {
         Args {
             target_paths,
             usage_threshold_percent: threshold,
+            inode_threshold_percent: None,
             check_interval_minutes: 1,
             include_patterns: vec!["*".to_string()],
             exclude_patterns: vec![],
             cleanup_mode: mode,
             dry_run,
+            skip_open_files: false,
+            archive_s3_bucket: None,
+            archive_s3_prefix: "filesystem-cleaner".to_string(),
             log_level: "info".to_string(),
         }
     }
@@ -282,6 +423,10 @@ mod tests {
         File::create(path).unwrap().write_all(content).unwrap();
     }
 
+    fn archiver(client: S3Client) -> S3Archiver {
+        S3Archiver::new(client, "archive", "filesystem-cleaner", "runner-0")
+    }
+
     #[test]
     fn test_cleaner_new_success() {
         let args = make_args(vec![PathBuf::from("/tmp")], 80, CleanupMode::Once, true);
@@ -329,6 +474,19 @@ mod tests {
         assert_eq!(usage, 0.0);
     }
 
+    #[test]
+    fn test_get_inode_usage_percent() {
+        let temp = TempDir::new().unwrap();
+        let args = make_args(vec![temp.path().to_path_buf()], 80, CleanupMode::Once, true);
+        let cleaner = Cleaner::new(args).unwrap();
+        let usage = cleaner.get_inode_usage_percent(temp.path());
+        assert!((0.0..=100.0).contains(&usage));
+
+        // statvfs fails for a missing path
+        let usage = cleaner.get_inode_usage_percent(Path::new("/does/not/exist/zzzz-test"));
+        assert_eq!(usage, 0.0);
+    }
+
     #[tokio::test]
     async fn test_clean_path_nonexistent() {
         let args = make_args(
@@ -375,13 +533,93 @@ mod tests {
 
         let args = make_args(vec![temp.path().to_path_buf()], 0, CleanupMode::Once, false);
         let cleaner = Cleaner::new(args).unwrap();
-        cleaner.clean_path(temp.path()).await;
+        let stats = cleaner.clean_path(temp.path()).await;
 
+        assert_eq!(stats.deleted_count, 3);
+        assert_eq!(stats.freed_space, 16);
+        assert_eq!(stats.archive, None);
         assert!(!temp.path().join("delete1.txt").exists());
         assert!(!temp.path().join("delete2.txt").exists());
         assert!(!temp.path().join("sub/delete3.txt").exists());
     }
 
+    #[tokio::test]
+    async fn test_clean_path_archives_before_deleting() {
+        let temp = TempDir::new().unwrap();
+        create_file(temp.path(), "uploaded.txt", b"hello");
+        create_file(temp.path(), "sub/uploaded.txt", b"nested");
+
+        let put = mock!(aws_sdk_s3::Client::put_object)
+            .match_requests(|req| {
+                req.bucket() == Some("archive")
+                    && req
+                        .key()
+                        .is_some_and(|key| key.starts_with("filesystem-cleaner/runner-0/"))
+            })
+            .then_output(|| PutObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&put]);
+
+        let args = make_args(vec![temp.path().to_path_buf()], 0, CleanupMode::Once, false);
+        let cleaner = Cleaner::new(args).unwrap().with_archiver(archiver(client));
+        let stats = cleaner.clean_path(temp.path()).await;
+
+        assert_eq!(put.num_calls(), 2);
+        assert_eq!(
+            stats.archive,
+            Some(ArchiveResult {
+                uploaded_bytes: 11,
+                upload_failures: 0,
+                deleted_count: 2,
+            })
+        );
+        assert!(!temp.path().join("uploaded.txt").exists());
+        assert!(!temp.path().join("sub/uploaded.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clean_path_keeps_files_whose_upload_failed() {
+        let temp = TempDir::new().unwrap();
+        create_file(temp.path(), "kept.txt", b"hello");
+
+        let put = mock!(aws_sdk_s3::Client::put_object)
+            .sequence()
+            .http_status(500, None)
+            .build();
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&put]);
+
+        let args = make_args(vec![temp.path().to_path_buf()], 0, CleanupMode::Once, false);
+        let cleaner = Cleaner::new(args).unwrap().with_archiver(archiver(client));
+        let stats = cleaner.clean_path(temp.path()).await;
+
+        assert_eq!(stats.deleted_count, 0);
+        assert_eq!(
+            stats.archive,
+            Some(ArchiveResult {
+                uploaded_bytes: 0,
+                upload_failures: 1,
+                deleted_count: 0,
+            })
+        );
+        assert!(temp.path().join("kept.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clean_path_dry_run_does_not_upload() {
+        let temp = TempDir::new().unwrap();
+        create_file(temp.path(), "keep.txt", b"hello");
+
+        let put = mock!(aws_sdk_s3::Client::put_object)
+            .then_output(|| PutObjectOutput::builder().build());
+        let client = mock_client!(aws_sdk_s3, RuleMode::MatchAny, &[&put]);
+
+        let args = make_args(vec![temp.path().to_path_buf()], 0, CleanupMode::Once, true);
+        let cleaner = Cleaner::new(args).unwrap().with_archiver(archiver(client));
+        cleaner.clean_path(temp.path()).await;
+
+        assert_eq!(put.num_calls(), 0);
+        assert!(temp.path().join("keep.txt").exists());
+    }
+
     #[tokio::test]
     async fn test_clean_path_respects_stop_flag() {
         let temp = TempDir::new().unwrap();
@@ -427,6 +665,45 @@ mod tests {
         assert!(!temp.path().join("to-delete.txt").exists());
     }
 
+    #[tokio::test]
+    #[cfg(target_os = "linux")]
+    async fn test_perform_cleanup_skips_open_files() {
+        let temp = TempDir::new().unwrap();
+        create_file(temp.path(), "closed.txt", b"closed");
+        create_file(temp.path(), "open.txt", b"open");
+        // Held open by the test process for the whole cleanup
+        let _open = File::open(temp.path().join("open.txt")).unwrap();
+
+        let mut args = make_args(vec![temp.path().to_path_buf()], 0, CleanupMode::Once, false);
+        args.skip_open_files = true;
+        let cleaner = Cleaner::new(args).unwrap();
+        cleaner.perform_cleanup().await;
+
+        assert!(temp.path().join("open.txt").exists());
+        assert!(!temp.path().join("closed.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_perform_cleanup_inode_threshold_cleans() {
+        let temp = TempDir::new().unwrap();
+        create_file(temp.path(), "small.txt", b"x");
+
+        // Disk usage never exceeds 100%, so only the inode threshold can trigger
+        let mut args = make_args(
+            vec![temp.path().to_path_buf()],
+            100,
+            CleanupMode::Once,
+            false,
+        );
+        args.inode_threshold_percent = Some(0);
+        let cleaner = Cleaner::new(args).unwrap();
+        let inode_usage = cleaner.get_inode_usage_percent(temp.path());
+        cleaner.perform_cleanup().await;
+
+        // Filesystems without an inode count never exceed the threshold
+        assert_eq!(temp.path().join("small.txt").exists(), inode_usage == 0.0);
+    }
+
     #[tokio::test]
     async fn test_run_once_mode_executes_and_returns() {
         let temp = TempDir::new().unwrap();
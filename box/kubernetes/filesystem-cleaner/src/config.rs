@@ -74,6 +74,14 @@ pub struct Args {
     )]
     pub usage_threshold_percent: u8,
 
+    /// Inode usage percentage threshold to trigger cleanup (0-100), checked alongside disk usage
+    #[arg(
+        long = "inode-threshold-percent",
+        env = "INODE_THRESHOLD_PERCENT",
+        help = "Inode usage percentage threshold to trigger cleanup (0-100) - disabled when unset"
+    )]
+    pub inode_threshold_percent: Option<u8>,
+
     /// Interval between cleanup checks in minutes (used with cleanup-mode=interval)
     #[arg(
         long = "check-interval-minutes",
@@ -123,6 +131,32 @@ pub struct Args {
     )]
     pub dry_run: bool,
 
+    /// Skip files currently held open by a process (checked via /proc/*/fd)
+    #[arg(
+        long = "skip-open-files",
+        env = "SKIP_OPEN_FILES",
+        default_value = "false",
+        help = "Skip files held open by a process - deleting them frees no space until closed"
+    )]
+    pub skip_open_files: bool,
+
+    /// S3 bucket that receives each file before it is deleted
+    #[arg(
+        long = "archive-s3-bucket",
+        env = "ARCHIVE_S3_BUCKET",
+        help = "Upload files to this S3 bucket before deleting them - files whose upload fails are kept"
+    )]
+    pub archive_s3_bucket: Option<String>,
+
+    /// Key prefix of archived files, followed by the hostname and the original path
+    #[arg(
+        long = "archive-s3-prefix",
+        env = "ARCHIVE_S3_PREFIX",
+        default_value = "filesystem-cleaner",
+        help = "Key prefix of archived files (<prefix>/<hostname>/<original-path>)"
+    )]
+    pub archive_s3_prefix: String,
+
     /// Log level (trace, debug, info, warn, error)
     #[arg(
         long = "log-level",
@@ -148,6 +182,16 @@ mod tests {
         assert!("invalid".parse::<CleanupMode>().is_err());
     }
 
+    #[test]
+    fn test_inode_threshold_is_optional() {
+        let args = Args::try_parse_from(["filesystem-cleaner"]).unwrap();
+        assert_eq!(args.inode_threshold_percent, None);
+
+        let args = Args::try_parse_from(["filesystem-cleaner", "--inode-threshold-percent", "90"])
+            .unwrap();
+        assert_eq!(args.inode_threshold_percent, Some(90));
+    }
+
     #[test]
     fn test_cleanup_mode_display() {
         assert_eq!(CleanupMode::Once.to_string(), "once");
@@ -4,11 +4,14 @@ use std::sync::Arc;
 use tokio::signal;
 use tracing::{error, info, warn};
 
+mod archiver;
 mod cleaner;
 mod config;
 mod matcher;
+mod open_files;
 mod scanner;
 
+use archiver::S3Archiver;
 use cleaner::Cleaner;
 use config::Args;
 
@@ -33,10 +36,14 @@ async fn main() -> Result<()> {
     info!(
         target_paths = ?args.target_paths,
         usage_threshold_percent = args.usage_threshold_percent,
+        inode_threshold_percent = ?args.inode_threshold_percent,
         cleanup_mode = ?args.cleanup_mode,
         include_patterns = ?args.include_patterns,
         exclude_patterns = ?args.exclude_patterns,
         dry_run = args.dry_run,
+        skip_open_files = args.skip_open_files,
+        archive_s3_bucket = ?args.archive_s3_bucket,
+        archive_s3_prefix = args.archive_s3_prefix,
         log_level = args.log_level,
         check_interval_minutes = args.check_interval_minutes,
         "Configuration loaded"
@@ -46,7 +53,24 @@ async fn main() -> Result<()> {
         warn!("Running in DRY-RUN mode - no files will be deleted");
     }
 
-    let cleaner = Arc::new(Cleaner::new(args)?);
+    let archiver = match &args.archive_s3_bucket {
+        Some(bucket) => {
+            let archiver = S3Archiver::from_env(bucket, &args.archive_s3_prefix).await;
+            info!(
+                bucket = %bucket,
+                hostname = archiver.hostname(),
+                "Files will be uploaded to S3 before deletion"
+            );
+            Some(archiver)
+        }
+        None => None,
+    };
+
+    let mut cleaner = Cleaner::new(args)?;
+    if let Some(archiver) = archiver {
+        cleaner = cleaner.with_archiver(archiver);
+    }
+    let cleaner = Arc::new(cleaner);
     let cleaner_clone = Arc::clone(&cleaner);
 
     // Setup signal handler
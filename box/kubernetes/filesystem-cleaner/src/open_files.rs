@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use tracing::debug;
+
+/// Files currently held open by any process, like `fuser`
+///
+/// Built from the `/proc/<pid>/fd` entries and keyed by device and inode, so
+/// a file matches no matter which path the process opened it through.
+/// Deleting such a file frees no space until the process closes it.
+/// Processes whose `fd` directory cannot be read (other users, or outside
+/// the container's PID namespace) are not seen.
+pub struct OpenFiles {
+    holders: HashMap<(u64, u64), u32>,
+}
+
+impl OpenFiles {
+    /// Take a snapshot of the open files of every visible process
+    pub fn snapshot() -> Self {
+        Self::from_proc(Path::new("/proc"))
+    }
+
+    fn from_proc(proc_root: &Path) -> Self {
+        let mut holders = HashMap::new();
+
+        let entries = match fs::read_dir(proc_root) {
+            Ok(entries) => entries,
+            Err(e) => {
+                debug!(path = %proc_root.display(), error = %e, "Cannot read process list");
+                return Self { holders };
+            }
+        };
+
+        for entry in entries.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u32>().ok())
+            else {
+                continue;
+            };
+
+            let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+                continue;
+            };
+            for fd in fds.flatten() {
+                // metadata() follows the fd link to the open file itself
+                if let Ok(metadata) = fs::metadata(fd.path())
+                    && metadata.is_file()
+                {
+                    holders
+                        .entry((metadata.dev(), metadata.ino()))
+                        .or_insert(pid);
+                }
+            }
+        }
+
+        debug!(open_files = holders.len(), "Collected open files");
+        Self { holders }
+    }
+
+    /// PID of a process holding the file open, if any
+    pub fn holder(&self, path: &Path) -> Option<u32> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+        self.holders.get(&(metadata.dev(), metadata.ino())).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_holder_of_open_and_closed_files() {
+        let temp = TempDir::new().unwrap();
+        let open_path = temp.path().join("open.bin");
+        let closed_path = temp.path().join("closed.bin");
+        let _open = File::create(&open_path).unwrap();
+        drop(File::create(&closed_path).unwrap());
+
+        let open_files = OpenFiles::snapshot();
+        assert_eq!(open_files.holder(&open_path), Some(std::process::id()));
+        assert_eq!(open_files.holder(&closed_path), None);
+    }
+
+    #[test]
+    fn test_missing_proc_sees_nothing() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("file.bin");
+        let _file = File::create(&path).unwrap();
+
+        let open_files = OpenFiles::from_proc(&temp.path().join("no-proc"));
+        assert_eq!(open_files.holder(&path), None);
+    }
+}
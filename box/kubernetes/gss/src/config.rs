@@ -18,11 +18,21 @@ pub struct Config {
     pub request_timeout: u64,
     pub concurrent_scans: usize,
     pub publisher_type: String,
+    pub group_by_schedule: bool,
+
+    // Repository Scope Configuration
+    pub include_repos: Vec<String>,
+    pub exclude_repos: Vec<String>,
 
     // Connectivity Configuration
     pub connectivity_max_retries: u32,
     pub connectivity_retry_interval: u64,
     pub connectivity_timeout: u64,
+
+    // Service Mode Configuration
+    pub serve: bool,
+    pub scan_interval: u64,
+    pub server_port: u16,
 }
 
 impl Config {
@@ -51,6 +61,12 @@ impl Config {
         let request_timeout = get_env_u64_with_default("REQUEST_TIMEOUT", 60);
         let concurrent_scans = get_env_usize_with_default("CONCURRENT_SCANS", 10);
         let publisher_type = get_env_with_default("PUBLISHER_TYPE", "console");
+        let group_by_schedule = has_flag("--group-by-schedule")
+            || get_env_bool_with_default("GROUP_BY_SCHEDULE", false);
+
+        // Load repository scope configuration (comma-separated names or globs)
+        let include_repos = get_env_list("INCLUDE_REPOS");
+        let exclude_repos = get_env_list("EXCLUDE_REPOS");
 
         // Load connectivity configuration with defaults
         let connectivity_max_retries = get_env_u32_with_default("CONNECTIVITY_MAX_RETRIES", 3);
@@ -58,6 +74,11 @@ impl Config {
             get_env_u64_with_default("CONNECTIVITY_RETRY_INTERVAL", 5);
         let connectivity_timeout = get_env_u64_with_default("CONNECTIVITY_TIMEOUT", 5);
 
+        // Load service mode configuration (one-shot run unless --serve)
+        let serve = has_flag("--serve") || get_env_bool_with_default("SERVE", false);
+        let scan_interval = get_env_u64_with_default("SCAN_INTERVAL", 3600);
+        let server_port = get_env_u16_with_default("SERVER_PORT", 8080);
+
         Ok(Config {
             slack_bot_token,
             slack_channel_id,
@@ -69,9 +90,15 @@ impl Config {
             request_timeout,
             concurrent_scans,
             publisher_type,
+            group_by_schedule,
+            include_repos,
+            exclude_repos,
             connectivity_max_retries,
             connectivity_retry_interval,
             connectivity_timeout,
+            serve,
+            scan_interval,
+            server_port,
         })
     }
 
@@ -106,6 +133,12 @@ impl Config {
             }
         }
 
+        if self.serve && self.scan_interval == 0 {
+            return Err(anyhow!(
+                "SCAN_INTERVAL must be greater than 0 in --serve mode"
+            ));
+        }
+
         Ok(())
     }
 }
@@ -118,6 +151,38 @@ fn get_env_optional(key: &str) -> Option<String> {
     env::var(key).ok()
 }
 
+fn get_env_list(key: &str) -> Vec<String> {
+    env::var(key).map(|v| parse_list(&v)).unwrap_or_default()
+}
+
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
+}
+
+fn has_flag(flag: &str) -> bool {
+    env::args().skip(1).any(|arg| arg == flag)
+}
+
+fn get_env_bool_with_default(key: &str, default: bool) -> bool {
+    env::var(key)
+        .ok()
+        .and_then(|v| parse_bool(&v))
+        .unwrap_or(default)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" => Some(true),
+        "false" | "0" | "no" => Some(false),
+        _ => None,
+    }
+}
+
 fn get_env_with_default(key: &str, default: &str) -> String {
     env::var(key).unwrap_or_else(|_| default.to_string())
 }
@@ -143,6 +208,13 @@ fn get_env_u32_with_default(key: &str, default: u32) -> u32 {
         .unwrap_or(default)
 }
 
+fn get_env_u16_with_default(key: &str, default: u16) -> u16 {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 #[cfg(test)]
 impl Config {
     pub fn new_for_test(github_token: String, github_org: String, github_base_url: String) -> Self {
@@ -154,12 +226,18 @@ impl Config {
             request_timeout: 60,
             concurrent_scans: 10,
             publisher_type: "console".to_string(),
+            group_by_schedule: false,
+            include_repos: Vec::new(),
+            exclude_repos: Vec::new(),
             slack_bot_token: None,
             slack_channel_id: None,
             slack_canvas_id: None,
             connectivity_max_retries: 3,
             connectivity_retry_interval: 5,
             connectivity_timeout: 5,
+            serve: false,
+            scan_interval: 3600,
+            server_port: 8080,
         }
     }
 }
@@ -319,5 +397,47 @@ mod tests {
         assert!(config.slack_bot_token.is_none());
         assert!(config.slack_channel_id.is_none());
         assert!(config.slack_canvas_id.is_none());
+        assert!(config.include_repos.is_empty());
+        assert!(config.exclude_repos.is_empty());
+        assert!(!config.group_by_schedule);
+        assert!(!config.serve);
+        assert_eq!(config.scan_interval, 3600);
+        assert_eq!(config.server_port, 8080);
+    }
+
+    #[test]
+    fn test_serve_requires_scan_interval() {
+        let mut config = Config::new_for_test(
+            "token".to_string(),
+            "org".to_string(),
+            "https://github.example.com".to_string(),
+        );
+        config.serve = true;
+        assert!(config.validate().is_ok());
+
+        config.scan_interval = 0;
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("SCAN_INTERVAL"));
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert_eq!(parse_bool("true"), Some(true));
+        assert_eq!(parse_bool(" TRUE "), Some(true));
+        assert_eq!(parse_bool("1"), Some(true));
+        assert_eq!(parse_bool("false"), Some(false));
+        assert_eq!(parse_bool("no"), Some(false));
+        assert_eq!(parse_bool("maybe"), None);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            parse_list("api-*, web-app ,,batch-?"),
+            vec!["api-*", "web-app", "batch-?"]
+        );
+        assert!(parse_list("").is_empty());
+        assert!(parse_list(" , ").is_empty());
     }
 }
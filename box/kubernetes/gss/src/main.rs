@@ -5,13 +5,18 @@ mod models;
 mod publisher;
 mod reporter;
 mod scanner;
+mod server;
 
 use anyhow::{Context, Result};
 use config::Config;
 use connectivity::ConnectivityChecker;
+use models::ScanResult;
 use octocrab::Octocrab;
-use publisher::PublisherFactory;
-use scanner::Scanner;
+use publisher::{Publisher, PublisherFactory};
+use scanner::{RepoFilter, Scanner};
+use server::ServerState;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
 use tracing::{error, info};
 
 #[tokio::main]
@@ -37,11 +42,17 @@ async fn run() -> Result<()> {
         github_base_url = %config.github_base_url,
         log_level = %config.log_level,
         publisher_type = %config.publisher_type,
+        group_by_schedule = config.group_by_schedule,
         request_timeout = config.request_timeout,
         concurrent_scans = config.concurrent_scans,
+        include_repos = ?config.include_repos,
+        exclude_repos = ?config.exclude_repos,
         connectivity_max_retries = config.connectivity_max_retries,
         connectivity_retry_interval = config.connectivity_retry_interval,
         connectivity_timeout = config.connectivity_timeout,
+        serve = config.serve,
+        scan_interval = config.scan_interval,
+        server_port = config.server_port,
         "Configuration loaded"
     );
 
@@ -68,9 +79,31 @@ async fn run() -> Result<()> {
         config.concurrent_scans,
         config.request_timeout,
     )
-    .context("Failed to create scanner")?;
+    .context("Failed to create scanner")?
+    .with_repo_filter(RepoFilter::new(
+        config.include_repos.clone(),
+        config.exclude_repos.clone(),
+    ));
 
-    // Scan for scheduled workflows
+    // Create publisher
+    let publisher = PublisherFactory::create(&config).context("Failed to create publisher")?;
+
+    if config.serve {
+        return serve(&config, &scanner, publisher.as_ref()).await;
+    }
+
+    scan_and_publish(&config, &scanner, publisher.as_ref()).await?;
+
+    info!("GHES Schedule Scanner completed successfully");
+    Ok(())
+}
+
+/// Scan the organization for scheduled workflows and publish the result.
+async fn scan_and_publish(
+    config: &Config,
+    scanner: &Scanner,
+    publisher: &dyn Publisher,
+) -> Result<ScanResult> {
     info!("Scanning organization: {}", config.github_organization);
     let scan_result = scanner
         .scan_scheduled_workflows(&config.github_organization)
@@ -82,17 +115,52 @@ async fn run() -> Result<()> {
         scan_result.workflows.len()
     );
 
-    // Create and use publisher
-    let publisher = PublisherFactory::create(&config).context("Failed to create publisher")?;
-
     info!("Publishing results using {} publisher", publisher.name());
     publisher
         .publish(&scan_result)
         .await
         .context("Failed to publish results")?;
 
-    info!("GHES Schedule Scanner completed successfully");
-    Ok(())
+    Ok(scan_result)
+}
+
+/// Re-scan every `SCAN_INTERVAL` seconds while serving /healthz, /readyz and
+/// /metrics.
+///
+/// A failed scan is logged and counted, and the next one runs on schedule.
+async fn serve(config: &Config, scanner: &Scanner, publisher: &dyn Publisher) -> Result<()> {
+    let state = ServerState::new();
+    let mut server = tokio::spawn(server::serve(config.server_port, state.clone()));
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.scan_interval));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    info!(
+        scan_interval = config.scan_interval,
+        "Running in serve mode, re-scanning every {} seconds", config.scan_interval
+    );
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            result = &mut server => {
+                result.context("Health and metrics server panicked")??;
+                anyhow::bail!("Health and metrics server stopped unexpectedly");
+            }
+        }
+
+        match scan_and_publish(config, scanner, publisher).await {
+            Ok(scan_result) => {
+                state.record_scan(
+                    scan_result.scan_duration.to_std().unwrap_or_default(),
+                    scan_result.workflows.len(),
+                );
+            }
+            Err(e) => {
+                error!("Scan failed: {:#}", e);
+                state.record_failure();
+            }
+        }
+    }
 }
 
 fn create_github_client(config: &Config) -> Result<Octocrab> {
@@ -60,6 +60,14 @@ impl Default for ScanResult {
     }
 }
 
+/// Scheduled workflows sharing the same normalized cron expression.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleGroup {
+    pub schedule: String,
+    pub workflow_count: usize,
+    pub repos: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct WorkflowFile {
     pub on: Option<WorkflowTrigger>,
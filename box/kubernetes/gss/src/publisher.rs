@@ -17,7 +17,9 @@ pub struct PublisherFactory;
 impl PublisherFactory {
     pub fn create(config: &Config) -> Result<Box<dyn Publisher>> {
         match config.publisher_type.as_str() {
-            "console" => Ok(Box::new(console::ConsolePublisher::new())),
+            "console" => Ok(Box::new(
+                console::ConsolePublisher::new().with_group_by_schedule(config.group_by_schedule),
+            )),
             "slack-canvas" => {
                 let token = config
                     .slack_bot_token
@@ -30,11 +32,14 @@ impl PublisherFactory {
                     anyhow!("Slack canvas ID is required for slack-canvas publisher")
                 })?;
 
-                Ok(Box::new(slack::SlackCanvasPublisher::new(
-                    token.clone(),
-                    channel_id.clone(),
-                    canvas_id.clone(),
-                )))
+                Ok(Box::new(
+                    slack::SlackCanvasPublisher::new(
+                        token.clone(),
+                        channel_id.clone(),
+                        canvas_id.clone(),
+                    )
+                    .with_group_by_schedule(config.group_by_schedule),
+                ))
             }
             _ => Err(anyhow!(
                 "Unknown publisher type: {}. Supported types: console, slack-canvas",
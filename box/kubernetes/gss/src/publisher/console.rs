@@ -14,6 +14,11 @@ impl ConsolePublisher {
             formatter: ConsoleFormatter::new(),
         }
     }
+
+    pub fn with_group_by_schedule(mut self, enabled: bool) -> Self {
+        self.formatter = self.formatter.with_group_by_schedule(enabled);
+        self
+    }
 }
 
 impl Default for ConsolePublisher {
@@ -62,4 +67,21 @@ mod tests {
         let publish_result = publisher.publish(&result).await;
         assert!(publish_result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_console_publisher_group_by_schedule() {
+        let publisher = ConsolePublisher::new().with_group_by_schedule(true);
+
+        let mut result = ScanResult::new();
+        let mut workflow = WorkflowInfo::new(
+            "test-repo".to_string(),
+            "nightly".to_string(),
+            1,
+            ".github/workflows/nightly.yml".to_string(),
+        );
+        workflow.cron_schedules = vec!["00 00 * * *".to_string()];
+        result.workflows.push(workflow);
+
+        assert!(publisher.publish(&result).await.is_ok());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::models::ScanResult;
 use crate::publisher::Publisher;
+use crate::reporter::group_by_schedule;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
@@ -12,6 +13,7 @@ pub struct SlackCanvasPublisher {
     client: Client,
     token: String,
     canvas_id: String,
+    group_by_schedule: bool,
 }
 
 impl SlackCanvasPublisher {
@@ -20,9 +22,15 @@ impl SlackCanvasPublisher {
             client: Client::new(),
             token,
             canvas_id,
+            group_by_schedule: false,
         }
     }
 
+    pub fn with_group_by_schedule(mut self, enabled: bool) -> Self {
+        self.group_by_schedule = enabled;
+        self
+    }
+
     fn convert_cron_to_kst(cron: &str) -> String {
         let parts: Vec<&str> = cron.split_whitespace().collect();
         if parts.len() != 5 {
@@ -89,6 +97,22 @@ impl SlackCanvasPublisher {
         if result.workflows.is_empty() {
             content.push_str("No scheduled workflows found.\n");
         } else {
+            if self.group_by_schedule {
+                content.push_str("## Schedule Groups\n\n");
+                content.push_str("| UTC Schedule | KST Schedule | Count | Repositories |\n");
+                content.push_str("|---|---|---|---|\n");
+                for group in group_by_schedule(&result.workflows) {
+                    content.push_str(&format!(
+                        "| `{}` | `{}` | {} | {} |\n",
+                        group.schedule,
+                        Self::convert_cron_to_kst(&group.schedule),
+                        group.workflow_count,
+                        group.repos.join(", ")
+                    ));
+                }
+                content.push('\n');
+            }
+
             content.push_str("## Scheduled Workflows\n\n");
 
             for (idx, workflow) in result.workflows.iter().enumerate() {
@@ -364,6 +388,55 @@ mod tests {
         assert!(content.contains("❌"));
     }
 
+    #[test]
+    fn test_format_canvas_content_group_by_schedule() {
+        let publisher = SlackCanvasPublisher::new(
+            "xoxb-test".to_string(),
+            "C123".to_string(),
+            "F456".to_string(),
+        )
+        .with_group_by_schedule(true);
+
+        let mut result = ScanResult::new();
+        for (repo, cron) in [("repo-a", "0 0 * * *"), ("repo-b", "00 00 * * *")] {
+            let mut wf = WorkflowInfo::new(
+                repo.to_string(),
+                "Nightly".to_string(),
+                1,
+                ".github/workflows/nightly.yml".to_string(),
+            );
+            wf.cron_schedules = vec![cron.to_string()];
+            result.workflows.push(wf);
+        }
+
+        let content = publisher.format_canvas_content(&result);
+        assert!(content.contains("## Schedule Groups"));
+        assert!(content.contains("| `0 0 * * *` | `0 9 * * *` | 2 | repo-a, repo-b |"));
+        assert!(content.contains("## Scheduled Workflows"));
+    }
+
+    #[test]
+    fn test_format_canvas_content_without_group_by_schedule() {
+        let publisher = SlackCanvasPublisher::new(
+            "xoxb-test".to_string(),
+            "C123".to_string(),
+            "F456".to_string(),
+        );
+
+        let mut result = ScanResult::new();
+        let mut wf = WorkflowInfo::new(
+            "repo-a".to_string(),
+            "Nightly".to_string(),
+            1,
+            ".github/workflows/nightly.yml".to_string(),
+        );
+        wf.cron_schedules = vec!["0 0 * * *".to_string()];
+        result.workflows.push(wf);
+
+        let content = publisher.format_canvas_content(&result);
+        assert!(!content.contains("## Schedule Groups"));
+    }
+
     #[test]
     fn test_slack_canvas_publisher_name() {
         let publisher = SlackCanvasPublisher::new(
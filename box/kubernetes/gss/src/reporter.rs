@@ -1,5 +1,6 @@
-use crate::models::ScanResult;
+use crate::models::{ScanResult, ScheduleGroup, WorkflowInfo};
 use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
 
 const KST_OFFSET_HOURS: i32 = 9;
 
@@ -7,11 +8,96 @@ pub trait ReportFormatter: Send + Sync {
     fn format(&self, result: &ScanResult) -> Result<String>;
 }
 
-pub struct ConsoleFormatter;
+/// Normalize a cron expression so equivalent schedules compare equal.
+///
+/// Collapses whitespace, strips leading zeros from numeric values
+/// (`00 00 * * *` -> `0 0 * * *`) and uppercases names (`mon` -> `MON`).
+pub fn normalize_cron(cron: &str) -> String {
+    cron.split_whitespace()
+        .map(normalize_cron_field)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn normalize_cron_field(field: &str) -> String {
+    let mut normalized = String::new();
+    let mut token = String::new();
+
+    for c in field.chars() {
+        if matches!(c, ',' | '-' | '/') {
+            normalized.push_str(&normalize_cron_token(&token));
+            normalized.push(c);
+            token.clear();
+        } else {
+            token.push(c);
+        }
+    }
+    normalized.push_str(&normalize_cron_token(&token));
+
+    normalized
+}
+
+fn normalize_cron_token(token: &str) -> String {
+    match token.parse::<u32>() {
+        Ok(n) => n.to_string(),
+        Err(_) => token.to_uppercase(),
+    }
+}
+
+/// Group workflows by normalized cron expression.
+///
+/// Groups are sorted by workflow count (descending), then by schedule.
+pub fn group_by_schedule(workflows: &[WorkflowInfo]) -> Vec<ScheduleGroup> {
+    let mut groups: BTreeMap<String, (usize, BTreeSet<String>)> = BTreeMap::new();
+
+    for workflow in workflows {
+        // A workflow listing the same schedule twice is only counted once
+        let schedules: BTreeSet<String> = workflow
+            .cron_schedules
+            .iter()
+            .map(|cron| normalize_cron(cron))
+            .filter(|cron| !cron.is_empty())
+            .collect();
+
+        for schedule in schedules {
+            let entry = groups.entry(schedule).or_default();
+            entry.0 += 1;
+            entry.1.insert(workflow.repo_name.clone());
+        }
+    }
+
+    let mut result: Vec<ScheduleGroup> = groups
+        .into_iter()
+        .map(|(schedule, (workflow_count, repos))| ScheduleGroup {
+            schedule,
+            workflow_count,
+            repos: repos.into_iter().collect(),
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        b.workflow_count
+            .cmp(&a.workflow_count)
+            .then_with(|| a.schedule.cmp(&b.schedule))
+    });
+
+    result
+}
+
+pub struct ConsoleFormatter {
+    group_by_schedule: bool,
+}
 
 impl ConsoleFormatter {
     pub fn new() -> Self {
-        Self
+        Self {
+            group_by_schedule: false,
+        }
+    }
+
+    /// Append a per-schedule aggregation section after the workflow table.
+    pub fn with_group_by_schedule(mut self, enabled: bool) -> Self {
+        self.group_by_schedule = enabled;
+        self
     }
 
     fn convert_cron_to_kst(cron: &str) -> String {
@@ -112,6 +198,30 @@ impl ConsoleFormatter {
         )
     }
 
+    fn format_schedule_groups(result: &ScanResult) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "{:<4} {:<20} {:<20} {:<6} {}\n",
+            "NO", "UTC SCHEDULE", "KST SCHEDULE", "COUNT", "REPOSITORIES"
+        ));
+        output.push_str(&"-".repeat(175));
+        output.push('\n');
+
+        for (idx, group) in group_by_schedule(&result.workflows).iter().enumerate() {
+            output.push_str(&format!(
+                "{:<4} {:<20} {:<20} {:<6} {}\n",
+                idx + 1,
+                truncate(&group.schedule, 20),
+                truncate(&Self::convert_cron_to_kst(&group.schedule), 20),
+                group.workflow_count,
+                group.repos.join(", ")
+            ));
+        }
+
+        output
+    }
+
     fn adjust_day_of_week(dow: &str) -> String {
         if dow.contains(',') {
             let days: Vec<&str> = dow.split(',').collect();
@@ -208,6 +318,11 @@ impl ReportFormatter for ConsoleFormatter {
             ));
         }
 
+        if self.group_by_schedule {
+            output.push('\n');
+            output.push_str(&Self::format_schedule_groups(result));
+        }
+
         output.push('\n');
         output.push_str(&format!(
             "Total: {} scheduled workflows found in {} repositories ({} excluded)\n",
@@ -235,6 +350,17 @@ mod tests {
     use crate::models::{ScanResult, WorkflowInfo};
     use chrono::Duration;
 
+    fn workflow_with_schedules(repo: &str, name: &str, schedules: &[&str]) -> WorkflowInfo {
+        let mut workflow = WorkflowInfo::new(
+            repo.to_string(),
+            name.to_string(),
+            1,
+            format!(".github/workflows/{}.yml", name),
+        );
+        workflow.cron_schedules = schedules.iter().map(|s| s.to_string()).collect();
+        workflow
+    }
+
     #[test]
     fn test_convert_cron_to_kst_simple() {
         assert_eq!(
@@ -402,4 +528,111 @@ mod tests {
         let output = formatter.format(&result).unwrap();
         assert!(output.contains("Total: 0 scheduled workflows"));
     }
+
+    #[test]
+    fn test_normalize_cron_leading_zeros() {
+        assert_eq!(normalize_cron("00 00 * * *"), "0 0 * * *");
+        assert_eq!(normalize_cron("05 09 01 * *"), "5 9 1 * *");
+        assert_eq!(normalize_cron("0 0 * * *"), "0 0 * * *");
+    }
+
+    #[test]
+    fn test_normalize_cron_whitespace() {
+        assert_eq!(normalize_cron("  0   0 *  * * "), "0 0 * * *");
+        assert_eq!(normalize_cron("0\t0 * * *"), "0 0 * * *");
+    }
+
+    #[test]
+    fn test_normalize_cron_lists_ranges_steps() {
+        assert_eq!(
+            normalize_cron("00,30 09-017 * * 01-05"),
+            "0,30 9-17 * * 1-5"
+        );
+        assert_eq!(normalize_cron("*/05 */06 * * *"), "*/5 */6 * * *");
+    }
+
+    #[test]
+    fn test_normalize_cron_names() {
+        assert_eq!(normalize_cron("0 0 * jan mon-fri"), "0 0 * JAN MON-FRI");
+    }
+
+    #[test]
+    fn test_group_by_schedule_merges_equivalent_crons() {
+        let workflows = vec![
+            workflow_with_schedules("repo-a", "nightly", &["0 0 * * *"]),
+            workflow_with_schedules("repo-b", "nightly", &["00 00 * * *"]),
+            workflow_with_schedules("repo-c", "weekly", &["0 9 * * 1"]),
+        ];
+
+        let groups = group_by_schedule(&workflows);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].schedule, "0 0 * * *");
+        assert_eq!(groups[0].workflow_count, 2);
+        assert_eq!(groups[0].repos, vec!["repo-a", "repo-b"]);
+        assert_eq!(groups[1].schedule, "0 9 * * 1");
+        assert_eq!(groups[1].workflow_count, 1);
+        assert_eq!(groups[1].repos, vec!["repo-c"]);
+    }
+
+    #[test]
+    fn test_group_by_schedule_dedupes_repos_and_schedules() {
+        let workflows = vec![
+            // Same schedule listed twice in one workflow counts once
+            workflow_with_schedules("repo-a", "backup", &["0 3 * * *", "00 03 * * *"]),
+            // Two workflows in the same repo count twice but list the repo once
+            workflow_with_schedules("repo-a", "cleanup", &["0 3 * * *"]),
+        ];
+
+        let groups = group_by_schedule(&workflows);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].workflow_count, 2);
+        assert_eq!(groups[0].repos, vec!["repo-a"]);
+    }
+
+    #[test]
+    fn test_group_by_schedule_sort_order() {
+        let workflows = vec![
+            workflow_with_schedules("repo-a", "b", &["30 1 * * *"]),
+            workflow_with_schedules("repo-b", "a", &["0 2 * * *"]),
+            workflow_with_schedules("repo-c", "a", &["0 2 * * *"]),
+            workflow_with_schedules("repo-d", "c", &["15 1 * * *"]),
+        ];
+
+        let schedules: Vec<String> = group_by_schedule(&workflows)
+            .into_iter()
+            .map(|g| g.schedule)
+            .collect();
+        assert_eq!(schedules, vec!["0 2 * * *", "15 1 * * *", "30 1 * * *"]);
+    }
+
+    #[test]
+    fn test_group_by_schedule_empty() {
+        assert!(group_by_schedule(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_console_formatter_group_by_schedule() {
+        let mut result = ScanResult::new();
+        result.workflows = vec![
+            workflow_with_schedules("repo-a", "nightly", &["0 0 * * *"]),
+            workflow_with_schedules("repo-b", "nightly", &["00 00 * * *"]),
+        ];
+
+        let output = ConsoleFormatter::new()
+            .with_group_by_schedule(true)
+            .format(&result)
+            .unwrap();
+        assert!(output.contains("REPOSITORIES"));
+        assert!(output.contains("repo-a, repo-b"));
+        assert!(output.contains("Total: 2 scheduled workflows"));
+    }
+
+    #[test]
+    fn test_console_formatter_without_group_by_schedule() {
+        let mut result = ScanResult::new();
+        result.workflows = vec![workflow_with_schedules("repo-a", "nightly", &["0 0 * * *"])];
+
+        let output = ConsoleFormatter::new().format(&result).unwrap();
+        assert!(!output.contains("REPOSITORIES"));
+    }
 }
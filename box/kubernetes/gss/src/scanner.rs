@@ -13,11 +13,65 @@ use tracing::{debug, info, warn};
 
 const EXCLUDE_REPOS_PATH: &str = "/etc/gss/exclude-repos.txt";
 
+/// Repository scope applied before any workflow files are fetched.
+///
+/// Exclusion always wins: a repository matching any exclude pattern is
+/// skipped even if it also matches an include pattern. An empty include
+/// list means every non-excluded repository is in scope. Patterns are
+/// repository names or globs (`*` and `?`).
+#[derive(Debug, Clone, Default)]
+pub struct RepoFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl RepoFilter {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Self {
+        Self { include, exclude }
+    }
+
+    pub fn matches(&self, repo_name: &str) -> bool {
+        if self.exclude.iter().any(|p| glob_match(p, repo_name)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|p| glob_match(p, repo_name))
+    }
+}
+
+/// Match `text` against a glob `pattern` supporting `*` (any sequence) and `?` (any single char).
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Backtrack: let the last '*' consume one more character
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 pub struct Scanner {
     client: Arc<Octocrab>,
     concurrent_scans: usize,
     request_timeout: u64,
     excluded_repos: HashSet<String>,
+    repo_filter: RepoFilter,
 }
 
 impl Scanner {
@@ -31,9 +85,24 @@ impl Scanner {
             concurrent_scans,
             request_timeout,
             excluded_repos,
+            repo_filter: RepoFilter::default(),
         })
     }
 
+    pub fn with_repo_filter(mut self, repo_filter: RepoFilter) -> Self {
+        self.repo_filter = repo_filter;
+        self
+    }
+
+    /// Whether a repository is in scope. Archived repositories are always skipped.
+    fn should_scan(&self, repo: &Repository) -> bool {
+        if repo.archived.unwrap_or(false) {
+            debug!("Skipping archived repository: {}", repo.name);
+            return false;
+        }
+        !self.excluded_repos.contains(&repo.name) && self.repo_filter.matches(&repo.name)
+    }
+
     fn load_excluded_repos() -> Result<HashSet<String>> {
         let path = Path::new(EXCLUDE_REPOS_PATH);
         if !path.exists() {
@@ -63,10 +132,10 @@ impl Scanner {
         let total_repos = repos.len();
         info!("Found {} repositories to scan", total_repos);
 
-        // Filter excluded repos
+        // Filter archived, excluded, and out-of-scope repos
         let repos_to_scan: Vec<_> = repos
             .into_iter()
-            .filter(|repo| !self.excluded_repos.contains(&repo.name))
+            .filter(|repo| self.should_scan(repo))
             .collect();
 
         let excluded_count = total_repos - repos_to_scan.len();
@@ -436,6 +505,183 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("api-server", "api-server"));
+        assert!(!glob_match("api-server", "api-server-v2"));
+        assert!(glob_match("api-*", "api-server"));
+        assert!(glob_match("api-*", "api-"));
+        assert!(!glob_match("api-*", "web-api"));
+        assert!(glob_match("*-batch", "nightly-batch"));
+        assert!(glob_match("*batch*", "my-batch-job"));
+        assert!(glob_match("svc-?", "svc-a"));
+        assert!(!glob_match("svc-?", "svc-ab"));
+        assert!(glob_match("*", "anything"));
+    }
+
+    #[test]
+    fn test_repo_filter_include_and_exclude() {
+        let filter = RepoFilter::new(vec!["api-*".to_string()], vec!["api-legacy".to_string()]);
+        assert!(filter.matches("api-server"));
+        assert!(!filter.matches("web-app"));
+        // Exclude wins over include
+        assert!(!filter.matches("api-legacy"));
+
+        let empty = RepoFilter::default();
+        assert!(empty.matches("any-repo"));
+    }
+
+    #[tokio::test]
+    async fn test_scan_excluded_and_archived_repos_never_scanned() {
+        let mock_server = MockServer::start().await;
+        let base_url = mock_server.uri();
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/test-org/repos"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "id": 1,
+                    "name": "keep-repo",
+                    "url": format!("{}/repos/test-org/keep-repo", base_url)
+                },
+                {
+                    "id": 2,
+                    "name": "legacy-repo",
+                    "url": format!("{}/repos/test-org/legacy-repo", base_url)
+                },
+                {
+                    "id": 3,
+                    "name": "archived-repo",
+                    "url": format!("{}/repos/test-org/archived-repo", base_url),
+                    "archived": true
+                }
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/test-org/repos"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-org/keep-repo/actions/workflows"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 0,
+                "workflows": []
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // Excluded and archived repos must never be fetched
+        Mock::given(method("GET"))
+            .and(path("/repos/test-org/legacy-repo/actions/workflows"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-org/archived-repo/actions/workflows"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let octocrab = Octocrab::builder()
+            .personal_token("test-token".to_string())
+            .base_uri(&base_url)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let scanner = Scanner::new(octocrab, 5, 30)
+            .unwrap()
+            .with_repo_filter(RepoFilter::new(
+                vec!["*".to_string()],
+                vec!["legacy-*".to_string()],
+            ));
+        let result = scanner.scan_scheduled_workflows("test-org").await.unwrap();
+
+        assert_eq!(result.total_repos, 3);
+        assert_eq!(result.excluded_repos_count, 2);
+        assert_eq!(result.workflows.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_scan_glob_include_matches_subset() {
+        let mock_server = MockServer::start().await;
+        let base_url = mock_server.uri();
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/test-org/repos"))
+            .and(query_param("page", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "id": 1,
+                    "name": "api-orders",
+                    "url": format!("{}/repos/test-org/api-orders", base_url)
+                },
+                {
+                    "id": 2,
+                    "name": "api-payments",
+                    "url": format!("{}/repos/test-org/api-payments", base_url)
+                },
+                {
+                    "id": 3,
+                    "name": "web-frontend",
+                    "url": format!("{}/repos/test-org/web-frontend", base_url)
+                }
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/test-org/repos"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(
+                r"^/repos/test-org/api-[a-z]+/actions/workflows$",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 0,
+                "workflows": []
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/test-org/web-frontend/actions/workflows"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let octocrab = Octocrab::builder()
+            .personal_token("test-token".to_string())
+            .base_uri(&base_url)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let scanner = Scanner::new(octocrab, 5, 30)
+            .unwrap()
+            .with_repo_filter(RepoFilter::new(vec!["api-*".to_string()], Vec::new()));
+        let result = scanner.scan_scheduled_workflows("test-org").await.unwrap();
+
+        assert_eq!(result.total_repos, 3);
+        assert_eq!(result.excluded_repos_count, 1);
+    }
+
     #[test]
     fn test_workflow_yaml_parsing_with_schedule() {
         let yaml = r#"
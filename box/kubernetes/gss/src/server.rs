@@ -0,0 +1,188 @@
+//! Health and metrics endpoints for `--serve` mode (/healthz, /readyz,
+//! /metrics).
+
+use anyhow::Result;
+use axum::Router;
+use axum::extract::State;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use prometheus_client::encoding::text::encode;
+use prometheus_client::metrics::counter::Counter;
+use prometheus_client::metrics::gauge::Gauge;
+use prometheus_client::registry::Registry;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tracing::info;
+
+/// Scan metrics and health shared between the scan loop and the HTTP server.
+#[derive(Clone)]
+pub struct ServerState {
+    ready: Arc<AtomicBool>,
+    registry: Arc<Registry>,
+    scans: Counter,
+    scan_failures: Counter,
+    last_scan_duration: Gauge<f64, AtomicU64>,
+    workflows_found: Gauge,
+}
+
+impl ServerState {
+    pub fn new() -> Self {
+        let mut registry = Registry::default();
+        let scans = Counter::default();
+        let scan_failures = Counter::default();
+        let last_scan_duration = Gauge::<f64, AtomicU64>::default();
+        let workflows_found = Gauge::default();
+
+        registry.register("gss_scans", "Completed organization scans", scans.clone());
+        registry.register(
+            "gss_scan_failures",
+            "Organization scans that failed",
+            scan_failures.clone(),
+        );
+        registry.register(
+            "gss_last_scan_duration_seconds",
+            "Duration of the last successful scan",
+            last_scan_duration.clone(),
+        );
+        registry.register(
+            "gss_workflows_found",
+            "Scheduled workflows found by the last successful scan",
+            workflows_found.clone(),
+        );
+
+        Self {
+            ready: Arc::new(AtomicBool::new(false)),
+            registry: Arc::new(registry),
+            scans,
+            scan_failures,
+            last_scan_duration,
+            workflows_found,
+        }
+    }
+
+    /// Record a successful scan. The first one marks the service ready.
+    pub fn record_scan(&self, duration: Duration, workflows: usize) {
+        self.scans.inc();
+        self.last_scan_duration.set(duration.as_secs_f64());
+        self.workflows_found.set(workflows as i64);
+        self.ready.store(true, Ordering::SeqCst);
+    }
+
+    /// Record a failed scan. Results of the previous scan stay exported.
+    pub fn record_failure(&self) {
+        self.scan_failures.inc();
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+}
+
+/// Liveness: 200 while the process is serving. A first scan can take longer
+/// than a liveness probe allows on a large organization, so this does not
+/// wait for it.
+async fn healthz() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Readiness: 200 once the first scan has completed, 503 before that.
+async fn readyz(State(state): State<ServerState>) -> StatusCode {
+    if state.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+async fn metrics(State(state): State<ServerState>) -> Response {
+    let mut buf = String::new();
+    match encode(&mut buf, &state.registry) {
+        Ok(()) => (
+            [(
+                header::CONTENT_TYPE,
+                "application/openmetrics-text; version=1.0.0; charset=utf-8",
+            )],
+            buf,
+        )
+            .into_response(),
+        Err(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to encode metrics",
+        )
+            .into_response(),
+    }
+}
+
+fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+/// Start the health and metrics server on the given port.
+pub async fn serve(port: u16, state: ServerState) -> Result<()> {
+    let listener = TcpListener::bind(format!("0.0.0.0:{port}")).await?;
+    info!("Health and metrics server listening on port {}", port);
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn spawn_server(state: ServerState) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            axum::serve(listener, router(state)).await.unwrap();
+        });
+        format!("http://127.0.0.1:{port}")
+    }
+
+    #[tokio::test]
+    async fn test_readyz_ok_after_first_scan() {
+        let state = ServerState::new();
+        let base = spawn_server(state.clone()).await;
+        let client = reqwest::Client::new();
+
+        // Live right away, ready only after the first scan
+        let resp = client.get(format!("{base}/healthz")).send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+        let resp = client.get(format!("{base}/readyz")).send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 503);
+
+        // A failed scan does not make the service ready
+        state.record_failure();
+        assert!(!state.is_ready());
+
+        state.record_scan(Duration::from_millis(1500), 42);
+        let resp = client.get(format!("{base}/readyz")).send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reports_last_scan() {
+        let state = ServerState::new();
+        state.record_scan(Duration::from_secs(2), 7);
+        state.record_scan(Duration::from_millis(1500), 42);
+        state.record_failure();
+        let base = spawn_server(state).await;
+
+        let body = reqwest::get(format!("{base}/metrics"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(body.contains("gss_scans_total 2"));
+        assert!(body.contains("gss_scan_failures_total 1"));
+        assert!(body.contains("gss_last_scan_duration_seconds 1.5"));
+        assert!(body.contains("gss_workflows_found 42"));
+    }
+}
@@ -35,3 +35,7 @@ app.kubernetes.io/instance: {{ .Release.Name }}
 {{- default "default" .Values.serviceAccount.name }}
 {{- end }}
 {{- end }}
+
+{{- define "kuo.webhookSecretName" -}}
+{{- default (printf "%s-webhook-tls" (include "kuo.fullname" .)) .Values.conversionWebhook.secretName }}
+{{- end }}
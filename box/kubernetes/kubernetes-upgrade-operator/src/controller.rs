@@ -1,20 +1,147 @@
 //! `EKSUpgrade` controller - reconcile dispatch and error policy.
 
-use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
-use kube::Api;
 use kube::api::{Patch, PatchParams};
 use kube::runtime::controller::Action;
+use kube::runtime::reflector::ObjectRef;
+use kube::{Api, ResourceExt};
 use tracing::{error, info, warn};
 
 use crate::aws::AwsClients;
-use crate::crd::{EKSUpgrade, EKSUpgradeStatus, UpgradePhase};
+use crate::crd::{ComponentStatus, EKSUpgrade, EKSUpgradeSpec, EKSUpgradeStatus, UpgradePhase};
+use crate::error::KuoError;
+use crate::maintenance::{self, WindowState};
 use crate::notify::{self, SlackNotifier};
 use crate::phases;
 use crate::status;
-use crate::telemetry::metrics::{Metrics, PhaseLabels, ReconcileLabels, UpgradeLabels};
+use crate::telemetry::metrics::{
+    Metrics, PhaseLabels, ReconcileLabels, RetryLabels, UpgradeLabels,
+};
+
+/// Annotation that pauses an in-progress upgrade when set to `"true"`.
+pub const PAUSE_ANNOTATION: &str = "kuo.io/pause";
+
+/// Requeue interval while an upgrade is paused.
+const PAUSE_REQUEUE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Whether the upgrade is paused via `spec.paused` or the pause annotation.
+fn is_paused(obj: &EKSUpgrade) -> bool {
+    obj.spec.paused
+        || obj
+            .annotations()
+            .get(PAUSE_ANNOTATION)
+            .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Fingerprint of everything in the spec but `paused`.
+///
+/// FNV-1a over the serialized spec, which stays stable across operator
+/// releases unlike `DefaultHasher`.
+fn spec_fingerprint(spec: &EKSUpgradeSpec) -> String {
+    let mut value = serde_json::to_value(spec).unwrap_or_default();
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("paused");
+    }
+    let hash = value
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Record that `status` reflects `spec` at `generation`.
+fn observe_spec(status: &mut EKSUpgradeStatus, spec: &EKSUpgradeSpec, generation: i64) {
+    status.observed_generation = generation;
+    status.spec_fingerprint = Some(spec_fingerprint(spec));
+}
+
+/// What a `Completed` or `Failed` upgrade does on reconcile.
+#[derive(Debug, PartialEq, Eq)]
+enum TerminalAction {
+    /// Nothing changed.
+    Idle,
+    /// Only `paused` changed; a finished upgrade has nothing to pause.
+    IgnorePauseEdit,
+    /// The spec changed but the upgrade is paused; restart once resumed.
+    HoldPaused,
+    /// The spec changed; restart from Pending.
+    Restart,
+}
+
+fn terminal_action(obj: &EKSUpgrade, status: &EKSUpgradeStatus, generation: i64) -> TerminalAction {
+    if status.observed_generation >= generation {
+        TerminalAction::Idle
+    } else if status.spec_fingerprint.as_deref() == Some(spec_fingerprint(&obj.spec).as_str()) {
+        TerminalAction::IgnorePauseEdit
+    } else if is_paused(obj) {
+        TerminalAction::HoldPaused
+    } else {
+        TerminalAction::Restart
+    }
+}
+
+/// First backoff delay after a throttled or failed AWS call.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(10);
+
+/// Upper bound for the backoff delay.
+const RETRY_MAX_DELAY: Duration = Duration::from_mins(10);
+
+/// Exponential backoff delay for the given retry attempt (0-based).
+///
+/// `min(base * 2^attempt, max_delay)`, scaled down by up to 20% by `jitter`
+/// (a fraction in `[0, 1)`) so resources failing together do not retry in
+/// lockstep.
+fn backoff_delay(attempt: u32, jitter: f64) -> Duration {
+    let exp = RETRY_BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt));
+    let capped = exp.min(RETRY_MAX_DELAY);
+    capped.mul_f64(0.2f64.mul_add(-jitter.clamp(0.0, 1.0), 1.0))
+}
+
+/// Cheap jitter source in `[0, 1)` from the sub-second clock.
+fn jitter_fraction() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    f64::from(nanos) / 1e9
+}
+
+/// Whether AWS-side work is already in flight: a control plane or node group
+/// update, or a blue/green replacement whose green node group was created.
+/// In-flight work is polled to completion even outside maintenance windows.
+fn has_active_update(status: &EKSUpgradeStatus) -> bool {
+    status
+        .phases
+        .control_plane
+        .as_ref()
+        .and_then(|cp| cp.update_id.as_ref())
+        .is_some()
+        || status.phases.nodegroups.iter().any(|ng| {
+            ng.update_id.is_some()
+                || (ng.blue_green.is_some() && ng.status == ComponentStatus::InProgress)
+        })
+}
+
+/// Longest requeue while waiting for a maintenance window, so a distant
+/// window is re-evaluated periodically rather than by a single long timer.
+const WINDOW_MAX_REQUEUE: Duration = Duration::from_hours(1);
+
+/// Requeue delay while outside every maintenance window: shortly after the
+/// next window opens, capped at `WINDOW_MAX_REQUEUE`.
+fn window_requeue(
+    next_start: Option<chrono::DateTime<chrono::Utc>>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Duration {
+    next_start
+        .and_then(|t| (t - now).to_std().ok())
+        .map_or(WINDOW_MAX_REQUEUE, |d| d + Duration::from_secs(5))
+        .min(WINDOW_MAX_REQUEUE)
+}
 
 /// Shared context for the controller.
 pub struct Context {
@@ -22,19 +149,62 @@ pub struct Context {
     pub metrics: Arc<Metrics>,
     /// Slack notifier. `None` when `SLACK_WEBHOOK_URL` is not set.
     pub slack: Option<Arc<SlackNotifier>>,
+    /// Consecutive retryable failures per resource, reset on a successful reconcile.
+    pub retry_counts: Arc<Mutex<HashMap<ObjectRef<EKSUpgrade>, u32>>>,
+}
+
+impl Context {
+    /// Record a retry for `obj` and return its backoff delay.
+    fn retry_backoff(&self, obj: &EKSUpgrade, status_code: u16) -> Duration {
+        let attempt = {
+            let mut counts = self.retry_counts.lock().unwrap();
+            let count = counts.entry(ObjectRef::from_obj(obj)).or_insert(0);
+            let attempt = *count;
+            *count = count.saturating_add(1);
+            attempt
+        };
+        self.metrics
+            .retry_total
+            .get_or_create(&RetryLabels {
+                cluster_name: obj.spec.cluster_name.clone(),
+                region: obj.spec.region.clone(),
+                status_code: status_code.to_string(),
+            })
+            .inc();
+        backoff_delay(attempt, jitter_fraction())
+    }
+
+    /// Count an upgrade that just moved to the `Failed` phase.
+    fn record_upgrade_failed(&self, obj: &EKSUpgrade) {
+        self.metrics
+            .upgrade_failed_total
+            .get_or_create(&UpgradeLabels {
+                cluster_name: obj.spec.cluster_name.clone(),
+                region: obj.spec.region.clone(),
+            })
+            .inc();
+    }
+
+    /// Forget the retry history of `obj` after a successful reconcile.
+    fn reset_retries(&self, obj: &EKSUpgrade) {
+        self.retry_counts
+            .lock()
+            .unwrap()
+            .remove(&ObjectRef::from_obj(obj));
+    }
 }
 
 /// Reconcile an `EKSUpgrade` resource.
 ///
 /// Phase-based state machine: reads current phase, executes one step, patches status.
 #[allow(clippy::too_many_lines)]
-pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action, kube::Error> {
+pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action, KuoError> {
     let name = obj.metadata.name.as_deref().unwrap_or("unknown");
 
     let api: Api<EKSUpgrade> = Api::all(ctx.kube_client.clone());
 
     let spec = &obj.spec;
-    let current_status = obj.status.clone().unwrap_or_default();
+    let mut current_status = obj.status.clone().unwrap_or_default();
     let phase = current_status
         .phase
         .clone()
@@ -47,39 +217,87 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
     // bumped), restart the reconcile from Pending. This is how a rollback is
     // triggered on an already-completed resource: edit the same EKSUpgrade
     // (e.g. set `upgradeMode: Rollback` and `targetVersion` to N-1) and the
-    // operator re-plans and executes against the new spec.
+    // operator re-plans and executes against the new spec. The pause is
+    // checked first: a pause edit alone never restarts a finished upgrade, and
+    // a real change waits until the pause is lifted.
     if phase == UpgradePhase::Completed || phase == UpgradePhase::Failed {
-        if current_status.observed_generation < generation {
-            info!(
-                "Spec changed for {} (phase {}); restarting reconcile from Pending",
-                name, phase
-            );
-            // Explicit null-emitting merge patch: fields with
-            // `skip_serializing_if` (message, completedAt, empty vecs, etc.)
-            // would otherwise survive a merge patch and leave stale run state.
-            let patch = reset_status_patch(&current_status, generation);
-            if let Err(e) = api
-                .patch_status(name, &PatchParams::apply("kuo"), &Patch::Merge(&patch))
-                .await
-            {
-                warn!("Failed to reset status for {}: {}", name, e);
-                return Ok(Action::requeue(Duration::from_secs(5)));
+        match terminal_action(&obj, &current_status, generation) {
+            TerminalAction::Idle => {}
+            TerminalAction::IgnorePauseEdit => {
+                info!(
+                    "Ignoring pause change for {} in terminal phase {}",
+                    name, phase
+                );
+                let patch = serde_json::json!({ "status": { "observedGeneration": generation } });
+                if let Err(e) = api
+                    .patch_status(name, &PatchParams::apply("kuo"), &Patch::Merge(&patch))
+                    .await
+                {
+                    warn!("Failed to patch status for {}: {}", name, e);
+                    return Ok(Action::requeue(Duration::from_secs(5)));
+                }
+            }
+            TerminalAction::HoldPaused => {
+                info!(
+                    "Spec changed for {} (phase {}) while paused; restarting once resumed",
+                    name, phase
+                );
+                return Ok(Action::requeue(PAUSE_REQUEUE_INTERVAL));
+            }
+            TerminalAction::Restart => {
+                info!(
+                    "Spec changed for {} (phase {}); restarting reconcile from Pending",
+                    name, phase
+                );
+                // Explicit null-emitting merge patch: fields with
+                // `skip_serializing_if` (message, completedAt, empty vecs, etc.)
+                // would otherwise survive a merge patch and leave stale run state.
+                let patch = reset_status_patch(&current_status, generation);
+                if let Err(e) = api
+                    .patch_status(name, &PatchParams::apply("kuo"), &Patch::Merge(&patch))
+                    .await
+                {
+                    warn!("Failed to reset status for {}: {}", name, e);
+                    return Ok(Action::requeue(Duration::from_secs(5)));
+                }
+                return Ok(Action::requeue(Duration::from_millis(100)));
             }
-            return Ok(Action::requeue(Duration::from_millis(100)));
         }
         return Ok(Action::await_change());
     }
-    let has_active_update = current_status
-        .phases
-        .control_plane
-        .as_ref()
-        .and_then(|cp| cp.update_id.as_ref())
-        .is_some()
-        || current_status
-            .phases
-            .nodegroups
-            .iter()
-            .any(|ng| ng.update_id.is_some());
+
+    // Emergency brake: a paused upgrade stays in its current phase and no phase
+    // handler runs, so nothing is mutated. Phase progress (update IDs, per
+    // component status) is already persisted, so lifting the pause resumes
+    // exactly where it stopped. The annotation does not bump the generation,
+    // hence the periodic requeue instead of waiting for a spec change.
+    let paused = is_paused(&obj);
+    let pause_changed = status::set_paused(&mut current_status, paused);
+    if paused {
+        if pause_changed {
+            info!("Upgrade {} paused in phase {}", name, phase);
+            if let Err(e) = status::patch_status(&api, name, &current_status).await {
+                warn!("Failed to patch status for {}: {}", name, e);
+                return Ok(Action::requeue(Duration::from_secs(5)));
+            }
+            status::EventRecorder::new(ctx.kube_client.clone(), &obj)
+                .publish("UpgradePaused", &format!("Upgrade paused in phase {phase}"))
+                .await;
+        }
+        return Ok(Action::requeue(PAUSE_REQUEUE_INTERVAL));
+    }
+    if pause_changed {
+        info!("Upgrade {} resumed in phase {}", name, phase);
+        let _ = status::patch_status(&api, name, &current_status).await;
+        status::EventRecorder::new(ctx.kube_client.clone(), &obj)
+            .publish(
+                "UpgradeResumed",
+                &format!("Upgrade resumed in phase {phase}"),
+            )
+            .await;
+    }
+
+    let has_active_update = has_active_update(&current_status);
     if current_status.observed_generation >= generation
         && phase != UpgradePhase::Pending
         && !has_active_update
@@ -100,6 +318,49 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
         }
     }
 
+    // Maintenance windows gate the mutating phases. An AWS update already in
+    // flight is still polled to completion; only new changes wait.
+    if phase.is_mutating() && !has_active_update {
+        let now = chrono::Utc::now();
+        match maintenance::evaluate(&spec.maintenance_windows, now) {
+            Ok(WindowState::Open) => {
+                if status::set_waiting_for_window(&mut current_status, false, None) {
+                    info!("Maintenance window open for {}, resuming", name);
+                }
+            }
+            Ok(WindowState::Closed { next_start }) => {
+                let message = next_start.map_or_else(
+                    || "No maintenance window opens within the next year".to_string(),
+                    |t| format!("Next maintenance window opens at {}", t.to_rfc3339()),
+                );
+                if status::set_waiting_for_window(&mut current_status, true, Some(message.clone()))
+                {
+                    info!(
+                        "Upgrade {} waiting for maintenance window: {}",
+                        name, message
+                    );
+                    if let Err(e) = status::patch_status(&api, name, &current_status).await {
+                        warn!("Failed to patch status for {}: {}", name, e);
+                        return Ok(Action::requeue(Duration::from_secs(5)));
+                    }
+                    status::EventRecorder::new(ctx.kube_client.clone(), &obj)
+                        .publish("WaitingForWindow", &message)
+                        .await;
+                }
+                return Ok(Action::requeue(window_requeue(next_start, now)));
+            }
+            Err(e) => {
+                error!("Invalid maintenance window for {}: {}", name, e);
+                let mut new_status = current_status.clone();
+                status::set_failed(&mut new_status, e.to_string());
+                observe_spec(&mut new_status, spec, generation);
+                ctx.record_upgrade_failed(&obj);
+                let _ = status::patch_status(&api, name, &new_status).await;
+                return Ok(Action::await_change());
+            }
+        }
+    }
+
     info!("Reconciling {} (phase: {})", name, phase);
 
     // Pre-initialize metric label combinations for this cluster (once per cluster)
@@ -134,7 +395,8 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
                 "AuthenticationFailed",
                 Some(e.to_string()),
             );
-            new_status.observed_generation = generation;
+            observe_spec(&mut new_status, spec, generation);
+            ctx.record_upgrade_failed(&obj);
             let _ = status::patch_status(&api, name, &new_status).await;
             return Ok(Action::requeue(Duration::from_mins(1)));
         }
@@ -157,7 +419,7 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
                     Some(format!("account={}", identity.account_id)),
                 );
                 new_status.identity = Some(identity);
-                new_status.observed_generation = generation;
+                observe_spec(&mut new_status, spec, generation);
                 let _ = status::patch_status(&api, name, &new_status).await;
             }
             Err(e) => {
@@ -170,7 +432,8 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
                     "IdentityVerificationFailed",
                     Some(e.to_string()),
                 );
-                new_status.observed_generation = generation;
+                observe_spec(&mut new_status, spec, generation);
+                ctx.record_upgrade_failed(&obj);
                 let _ = status::patch_status(&api, name, &new_status).await;
                 return Ok(Action::requeue(Duration::from_mins(1)));
             }
@@ -217,7 +480,7 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
             phases::addons::execute(spec, &current_status, &aws).await
         }
         UpgradePhase::UpgradingNodeGroups | UpgradePhase::RollingBackNodeGroups => {
-            phases::nodegroups::execute(spec, &current_status, &aws).await
+            phases::nodegroups::execute(spec, &current_status, &aws, &ctx.kube_client).await
         }
         // Karpenter NodePool replacement is forward-only (no rollback variant).
         UpgradePhase::UpgradingKarpenterNodePools => {
@@ -230,7 +493,8 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
 
     match result {
         Ok((mut new_status, requeue)) => {
-            new_status.observed_generation = generation;
+            ctx.reset_retries(&obj);
+            observe_spec(&mut new_status, spec, generation);
 
             // Refresh the completed/total progress shown in the PROGRESS column.
             new_status.progress = status::compute_progress(&new_status);
@@ -346,6 +610,8 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
                         .upgrade_completed_total
                         .get_or_create(&upgrade_labels)
                         .inc();
+                    ctx.metrics
+                        .observe_upgrade_duration(&upgrade_labels, new_status.started_at);
                     let msg = new_status
                         .message
                         .as_deref()
@@ -361,10 +627,7 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
                     }
                 }
                 Some(UpgradePhase::Failed) => {
-                    ctx.metrics
-                        .upgrade_failed_total
-                        .get_or_create(&upgrade_labels)
-                        .inc();
+                    ctx.record_upgrade_failed(&obj);
                     let msg = new_status.message.as_deref().unwrap_or("Upgrade failed");
                     recorder.publish_warning("UpgradeFailed", msg).await;
 
@@ -404,30 +667,44 @@ pub async fn reconcile(obj: Arc<EKSUpgrade>, ctx: Arc<Context>) -> Result<Action
                 .inc();
 
             let mut new_status = current_status.clone();
-            new_status.observed_generation = generation;
+            observe_spec(&mut new_status, spec, generation);
 
             // Determine if error is transient
-            if let Some(kuo_err) = e.downcast_ref::<crate::error::KuoError>()
-                && kuo_err.is_transient()
+            if let Some(retryable) = e
+                .downcast_ref::<KuoError>()
+                .filter(|kuo_err| kuo_err.is_transient())
+                .map(KuoError::retryable_status)
             {
-                warn!("Transient error for {}, will retry: {}", name, e);
+                let message = e.to_string();
                 status::set_condition(
                     &mut new_status,
                     "Ready",
                     "False",
                     "TransientError",
-                    Some(e.to_string()),
+                    Some(message.clone()),
                 );
                 let _ = status::patch_status(&api, name, &new_status).await;
+
+                // Throttling and AWS server errors are handed to
+                // `error_policy`, which backs off exponentially.
+                if retryable.is_some()
+                    && let Ok(kuo_err) = e.downcast::<KuoError>()
+                {
+                    warn!("Retryable AWS error for {}, backing off: {}", name, message);
+                    return Err(kuo_err);
+                }
+
+                // Other transient errors retry on a short fixed interval.
+                warn!(
+                    "Transient error for {}, will retry in 10s: {}",
+                    name, message
+                );
                 return Ok(Action::requeue(Duration::from_secs(10)));
             }
 
             // Permanent error → Failed
             status::set_failed(&mut new_status, e.to_string());
-            ctx.metrics
-                .upgrade_failed_total
-                .get_or_create(&upgrade_labels)
-                .inc();
+            ctx.record_upgrade_failed(&obj);
             let _ = status::patch_status(&api, name, &new_status).await;
             recorder
                 .publish_warning("UpgradeFailed", &e.to_string())
@@ -487,17 +764,26 @@ fn reset_status_patch(current: &EKSUpgradeStatus, generation: i64) -> serde_json
 }
 
 /// Error policy for the controller.
+///
+/// `reconcile` only returns an error for AWS calls that were throttled or
+/// failed server-side (HTTP 429, 500, 503); those back off exponentially per
+/// resource. Anything else is requeued on a fixed interval.
 #[allow(clippy::needless_pass_by_value)]
-pub fn error_policy(obj: Arc<EKSUpgrade>, err: &kube::Error, _ctx: Arc<Context>) -> Action {
+pub fn error_policy(obj: Arc<EKSUpgrade>, err: &KuoError, ctx: Arc<Context>) -> Action {
     let name = obj.metadata.name.as_deref().unwrap_or("unknown");
     error!("Controller error for {}: {}", name, err);
-    Action::requeue(Duration::from_secs(30))
+    match err.retryable_status() {
+        Some(code) => Action::requeue(ctx.retry_backoff(&obj, code)),
+        None => Action::requeue(Duration::from_secs(30)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crd::{ControlPlaneStatus, UpgradeCondition};
+    use crate::crd::{
+        BlueGreenStage, BlueGreenStatus, ControlPlaneStatus, NodegroupStatus, UpgradeCondition,
+    };
 
     #[test]
     fn test_reset_status_patch() {
@@ -555,4 +841,264 @@ mod tests {
         // merge: the consecutive-rollback guardrail depends on it persisting.
         assert!(status.get("lastTransition").is_none());
     }
+
+    fn make_upgrade(phase: UpgradePhase) -> EKSUpgrade {
+        let spec = serde_json::from_value(serde_json::json!({
+            "clusterName": "my-cluster",
+            "targetVersion": "1.34",
+            "region": "ap-northeast-2",
+            "upgradeMode": "Forward",
+        }))
+        .unwrap();
+        let mut obj = EKSUpgrade::new("my-upgrade", spec);
+        obj.metadata.generation = Some(1);
+        obj.status = Some(EKSUpgradeStatus {
+            phase: Some(phase),
+            observed_generation: 1,
+            ..Default::default()
+        });
+        obj
+    }
+
+    fn annotate_pause(obj: &mut EKSUpgrade, value: &str) {
+        obj.annotations_mut()
+            .insert(PAUSE_ANNOTATION.to_string(), value.to_string());
+    }
+
+    #[test]
+    fn test_has_active_update_includes_pending_blue_green() {
+        let mut status = EKSUpgradeStatus::default();
+        status.phases.nodegroups.push(NodegroupStatus {
+            name: "ng-app".to_string(),
+            current_version: "1.33".to_string(),
+            target_version: "1.34".to_string(),
+            status: ComponentStatus::Pending,
+            update_id: None,
+            started_at: None,
+            completed_at: None,
+            blue_green: None,
+        });
+        assert!(!has_active_update(&status));
+
+        // The green group exists: draining and deleting must not wait for a window.
+        let ng = &mut status.phases.nodegroups[0];
+        ng.status = ComponentStatus::InProgress;
+        ng.blue_green = Some(BlueGreenStatus {
+            green_nodegroup: "ng-app-1-34".to_string(),
+            blue_nodegroup: "ng-app".to_string(),
+            stage: BlueGreenStage::DrainingBlue,
+            stage_started_at: None,
+            extra_nodes: 3,
+            estimated_extra_cost: String::new(),
+        });
+        assert!(has_active_update(&status));
+
+        status.phases.nodegroups[0].status = ComponentStatus::Completed;
+        assert!(!has_active_update(&status));
+    }
+
+    #[test]
+    fn test_is_paused() {
+        let mut obj = make_upgrade(UpgradePhase::UpgradingAddons);
+        assert!(!is_paused(&obj));
+
+        annotate_pause(&mut obj, "false");
+        assert!(!is_paused(&obj));
+
+        annotate_pause(&mut obj, "True");
+        assert!(is_paused(&obj));
+
+        let mut obj = make_upgrade(UpgradePhase::UpgradingAddons);
+        obj.spec.paused = true;
+        assert!(is_paused(&obj));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_paused_does_not_advance_phase() {
+        // Unreachable API server: a status patch would fail and requeue after
+        // 5s instead of the pause interval.
+        let config = kube::Config::new("http://127.0.0.1:1".parse().unwrap());
+        let ctx = Arc::new(Context {
+            kube_client: kube::Client::try_from(config).unwrap(),
+            metrics: Arc::new(Metrics::new(
+                &mut prometheus_client::registry::Registry::default(),
+            )),
+            slack: None,
+            retry_counts: Arc::default(),
+        });
+
+        let mut obj = make_upgrade(UpgradePhase::UpgradingControlPlane);
+        annotate_pause(&mut obj, "true");
+        let current = obj.status.as_mut().unwrap();
+        current.phases.control_plane = Some(ControlPlaneStatus {
+            current_step: 1,
+            total_steps: 2,
+            ..Default::default()
+        });
+        // Already marked paused by an earlier reconcile, so no patch is due.
+        assert!(status::set_paused(current, true));
+
+        // Every reconcile returns before the phase handler (which would reach
+        // AWS and fail) and keeps requeueing at the pause interval.
+        for _ in 0..3 {
+            let action = reconcile(Arc::new(obj.clone()), ctx.clone()).await.unwrap();
+            assert_eq!(action, Action::requeue(PAUSE_REQUEUE_INTERVAL));
+        }
+    }
+
+    #[test]
+    fn test_spec_fingerprint_ignores_paused() {
+        let obj = make_upgrade(UpgradePhase::Completed);
+        let mut paused = obj.spec.clone();
+        paused.paused = true;
+        assert_eq!(spec_fingerprint(&obj.spec), spec_fingerprint(&paused));
+
+        let mut rollback = obj.spec.clone();
+        rollback.target_version = "1.33".to_string();
+        assert_ne!(spec_fingerprint(&obj.spec), spec_fingerprint(&rollback));
+    }
+
+    #[test]
+    fn test_terminal_action() {
+        let mut obj = make_upgrade(UpgradePhase::Completed);
+        let mut status = obj.status.clone().unwrap();
+        observe_spec(&mut status, &obj.spec, 1);
+        assert_eq!(terminal_action(&obj, &status, 1), TerminalAction::Idle);
+
+        // Toggling spec.paused bumps the generation but changes nothing else
+        obj.spec.paused = true;
+        assert_eq!(
+            terminal_action(&obj, &status, 2),
+            TerminalAction::IgnorePauseEdit
+        );
+
+        // A rollback staged while paused waits for the pause to be lifted
+        obj.spec.target_version = "1.33".to_string();
+        assert_eq!(
+            terminal_action(&obj, &status, 3),
+            TerminalAction::HoldPaused
+        );
+        obj.spec.paused = false;
+        assert_eq!(terminal_action(&obj, &status, 4), TerminalAction::Restart);
+
+        // Status written before fingerprints existed restarts on any change
+        status.spec_fingerprint = None;
+        obj.spec.target_version = "1.34".to_string();
+        assert_eq!(terminal_action(&obj, &status, 5), TerminalAction::Restart);
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_until_cap() {
+        assert_eq!(backoff_delay(0, 0.0), Duration::from_secs(10));
+        assert_eq!(backoff_delay(1, 0.0), Duration::from_secs(20));
+        assert_eq!(backoff_delay(5, 0.0), Duration::from_secs(320));
+        assert_eq!(backoff_delay(6, 0.0), RETRY_MAX_DELAY);
+        assert_eq!(backoff_delay(u32::MAX, 0.0), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_backoff_delay_jitter_stays_within_bounds() {
+        for jitter in [0.0, 0.25, 0.5, 0.999] {
+            let delay = backoff_delay(3, jitter);
+            assert!(delay <= Duration::from_secs(80));
+            assert!(delay >= Duration::from_secs(64));
+        }
+        assert!(backoff_delay(20, 0.999) <= RETRY_MAX_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_retry_backoff_counts_and_resets() {
+        let config = kube::Config::new("http://127.0.0.1:1".parse().unwrap());
+        let ctx = Context {
+            kube_client: kube::Client::try_from(config).unwrap(),
+            metrics: Arc::new(Metrics::new(
+                &mut prometheus_client::registry::Registry::default(),
+            )),
+            slack: None,
+            retry_counts: Arc::default(),
+        };
+        let obj = make_upgrade(UpgradePhase::UpgradingAddons);
+
+        let first = ctx.retry_backoff(&obj, 429);
+        let second = ctx.retry_backoff(&obj, 429);
+        assert!(first <= RETRY_BASE_DELAY);
+        assert!(second > RETRY_BASE_DELAY);
+        let labels = RetryLabels {
+            cluster_name: "my-cluster".to_string(),
+            region: "ap-northeast-2".to_string(),
+            status_code: "429".to_string(),
+        };
+        assert_eq!(ctx.metrics.retry_total.get_or_create(&labels).get(), 2);
+
+        ctx.reset_retries(&obj);
+        assert!(ctx.retry_counts.lock().unwrap().is_empty());
+        assert!(ctx.retry_backoff(&obj, 503) <= RETRY_BASE_DELAY);
+    }
+
+    #[tokio::test]
+    async fn test_error_policy_backs_off_only_on_retryable_aws_errors() {
+        let config = kube::Config::new("http://127.0.0.1:1".parse().unwrap());
+        let ctx = Arc::new(Context {
+            kube_client: kube::Client::try_from(config).unwrap(),
+            metrics: Arc::new(Metrics::new(
+                &mut prometheus_client::registry::Registry::default(),
+            )),
+            slack: None,
+            retry_counts: Arc::default(),
+        });
+        let obj = Arc::new(make_upgrade(UpgradePhase::UpgradingAddons));
+
+        let throttled = KuoError::AwsRetryable("eks::addon".into(), 429, "Rate exceeded".into());
+        error_policy(obj.clone(), &throttled, ctx.clone());
+        error_policy(obj.clone(), &throttled, ctx.clone());
+        assert_eq!(ctx.retry_counts.lock().unwrap().values().sum::<u32>(), 2);
+
+        let other = KuoError::AwsSdk("eks::addon".into(), "bad".into());
+        assert_eq!(
+            error_policy(obj, &other, ctx.clone()),
+            Action::requeue(Duration::from_secs(30))
+        );
+        assert_eq!(ctx.retry_counts.lock().unwrap().values().sum::<u32>(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_record_upgrade_failed() {
+        let config = kube::Config::new("http://127.0.0.1:1".parse().unwrap());
+        let ctx = Context {
+            kube_client: kube::Client::try_from(config).unwrap(),
+            metrics: Arc::new(Metrics::new(
+                &mut prometheus_client::registry::Registry::default(),
+            )),
+            slack: None,
+            retry_counts: Arc::default(),
+        };
+        let obj = make_upgrade(UpgradePhase::UpgradingAddons);
+
+        ctx.record_upgrade_failed(&obj);
+        let labels = UpgradeLabels {
+            cluster_name: "my-cluster".to_string(),
+            region: "ap-northeast-2".to_string(),
+        };
+        assert_eq!(
+            ctx.metrics
+                .upgrade_failed_total
+                .get_or_create(&labels)
+                .get(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_window_requeue() {
+        let now = chrono::Utc::now();
+        assert_eq!(
+            window_requeue(Some(now + chrono::Duration::minutes(10)), now),
+            Duration::from_secs(605)
+        );
+        assert_eq!(
+            window_requeue(Some(now + chrono::Duration::days(2)), now),
+            WINDOW_MAX_REQUEUE
+        );
+        assert_eq!(window_requeue(None, now), WINDOW_MAX_REQUEUE);
+    }
 }
@@ -1,5 +1,6 @@
 //! `EKSUpgrade` CRD type definition.
 
+pub mod conversion;
 pub mod spec;
 pub mod status;
 pub mod types;
@@ -9,14 +10,14 @@ pub mod types;
 // the public API consistent across `crate::crd::*`.
 #[allow(unused_imports)]
 pub use spec::{
-    EKSUpgrade, EKSUpgradeSpec, KarpenterNodePoolsConfig, KarpenterStrategy, NotificationConfig,
-    TimeoutConfig, UpgradeMode,
+    EKSUpgrade, EKSUpgradeSpec, KarpenterNodePoolsConfig, KarpenterStrategy, MaintenanceWindow,
+    NodeGroupStrategy, NotificationConfig, TimeoutConfig, UpgradeMode,
 };
 #[allow(unused_imports)]
 pub use status::{
-    AddonStatus, AwsIdentity, ControlPlaneStatus, CurrentBatchEntry, EKSUpgradeStatus,
-    KarpenterNodePoolsStatus, KarpenterPoolStatus, LifecycleStatus, NodeClaimReplacement,
-    NodegroupStatus, PhaseStatuses, PlanningStatus, PreflightCheckStatus, PreflightStatus,
-    TransitionRecord, UpgradeCondition, VersionLifecycleInfo,
+    AddonStatus, AwsIdentity, BlueGreenStatus, ControlPlaneStatus, CurrentBatchEntry,
+    EKSUpgradeStatus, KarpenterNodePoolsStatus, KarpenterPoolStatus, LifecycleStatus,
+    NodeClaimReplacement, NodegroupStatus, PhaseStatuses, PlanningStatus, PreflightCheckStatus,
+    PreflightStatus, TransitionRecord, UpgradeCondition, VersionLifecycleInfo,
 };
-pub use types::{ComponentStatus, UpgradePhase};
+pub use types::{BlueGreenStage, ComponentStatus, UpgradePhase};
@@ -0,0 +1,264 @@
+//! `EKSUpgrade` CRD version conversion.
+//!
+//! `v1beta1` is the hub (storage) version and `v1alpha1` is a deprecated spoke
+//! that stays served for existing manifests. `v1beta1` promotes the `v1alpha1`
+//! schema unchanged, so converting an object only rewrites its `apiVersion`;
+//! every spec and status field is carried over verbatim.
+//!
+//! There is no field mapping because no field differs between the versions:
+//! the CRD declares the `v1beta1` schema once and `v1alpha1` reuses it through
+//! a YAML anchor (`schema: *schema`), which a test below enforces. A change
+//! that gives the versions different schemas must drop the anchor and add the
+//! mapping for both directions to `convert_object`.
+
+use kube::core::Status;
+use kube::core::conversion::{ConversionRequest, ConversionResponse, ConversionReview};
+
+/// API group served by the `EKSUpgrade` CRD.
+pub const GROUP: &str = "kuo.io";
+
+/// Hub version: the storage version the controller reads and writes.
+pub const HUB_VERSION: &str = "v1beta1";
+
+/// Spoke versions converted to and from the hub.
+pub const SPOKE_VERSIONS: &[&str] = &["v1alpha1"];
+
+const KIND: &str = "EKSUpgrade";
+
+/// Whether `api_version` (e.g. `kuo.io/v1alpha1`) is served by the CRD.
+fn is_served(api_version: &str) -> bool {
+    api_version
+        .strip_prefix(GROUP)
+        .and_then(|v| v.strip_prefix('/'))
+        .is_some_and(|v| v == HUB_VERSION || SPOKE_VERSIONS.contains(&v))
+}
+
+/// Convert a single `EKSUpgrade` object to `desired_api_version`.
+///
+/// Rejects objects of another kind or from an unknown version rather than
+/// passing them through, so a misrouted request fails loudly.
+pub fn convert_object(
+    mut obj: serde_json::Value,
+    desired_api_version: &str,
+) -> Result<serde_json::Value, String> {
+    if !is_served(desired_api_version) {
+        return Err(format!(
+            "unsupported desired apiVersion {desired_api_version}"
+        ));
+    }
+
+    let kind = obj.get("kind").and_then(serde_json::Value::as_str);
+    if kind != Some(KIND) {
+        return Err(format!(
+            "expected kind {KIND}, got {}",
+            kind.unwrap_or("<none>")
+        ));
+    }
+
+    let source = obj
+        .get("apiVersion")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    if !is_served(source) {
+        return Err(format!("unsupported source apiVersion {source}"));
+    }
+
+    obj["apiVersion"] = serde_json::Value::String(desired_api_version.to_string());
+    Ok(obj)
+}
+
+/// Handle a `ConversionReview` from the API server.
+pub fn review(review: ConversionReview) -> ConversionReview {
+    let request = match ConversionRequest::from_review(review) {
+        Ok(request) => request,
+        Err(e) => {
+            return ConversionResponse::invalid(Status::failure(
+                &e.to_string(),
+                "InvalidConversionReview",
+            ))
+            .into_review();
+        }
+    };
+
+    let desired = request.desired_api_version.clone();
+    let converted = request
+        .objects
+        .iter()
+        .cloned()
+        .map(|obj| convert_object(obj, &desired))
+        .collect::<Result<Vec<_>, _>>();
+
+    let response = ConversionResponse::for_request(request);
+    match converted {
+        Ok(objects) => response.success(objects),
+        Err(e) => response.failure(Status::failure(&e, "ConversionFailed")),
+    }
+    .into_review()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::EKSUpgrade;
+
+    /// An `EKSUpgrade` with every spec field and a populated status.
+    fn full_object(api_version: &str) -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": api_version,
+            "kind": "EKSUpgrade",
+            "metadata": {
+                "name": "production-upgrade",
+                "generation": 3,
+                "annotations": { "team": "platform" }
+            },
+            "spec": {
+                "clusterName": "production-cluster",
+                "targetVersion": "1.34",
+                "region": "ap-northeast-2",
+                "upgradeMode": "Forward",
+                "assumeRoleArn": "arn:aws:iam::123456789012:role/kuo-spoke-role",
+                "addonVersions": { "vpc-cni": "v1.18.1-eksbuild.3" },
+                "dryRun": true,
+                "timeouts": { "controlPlaneMinutes": 40, "nodegroupMinutes": 90 },
+                "notification": { "onUpgrade": true, "onDryRun": false },
+                "karpenterNodePools": {
+                    "enabled": true,
+                    "nodePools": ["default", "spot"],
+                    "strategy": "Replace",
+                    "maxUnavailable": "10%",
+                    "nodeDrainTimeoutMinutes": 20,
+                    "controllerStableTimeoutMinutes": 5
+                }
+            },
+            "status": {
+                "phase": "UpgradingControlPlane",
+                "currentVersion": "1.33",
+                "observedGeneration": 3,
+                "message": "Upgrading control plane to 1.34",
+                "startedAt": "2026-01-01T00:00:00Z",
+                "phases": {
+                    "planning": { "sourceVersion": "1.33", "upgradePath": ["1.34"] },
+                    "controlPlane": {
+                        "currentStep": 1,
+                        "totalSteps": 1,
+                        "target": "1.34",
+                        "updateId": "abc-123",
+                        "startedAt": "2026-01-01T00:05:00Z"
+                    }
+                }
+            }
+        })
+    }
+
+    /// Run `review` and return the serialized `response` section.
+    fn response_of(input: ConversionReview) -> serde_json::Value {
+        serde_json::to_value(review(input)).unwrap()["response"].clone()
+    }
+
+    fn review_for(desired: &str, objects: Vec<serde_json::Value>) -> ConversionReview {
+        serde_json::from_value(serde_json::json!({
+            "apiVersion": "apiextensions.k8s.io/v1",
+            "kind": "ConversionReview",
+            "request": {
+                "uid": "705ab4f5-6393-11e8-b7cc-42010a800002",
+                "desiredAPIVersion": desired,
+                "objects": objects
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_preserves_all_fields() {
+        let original = full_object("kuo.io/v1alpha1");
+
+        let hub = convert_object(original.clone(), "kuo.io/v1beta1").unwrap();
+        assert_eq!(hub["apiVersion"], "kuo.io/v1beta1");
+        assert_eq!(hub["spec"], original["spec"]);
+        assert_eq!(hub["status"], original["status"]);
+        assert_eq!(hub["metadata"], original["metadata"]);
+
+        let back = convert_object(hub, "kuo.io/v1alpha1").unwrap();
+        assert_eq!(back, original);
+    }
+
+    #[test]
+    fn test_converted_object_deserializes_as_hub_type() {
+        let hub = convert_object(full_object("kuo.io/v1alpha1"), "kuo.io/v1beta1").unwrap();
+        let typed: EKSUpgrade = serde_json::from_value(hub).unwrap();
+        assert_eq!(typed.spec.cluster_name, "production-cluster");
+        assert!(typed.spec.dry_run);
+        assert_eq!(
+            typed.spec.karpenter_node_pools.unwrap().node_pools,
+            vec!["default", "spot"]
+        );
+        assert_eq!(
+            typed
+                .status
+                .unwrap()
+                .phases
+                .control_plane
+                .unwrap()
+                .update_id,
+            Some("abc-123".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convert_rejects_unknown_versions_and_kinds() {
+        let obj = full_object("kuo.io/v1alpha1");
+        assert!(convert_object(obj.clone(), "kuo.io/v2").is_err());
+        assert!(convert_object(obj.clone(), "other.io/v1beta1").is_err());
+        assert!(convert_object(full_object("kuo.io/v0"), "kuo.io/v1beta1").is_err());
+
+        let mut wrong_kind = obj;
+        wrong_kind["kind"] = serde_json::json!("ConfigMap");
+        assert!(convert_object(wrong_kind, "kuo.io/v1beta1").is_err());
+    }
+
+    #[test]
+    fn test_versions_share_one_schema() {
+        // Conversion copies every field, which is only correct while the
+        // spoke reuses the hub schema instead of declaring its own.
+        let crd = include_str!("../../charts/kuo/templates/crds/eksupgrades.kuo.io.yaml");
+        assert_eq!(crd.matches("openAPIV3Schema:").count(), 1);
+        let spoke = crd
+            .split("- name: v1alpha1")
+            .nth(1)
+            .expect("v1alpha1 version block");
+        assert!(spoke.contains("schema: *schema"));
+    }
+
+    #[test]
+    fn test_review_success() {
+        let review = review_for(
+            "kuo.io/v1beta1",
+            vec![
+                full_object("kuo.io/v1alpha1"),
+                full_object("kuo.io/v1alpha1"),
+            ],
+        );
+        let response = response_of(review);
+        assert_eq!(response["uid"], "705ab4f5-6393-11e8-b7cc-42010a800002");
+        assert_eq!(response["result"]["status"], "Success");
+        let converted = response["convertedObjects"].as_array().unwrap();
+        assert_eq!(converted.len(), 2);
+        assert!(
+            converted
+                .iter()
+                .all(|o| o["apiVersion"] == "kuo.io/v1beta1")
+        );
+    }
+
+    #[test]
+    fn test_review_failure() {
+        let review = review_for("kuo.io/v9", vec![full_object("kuo.io/v1alpha1")]);
+        let response = response_of(review);
+        assert_eq!(response["result"]["status"], "Failure");
+        assert!(
+            response["convertedObjects"]
+                .as_array()
+                .is_none_or(Vec::is_empty)
+        );
+    }
+}
@@ -10,7 +10,7 @@ use super::status::EKSUpgradeStatus;
 #[derive(CustomResource, Deserialize, Serialize, Clone, Debug, JsonSchema)]
 #[kube(
     group = "kuo.io",
-    version = "v1alpha1",
+    version = "v1beta1",
     kind = "EKSUpgrade",
     status = "EKSUpgradeStatus",
     printcolumn = r#"{"name":"CLUSTER","type":"string","jsonPath":".spec.clusterName"}"#,
@@ -52,10 +52,34 @@ pub struct EKSUpgradeSpec {
     #[serde(default)]
     pub dry_run: bool,
 
+    /// Hold the upgrade in its current phase.
+    ///
+    /// Equivalent to the `kuo.io/pause: "true"` annotation. While paused, no
+    /// further AWS or cluster changes are made; setting it back to `false`
+    /// resumes from the persisted phase state.
+    #[serde(default)]
+    pub paused: bool,
+
     /// Timeout configuration.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timeouts: Option<TimeoutConfig>,
 
+    /// Windows during which mutating phases may run.
+    ///
+    /// Empty (default) means upgrades run at any time. Otherwise planning and
+    /// preflight still run immediately, but control plane, add-on, and node
+    /// changes wait until at least one window is open.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+
+    /// How managed node groups are moved to the target version.
+    ///
+    /// `RollingUpdate` (default) replaces nodes in place through
+    /// `UpdateNodegroupVersion`. `BlueGreen` creates a replacement node group
+    /// at the target version, drains the original, then deletes it.
+    #[serde(default)]
+    pub node_group_strategy: NodeGroupStrategy,
+
     /// Slack notification configuration for this upgrade.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notification: Option<NotificationConfig>,
@@ -69,6 +93,22 @@ pub struct EKSUpgradeSpec {
     pub karpenter_node_pools: Option<KarpenterNodePoolsConfig>,
 }
 
+/// A recurring window during which mutating upgrade phases may run.
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindow {
+    /// 5-field cron expression for the window start (e.g., "0 22 * * MON-FRI").
+    pub cron: String,
+
+    /// How long each window stays open, in minutes.
+    pub duration_minutes: u32,
+
+    /// Offset from UTC in hours that `cron` is evaluated in (e.g., 9 for
+    /// KST). Defaults to 0 (UTC). A fixed offset does not follow DST.
+    #[serde(default)]
+    pub utc_offset_hours: i32,
+}
+
 /// Direction of the version change for an `EKSUpgrade`.
 #[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
 pub enum UpgradeMode {
@@ -88,6 +128,26 @@ impl std::fmt::Display for UpgradeMode {
     }
 }
 
+/// Managed node group upgrade strategy.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub enum NodeGroupStrategy {
+    /// In-place rolling update via `UpdateNodegroupVersion` (default).
+    #[default]
+    RollingUpdate,
+    /// Create a new node group at the target version, drain and delete the
+    /// old one. Temporarily requires twice the node group's capacity.
+    BlueGreen,
+}
+
+impl std::fmt::Display for NodeGroupStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RollingUpdate => write!(f, "RollingUpdate"),
+            Self::BlueGreen => write!(f, "BlueGreen"),
+        }
+    }
+}
+
 /// Slack notification configuration.
 #[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -244,6 +304,23 @@ mod tests {
         assert_eq!(UpgradeMode::Rollback.to_string(), "Rollback");
     }
 
+    #[test]
+    fn test_node_group_strategy_default_and_display() {
+        let json =
+            r#"{"clusterName":"c","targetVersion":"1.34","region":"r","upgradeMode":"Forward"}"#;
+        let spec: EKSUpgradeSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.node_group_strategy, NodeGroupStrategy::RollingUpdate);
+        assert_eq!(
+            NodeGroupStrategy::RollingUpdate.to_string(),
+            "RollingUpdate"
+        );
+        assert_eq!(NodeGroupStrategy::BlueGreen.to_string(), "BlueGreen");
+
+        let json = r#"{"clusterName":"c","targetVersion":"1.34","region":"r","upgradeMode":"Forward","nodeGroupStrategy":"BlueGreen"}"#;
+        let spec: EKSUpgradeSpec = serde_json::from_str(json).unwrap();
+        assert_eq!(spec.node_group_strategy, NodeGroupStrategy::BlueGreen);
+    }
+
     #[test]
     fn test_karpenter_config_serde_defaults() {
         let json = r#"{"enabled":true}"#;
@@ -5,7 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::spec::UpgradeMode;
-use super::types::{ComponentStatus, UpgradePhase};
+use super::types::{BlueGreenStage, ComponentStatus, UpgradePhase};
 
 // ============================================================================
 // Phase-specific status structs
@@ -117,6 +117,36 @@ pub struct NodegroupStatus {
     /// Timestamp when this node group upgrade completed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+
+    /// Blue/green replacement progress. Only set with `nodeGroupStrategy: BlueGreen`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blue_green: Option<BlueGreenStatus>,
+}
+
+/// Blue/green replacement progress of a managed node group.
+#[derive(Deserialize, Serialize, Clone, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlueGreenStatus {
+    /// Name of the replacement (green) node group created at the target version.
+    pub green_nodegroup: String,
+    /// Name of the original (blue) node group. Once the replacement
+    /// completes, the entry's `name` switches to the green node group.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub blue_nodegroup: String,
+    /// Current replacement stage.
+    pub stage: BlueGreenStage,
+    /// When the current stage started. Each stage is timed separately
+    /// against `timeouts.nodegroupMinutes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stage_started_at: Option<DateTime<Utc>>,
+    /// Nodes running on top of the original group while both exist
+    /// (the original group's desired size).
+    #[serde(default)]
+    pub extra_nodes: i32,
+    /// Human-readable estimate of the temporary extra capacity, e.g.
+    /// `3 x m5.large until ng-app is deleted`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub estimated_extra_cost: String,
 }
 
 /// One `NodeClaim` currently being replaced within a `NodePool`.
@@ -335,6 +365,12 @@ pub struct EKSUpgradeStatus {
     #[serde(default)]
     pub observed_generation: i64,
 
+    /// Fingerprint of the spec at `observed_generation`, ignoring `paused`.
+    /// A finished upgrade restarts only when this changes, so toggling the
+    /// pause on it does not rerun the upgrade.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec_fingerprint: Option<String>,
+
     /// Error message if the upgrade failed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
@@ -584,6 +620,7 @@ mod tests {
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         };
         let json = serde_json::to_value(&ng).unwrap();
         let obj = json.as_object().unwrap();
@@ -39,6 +39,24 @@ impl std::fmt::Display for UpgradePhase {
     }
 }
 
+impl UpgradePhase {
+    /// Whether this phase changes the cluster (and so is gated by
+    /// maintenance windows).
+    #[must_use]
+    pub const fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Self::UpgradingControlPlane
+                | Self::UpgradingAddons
+                | Self::UpgradingNodeGroups
+                | Self::UpgradingKarpenterNodePools
+                | Self::RollingBackNodeGroups
+                | Self::RollingBackAddons
+                | Self::RollingBackControlPlane
+        )
+    }
+}
+
 /// Status of a component upgrade.
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub enum ComponentStatus {
@@ -49,6 +67,27 @@ pub enum ComponentStatus {
     Skipped,
 }
 
+/// Stage of a blue/green node group replacement.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub enum BlueGreenStage {
+    /// Waiting for the green node group to become `ACTIVE`.
+    CreatingGreen,
+    /// Cordoning the blue nodes and evicting their pods.
+    DrainingBlue,
+    /// Waiting for the blue node group to be deleted.
+    DeletingBlue,
+}
+
+impl std::fmt::Display for BlueGreenStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreatingGreen => write!(f, "CreatingGreen"),
+            Self::DrainingBlue => write!(f, "DrainingBlue"),
+            Self::DeletingBlue => write!(f, "DeletingBlue"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +103,17 @@ mod tests {
         assert_eq!(UpgradePhase::Failed.to_string(), "Failed");
     }
 
+    #[test]
+    fn test_upgrade_phase_is_mutating() {
+        assert!(UpgradePhase::UpgradingControlPlane.is_mutating());
+        assert!(UpgradePhase::UpgradingKarpenterNodePools.is_mutating());
+        assert!(UpgradePhase::RollingBackNodeGroups.is_mutating());
+        assert!(!UpgradePhase::Pending.is_mutating());
+        assert!(!UpgradePhase::Planning.is_mutating());
+        assert!(!UpgradePhase::PreflightChecking.is_mutating());
+        assert!(!UpgradePhase::Completed.is_mutating());
+    }
+
     #[test]
     fn test_component_status_equality() {
         assert_eq!(ComponentStatus::Pending, ComponentStatus::Pending);
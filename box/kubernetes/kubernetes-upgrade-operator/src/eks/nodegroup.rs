@@ -2,6 +2,10 @@
 
 use anyhow::Result;
 use aws_sdk_eks::Client;
+use aws_sdk_eks::operation::create_nodegroup::CreateNodegroupError;
+use aws_sdk_eks::operation::delete_nodegroup::DeleteNodegroupError;
+use aws_sdk_eks::operation::describe_nodegroup::DescribeNodegroupError;
+use aws_sdk_eks::types::{AmiTypes, NodegroupScalingConfig};
 use futures::future::join_all;
 use tracing::{debug, info};
 
@@ -112,6 +116,203 @@ pub async fn update_nodegroup_version(
     Ok(update_id)
 }
 
+/// Maximum length of an EKS managed node group name.
+const MAX_NODEGROUP_NAME_LEN: usize = 63;
+
+/// Name of the blue/green replacement node group for `name` at `target_version`.
+///
+/// Appends the target version (`ng-app` -> `ng-app-1-34`). A version suffix
+/// left by an earlier blue/green upgrade is replaced rather than stacked
+/// (`ng-app-1-33` -> `ng-app-1-34`). The base is truncated so the result fits
+/// the 63 character limit.
+#[must_use]
+pub fn green_nodegroup_name(name: &str, target_version: &str) -> String {
+    let suffix = format!("-{}", target_version.replace('.', "-"));
+
+    // Strip a trailing `-<major>-<minor>` suffix from a previous replacement.
+    let mut parts = name.rsplitn(3, '-');
+    let minor = parts.next().unwrap_or_default();
+    let major = parts.next().unwrap_or_default();
+    let base = match parts.next() {
+        Some(base)
+            if !base.is_empty()
+                && !major.is_empty()
+                && !minor.is_empty()
+                && major.chars().all(|c| c.is_ascii_digit())
+                && minor.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => name,
+    };
+
+    let max_base = MAX_NODEGROUP_NAME_LEN.saturating_sub(suffix.len());
+    let base: String = base.chars().take(max_base).collect();
+    format!("{}{suffix}", base.trim_end_matches('-'))
+}
+
+/// Capacity of a node group that a blue/green replacement duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeGroupCapacity {
+    pub desired_size: i32,
+    pub instance_types: Vec<String>,
+}
+
+impl NodeGroupCapacity {
+    /// Describe the temporary extra capacity while both node groups run.
+    #[must_use]
+    pub fn extra_cost_estimate(&self, blue_name: &str) -> String {
+        let types = if self.instance_types.is_empty() {
+            "launch template instances".to_string()
+        } else {
+            self.instance_types.join("/")
+        };
+        format!(
+            "{} x {types} until {blue_name} is deleted (2x node group capacity)",
+            self.desired_size
+        )
+    }
+}
+
+/// Create a replacement node group at `target_version` with the same
+/// configuration as `source_name`.
+///
+/// Copies role, subnets, scaling, instance types, AMI and capacity type, disk
+/// size, labels, taints, tags, launch template, remote access and update
+/// config. Succeeds without creating anything if `green_name` already exists,
+/// so a reconcile interrupted after the create call can safely retry.
+pub async fn create_green_nodegroup(
+    client: &Client,
+    cluster_name: &str,
+    source_name: &str,
+    green_name: &str,
+    target_version: &str,
+) -> Result<NodeGroupCapacity> {
+    let response = client
+        .describe_nodegroup()
+        .cluster_name(cluster_name)
+        .nodegroup_name(source_name)
+        .send()
+        .await
+        .map_err(|e| KuoError::aws(module_path!(), e))?;
+    let source = response.nodegroup().ok_or_else(|| {
+        KuoError::UpgradeNotPossible(format!("Node group {source_name} not found"))
+    })?;
+
+    if source.ami_type() == Some(&AmiTypes::Custom) {
+        return Err(KuoError::UpgradeNotPossible(format!(
+            "Node group {source_name} uses a custom AMI; BlueGreen cannot pin its version"
+        ))
+        .into());
+    }
+
+    let capacity = NodeGroupCapacity {
+        desired_size: source
+            .scaling_config()
+            .and_then(NodegroupScalingConfig::desired_size)
+            .unwrap_or(0),
+        instance_types: source.instance_types().to_vec(),
+    };
+
+    info!(
+        "Creating node group {} from {} at version {}",
+        green_name, source_name, target_version
+    );
+
+    let result = client
+        .create_nodegroup()
+        .cluster_name(cluster_name)
+        .nodegroup_name(green_name)
+        .version(target_version)
+        .set_node_role(source.node_role().map(ToString::to_string))
+        .set_subnets(Some(source.subnets().to_vec()))
+        .set_scaling_config(source.scaling_config().cloned())
+        .set_instance_types(
+            (!source.instance_types().is_empty()).then(|| source.instance_types().to_vec()),
+        )
+        .set_ami_type(source.ami_type().cloned())
+        .set_capacity_type(source.capacity_type().cloned())
+        .set_disk_size(source.disk_size())
+        .set_labels(source.labels().cloned())
+        .set_taints((!source.taints().is_empty()).then(|| source.taints().to_vec()))
+        .set_tags(source.tags().cloned())
+        .set_launch_template(source.launch_template().cloned())
+        .set_remote_access(source.remote_access().cloned())
+        .set_update_config(source.update_config().cloned())
+        .send()
+        .await;
+
+    match result {
+        Ok(_) => {
+            info!("Node group {} creation initiated", green_name);
+            Ok(capacity)
+        }
+        Err(e)
+            if e.as_service_error()
+                .is_some_and(CreateNodegroupError::is_resource_in_use_exception) =>
+        {
+            info!("Node group {} already exists, resuming", green_name);
+            Ok(capacity)
+        }
+        Err(e) => Err(KuoError::aws(module_path!(), e).into()),
+    }
+}
+
+/// Current node group status (e.g. `CREATING`, `ACTIVE`, `DELETING`).
+///
+/// Returns `None` once the node group no longer exists.
+pub async fn nodegroup_status(
+    client: &Client,
+    cluster_name: &str,
+    nodegroup_name: &str,
+) -> Result<Option<String>> {
+    match client
+        .describe_nodegroup()
+        .cluster_name(cluster_name)
+        .nodegroup_name(nodegroup_name)
+        .send()
+        .await
+    {
+        Ok(response) => Ok(response
+            .nodegroup()
+            .and_then(|ng| ng.status())
+            .map(|s| s.as_str().to_string())),
+        Err(e)
+            if e.as_service_error()
+                .is_some_and(DescribeNodegroupError::is_resource_not_found_exception) =>
+        {
+            Ok(None)
+        }
+        Err(e) => Err(KuoError::aws(module_path!(), e).into()),
+    }
+}
+
+/// Delete a managed node group. Already-deleted node groups are not an error.
+pub async fn delete_nodegroup(
+    client: &Client,
+    cluster_name: &str,
+    nodegroup_name: &str,
+) -> Result<()> {
+    info!("Deleting managed node group {}", nodegroup_name);
+
+    match client
+        .delete_nodegroup()
+        .cluster_name(cluster_name)
+        .nodegroup_name(nodegroup_name)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e)
+            if e.as_service_error()
+                .is_some_and(DeleteNodegroupError::is_resource_not_found_exception) =>
+        {
+            Ok(())
+        }
+        Err(e) => Err(KuoError::aws(module_path!(), e).into()),
+    }
+}
+
 /// Plan managed node group upgrades to target version.
 pub async fn plan_nodegroup_upgrades(
     client: &Client,
@@ -201,6 +402,54 @@ mod tests {
         assert_eq!(result.upgrades[0].name, "ng-app");
     }
 
+    #[test]
+    fn test_green_nodegroup_name() {
+        assert_eq!(green_nodegroup_name("ng-app", "1.34"), "ng-app-1-34");
+        assert_eq!(green_nodegroup_name("system", "1.33"), "system-1-33");
+    }
+
+    #[test]
+    fn test_green_nodegroup_name_replaces_previous_suffix() {
+        assert_eq!(green_nodegroup_name("ng-app-1-33", "1.34"), "ng-app-1-34");
+        // Rollback back to the original minor reuses the same naming scheme
+        assert_eq!(green_nodegroup_name("ng-app-1-34", "1.33"), "ng-app-1-33");
+        // Non-numeric trailing segments are part of the base name
+        assert_eq!(
+            green_nodegroup_name("ng-app-spot", "1.34"),
+            "ng-app-spot-1-34"
+        );
+    }
+
+    #[test]
+    fn test_green_nodegroup_name_truncates() {
+        let long = "a".repeat(70);
+        let name = green_nodegroup_name(&long, "1.34");
+        assert_eq!(name.len(), 63);
+        assert!(name.ends_with("-1-34"));
+    }
+
+    #[test]
+    fn test_extra_cost_estimate() {
+        let capacity = NodeGroupCapacity {
+            desired_size: 3,
+            instance_types: vec!["m5.large".to_string(), "m5a.large".to_string()],
+        };
+        assert_eq!(
+            capacity.extra_cost_estimate("ng-app"),
+            "3 x m5.large/m5a.large until ng-app is deleted (2x node group capacity)"
+        );
+
+        let lt_capacity = NodeGroupCapacity {
+            desired_size: 2,
+            instance_types: vec![],
+        };
+        assert!(
+            lt_capacity
+                .extra_cost_estimate("ng-app")
+                .starts_with("2 x launch template instances")
+        );
+    }
+
     #[test]
     fn test_nodegroup_info_debug() {
         let ng = NodeGroupInfo {
@@ -1,7 +1,12 @@
 //! Custom error types for kuo.
 
+use aws_sdk_eks::config::http::HttpResponse;
+use aws_sdk_eks::error::{ProvideErrorMetadata, SdkError};
 use thiserror::Error;
 
+/// HTTP status codes from AWS that are retried with exponential backoff.
+pub const RETRYABLE_STATUS_CODES: [u16; 3] = [429, 500, 503];
+
 /// Errors that can occur during EKS upgrade operations.
 #[derive(Error, Debug)]
 pub enum KuoError {
@@ -14,12 +19,18 @@ pub enum KuoError {
     #[error("[{0}] AWS region not configured: {1}")]
     AwsRegion(String, String),
 
+    #[error("[{0}] AWS API returned HTTP {1}: {2}")]
+    AwsRetryable(String, u16, String),
+
     #[error("Cluster not found: {0}")]
     ClusterNotFound(String),
 
     #[error("Invalid version format: {0}")]
     InvalidVersion(String),
 
+    #[error("Invalid maintenance window {0}")]
+    InvalidMaintenanceWindow(String),
+
     #[error("Upgrade not possible: {0}")]
     UpgradeNotPossible(String),
 
@@ -31,13 +42,34 @@ pub enum KuoError {
 }
 
 impl KuoError {
-    /// Create an AWS SDK error from any error type.
-    /// Analyzes the error message to provide more specific error types.
-    pub fn aws<E: std::fmt::Debug + std::fmt::Display>(component: &str, err: E) -> Self {
+    /// Create an AWS SDK error from a failed SDK call.
+    /// The HTTP status comes from the raw response and the message from the
+    /// service error metadata; credentials and region problems are still
+    /// recognised by their message since the SDK reports them without a status.
+    pub fn aws<E>(component: &str, err: SdkError<E, HttpResponse>) -> Self
+    where
+        E: ProvideErrorMetadata + std::fmt::Debug,
+    {
+        let status = err.raw_response().map(|raw| raw.status().as_u16());
+        let message = err.message().map(str::to_string);
+        Self::classify(component, &err, message.as_deref(), status)
+    }
+
+    /// Classify an AWS error into the most specific variant.
+    fn classify<E: std::fmt::Debug + std::fmt::Display>(
+        component: &str,
+        err: E,
+        message: Option<&str>,
+        status: Option<u16>,
+    ) -> Self {
         // Use Debug format to get more detailed error information
         let err_debug = format!("{err:?}");
         let err_display = err.to_string();
         let component = component.to_string();
+        let details = message.map_or_else(
+            || Self::extract_error_details(&err_debug, &err_display),
+            str::to_string,
+        );
 
         // Combine both for analysis
         let combined = format!("{err_display} {err_debug}");
@@ -58,10 +90,7 @@ impl KuoError {
             || combined_lower.contains("not authorized")
             || combined_lower.contains("accessdenied")
         {
-            return Self::AwsCredentials(
-                component,
-                Self::extract_error_details(&err_debug, &err_display),
-            );
+            return Self::AwsCredentials(component, details);
         }
 
         // Check for region-related errors
@@ -69,16 +98,17 @@ impl KuoError {
             || combined_lower.contains("region not found")
             || combined_lower.contains("missing region")
         {
-            return Self::AwsRegion(
-                component,
-                Self::extract_error_details(&err_debug, &err_display),
-            );
+            return Self::AwsRegion(component, details);
         }
 
-        Self::AwsSdk(
-            component,
-            Self::extract_error_details(&err_debug, &err_display),
-        )
+        // Throttling and server-side failures are retried with backoff
+        if let Some(code) = status
+            && RETRYABLE_STATUS_CODES.contains(&code)
+        {
+            return Self::AwsRetryable(component, code, details);
+        }
+
+        Self::AwsSdk(component, details)
     }
 
     /// Extract meaningful error details from AWS SDK error.
@@ -109,13 +139,28 @@ impl KuoError {
     /// resolved by retrying, so the upgrade fails fast instead of looping until
     /// an operator fixes the RBAC.
     pub const fn is_transient(&self) -> bool {
-        matches!(self, Self::AwsSdk(_, _) | Self::KubernetesApi(_))
+        matches!(
+            self,
+            Self::AwsSdk(_, _) | Self::AwsRetryable(_, _, _) | Self::KubernetesApi(_)
+        )
+    }
+
+    /// HTTP status code of a throttled or failed AWS call that warrants
+    /// exponential backoff, if any.
+    pub const fn retryable_status(&self) -> Option<u16> {
+        match self {
+            Self::AwsRetryable(_, code, _) => Some(*code),
+            _ => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aws_sdk_eks::error::ErrorMetadata;
+    use aws_sdk_eks::operation::describe_cluster::DescribeClusterError;
+    use aws_smithy_types::body::SdkBody;
 
     #[test]
     fn test_error_display_cluster_not_found() {
@@ -131,14 +176,19 @@ mod tests {
 
     #[test]
     fn test_error_aws_helper_generic() {
-        let err = KuoError::aws("eks::client", "connection failed");
+        let err = KuoError::classify("eks::client", "connection failed", None, None);
         assert!(err.to_string().contains("[eks::client]"));
         assert!(err.to_string().contains("connection failed"));
     }
 
     #[test]
     fn test_error_aws_credentials_no_credentials() {
-        let err = KuoError::aws("eks::client", "No credentials in the property bag");
+        let err = KuoError::classify(
+            "eks::client",
+            "No credentials in the property bag",
+            None,
+            None,
+        );
         assert!(matches!(err, KuoError::AwsCredentials(_, _)));
         assert!(err.to_string().contains("[eks::client]"));
         assert!(err.to_string().contains("AWS credentials error"));
@@ -146,9 +196,11 @@ mod tests {
 
     #[test]
     fn test_error_aws_credentials_expired() {
-        let err = KuoError::aws(
+        let err = KuoError::classify(
             "eks::addon",
             "The security token included in the request is expired",
+            None,
+            Some(403),
         );
         assert!(matches!(err, KuoError::AwsCredentials(_, _)));
         assert!(err.to_string().contains("[eks::addon]"));
@@ -156,14 +208,14 @@ mod tests {
 
     #[test]
     fn test_error_aws_credentials_access_denied() {
-        let err = KuoError::aws("eks::nodegroup", "Access Denied");
+        let err = KuoError::classify("eks::nodegroup", "Access Denied", None, Some(403));
         assert!(matches!(err, KuoError::AwsCredentials(_, _)));
         assert!(err.to_string().contains("[eks::nodegroup]"));
     }
 
     #[test]
     fn test_error_aws_region_missing() {
-        let err = KuoError::aws("eks::insights", "No region was found");
+        let err = KuoError::classify("eks::insights", "No region was found", None, None);
         assert!(matches!(err, KuoError::AwsRegion(_, _)));
         assert!(err.to_string().contains("[eks::insights]"));
         assert!(err.to_string().contains("AWS region not configured"));
@@ -220,17 +272,18 @@ mod tests {
         assert!(!KuoError::AwsRegion("x".into(), "y".into()).is_transient());
         assert!(!KuoError::InvalidVersion("x".into()).is_transient());
         assert!(!KuoError::UpgradeNotPossible("x".into()).is_transient());
+        assert!(!KuoError::InvalidMaintenanceWindow("x".into()).is_transient());
     }
 
     #[test]
     fn test_error_aws_credentials_invalid_token() {
-        let err = KuoError::aws("test", "InvalidClientTokenId: token is bad");
+        let err = KuoError::classify("test", "InvalidClientTokenId: token is bad", None, None);
         assert!(matches!(err, KuoError::AwsCredentials(_, _)));
     }
 
     #[test]
     fn test_error_aws_credentials_signature() {
-        let err = KuoError::aws("test", "SignatureDoesNotMatch");
+        let err = KuoError::classify("test", "SignatureDoesNotMatch", None, None);
         assert!(matches!(err, KuoError::AwsCredentials(_, _)));
     }
 
@@ -246,4 +299,65 @@ mod tests {
         // Permission denial must not be retried; it fails the upgrade fast.
         assert!(!KuoError::KubernetesForbidden("nodeclaims delete".into()).is_transient());
     }
+
+    /// Build the `SdkError` the EKS client returns for a service error with
+    /// the given HTTP status.
+    fn service_error(
+        status: u16,
+        code: &str,
+        message: &str,
+    ) -> SdkError<DescribeClusterError, HttpResponse> {
+        let meta = ErrorMetadata::builder().code(code).message(message).build();
+        SdkError::service_error(
+            DescribeClusterError::generic(meta),
+            HttpResponse::new(status.try_into().unwrap(), SdkBody::empty()),
+        )
+    }
+
+    #[test]
+    fn test_error_aws_retryable_status_codes() {
+        for code in RETRYABLE_STATUS_CODES {
+            let err = KuoError::aws(
+                "eks::addon",
+                service_error(code, "ThrottlingException", "Rate exceeded"),
+            );
+            assert_eq!(err.retryable_status(), Some(code));
+            assert!(err.is_transient());
+            assert!(err.to_string().contains(&format!("HTTP {code}")));
+            assert!(err.to_string().contains("Rate exceeded"));
+        }
+    }
+
+    #[test]
+    fn test_error_aws_non_retryable_status_code() {
+        let err = KuoError::aws(
+            "eks::addon",
+            service_error(400, "InvalidParameterException", "bad"),
+        );
+        assert!(matches!(err, KuoError::AwsSdk(_, _)));
+        assert_eq!(err.retryable_status(), None);
+        assert_eq!(err.to_string(), "[eks::addon] bad");
+    }
+
+    #[test]
+    fn test_error_aws_credentials_from_service_error() {
+        let err = KuoError::aws(
+            "eks::client",
+            service_error(
+                403,
+                "AccessDeniedException",
+                "User is not authorized to perform eks:DescribeCluster",
+            ),
+        );
+        assert!(matches!(err, KuoError::AwsCredentials(_, _)));
+        assert_eq!(err.retryable_status(), None);
+    }
+
+    #[test]
+    fn test_error_aws_without_response_is_not_retryable() {
+        // Dispatch failures never reached AWS, so there is no status to back off on.
+        let err = KuoError::classify("eks::client", "dispatch failure", None, None);
+        assert!(matches!(err, KuoError::AwsSdk(_, _)));
+        assert_eq!(err.retryable_status(), None);
+    }
 }
@@ -1,6 +1,7 @@
 //! Kubernetes API operations module.
 
 pub mod client;
+pub mod drain;
 pub mod karpenter;
 pub mod node;
 pub mod pdb;
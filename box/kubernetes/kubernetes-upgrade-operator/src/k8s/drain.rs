@@ -0,0 +1,180 @@
+//! Cordon and drain for blue/green node group replacement.
+//!
+//! Managed node groups label their nodes with `eks.amazonaws.com/nodegroup`,
+//! so the old (blue) group's nodes are found by label. Pods are removed with
+//! the Eviction API, which honours `PodDisruptionBudgets`; an eviction refused
+//! by a PDB is simply retried on the next poll.
+//!
+//! The pod filter is pure and unit-tested. The API wrappers are exercised
+//! against a cluster.
+
+use anyhow::Result;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::Api;
+use kube::api::{EvictParams, ListParams, Patch, PatchParams};
+use serde_json::json;
+use tracing::{debug, info};
+
+use crate::error::KuoError;
+use crate::k8s::{node, workload};
+
+/// Label EKS sets on every node of a managed node group.
+pub const NODEGROUP_LABEL: &str = "eks.amazonaws.com/nodegroup";
+
+/// Annotation marking a mirror (static) pod, which cannot be evicted.
+const MIRROR_POD_ANNOTATION: &str = "kubernetes.io/config.mirror";
+
+/// Whether a pod must be evicted before its node can be removed.
+///
+/// `DaemonSet` pods, mirror pods, finished pods, and pods already terminating
+/// are skipped, matching `kubectl drain --ignore-daemonsets`.
+#[must_use]
+pub fn is_evictable(pod: &Pod) -> bool {
+    if pod.metadata.deletion_timestamp.is_some() {
+        return false;
+    }
+    if pod
+        .metadata
+        .annotations
+        .as_ref()
+        .is_some_and(|a| a.contains_key(MIRROR_POD_ANNOTATION))
+    {
+        return false;
+    }
+    if pod
+        .status
+        .as_ref()
+        .and_then(|s| s.phase.as_deref())
+        .is_some_and(|p| p == "Succeeded" || p == "Failed")
+    {
+        return false;
+    }
+    workload::controller_owner(pod).is_none_or(|o| o.kind != "DaemonSet")
+}
+
+/// List the nodes belonging to a managed node group.
+pub async fn nodegroup_nodes(client: &kube::Client, nodegroup: &str) -> Result<Vec<Node>> {
+    let nodes: Api<Node> = Api::all(client.clone());
+    let params = ListParams::default().labels(&format!("{NODEGROUP_LABEL}={nodegroup}"));
+    let list = nodes.list(&params).await.map_err(|e| {
+        KuoError::KubernetesApi(format!(
+            "Failed to list nodes of node group {nodegroup}: {e}"
+        ))
+    })?;
+    Ok(list.items)
+}
+
+/// Mark a node unschedulable. Idempotent.
+pub async fn cordon(client: &kube::Client, node_name: &str) -> Result<()> {
+    let nodes: Api<Node> = Api::all(client.clone());
+    let patch = json!({ "spec": { "unschedulable": true } });
+    nodes
+        .patch(node_name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| KuoError::KubernetesApi(format!("Failed to cordon node {node_name}: {e}")))?;
+    Ok(())
+}
+
+/// Request eviction of every evictable pod on a node.
+///
+/// Returns the number of evictable pods found. Zero means the node is drained.
+/// Evictions refused by a `PodDisruptionBudget` (HTTP 429) are left for the
+/// next call.
+pub async fn evict_pods(client: &kube::Client, node_name: &str) -> Result<usize> {
+    let pods: Vec<Pod> = node::pods_on_node(client, node_name)
+        .await?
+        .into_iter()
+        .filter(is_evictable)
+        .collect();
+
+    for pod in &pods {
+        let name = pod.metadata.name.as_deref().unwrap_or_default();
+        let namespace = pod.metadata.namespace.as_deref().unwrap_or("default");
+        let api: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        match api.evict(name, &EvictParams::default()).await {
+            Ok(_) => info!("Evicted pod {}/{} from node {}", namespace, name, node_name),
+            Err(kube::Error::Api(status)) if status.code == 429 => debug!(
+                "Eviction of pod {}/{} blocked by a PodDisruptionBudget, retrying later",
+                namespace, name
+            ),
+            Err(kube::Error::Api(status)) if status.code == 404 => {}
+            Err(e) => {
+                return Err(KuoError::KubernetesApi(format!(
+                    "Failed to evict pod {namespace}/{name}: {e}"
+                ))
+                .into());
+            }
+        }
+    }
+
+    Ok(pods.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::PodStatus;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+
+    fn pod_owned_by(kind: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some("p".to_string()),
+                namespace: Some("default".to_string()),
+                owner_references: Some(vec![OwnerReference {
+                    kind: kind.to_string(),
+                    name: "owner".to_string(),
+                    controller: Some(true),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_evictable_regular_pods() {
+        assert!(is_evictable(&pod_owned_by("ReplicaSet")));
+        assert!(is_evictable(&pod_owned_by("StatefulSet")));
+        assert!(is_evictable(&Pod::default()));
+    }
+
+    #[test]
+    fn test_is_evictable_skips_daemonset_pods() {
+        assert!(!is_evictable(&pod_owned_by("DaemonSet")));
+    }
+
+    #[test]
+    fn test_is_evictable_skips_mirror_pods() {
+        let mut pod = Pod::default();
+        pod.metadata.annotations = Some(
+            [(MIRROR_POD_ANNOTATION.to_string(), "hash".to_string())]
+                .into_iter()
+                .collect(),
+        );
+        assert!(!is_evictable(&pod));
+    }
+
+    #[test]
+    fn test_is_evictable_skips_finished_pods() {
+        for phase in ["Succeeded", "Failed"] {
+            let pod = Pod {
+                status: Some(PodStatus {
+                    phase: Some(phase.to_string()),
+                    ..Default::default()
+                }),
+                ..pod_owned_by("Job")
+            };
+            assert!(!is_evictable(&pod));
+        }
+    }
+
+    #[test]
+    fn test_is_evictable_skips_terminating_pods() {
+        let mut pod = pod_owned_by("ReplicaSet");
+        pod.metadata.deletion_timestamp =
+            Some(serde_json::from_value(json!("2026-01-01T00:00:00Z")).unwrap());
+        assert!(!is_evictable(&pod));
+    }
+}
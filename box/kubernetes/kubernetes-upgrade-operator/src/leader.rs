@@ -0,0 +1,413 @@
+//! Leader election using a `coordination.k8s.io/v1` `Lease`.
+//!
+//! Only the replica holding the lease runs the controller. Standby replicas
+//! poll the lease until it expires and report not-ready so readiness probes
+//! keep them out of the Service. Updates carry the observed `resourceVersion`,
+//! so two replicas racing for an expired lease cannot both win.
+//!
+//! The leader steps down once it has not renewed for `RENEW_DEADLINE`, which
+//! is shorter than `LEASE_DURATION`, so it stops reconciling before a standby
+//! can take the expired lease over.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use k8s_openapi::api::coordination::v1::Lease;
+use kube::Api;
+use kube::api::{Patch, PatchParams, PostParams};
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+/// Default lease name when `LEADER_ELECTION_LEASE_NAME` is unset.
+pub const DEFAULT_LEASE_NAME: &str = "kuo-leader";
+
+/// How long a lease stays valid without renewal.
+const LEASE_DURATION: Duration = Duration::from_secs(15);
+
+/// How often the leader renews its lease.
+const RENEW_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the leader keeps trying to renew before it steps down. Must be
+/// shorter than `LEASE_DURATION` (client-go's `RenewDeadline`).
+const RENEW_DEADLINE: Duration = Duration::from_secs(10);
+
+/// How often a standby replica retries acquiring the lease.
+const RETRY_PERIOD: Duration = Duration::from_secs(2);
+
+/// Service account namespace file mounted into every pod.
+const NAMESPACE_FILE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/namespace";
+
+/// Lease holder state as observed on the API server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct LeaseRecord {
+    holder: Option<String>,
+    acquired_at: Option<DateTime<Utc>>,
+    renewed_at: Option<DateTime<Utc>>,
+    duration_seconds: i64,
+    transitions: i32,
+}
+
+impl LeaseRecord {
+    fn from_lease(lease: &Lease) -> Self {
+        let spec = lease.spec.clone().unwrap_or_default();
+        Self {
+            holder: spec.holder_identity.filter(|h| !h.is_empty()),
+            acquired_at: spec
+                .acquire_time
+                .and_then(|t| DateTime::from_timestamp(t.0.as_second(), 0)),
+            renewed_at: spec
+                .renew_time
+                .and_then(|t| DateTime::from_timestamp(t.0.as_second(), 0)),
+            duration_seconds: spec.lease_duration_seconds.map_or(0, i64::from),
+            transitions: spec.lease_transitions.unwrap_or(0),
+        }
+    }
+
+    /// Whether `identity` may take (or keep) the lease at `now`.
+    ///
+    /// True when the lease is unheld, already held by `identity`, or its holder
+    /// has not renewed within the lease duration.
+    fn can_acquire(&self, identity: &str, now: DateTime<Utc>) -> bool {
+        let (Some(holder), Some(renewed_at)) = (&self.holder, self.renewed_at) else {
+            return true;
+        };
+        holder == identity || renewed_at + chrono::Duration::seconds(self.duration_seconds) < now
+    }
+}
+
+/// Build the `spec` of a lease held by `identity`.
+fn lease_spec(
+    identity: &str,
+    acquired_at: DateTime<Utc>,
+    renewed_at: DateTime<Utc>,
+    transitions: i32,
+) -> Value {
+    json!({
+        "holderIdentity": identity,
+        "leaseDurationSeconds": LEASE_DURATION.as_secs(),
+        "acquireTime": acquired_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        "renewTime": renewed_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        "leaseTransitions": transitions,
+    })
+}
+
+/// Call `renew` every `interval` until it reports the lease held by another
+/// replica, or until no call has succeeded for `deadline`.
+///
+/// A call still in flight when the deadline passes is abandoned, so a hanging
+/// API request cannot keep the leader running past it.
+async fn renew_until_lost<F, Fut>(
+    lease_name: &str,
+    interval: Duration,
+    deadline: Duration,
+    mut renew: F,
+) -> anyhow::Error
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    let mut last_renewed = Instant::now();
+    loop {
+        let remaining = deadline.saturating_sub(last_renewed.elapsed());
+        tokio::time::sleep(interval.min(remaining)).await;
+
+        let started = Instant::now();
+        let remaining = deadline.saturating_sub(last_renewed.elapsed());
+        let result = tokio::time::timeout(remaining, renew())
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("renewal timed out")));
+        match result {
+            // The lease was renewed no earlier than the call started
+            Ok(true) => last_renewed = started,
+            Ok(false) => {
+                return anyhow::anyhow!(
+                    "Leader lease {} was taken over by another replica",
+                    lease_name
+                );
+            }
+            Err(e) if last_renewed.elapsed() >= deadline => {
+                return anyhow::anyhow!(
+                    "Failed to renew leader lease {} within {}s: {e}",
+                    lease_name,
+                    deadline.as_secs_f64()
+                );
+            }
+            Err(e) => warn!("Failed to renew leader lease {}: {}", lease_name, e),
+        }
+    }
+}
+
+/// Whether a kube error is an HTTP 409 (lost a create or update race).
+fn is_conflict(e: &kube::Error) -> bool {
+    matches!(e, kube::Error::Api(status) if status.code == 409)
+}
+
+/// Acquires and renews the operator's leader lease.
+pub struct LeaderElector {
+    api: Api<Lease>,
+    lease_name: String,
+    identity: String,
+}
+
+impl LeaderElector {
+    pub fn new(client: kube::Client, namespace: &str, lease_name: &str, identity: &str) -> Self {
+        Self {
+            api: Api::namespaced(client, namespace),
+            lease_name: lease_name.to_string(),
+            identity: identity.to_string(),
+        }
+    }
+
+    /// Build an elector from the pod environment.
+    ///
+    /// Identity comes from `POD_NAME` (falling back to `HOSTNAME`), the
+    /// namespace from `POD_NAMESPACE` (falling back to the service account
+    /// namespace file), and the lease name from `LEADER_ELECTION_LEASE_NAME`.
+    pub fn from_env(client: kube::Client) -> Self {
+        let env = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+
+        let identity = env("POD_NAME")
+            .or_else(|| env("HOSTNAME"))
+            .unwrap_or_else(|| "kuo".to_string());
+        let namespace = env("POD_NAMESPACE")
+            .or_else(|| {
+                std::fs::read_to_string(NAMESPACE_FILE)
+                    .ok()
+                    .map(|ns| ns.trim().to_string())
+            })
+            .unwrap_or_else(|| "default".to_string());
+        let lease_name =
+            env("LEADER_ELECTION_LEASE_NAME").unwrap_or_else(|| DEFAULT_LEASE_NAME.to_string());
+
+        Self::new(client, &namespace, &lease_name, &identity)
+    }
+
+    #[must_use]
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    #[must_use]
+    pub fn lease_name(&self) -> &str {
+        &self.lease_name
+    }
+
+    /// Block until this replica holds the lease.
+    pub async fn acquire(&self) {
+        loop {
+            match self.try_acquire_or_renew().await {
+                Ok(true) => {
+                    info!(
+                        "Acquired leader lease {} as {}",
+                        self.lease_name, self.identity
+                    );
+                    return;
+                }
+                Ok(false) => debug!(
+                    "Leader lease {} is held by another replica",
+                    self.lease_name
+                ),
+                Err(e) => warn!("Failed to acquire leader lease {}: {}", self.lease_name, e),
+            }
+            tokio::time::sleep(RETRY_PERIOD).await;
+        }
+    }
+
+    /// Renew the lease until leadership is lost, then return the reason.
+    ///
+    /// Leadership is lost when another replica takes the lease over, or when
+    /// no renewal has succeeded within `RENEW_DEADLINE`.
+    pub async fn renew_until_lost(&self) -> anyhow::Error {
+        renew_until_lost(&self.lease_name, RENEW_INTERVAL, RENEW_DEADLINE, || {
+            self.try_acquire_or_renew()
+        })
+        .await
+    }
+
+    /// Take or renew the lease. Returns `false` if another replica holds it.
+    async fn try_acquire_or_renew(&self) -> Result<bool> {
+        let now = Utc::now();
+
+        let Some(lease) = self.api.get_opt(&self.lease_name).await? else {
+            let lease: Lease = serde_json::from_value(json!({
+                "apiVersion": "coordination.k8s.io/v1",
+                "kind": "Lease",
+                "metadata": { "name": self.lease_name },
+                "spec": lease_spec(&self.identity, now, now, 0),
+            }))?;
+            return match self.api.create(&PostParams::default(), &lease).await {
+                Ok(_) => Ok(true),
+                Err(e) if is_conflict(&e) => Ok(false),
+                Err(e) => Err(e.into()),
+            };
+        };
+
+        let record = LeaseRecord::from_lease(&lease);
+        if !record.can_acquire(&self.identity, now) {
+            return Ok(false);
+        }
+
+        let (acquired_at, transitions) = if record.holder.as_deref() == Some(&self.identity) {
+            (record.acquired_at.unwrap_or(now), record.transitions)
+        } else {
+            (now, record.transitions + 1)
+        };
+
+        // resourceVersion makes the patch fail with 409 if the lease changed
+        // since it was read.
+        let patch = json!({
+            "metadata": { "resourceVersion": lease.metadata.resource_version },
+            "spec": lease_spec(&self.identity, acquired_at, now, transitions),
+        });
+        match self
+            .api
+            .patch(
+                &self.lease_name,
+                &PatchParams::default(),
+                &Patch::Merge(&patch),
+            )
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if is_conflict(&e) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    fn held_by(holder: &str, renewed_at: DateTime<Utc>) -> LeaseRecord {
+        LeaseRecord {
+            holder: Some(holder.to_string()),
+            acquired_at: Some(renewed_at),
+            renewed_at: Some(renewed_at),
+            duration_seconds: 15,
+            transitions: 0,
+        }
+    }
+
+    #[test]
+    fn test_can_acquire_unheld() {
+        assert!(LeaseRecord::default().can_acquire("kuo-a", at(0)));
+    }
+
+    #[test]
+    fn test_can_acquire_own_lease() {
+        assert!(held_by("kuo-a", at(0)).can_acquire("kuo-a", at(5)));
+    }
+
+    #[test]
+    fn test_cannot_acquire_live_lease() {
+        let record = held_by("kuo-a", at(0));
+        assert!(!record.can_acquire("kuo-b", at(10)));
+        assert!(!record.can_acquire("kuo-b", at(15)));
+    }
+
+    #[test]
+    fn test_can_acquire_expired_lease() {
+        assert!(held_by("kuo-a", at(0)).can_acquire("kuo-b", at(16)));
+    }
+
+    #[test]
+    fn test_renew_deadline_is_shorter_than_lease() {
+        assert!(RENEW_INTERVAL < RENEW_DEADLINE);
+        assert!(RENEW_DEADLINE < LEASE_DURATION);
+    }
+
+    #[tokio::test]
+    async fn test_leader_steps_down_before_lease_expires() {
+        // Scaled down: 20ms interval, 100ms deadline, 300ms lease
+        let interval = Duration::from_millis(20);
+        let deadline = Duration::from_millis(100);
+        let lease = Duration::from_millis(300);
+
+        let started = Instant::now();
+        let mut attempts = 0;
+        let err = renew_until_lost("kuo-leader", interval, deadline, || {
+            attempts += 1;
+            async { Err(anyhow::anyhow!("apiserver unavailable")) }
+        })
+        .await;
+        let elapsed = started.elapsed();
+
+        assert!(err.to_string().contains("Failed to renew"), "{err}");
+        assert!(attempts > 1);
+        assert!(elapsed >= deadline, "stepped down after {elapsed:?}");
+        // A standby can only take the lease once it has expired
+        assert!(elapsed < lease, "stepped down after {elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn test_leader_abandons_hanging_renewal_at_deadline() {
+        let deadline = Duration::from_millis(100);
+        let started = Instant::now();
+        let mut first = true;
+        let err = renew_until_lost("kuo-leader", Duration::from_millis(20), deadline, || {
+            let hang = std::mem::take(&mut first);
+            async move {
+                if hang {
+                    std::future::pending::<()>().await;
+                }
+                Err(anyhow::anyhow!("apiserver unavailable"))
+            }
+        })
+        .await;
+
+        assert!(err.to_string().contains("Failed to renew"), "{err}");
+        assert!(started.elapsed() < Duration::from_millis(300));
+    }
+
+    #[tokio::test]
+    async fn test_leader_stops_when_lease_is_taken_over() {
+        let err = renew_until_lost(
+            "kuo-leader",
+            Duration::from_millis(1),
+            Duration::from_secs(1),
+            || async { Ok(false) },
+        )
+        .await;
+        assert!(err.to_string().contains("taken over"), "{err}");
+    }
+
+    #[test]
+    fn test_record_from_lease() {
+        let lease: Lease = serde_json::from_value(json!({
+            "apiVersion": "coordination.k8s.io/v1",
+            "kind": "Lease",
+            "metadata": { "name": DEFAULT_LEASE_NAME },
+            "spec": lease_spec("kuo-a", at(0), at(5), 3),
+        }))
+        .unwrap();
+
+        let record = LeaseRecord::from_lease(&lease);
+        assert_eq!(record.holder.as_deref(), Some("kuo-a"));
+        assert_eq!(record.acquired_at, Some(at(0)));
+        assert_eq!(record.renewed_at, Some(at(5)));
+        assert_eq!(record.duration_seconds, 15);
+        assert_eq!(record.transitions, 3);
+    }
+
+    #[test]
+    fn test_record_from_empty_lease() {
+        let lease: Lease = serde_json::from_value(json!({
+            "apiVersion": "coordination.k8s.io/v1",
+            "kind": "Lease",
+            "metadata": { "name": DEFAULT_LEASE_NAME },
+            "spec": { "holderIdentity": "" },
+        }))
+        .unwrap();
+
+        let record = LeaseRecord::from_lease(&lease);
+        assert!(record.holder.is_none());
+        assert!(record.can_acquire("kuo-a", at(0)));
+    }
+}
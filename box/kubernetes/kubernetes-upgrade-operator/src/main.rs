@@ -10,11 +10,15 @@ mod crd;
 mod eks;
 mod error;
 mod k8s;
+mod leader;
+mod maintenance;
 mod notify;
 mod phases;
 mod status;
 mod telemetry;
+mod webhook;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -41,17 +45,54 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // `--alerting-rules-path <PATH>`: write the bundled PrometheusRule and exit
+    if let Some(path) = alerting_rules_path(std::env::args().skip(1)) {
+        match telemetry::alerts::write_rules(&path) {
+            Ok(()) => {
+                info!("Wrote alerting rules to {}", path.display());
+                return;
+            }
+            Err(e) => {
+                error!("{:#}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     info!(
         "Starting kuo v{} (commit: {}, build: {})",
         VERSION, COMMIT, BUILD_DATE
     );
 
-    if let Err(e) = run().await {
+    let leader_election = has_flag(std::env::args().skip(1), "--leader-election");
+
+    if let Err(e) = run(leader_election).await {
         error!("Operator failed: {}", e);
         std::process::exit(1);
     }
 }
 
+/// Extract the `--alerting-rules-path` value from command-line arguments.
+///
+/// Accepts both `--alerting-rules-path <PATH>` and `--alerting-rules-path=<PATH>`.
+fn alerting_rules_path(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    const FLAG: &str = "--alerting-rules-path";
+    while let Some(arg) = args.next() {
+        if arg == FLAG {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(value) = arg.strip_prefix(FLAG).and_then(|v| v.strip_prefix('=')) {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+/// Whether a boolean `flag` is present in command-line arguments.
+fn has_flag(mut args: impl Iterator<Item = String>, flag: &str) -> bool {
+    args.any(|arg| arg == flag)
+}
+
 /// Initialize tracing subscriber with JSON format for production.
 fn init_tracing() -> Result<()> {
     use tracing_subscriber::{EnvFilter, fmt};
@@ -70,7 +111,10 @@ fn init_tracing() -> Result<()> {
 }
 
 /// Main operator loop.
-async fn run() -> Result<()> {
+///
+/// With `leader_election`, the controller only starts once this replica holds
+/// the leader lease; until then `/readyz` reports 503.
+async fn run(leader_election: bool) -> Result<()> {
     // Build in-cluster Kubernetes client
     let client = kube::Client::try_default().await?;
     info!("Connected to Kubernetes API server");
@@ -98,6 +142,24 @@ async fn run() -> Result<()> {
         }
     });
 
+    // Start CRD conversion webhook (port 8444) when a serving certificate is mounted
+    let cert_dir = std::env::var("WEBHOOK_CERT_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| PathBuf::from(webhook::DEFAULT_CERT_DIR), PathBuf::from);
+    if cert_dir.join("tls.crt").exists() {
+        tokio::spawn(async move {
+            if let Err(e) = webhook::serve(8444, &cert_dir).await {
+                error!("Conversion webhook failed: {}", e);
+            }
+        });
+    } else {
+        info!(
+            "Conversion webhook disabled, no certificate found in {}",
+            cert_dir.display()
+        );
+    }
+
     // Initialize Slack notifier (if webhook URL is configured)
     let slack = std::env::var("SLACK_WEBHOOK_URL")
         .ok()
@@ -107,6 +169,20 @@ async fn run() -> Result<()> {
             Arc::new(notify::SlackNotifier::new(url))
         });
 
+    // Wait for the leader lease before touching any EKSUpgrade resources
+    let elector = if leader_election {
+        let elector = leader::LeaderElector::from_env(client.clone());
+        info!(
+            "Leader election enabled, waiting for lease {} as {}",
+            elector.lease_name(),
+            elector.identity()
+        );
+        elector.acquire().await;
+        Some(elector)
+    } else {
+        None
+    };
+
     // Set up the controller
     let api: Api<EKSUpgrade> = Api::all(client.clone());
 
@@ -118,21 +194,34 @@ async fn run() -> Result<()> {
         kube_client: client.clone(),
         metrics,
         slack,
+        retry_counts: Arc::default(),
     });
 
     // Mark as ready once controller starts
     health_state.set_ready(true);
 
     info!("Starting EKSUpgrade controller");
-    Controller::new(api, Config::default())
+    let controller = Controller::new(api, Config::default())
         .run(controller::reconcile, controller::error_policy, ctx)
         .for_each(|res| async move {
             match res {
                 Ok(o) => info!("Reconciled: {:?}", o),
                 Err(e) => error!("Reconcile failed: {:?}", e),
             }
-        })
-        .await;
+        });
+
+    // A replica that loses the lease exits so it restarts as a standby
+    // instead of reconciling alongside the new leader.
+    match elector {
+        Some(elector) => tokio::select! {
+            () = controller => {}
+            e = elector.renew_until_lost() => {
+                health_state.set_ready(false);
+                return Err(e);
+            }
+        },
+        None => controller.await,
+    }
 
     Ok(())
 }
@@ -198,3 +287,44 @@ async fn probe_nodeclaims(spec: &EKSUpgradeSpec) -> Result<(usize, bool)> {
     let can_delete = k8s::karpenter::can_delete_nodeclaims(&kube_client).await?;
     Ok((count, can_delete))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> impl Iterator<Item = String> {
+        list.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn test_alerting_rules_path() {
+        assert_eq!(
+            alerting_rules_path(args(&["--alerting-rules-path", "/tmp/rules.yaml"])),
+            Some(PathBuf::from("/tmp/rules.yaml"))
+        );
+        assert_eq!(
+            alerting_rules_path(args(&["--alerting-rules-path=/tmp/rules.yaml"])),
+            Some(PathBuf::from("/tmp/rules.yaml"))
+        );
+        assert_eq!(alerting_rules_path(args(&[])), None);
+        assert_eq!(alerting_rules_path(args(&["--alerting-rules-path"])), None);
+        assert_eq!(alerting_rules_path(args(&["--other"])), None);
+    }
+
+    #[test]
+    fn test_has_flag() {
+        assert!(has_flag(args(&["--leader-election"]), "--leader-election"));
+        assert!(has_flag(
+            args(&["--other", "--leader-election"]),
+            "--leader-election"
+        ));
+        assert!(!has_flag(args(&[]), "--leader-election"));
+        assert!(!has_flag(
+            args(&["--leader-election=false"]),
+            "--leader-election"
+        ));
+    }
+}
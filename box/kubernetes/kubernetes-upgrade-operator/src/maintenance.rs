@@ -0,0 +1,453 @@
+//! Maintenance window evaluation.
+//!
+//! A window opens at every time matching a standard 5-field cron expression
+//! (`minute hour day-of-month month day-of-week`) at a fixed UTC offset
+//! (`utcOffsetHours`) and stays open for `durationMinutes`. Mutating phases
+//! only run while at least one window is open.
+//!
+//! Cron fields accept `*`, numbers, ranges (`1-5`), steps (`*/15`, `0-30/10`),
+//! comma lists, and `JAN`-`DEC` / `SUN`-`SAT` names. Day-of-week `7` is Sunday.
+//! As in Vixie cron, when both day-of-month and day-of-week are restricted a
+//! day matches if either does.
+//!
+//! The offset is not a named timezone and does not follow DST: a window set
+//! for `22:00` at `+1` opens at 23:00 local time while summer time is in
+//! effect.
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+
+use crate::crd::MaintenanceWindow;
+use crate::error::KuoError;
+
+/// How far ahead to look for the next window start.
+const SEARCH_HORIZON_DAYS: i64 = 366;
+
+const MONTH_NAMES: &[&str] = &[
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Whether upgrades may progress right now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowState {
+    /// No windows are configured, or one is currently open.
+    Open,
+    /// Every window is closed. `next_start` is the earliest upcoming opening,
+    /// `None` if no window opens within a year.
+    Closed { next_start: Option<DateTime<Utc>> },
+}
+
+/// Parsed 5-field cron expression. Each field is a bitmask of allowed values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields.as_slice() else {
+            return Err(format!(
+                "cron expression '{expr}' must have 5 fields (minute hour day-of-month month day-of-week)"
+            ));
+        };
+
+        let mut days_of_week = parse_field(dow, 0, 7, WEEKDAY_NAMES, 0)?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days_of_month: parse_field(dom, 1, 31, &[], 0)?,
+            months: parse_field(month, 1, 12, MONTH_NAMES, 1)?,
+            days_of_week,
+            dom_restricted: !dom.starts_with('*'),
+            dow_restricted: !dow.starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// Start times on `date` (local to `offset`) in ascending order.
+    fn starts_on(&self, date: NaiveDate, offset: FixedOffset) -> Vec<DateTime<Utc>> {
+        if !self.matches_day(date) {
+            return Vec::new();
+        }
+        let mut starts = Vec::new();
+        for hour in (0..24).filter(|h| self.hours & (1 << h) != 0) {
+            for minute in (0..60).filter(|m| self.minutes & (1 << m) != 0) {
+                let Some(local) = date.and_hms_opt(hour, minute, 0) else {
+                    continue;
+                };
+                if let Some(start) = offset.from_local_datetime(&local).single() {
+                    starts.push(start.with_timezone(&Utc));
+                }
+            }
+        }
+        starts
+    }
+}
+
+/// Parse one cron field into a bitmask over `min..=max`.
+///
+/// `names[i]` is accepted as an alias for `i + name_offset`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_offset: u32,
+) -> Result<u64, String> {
+    let value = |s: &str| -> Result<u32, String> {
+        let upper = s.to_ascii_uppercase();
+        if let Some(i) = names.iter().position(|n| *n == upper) {
+            #[allow(clippy::cast_possible_truncation)]
+            return Ok(i as u32 + name_offset);
+        }
+        let v: u32 = s
+            .parse()
+            .map_err(|_| format!("invalid cron value '{s}' in field '{field}'"))?;
+        if v < min || v > max {
+            return Err(format!(
+                "cron value {v} in field '{field}' is outside {min}-{max}"
+            ));
+        }
+        Ok(v)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| format!("invalid cron step '{step}' in field '{field}'"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (value(a)?, value(b)?)
+        } else {
+            let v = value(range)?;
+            // `5/10` means "from 5 to the end in steps of 10"
+            (v, if part.contains('/') { max } else { v })
+        };
+        if start > end {
+            return Err(format!("invalid cron range '{range}' in field '{field}'"));
+        }
+        for v in (start..=end).step_by(step as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+/// A validated maintenance window.
+struct Window {
+    schedule: CronSchedule,
+    duration: Duration,
+    offset: FixedOffset,
+}
+
+impl Window {
+    fn parse(window: &MaintenanceWindow) -> Result<Self, KuoError> {
+        let invalid = |reason: String| {
+            KuoError::InvalidMaintenanceWindow(format!("'{}': {reason}", window.cron))
+        };
+        let schedule = CronSchedule::parse(&window.cron).map_err(invalid)?;
+        let offset = FixedOffset::east_opt(window.utc_offset_hours.saturating_mul(3600))
+            .filter(|_| (-12..=14).contains(&window.utc_offset_hours))
+            .ok_or_else(|| {
+                invalid(format!(
+                    "utcOffsetHours {} is outside -12..14",
+                    window.utc_offset_hours
+                ))
+            })?;
+        if window.duration_minutes == 0 {
+            return Err(invalid(
+                "durationMinutes must be greater than 0".to_string(),
+            ));
+        }
+        Ok(Self {
+            schedule,
+            duration: Duration::minutes(i64::from(window.duration_minutes)),
+            offset,
+        })
+    }
+
+    fn local_date(&self, at: DateTime<Utc>) -> NaiveDate {
+        at.with_timezone(&self.offset).date_naive()
+    }
+
+    /// Whether a window that started at or before `now` is still open.
+    fn is_open(&self, now: DateTime<Utc>) -> bool {
+        let earliest = now - self.duration;
+        let mut date = self.local_date(now);
+        // A start up to `duration` ago may fall on an earlier local day
+        while date >= self.local_date(earliest) - Duration::days(1) {
+            if self
+                .schedule
+                .starts_on(date, self.offset)
+                .iter()
+                .any(|start| *start <= now && *start > earliest)
+            {
+                return true;
+            }
+            let Some(prev) = date.pred_opt() else {
+                break;
+            };
+            date = prev;
+        }
+        false
+    }
+
+    /// The first start strictly after `now`.
+    fn next_start(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let today = self.local_date(now);
+        (0..=SEARCH_HORIZON_DAYS)
+            .map(|d| today + Duration::days(d))
+            .find_map(|date| {
+                self.schedule
+                    .starts_on(date, self.offset)
+                    .into_iter()
+                    .find(|start| *start > now)
+            })
+    }
+}
+
+/// Evaluate maintenance windows at `now`.
+///
+/// Returns an error if any window has an invalid cron expression, UTC
+/// offset, or duration.
+pub fn evaluate(
+    windows: &[MaintenanceWindow],
+    now: DateTime<Utc>,
+) -> Result<WindowState, KuoError> {
+    let parsed = windows
+        .iter()
+        .map(Window::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if parsed.is_empty() || parsed.iter().any(|w| w.is_open(now)) {
+        return Ok(WindowState::Open);
+    }
+
+    let next_start = parsed.iter().filter_map(|w| w.next_start(now)).min();
+    Ok(WindowState::Closed { next_start })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(cron: &str, duration_minutes: u32, utc_offset_hours: i32) -> MaintenanceWindow {
+        MaintenanceWindow {
+            cron: cron.to_string(),
+            duration_minutes,
+            utc_offset_hours,
+        }
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    /// Weeknights 22:00-02:00 KST (UTC+9).
+    fn weeknights() -> Vec<MaintenanceWindow> {
+        vec![window("0 22 * * MON-FRI", 240, 9)]
+    }
+
+    #[test]
+    fn test_no_windows_is_always_open() {
+        assert_eq!(
+            evaluate(&[], utc("2026-10-17T12:00:00Z")).unwrap(),
+            WindowState::Open
+        );
+    }
+
+    #[test]
+    fn test_inside_weekday_window() {
+        // Tuesday 2026-10-13 23:30 KST
+        let now = utc("2026-10-13T14:30:00Z");
+        assert_eq!(evaluate(&weeknights(), now).unwrap(), WindowState::Open);
+    }
+
+    #[test]
+    fn test_window_start_is_inclusive_and_end_exclusive() {
+        // Tuesday 22:00 KST opens, Wednesday 02:00 KST closes
+        assert_eq!(
+            evaluate(&weeknights(), utc("2026-10-13T13:00:00Z")).unwrap(),
+            WindowState::Open
+        );
+        assert!(matches!(
+            evaluate(&weeknights(), utc("2026-10-13T17:00:00Z")).unwrap(),
+            WindowState::Closed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_window_spanning_midnight_into_weekend() {
+        // Friday's window runs into Saturday 2026-10-17 01:00 KST
+        let now = utc("2026-10-16T16:00:00Z");
+        assert_eq!(evaluate(&weeknights(), now).unwrap(), WindowState::Open);
+    }
+
+    #[test]
+    fn test_outside_window_on_weekend() {
+        // Saturday 2026-10-17 23:00 KST: no window until Monday 22:00 KST
+        let now = utc("2026-10-17T14:00:00Z");
+        assert_eq!(
+            evaluate(&weeknights(), now).unwrap(),
+            WindowState::Closed {
+                next_start: Some(utc("2026-10-19T13:00:00Z"))
+            }
+        );
+    }
+
+    #[test]
+    fn test_outside_window_during_weekday() {
+        // Wednesday 2026-10-14 10:00 KST: next window opens the same evening
+        let now = utc("2026-10-14T01:00:00Z");
+        assert_eq!(
+            evaluate(&weeknights(), now).unwrap(),
+            WindowState::Closed {
+                next_start: Some(utc("2026-10-14T13:00:00Z"))
+            }
+        );
+    }
+
+    #[test]
+    fn test_earliest_next_start_across_windows() {
+        let windows = vec![
+            window("0 22 * * MON-FRI", 240, 9),
+            window("0 3 * * SAT,SUN", 60, 0),
+        ];
+        // Saturday 2026-10-17 14:00 UTC: Sunday 03:00 UTC comes first
+        let now = utc("2026-10-17T14:00:00Z");
+        assert_eq!(
+            evaluate(&windows, now).unwrap(),
+            WindowState::Closed {
+                next_start: Some(utc("2026-10-18T03:00:00Z"))
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_windows_are_rejected() {
+        let now = utc("2026-10-17T14:00:00Z");
+        for w in [
+            window("0 22 * *", 60, 0),
+            window("0 25 * * *", 60, 0),
+            window("0 22 * * MON-FRI", 60, 15),
+            window("0 22 * * MON-FRI", 60, -13),
+            window("0 22 * * *", 0, 0),
+            window("*/0 * * * *", 60, 0),
+        ] {
+            let err = evaluate(&[w], now).unwrap_err();
+            assert!(matches!(err, KuoError::InvalidMaintenanceWindow(_)));
+        }
+    }
+
+    #[test]
+    fn test_numeric_weekday_cron() {
+        // Weekdays 02:00-04:00 UTC
+        let windows = vec![window("0 2 * * 1-5", 120, 0)];
+        // Friday 2026-10-16 03:00 UTC
+        assert_eq!(
+            evaluate(&windows, utc("2026-10-16T03:00:00Z")).unwrap(),
+            WindowState::Open
+        );
+        // Saturday 2026-10-17 03:00 UTC: next opening is Monday
+        assert_eq!(
+            evaluate(&windows, utc("2026-10-17T03:00:00Z")).unwrap(),
+            WindowState::Closed {
+                next_start: Some(utc("2026-10-19T02:00:00Z"))
+            }
+        );
+    }
+
+    #[test]
+    fn test_negative_offset_shifts_local_day() {
+        // Monday 22:00 at UTC-5 is Tuesday 03:00 UTC
+        let windows = vec![window("0 22 * * MON", 60, -5)];
+        assert_eq!(
+            evaluate(&windows, utc("2026-10-13T03:30:00Z")).unwrap(),
+            WindowState::Open
+        );
+        assert!(matches!(
+            evaluate(&windows, utc("2026-10-12T22:30:00Z")).unwrap(),
+            WindowState::Closed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_window_longer_than_a_day() {
+        // Opens Saturday 00:00 UTC and stays open for the whole weekend
+        let weekend = vec![window("0 0 * * SAT", 48 * 60, 0)];
+        assert_eq!(
+            evaluate(&weekend, utc("2026-10-18T23:59:00Z")).unwrap(),
+            WindowState::Open
+        );
+        assert!(matches!(
+            evaluate(&weekend, utc("2026-10-19T00:00:00Z")).unwrap(),
+            WindowState::Closed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_field_forms() {
+        assert_eq!(
+            parse_field("*/15", 0, 59, &[], 0).unwrap(),
+            1 | (1 << 15) | (1 << 30) | (1 << 45)
+        );
+        assert_eq!(parse_field("1-3,5", 0, 59, &[], 0).unwrap(), 0b10_1110);
+        assert_eq!(
+            parse_field("50/5", 0, 59, &[], 0).unwrap(),
+            (1 << 50) | (1 << 55)
+        );
+        assert_eq!(
+            parse_field("mon,WED", 0, 7, WEEKDAY_NAMES, 0).unwrap(),
+            (1 << 1) | (1 << 3)
+        );
+        assert_eq!(parse_field("FEB", 1, 12, MONTH_NAMES, 1).unwrap(), 1 << 2);
+        assert!(parse_field("5-1", 0, 59, &[], 0).is_err());
+    }
+
+    #[test]
+    fn test_sunday_alias_and_day_or_semantics() {
+        let sunday = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert!(sunday.matches_day(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()));
+
+        // Day 1 of the month OR any Monday
+        let either = CronSchedule::parse("0 0 1 * MON").unwrap();
+        assert!(either.matches_day(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()));
+        assert!(either.matches_day(NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()));
+        assert!(!either.matches_day(NaiveDate::from_ymd_opt(2026, 10, 13).unwrap()));
+    }
+}
@@ -448,7 +448,10 @@ mod tests {
             assume_role_arn: None,
             addon_versions: None,
             dry_run,
+            paused: false,
             timeouts: None,
+            maintenance_windows: vec![],
+            node_group_strategy: crate::crd::NodeGroupStrategy::RollingUpdate,
             notification,
             karpenter_node_pools: None,
         }
@@ -136,6 +136,7 @@ mod tests {
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         });
         advance_to_next_phase(&mut s, &UpgradeMode::Forward);
         assert_eq!(s.phase, Some(UpgradePhase::UpgradingNodeGroups));
@@ -167,7 +168,10 @@ mod tests {
             assume_role_arn: None,
             addon_versions: None,
             dry_run: false,
+            paused: false,
             timeouts: None,
+            maintenance_windows: vec![],
+            node_group_strategy: crate::crd::NodeGroupStrategy::RollingUpdate,
             notification: None,
             karpenter_node_pools: None,
         }
@@ -201,6 +205,7 @@ mod tests {
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         });
         let (new_status, requeue) = execute(&spec, &status, &aws).await.unwrap();
         assert!(requeue.is_none());
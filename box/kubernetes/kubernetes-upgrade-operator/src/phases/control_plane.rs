@@ -267,6 +267,7 @@ mod tests {
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         });
         advance_to_next_phase(&mut s, &UpgradeMode::Forward);
         assert_eq!(s.phase, Some(UpgradePhase::UpgradingNodeGroups));
@@ -387,7 +388,10 @@ mod tests {
             assume_role_arn: None,
             addon_versions: None,
             dry_run: false,
+            paused: false,
             timeouts: None,
+            maintenance_windows: vec![],
+            node_group_strategy: crate::crd::NodeGroupStrategy::RollingUpdate,
             notification: None,
             karpenter_node_pools: None,
         }
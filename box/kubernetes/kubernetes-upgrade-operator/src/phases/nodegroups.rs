@@ -1,15 +1,23 @@
 //! Node group upgrade phase.
 //!
 //! Upgrades managed node groups one at a time, polling status between reconciles.
+//! With `nodeGroupStrategy: BlueGreen` each node group is replaced instead:
+//! a green group is created at the target version, the blue group's nodes are
+//! cordoned and drained, and the blue group is deleted.
 
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::time::Duration;
 use tracing::{info, warn};
 
 use crate::aws::AwsClients;
-use crate::crd::{ComponentStatus, EKSUpgradeSpec, EKSUpgradeStatus};
+use crate::crd::{
+    BlueGreenStage, BlueGreenStatus, ComponentStatus, EKSUpgradeSpec, EKSUpgradeStatus,
+    NodeGroupStrategy, NodegroupStatus,
+};
+use crate::eks::client::EksClient;
 use crate::eks::nodegroup;
+use crate::k8s::drain;
 use crate::phases::transition;
 use crate::status;
 
@@ -75,6 +83,236 @@ fn apply_timeout(
     );
 }
 
+/// Whether the node group is upgraded by blue/green replacement.
+///
+/// Work already started in one strategy finishes in it, so switching
+/// `nodeGroupStrategy` mid-upgrade never runs both at once: an in-place update
+/// in flight keeps being polled, and a started replacement runs to the end.
+fn uses_blue_green(spec: &EKSUpgradeSpec, ng: &NodegroupStatus) -> bool {
+    if ng.blue_green.is_some() {
+        return true;
+    }
+    if ng.update_id.is_some() {
+        return false;
+    }
+    spec.node_group_strategy == NodeGroupStrategy::BlueGreen
+}
+
+/// Move the replacement to `stage` and restart its stage timer.
+fn enter_stage(ng: &mut NodegroupStatus, stage: BlueGreenStage) {
+    if let Some(bg) = ng.blue_green.as_mut() {
+        bg.stage = stage;
+        bg.stage_started_at = Some(Utc::now());
+    }
+}
+
+/// Start of the current blue/green stage, falling back to the start of the
+/// whole replacement for status written before stages were timed.
+fn stage_started_at(ng: &NodegroupStatus) -> Option<DateTime<Utc>> {
+    ng.blue_green
+        .as_ref()
+        .and_then(|bg| bg.stage_started_at)
+        .or(ng.started_at)
+}
+
+/// Apply the polled state of the green node group while it is being created.
+fn apply_green_state(
+    new_status: &mut EKSUpgradeStatus,
+    idx: usize,
+    green_name: &str,
+    state: Option<&str>,
+) -> Option<Duration> {
+    let ng_name = new_status.phases.nodegroups[idx].name.clone();
+    match state {
+        Some("ACTIVE") => {
+            info!(
+                "Node group {} is ACTIVE, draining node group {}",
+                green_name, ng_name
+            );
+            enter_stage(
+                &mut new_status.phases.nodegroups[idx],
+                BlueGreenStage::DrainingBlue,
+            );
+            Some(Duration::from_secs(0))
+        }
+        Some(failed @ ("CREATE_FAILED" | "DEGRADED")) => {
+            warn!("Node group {} creation failed: {}", green_name, failed);
+            new_status.phases.nodegroups[idx].status = ComponentStatus::Failed;
+            status::set_failed(
+                new_status,
+                format!(
+                    "Blue/green replacement of nodegroup {ng_name} failed: {green_name} is {failed}"
+                ),
+            );
+            None
+        }
+        None => {
+            new_status.phases.nodegroups[idx].status = ComponentStatus::Failed;
+            status::set_failed(
+                new_status,
+                format!(
+                    "Blue/green replacement of nodegroup {ng_name} failed: {green_name} no longer exists"
+                ),
+            );
+            None
+        }
+        Some(_) => Some(POLL_INTERVAL),
+    }
+}
+
+/// Apply the polled state of the blue node group while it is being deleted.
+fn apply_blue_deletion_state(
+    new_status: &mut EKSUpgradeStatus,
+    idx: usize,
+    state: Option<&str>,
+) -> Option<Duration> {
+    let ng_name = new_status.phases.nodegroups[idx].name.clone();
+    match state {
+        None => {
+            info!(
+                "Nodegroup {} deleted, blue/green replacement completed",
+                ng_name
+            );
+            let ng = &mut new_status.phases.nodegroups[idx];
+            ng.status = ComponentStatus::Completed;
+            ng.started_at = None;
+            ng.completed_at = Some(Utc::now());
+            // The blue group is gone; the entry now names its replacement.
+            if let Some(bg) = ng.blue_green.as_mut() {
+                bg.blue_nodegroup = std::mem::replace(&mut ng.name, bg.green_nodegroup.clone());
+                bg.stage_started_at = None;
+            }
+            Some(Duration::from_secs(0))
+        }
+        Some("DELETE_FAILED") => {
+            warn!("Nodegroup {} deletion failed", ng_name);
+            new_status.phases.nodegroups[idx].status = ComponentStatus::Failed;
+            status::set_failed(
+                new_status,
+                format!("Blue/green replacement of nodegroup {ng_name} failed: DELETE_FAILED"),
+            );
+            None
+        }
+        Some(_) => Some(POLL_INTERVAL),
+    }
+}
+
+/// Execute one blue/green step for the nodegroup at `idx`.
+///
+/// Pending creates the green node group. Afterwards the persisted
+/// `BlueGreenStage` decides the step: wait for the green group to become
+/// `ACTIVE`, cordon and drain the blue nodes then delete the blue group, and
+/// finally wait for the deletion to finish. Each stage is timed separately.
+async fn execute_blue_green(
+    spec: &EKSUpgradeSpec,
+    mut new_status: EKSUpgradeStatus,
+    idx: usize,
+    aws: &AwsClients,
+    in_cluster: &kube::Client,
+) -> Result<(EKSUpgradeStatus, Option<Duration>)> {
+    let ng = new_status.phases.nodegroups[idx].clone();
+
+    let Some(bg) = ng.blue_green.clone() else {
+        let green_name = nodegroup::green_nodegroup_name(&ng.name, &ng.target_version);
+        let capacity = nodegroup::create_green_nodegroup(
+            &aws.eks,
+            &spec.cluster_name,
+            &ng.name,
+            &green_name,
+            &ng.target_version,
+        )
+        .await?;
+        let estimate = capacity.extra_cost_estimate(&ng.name);
+        info!(
+            "Blue/green replacement of nodegroup {} via {}, estimated extra capacity: {}",
+            ng.name, green_name, estimate
+        );
+
+        let now = Utc::now();
+        let entry = &mut new_status.phases.nodegroups[idx];
+        entry.status = ComponentStatus::InProgress;
+        entry.started_at = Some(now);
+        entry.blue_green = Some(BlueGreenStatus {
+            green_nodegroup: green_name,
+            blue_nodegroup: ng.name.clone(),
+            stage: BlueGreenStage::CreatingGreen,
+            stage_started_at: Some(now),
+            extra_nodes: capacity.desired_size,
+            estimated_extra_cost: estimate,
+        });
+        return Ok((new_status, Some(POLL_INTERVAL)));
+    };
+
+    let timeout_minutes = spec.timeouts.as_ref().map_or(60, |t| t.nodegroup_minutes);
+    if let Some(started) = stage_started_at(&ng) {
+        let elapsed = Utc::now().signed_duration_since(started);
+        #[allow(clippy::cast_possible_wrap)]
+        if elapsed.num_minutes() >= timeout_minutes as i64 {
+            apply_timeout(
+                &mut new_status,
+                idx,
+                &ng.name,
+                elapsed.num_minutes(),
+                timeout_minutes,
+            );
+            return Ok((new_status, None));
+        }
+    }
+
+    match bg.stage {
+        BlueGreenStage::CreatingGreen => {
+            let state =
+                nodegroup::nodegroup_status(&aws.eks, &spec.cluster_name, &bg.green_nodegroup)
+                    .await?;
+            let requeue =
+                apply_green_state(&mut new_status, idx, &bg.green_nodegroup, state.as_deref());
+            Ok((new_status, requeue))
+        }
+        BlueGreenStage::DrainingBlue => {
+            let eks_client = EksClient::new(aws.eks.clone(), aws.region.clone());
+            let client = crate::k8s::client::resolve_client(
+                in_cluster,
+                &eks_client,
+                &spec.cluster_name,
+                spec.assume_role_arn.as_deref(),
+            )
+            .await?;
+
+            let nodes = drain::nodegroup_nodes(&client, &ng.name).await?;
+            let mut remaining = 0;
+            for node in &nodes {
+                let Some(node_name) = node.metadata.name.as_deref() else {
+                    continue;
+                };
+                drain::cordon(&client, node_name).await?;
+                remaining += drain::evict_pods(&client, node_name).await?;
+            }
+
+            if remaining > 0 {
+                info!(
+                    "Draining nodegroup {}: {} pods left on {} nodes",
+                    ng.name,
+                    remaining,
+                    nodes.len()
+                );
+                return Ok((new_status, Some(POLL_INTERVAL)));
+            }
+
+            nodegroup::delete_nodegroup(&aws.eks, &spec.cluster_name, &ng.name).await?;
+            enter_stage(
+                &mut new_status.phases.nodegroups[idx],
+                BlueGreenStage::DeletingBlue,
+            );
+            Ok((new_status, Some(POLL_INTERVAL)))
+        }
+        BlueGreenStage::DeletingBlue => {
+            let state = nodegroup::nodegroup_status(&aws.eks, &spec.cluster_name, &ng.name).await?;
+            let requeue = apply_blue_deletion_state(&mut new_status, idx, state.as_deref());
+            Ok((new_status, requeue))
+        }
+    }
+}
+
 /// Execute one step of nodegroup upgrades.
 ///
 /// Finds the first pending/in-progress nodegroup and either initiates or polls it.
@@ -83,6 +321,7 @@ pub async fn execute(
     spec: &EKSUpgradeSpec,
     current_status: &EKSUpgradeStatus,
     aws: &AwsClients,
+    in_cluster: &kube::Client,
 ) -> Result<(EKSUpgradeStatus, Option<Duration>)> {
     let mut new_status = current_status.clone();
 
@@ -100,6 +339,14 @@ pub async fn execute(
     let current_version = ng_status.current_version.clone();
     let target_version = ng_status.target_version.clone();
 
+    if matches!(
+        ng_status.status,
+        ComponentStatus::Pending | ComponentStatus::InProgress
+    ) && uses_blue_green(spec, ng_status)
+    {
+        return execute_blue_green(spec, new_status, idx, aws, in_cluster).await;
+    }
+
     let timeout_minutes = spec.timeouts.as_ref().map_or(60, |t| t.nodegroup_minutes);
 
     match ng_status.status {
@@ -173,7 +420,7 @@ pub async fn execute(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crd::{NodegroupStatus, UpgradePhase};
+    use crate::crd::UpgradePhase;
 
     fn make_ng(name: &str, status: ComponentStatus) -> NodegroupStatus {
         NodegroupStatus {
@@ -184,6 +431,7 @@ mod tests {
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         }
     }
 
@@ -284,6 +532,11 @@ mod tests {
 
     // --- execute early-return path tests ---
 
+    fn test_kube_client() -> kube::Client {
+        let config = kube::Config::new("http://127.0.0.1:1".parse().unwrap());
+        kube::Client::try_from(config).unwrap()
+    }
+
     fn make_spec() -> EKSUpgradeSpec {
         EKSUpgradeSpec {
             cluster_name: "test-cluster".to_string(),
@@ -293,7 +546,10 @@ mod tests {
             assume_role_arn: None,
             addon_versions: None,
             dry_run: false,
+            paused: false,
             timeouts: None,
+            maintenance_windows: vec![],
+            node_group_strategy: crate::crd::NodeGroupStrategy::RollingUpdate,
             notification: None,
             karpenter_node_pools: None,
         }
@@ -307,7 +563,9 @@ mod tests {
             make_ng("ng-1", ComponentStatus::Completed),
             make_ng("ng-2", ComponentStatus::Completed),
         ]);
-        let (new_status, requeue) = execute(&spec, &status, &aws).await.unwrap();
+        let (new_status, requeue) = execute(&spec, &status, &aws, &test_kube_client())
+            .await
+            .unwrap();
         assert!(requeue.is_none());
         assert_eq!(new_status.phase, Some(UpgradePhase::Completed));
     }
@@ -317,7 +575,9 @@ mod tests {
         let aws = crate::aws::AwsClients::test_instance("us-east-1").await;
         let spec = make_spec();
         let status = EKSUpgradeStatus::default();
-        let (new_status, requeue) = execute(&spec, &status, &aws).await.unwrap();
+        let (new_status, requeue) = execute(&spec, &status, &aws, &test_kube_client())
+            .await
+            .unwrap();
         assert!(requeue.is_none());
         assert_eq!(new_status.phase, Some(UpgradePhase::Completed));
     }
@@ -327,7 +587,9 @@ mod tests {
         let aws = crate::aws::AwsClients::test_instance("us-east-1").await;
         let spec = make_spec();
         let status = make_status_with_ngs(vec![make_ng("ng-1", ComponentStatus::Failed)]);
-        let (new_status, requeue) = execute(&spec, &status, &aws).await.unwrap();
+        let (new_status, requeue) = execute(&spec, &status, &aws, &test_kube_client())
+            .await
+            .unwrap();
         assert!(requeue.is_none());
         assert_eq!(new_status.phase, Some(UpgradePhase::Failed));
     }
@@ -340,7 +602,9 @@ mod tests {
             make_ng("ng-1", ComponentStatus::Skipped),
             make_ng("ng-2", ComponentStatus::Completed),
         ]);
-        let (new_status, requeue) = execute(&spec, &status, &aws).await.unwrap();
+        let (new_status, requeue) = execute(&spec, &status, &aws, &test_kube_client())
+            .await
+            .unwrap();
         assert!(requeue.is_none());
         assert_eq!(new_status.phase, Some(UpgradePhase::Completed));
     }
@@ -354,7 +618,9 @@ mod tests {
         ngs[0].update_id = Some("upd-1".to_string());
         ngs[0].started_at = Some(two_hours_ago);
         let status = make_status_with_ngs(ngs);
-        let (new_status, requeue) = execute(&spec, &status, &aws).await.unwrap();
+        let (new_status, requeue) = execute(&spec, &status, &aws, &test_kube_client())
+            .await
+            .unwrap();
         assert!(requeue.is_none());
         assert_eq!(new_status.phase, Some(UpgradePhase::Failed));
         assert!(new_status.message.as_ref().unwrap().contains("timed out"));
@@ -373,4 +639,150 @@ mod tests {
         assert!(s.message.as_ref().unwrap().contains("timed out"));
         assert!(s.message.as_ref().unwrap().contains("65 minutes"));
     }
+
+    // --- blue/green tests ---
+
+    fn make_blue_green_ng(stage: BlueGreenStage) -> NodegroupStatus {
+        let mut ng = make_ng("ng-1", ComponentStatus::InProgress);
+        ng.started_at = Some(Utc::now());
+        ng.blue_green = Some(BlueGreenStatus {
+            green_nodegroup: "ng-1-1-33".to_string(),
+            blue_nodegroup: "ng-1".to_string(),
+            stage,
+            stage_started_at: Some(Utc::now()),
+            extra_nodes: 3,
+            estimated_extra_cost: "3 x m5.large until ng-1 is deleted".to_string(),
+        });
+        ng
+    }
+
+    fn stage_of(s: &EKSUpgradeStatus) -> BlueGreenStage {
+        s.phases.nodegroups[0]
+            .blue_green
+            .as_ref()
+            .unwrap()
+            .stage
+            .clone()
+    }
+
+    #[test]
+    fn test_apply_green_state_active_starts_drain() {
+        let mut ng = make_blue_green_ng(BlueGreenStage::CreatingGreen);
+        ng.started_at = Some(Utc::now() - chrono::Duration::hours(1));
+        let mut s = make_status_with_ngs(vec![ng]);
+        let requeue = apply_green_state(&mut s, 0, "ng-1-1-33", Some("ACTIVE"));
+        assert_eq!(requeue, Some(Duration::from_secs(0)));
+        assert_eq!(stage_of(&s), BlueGreenStage::DrainingBlue);
+        assert_eq!(s.phases.nodegroups[0].status, ComponentStatus::InProgress);
+        // The drain gets its own timeout budget
+        let bg = s.phases.nodegroups[0].blue_green.as_ref().unwrap();
+        assert!(bg.stage_started_at.unwrap() > s.phases.nodegroups[0].started_at.unwrap());
+    }
+
+    #[test]
+    fn test_apply_green_state_creating_polls() {
+        let mut s = make_status_with_ngs(vec![make_blue_green_ng(BlueGreenStage::CreatingGreen)]);
+        let requeue = apply_green_state(&mut s, 0, "ng-1-1-33", Some("CREATING"));
+        assert_eq!(requeue, Some(POLL_INTERVAL));
+        assert_eq!(stage_of(&s), BlueGreenStage::CreatingGreen);
+    }
+
+    #[test]
+    fn test_apply_green_state_failures() {
+        for state in [Some("CREATE_FAILED"), Some("DEGRADED"), None] {
+            let mut s =
+                make_status_with_ngs(vec![make_blue_green_ng(BlueGreenStage::CreatingGreen)]);
+            let requeue = apply_green_state(&mut s, 0, "ng-1-1-33", state);
+            assert!(requeue.is_none());
+            assert_eq!(s.phases.nodegroups[0].status, ComponentStatus::Failed);
+            assert_eq!(s.phase, Some(UpgradePhase::Failed));
+            assert!(s.message.as_ref().unwrap().contains("ng-1-1-33"));
+        }
+    }
+
+    #[test]
+    fn test_apply_blue_deletion_state_deleted_completes() {
+        let mut s = make_status_with_ngs(vec![make_blue_green_ng(BlueGreenStage::DeletingBlue)]);
+        let requeue = apply_blue_deletion_state(&mut s, 0, None);
+        assert_eq!(requeue, Some(Duration::from_secs(0)));
+        assert_eq!(s.phases.nodegroups[0].status, ComponentStatus::Completed);
+        assert!(s.phases.nodegroups[0].completed_at.is_some());
+        // The entry now names the green group; the blue name stays on record
+        assert_eq!(s.phases.nodegroups[0].name, "ng-1-1-33");
+        let bg = s.phases.nodegroups[0].blue_green.as_ref().unwrap();
+        assert_eq!(bg.blue_nodegroup, "ng-1");
+        // The extra cost estimate stays on record after completion
+        assert!(!bg.estimated_extra_cost.is_empty());
+    }
+
+    #[test]
+    fn test_apply_blue_deletion_state_deleting_polls() {
+        let mut s = make_status_with_ngs(vec![make_blue_green_ng(BlueGreenStage::DeletingBlue)]);
+        let requeue = apply_blue_deletion_state(&mut s, 0, Some("DELETING"));
+        assert_eq!(requeue, Some(POLL_INTERVAL));
+        assert_eq!(s.phases.nodegroups[0].status, ComponentStatus::InProgress);
+    }
+
+    #[test]
+    fn test_apply_blue_deletion_state_failed() {
+        let mut s = make_status_with_ngs(vec![make_blue_green_ng(BlueGreenStage::DeletingBlue)]);
+        let requeue = apply_blue_deletion_state(&mut s, 0, Some("DELETE_FAILED"));
+        assert!(requeue.is_none());
+        assert_eq!(s.phase, Some(UpgradePhase::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_execute_blue_green_timeout_triggers_failure() {
+        let aws = crate::aws::AwsClients::test_instance("us-east-1").await;
+        let mut spec = make_spec();
+        spec.node_group_strategy = NodeGroupStrategy::BlueGreen;
+        let mut ng = make_blue_green_ng(BlueGreenStage::DrainingBlue);
+        ng.started_at = Some(Utc::now() - chrono::Duration::hours(3));
+        ng.blue_green.as_mut().unwrap().stage_started_at =
+            Some(Utc::now() - chrono::Duration::hours(2));
+        let status = make_status_with_ngs(vec![ng]);
+        let (new_status, requeue) = execute(&spec, &status, &aws, &test_kube_client())
+            .await
+            .unwrap();
+        assert!(requeue.is_none());
+        assert_eq!(new_status.phase, Some(UpgradePhase::Failed));
+        assert!(new_status.message.as_ref().unwrap().contains("timed out"));
+    }
+
+    #[test]
+    fn test_stage_started_at_falls_back_to_started_at() {
+        let mut ng = make_blue_green_ng(BlueGreenStage::DeletingBlue);
+        let stage_start = ng.blue_green.as_ref().unwrap().stage_started_at;
+        ng.started_at = Some(Utc::now() - chrono::Duration::hours(2));
+        assert_eq!(stage_started_at(&ng), stage_start);
+
+        ng.blue_green.as_mut().unwrap().stage_started_at = None;
+        assert_eq!(stage_started_at(&ng), ng.started_at);
+    }
+
+    #[test]
+    fn test_uses_blue_green_finishes_started_work() {
+        let mut spec = make_spec();
+        spec.node_group_strategy = NodeGroupStrategy::BlueGreen;
+        assert!(uses_blue_green(
+            &spec,
+            &make_ng("ng-1", ComponentStatus::Pending)
+        ));
+
+        // Strategy switched to BlueGreen while an in-place update runs
+        let mut in_place = make_ng("ng-1", ComponentStatus::InProgress);
+        in_place.update_id = Some("upd-1".to_string());
+        assert!(!uses_blue_green(&spec, &in_place));
+
+        // Strategy switched back while a replacement runs
+        spec.node_group_strategy = NodeGroupStrategy::RollingUpdate;
+        assert!(uses_blue_green(
+            &spec,
+            &make_blue_green_ng(BlueGreenStage::DrainingBlue)
+        ));
+        assert!(!uses_blue_green(
+            &spec,
+            &make_ng("ng-1", ComponentStatus::Pending)
+        ));
+    }
 }
@@ -108,6 +108,7 @@ pub async fn execute(
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         })
         .collect();
 
@@ -336,7 +337,10 @@ mod tests {
             assume_role_arn: None,
             addon_versions: None,
             dry_run: false,
+            paused: false,
             timeouts: None,
+            maintenance_windows: vec![],
+            node_group_strategy: crate::crd::NodeGroupStrategy::RollingUpdate,
             notification: None,
             karpenter_node_pools: None,
         }
@@ -196,6 +196,7 @@ mod tests {
                 update_id: None,
                 started_at: None,
                 completed_at: None,
+                blue_green: None,
             });
         }
         s
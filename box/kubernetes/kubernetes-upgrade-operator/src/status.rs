@@ -27,11 +27,84 @@ pub async fn patch_status(
 }
 
 /// Set the phase on a status, preserving other fields.
+///
+/// Also refreshes the `Progressing`, `Completed`, `Failed`, and `Degraded`
+/// conditions to match the new phase.
 pub fn set_phase(status: &mut EKSUpgradeStatus, phase: UpgradePhase) {
     if phase == UpgradePhase::Completed {
         status.completed_at = Some(Utc::now());
     }
     status.phase = Some(phase);
+    sync_phase_conditions(status);
+}
+
+/// Derive the lifecycle conditions from the current phase and component
+/// statuses.
+///
+/// `Progressing` is `True` while the flow is in a non-terminal phase, with the
+/// phase name as its reason. `Completed` and `Failed` flip to `True` on the
+/// matching terminal phase. `Degraded` is `True` while any add-on, node group,
+/// or Karpenter `NodePool` is `Failed`. Because `set_condition` keeps
+/// `lastTransitionTime` for an unchanged status, moving between two running
+/// phases only updates the `Progressing` reason.
+pub fn sync_phase_conditions(status: &mut EKSUpgradeStatus) {
+    let phase = status.phase.clone().unwrap_or(UpgradePhase::Pending);
+    let message = status.message.clone();
+
+    match phase {
+        UpgradePhase::Completed => {
+            set_condition(status, "Progressing", "False", "UpgradeCompleted", None);
+            set_condition(status, "Completed", "True", "UpgradeCompleted", None);
+            set_condition(status, "Failed", "False", "UpgradeCompleted", None);
+        }
+        UpgradePhase::Failed => {
+            set_condition(status, "Progressing", "False", "UpgradeFailed", None);
+            set_condition(status, "Completed", "False", "UpgradeFailed", None);
+            set_condition(status, "Failed", "True", "UpgradeFailed", message);
+        }
+        running => {
+            let reason = running.to_string();
+            set_condition(status, "Progressing", "True", &reason, None);
+            set_condition(status, "Completed", "False", "UpgradeInProgress", None);
+            set_condition(status, "Failed", "False", "UpgradeInProgress", None);
+        }
+    }
+
+    let failed = failed_components(status);
+    if failed.is_empty() {
+        set_condition(status, "Degraded", "False", "AllComponentsHealthy", None);
+    } else {
+        set_condition(
+            status,
+            "Degraded",
+            "True",
+            "ComponentFailed",
+            Some(format!("Failed components: {}", failed.join(", "))),
+        );
+    }
+}
+
+/// Names of add-ons, node groups, and Karpenter `NodePools` in `Failed` state.
+fn failed_components(status: &EKSUpgradeStatus) -> Vec<String> {
+    let failed = |s: &ComponentStatus| *s == ComponentStatus::Failed;
+    let phases = &status.phases;
+    let addons = phases
+        .addons
+        .iter()
+        .filter(|a| failed(&a.status))
+        .map(|a| format!("addon/{}", a.name));
+    let nodegroups = phases
+        .nodegroups
+        .iter()
+        .filter(|n| failed(&n.status))
+        .map(|n| format!("nodegroup/{}", n.name));
+    let pools = phases
+        .karpenter_node_pools
+        .iter()
+        .flat_map(|k| &k.pools)
+        .filter(|p| failed(&p.status))
+        .map(|p| format!("nodepool/{}", p.name));
+    addons.chain(nodegroups).chain(pools).collect()
 }
 
 /// Compute overall upgrade progress as `completed/total` component units.
@@ -93,9 +166,14 @@ pub fn set_failed(status: &mut EKSUpgradeStatus, message: impl Into<String>) {
         "UpgradeFailed",
         status.message.clone(),
     );
+    sync_phase_conditions(status);
 }
 
 /// Set a condition on the status.
+///
+/// Following Kubernetes conventions, `lastTransitionTime` only moves when the
+/// condition's status actually changes; re-setting the same status refreshes
+/// the reason and message but keeps the original transition time.
 pub fn set_condition(
     status: &mut EKSUpgradeStatus,
     condition_type: &str,
@@ -103,20 +181,89 @@ pub fn set_condition(
     reason: &str,
     message: Option<String>,
 ) {
-    let now = Utc::now();
-
-    // Remove existing condition of same type
-    status.conditions.retain(|c| c.r#type != condition_type);
+    if let Some(existing) = status
+        .conditions
+        .iter_mut()
+        .find(|c| c.r#type == condition_type)
+    {
+        if existing.status != condition_status {
+            existing.status = condition_status.to_string();
+            existing.last_transition_time = Utc::now();
+        }
+        existing.reason = reason.to_string();
+        existing.message = message;
+        return;
+    }
 
     status.conditions.push(UpgradeCondition {
         r#type: condition_type.to_string(),
         status: condition_status.to_string(),
         reason: reason.to_string(),
         message,
-        last_transition_time: now,
+        last_transition_time: Utc::now(),
     });
 }
 
+/// Record the pause state as the `Paused` condition.
+///
+/// Returns `true` when the condition changed. A status that was never paused
+/// gets no condition, so unpaused upgrades keep their condition list unchanged.
+pub fn set_paused(status: &mut EKSUpgradeStatus, paused: bool) -> bool {
+    if paused == is_condition_true(status, "Paused") {
+        return false;
+    }
+
+    if paused {
+        let phase = status.phase.clone().unwrap_or(UpgradePhase::Pending);
+        set_condition(
+            status,
+            "Paused",
+            "True",
+            "PauseRequested",
+            Some(format!("Upgrade paused in phase {phase}")),
+        );
+    } else {
+        set_condition(status, "Paused", "False", "Resumed", None);
+    }
+    true
+}
+
+/// Record whether the upgrade is held outside its maintenance windows as the
+/// `WaitingForWindow` condition.
+///
+/// Returns `true` when the condition flipped. Like `set_paused`, an upgrade
+/// that never waited gets no condition.
+pub fn set_waiting_for_window(
+    status: &mut EKSUpgradeStatus,
+    waiting: bool,
+    message: Option<String>,
+) -> bool {
+    if waiting == is_condition_true(status, "WaitingForWindow") {
+        return false;
+    }
+
+    if waiting {
+        set_condition(
+            status,
+            "WaitingForWindow",
+            "True",
+            "OutsideMaintenanceWindow",
+            message,
+        );
+    } else {
+        set_condition(status, "WaitingForWindow", "False", "WindowOpen", message);
+    }
+    true
+}
+
+/// Whether a condition of the given type is present with status `True`.
+fn is_condition_true(status: &EKSUpgradeStatus, condition_type: &str) -> bool {
+    status
+        .conditions
+        .iter()
+        .any(|c| c.r#type == condition_type && c.status == "True")
+}
+
 /// Event recorder bundled with its target `ObjectReference`.
 pub struct EventRecorder {
     recorder: Recorder,
@@ -213,6 +360,7 @@ mod tests {
             update_id: None,
             started_at: None,
             completed_at: None,
+            blue_green: None,
         }
     }
 
@@ -376,10 +524,109 @@ mod tests {
     fn test_set_failed_then_set_condition_overwrites() {
         let mut status = EKSUpgradeStatus::default();
         set_failed(&mut status, "first error");
-        assert_eq!(status.conditions.len(), 1);
+        let count = status.conditions.len();
         set_condition(&mut status, "Ready", "True", "Fixed", None);
-        assert_eq!(status.conditions.len(), 1);
-        assert_eq!(status.conditions[0].status, "True");
+        assert_eq!(status.conditions.len(), count);
+        assert_eq!(condition(&status, "Ready").status, "True");
+    }
+
+    fn condition<'a>(status: &'a EKSUpgradeStatus, condition_type: &str) -> &'a UpgradeCondition {
+        status
+            .conditions
+            .iter()
+            .find(|c| c.r#type == condition_type)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_set_condition_same_status_keeps_transition_time() {
+        let mut status = EKSUpgradeStatus::default();
+        set_condition(&mut status, "Ready", "False", "UpgradeInProgress", None);
+        let first = condition(&status, "Ready").last_transition_time;
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        set_condition(
+            &mut status,
+            "Ready",
+            "False",
+            "TransientError",
+            Some("throttled".to_string()),
+        );
+        let cond = condition(&status, "Ready");
+        assert_eq!(cond.last_transition_time, first);
+        assert_eq!(cond.reason, "TransientError");
+        assert_eq!(cond.message.as_deref(), Some("throttled"));
+
+        set_condition(&mut status, "Ready", "True", "UpgradeCompleted", None);
+        assert!(condition(&status, "Ready").last_transition_time > first);
+    }
+
+    #[test]
+    fn test_set_phase_syncs_lifecycle_conditions() {
+        let mut status = EKSUpgradeStatus::default();
+        set_phase(&mut status, UpgradePhase::Planning);
+        let progressing = condition(&status, "Progressing");
+        assert_eq!(progressing.status, "True");
+        assert_eq!(progressing.reason, "Planning");
+        assert_eq!(condition(&status, "Completed").status, "False");
+        assert_eq!(condition(&status, "Failed").status, "False");
+        assert_eq!(condition(&status, "Degraded").status, "False");
+
+        set_phase(&mut status, UpgradePhase::Completed);
+        assert_eq!(condition(&status, "Progressing").status, "False");
+        assert_eq!(condition(&status, "Completed").status, "True");
+        assert_eq!(condition(&status, "Failed").status, "False");
+    }
+
+    #[test]
+    fn test_repeated_reconcile_keeps_transition_times() {
+        let mut status = EKSUpgradeStatus::default();
+        set_phase(&mut status, UpgradePhase::UpgradingControlPlane);
+        let before = status.conditions.clone();
+
+        // A requeued reconcile that lands in the same phase, and one that
+        // advances to another running phase, must not bump any transition.
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        set_phase(&mut status, UpgradePhase::UpgradingControlPlane);
+        set_phase(&mut status, UpgradePhase::UpgradingAddons);
+        for (old, new) in before.iter().zip(&status.conditions) {
+            assert_eq!(old.r#type, new.r#type);
+            assert_eq!(old.last_transition_time, new.last_transition_time);
+        }
+        assert_eq!(condition(&status, "Progressing").reason, "UpgradingAddons");
+
+        // Failing flips Progressing and Failed, leaving Completed untouched.
+        set_failed(&mut status, "addon update failed");
+        let progressing = condition(&status, "Progressing");
+        assert_eq!(progressing.status, "False");
+        assert!(progressing.last_transition_time > before[0].last_transition_time);
+        let failed = condition(&status, "Failed");
+        assert_eq!(failed.status, "True");
+        assert_eq!(failed.message.as_deref(), Some("addon update failed"));
+        assert_eq!(
+            condition(&status, "Completed").last_transition_time,
+            before
+                .iter()
+                .find(|c| c.r#type == "Completed")
+                .unwrap()
+                .last_transition_time
+        );
+    }
+
+    #[test]
+    fn test_degraded_lists_failed_components() {
+        let mut status = EKSUpgradeStatus::default();
+        status.phases.addons = vec![addon(ComponentStatus::Completed)];
+        status.phases.nodegroups = vec![nodegroup(ComponentStatus::Failed)];
+        set_phase(&mut status, UpgradePhase::UpgradingNodeGroups);
+
+        let degraded = condition(&status, "Degraded");
+        assert_eq!(degraded.status, "True");
+        assert_eq!(degraded.reason, "ComponentFailed");
+        assert_eq!(
+            degraded.message.as_deref(),
+            Some("Failed components: nodegroup/ng-1")
+        );
     }
 
     #[test]
@@ -418,4 +665,47 @@ mod tests {
         let elapsed = Utc::now().signed_duration_since(cond.last_transition_time);
         assert!(elapsed.num_seconds() < 2);
     }
+
+    #[test]
+    fn test_set_paused_toggles_condition() {
+        let mut s = EKSUpgradeStatus {
+            phase: Some(UpgradePhase::UpgradingAddons),
+            ..Default::default()
+        };
+
+        assert!(!set_paused(&mut s, false));
+        assert!(s.conditions.is_empty());
+
+        assert!(set_paused(&mut s, true));
+        assert!(!set_paused(&mut s, true));
+        let cond = &s.conditions[0];
+        assert_eq!(
+            (cond.status.as_str(), cond.reason.as_str()),
+            ("True", "PauseRequested")
+        );
+        assert!(cond.message.as_ref().unwrap().contains("UpgradingAddons"));
+
+        assert!(set_paused(&mut s, false));
+        assert_eq!(s.conditions.len(), 1);
+        assert_eq!(s.conditions[0].status, "False");
+        assert_eq!(s.conditions[0].reason, "Resumed");
+        assert_eq!(s.phase, Some(UpgradePhase::UpgradingAddons));
+    }
+
+    #[test]
+    fn test_set_waiting_for_window_toggles_condition() {
+        let mut s = EKSUpgradeStatus::default();
+        assert!(!set_waiting_for_window(&mut s, false, None));
+        assert!(s.conditions.is_empty());
+
+        let msg = Some("Next maintenance window opens at 2026-10-19T13:00:00+00:00".to_string());
+        assert!(set_waiting_for_window(&mut s, true, msg.clone()));
+        assert!(!set_waiting_for_window(&mut s, true, msg));
+        assert_eq!(s.conditions[0].reason, "OutsideMaintenanceWindow");
+
+        assert!(set_waiting_for_window(&mut s, false, None));
+        assert_eq!(s.conditions.len(), 1);
+        assert_eq!(s.conditions[0].status, "False");
+        assert_eq!(s.conditions[0].reason, "WindowOpen");
+    }
 }
@@ -1,4 +1,5 @@
-//! Telemetry module: health checks and Prometheus metrics.
+//! Telemetry module: health checks, Prometheus metrics, and alerting rules.
 
+pub mod alerts;
 pub mod health;
 pub mod metrics;
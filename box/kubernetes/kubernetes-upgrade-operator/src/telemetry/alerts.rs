@@ -0,0 +1,68 @@
+//! Prometheus alerting rules for the kuo operator.
+//!
+//! The rules are shipped as a static `PrometheusRule` manifest embedded at
+//! build time, so the alert expressions always match the metric names
+//! registered in [`super::metrics`].
+
+use std::path::Path;
+
+use anyhow::Context as _;
+
+/// `PrometheusRule` manifest containing `KuoUpgradeStuck` and `KuoUpgradeFailed`.
+pub const ALERTING_RULES: &str = include_str!("alerting-rules.yaml");
+
+/// Write the embedded alerting rules to `path`, creating parent directories.
+pub fn write_rules(path: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(path, ALERTING_RULES)
+        .with_context(|| format!("Failed to write alerting rules to {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collect `alert` names from the parsed `PrometheusRule` manifest.
+    fn alert_names(rules: &serde_yaml::Value) -> Vec<String> {
+        rules["spec"]["groups"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .flat_map(|g| g["rules"].as_sequence().unwrap().iter())
+            .filter_map(|r| r["alert"].as_str().map(ToString::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_alerting_rules_parse() {
+        let rules: serde_yaml::Value = serde_yaml::from_str(ALERTING_RULES).unwrap();
+        assert_eq!(rules["kind"], "PrometheusRule");
+        assert_eq!(rules["apiVersion"], "monitoring.coreos.com/v1");
+        assert_eq!(
+            alert_names(&rules),
+            vec![
+                "KuoUpgradeStuck".to_string(),
+                "KuoUpgradeFailed".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alerting_rules_reference_registered_metrics() {
+        assert!(ALERTING_RULES.contains("kuo_upgrade_phase_info"));
+        assert!(ALERTING_RULES.contains("kuo_upgrade_failed_total"));
+        assert!(ALERTING_RULES.contains("for: 2h"));
+    }
+
+    #[test]
+    fn test_write_rules() {
+        let dir = std::env::temp_dir().join(format!("kuo-alerts-{}", std::process::id()));
+        let path = dir.join("rules").join("kuo.yaml");
+        write_rules(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), ALERTING_RULES);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
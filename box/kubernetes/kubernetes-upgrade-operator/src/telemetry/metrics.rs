@@ -7,6 +7,7 @@ use std::time::Instant;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use chrono::{DateTime, Utc};
 use prometheus_client::encoding::EncodeLabelSet;
 use prometheus_client::encoding::text::encode;
 use prometheus_client::metrics::counter::Counter;
@@ -31,6 +32,14 @@ pub struct UpgradeLabels {
     pub region: String,
 }
 
+/// Labels for backoff retry metrics.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RetryLabels {
+    pub cluster_name: String,
+    pub region: String,
+    pub status_code: String,
+}
+
 /// Labels for phase metrics.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 pub struct PhaseLabels {
@@ -76,8 +85,10 @@ pub struct Metrics {
     pub upgrade_phase_info: Family<PhaseLabels, Gauge>,
     pub upgrade_completed_total: Family<UpgradeLabels, Counter>,
     pub upgrade_failed_total: Family<UpgradeLabels, Counter>,
+    pub upgrade_duration_seconds: Family<UpgradeLabels, Histogram>,
     pub phase_transition_total: Family<PhaseLabels, Counter>,
     pub phase_duration_seconds: Family<PhaseLabels, Histogram>,
+    pub retry_total: Family<RetryLabels, Counter>,
     /// In-memory tracking of when the current phase started for each cluster.
     phase_start_times: Mutex<HashMap<ClusterKey, Instant>>,
     /// Tracks which clusters have had their metrics pre-initialized.
@@ -94,6 +105,11 @@ const PHASE_DURATION_BUCKETS: &[f64] = &[
     1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0, 3600.0,
 ];
 
+/// Buckets for end-to-end upgrade duration (1m to 8h): a multi-step control
+/// plane upgrade followed by node group rolling updates can take hours.
+const UPGRADE_DURATION_BUCKETS: &[f64] =
+    &[60.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0, 14400.0, 28800.0];
+
 impl Metrics {
     /// Create and register all metrics with the given registry.
     pub fn new(registry: &mut Registry) -> Self {
@@ -135,6 +151,16 @@ impl Metrics {
             upgrade_failed_total.clone(),
         );
 
+        let upgrade_duration_seconds =
+            Family::<UpgradeLabels, Histogram>::new_with_constructor(|| {
+                Histogram::new(UPGRADE_DURATION_BUCKETS.iter().copied())
+            });
+        registry.register(
+            "kuo_upgrade_duration_seconds",
+            "Time from upgrade start to Completed phase in seconds",
+            upgrade_duration_seconds.clone(),
+        );
+
         let phase_transition_total = Family::<PhaseLabels, Counter>::default();
         registry.register(
             "kuo_phase_transition",
@@ -151,14 +177,23 @@ impl Metrics {
             phase_duration_seconds.clone(),
         );
 
+        let retry_total = Family::<RetryLabels, Counter>::default();
+        registry.register(
+            "kuo_retry",
+            "Total number of backoff retries after throttled or failed AWS calls",
+            retry_total.clone(),
+        );
+
         Self {
             reconcile_total,
             reconcile_duration_seconds,
             upgrade_phase_info,
             upgrade_completed_total,
             upgrade_failed_total,
+            upgrade_duration_seconds,
             phase_transition_total,
             phase_duration_seconds,
+            retry_total,
             phase_start_times: Mutex::new(HashMap::new()),
             initialized_clusters: Mutex::new(HashSet::new()),
         }
@@ -237,6 +272,25 @@ impl Metrics {
             .observe(duration);
         Some(duration)
     }
+
+    /// Observe the end-to-end upgrade duration measured from `status.startedAt`.
+    ///
+    /// Uses the persisted start timestamp rather than in-memory state so the
+    /// duration survives operator restarts mid-upgrade. Returns the observed
+    /// duration in seconds, or None if no start time was recorded.
+    pub fn observe_upgrade_duration(
+        &self,
+        labels: &UpgradeLabels,
+        started_at: Option<DateTime<Utc>>,
+    ) -> Option<f64> {
+        let started_at = started_at?;
+        #[allow(clippy::cast_precision_loss)]
+        let duration = (Utc::now() - started_at).num_milliseconds().max(0) as f64 / 1000.0;
+        self.upgrade_duration_seconds
+            .get_or_create(labels)
+            .observe(duration);
+        Some(duration)
+    }
 }
 
 /// `OpenMetrics` content type. Without this header Prometheus falls back to
@@ -434,6 +488,34 @@ mod tests {
         assert!(duration.unwrap() >= 0.01);
     }
 
+    #[test]
+    fn test_upgrade_duration_observe() {
+        let mut registry = Registry::default();
+        let metrics = Metrics::new(&mut registry);
+
+        let labels = UpgradeLabels {
+            cluster_name: "test-cluster".to_string(),
+            region: "us-east-1".to_string(),
+        };
+
+        // No startedAt recorded: nothing is observed
+        assert!(metrics.observe_upgrade_duration(&labels, None).is_none());
+
+        let started_at = Utc::now() - chrono::Duration::minutes(90);
+        let duration = metrics
+            .observe_upgrade_duration(&labels, Some(started_at))
+            .unwrap();
+        assert!(duration >= 5400.0);
+
+        let mut buf = String::new();
+        encode(&mut buf, &registry).unwrap();
+        assert!(buf.contains("# TYPE kuo_upgrade_duration_seconds histogram"));
+        assert!(
+            buf.contains(r#"kuo_upgrade_duration_seconds_count{cluster_name="test-cluster",region="us-east-1"} 1"#),
+            "missing upgrade_duration_seconds count: {buf}"
+        );
+    }
+
     #[test]
     fn test_init_for_cluster_creates_zero_counters() {
         let mut registry = Registry::default();
@@ -0,0 +1,89 @@
+//! CRD conversion webhook server (/convert).
+//!
+//! Served over TLS on its own port so the API server can reach it through the
+//! operator Service without sharing the health or metrics listeners.
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use axum::routing::post;
+use axum::{Json, Router};
+use axum_server::tls_rustls::RustlsConfig;
+use kube::core::conversion::ConversionReview;
+use tracing::info;
+
+use crate::crd::conversion;
+
+/// Default directory holding the serving certificate (`tls.crt`, `tls.key`).
+pub const DEFAULT_CERT_DIR: &str = "/etc/kuo/webhook-certs";
+
+async fn convert(Json(review): Json<ConversionReview>) -> Json<ConversionReview> {
+    Json(conversion::review(review))
+}
+
+fn router() -> Router {
+    Router::new().route("/convert", post(convert))
+}
+
+/// Start the conversion webhook server on the given port.
+pub async fn serve(port: u16, cert_dir: &Path) -> anyhow::Result<()> {
+    let tls = RustlsConfig::from_pem_file(cert_dir.join("tls.crt"), cert_dir.join("tls.key"))
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to load webhook certificate from {}: {e}",
+                cert_dir.display()
+            )
+        })?;
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    info!("Conversion webhook listening on port {}", port);
+    axum_server::bind_rustls(addr, tls)
+        .serve(router().into_make_service())
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_convert_handler() {
+        let review: ConversionReview = serde_json::from_value(serde_json::json!({
+            "apiVersion": "apiextensions.k8s.io/v1",
+            "kind": "ConversionReview",
+            "request": {
+                "uid": "uid-1",
+                "desiredAPIVersion": "kuo.io/v1beta1",
+                "objects": [{
+                    "apiVersion": "kuo.io/v1alpha1",
+                    "kind": "EKSUpgrade",
+                    "metadata": { "name": "test" },
+                    "spec": {
+                        "clusterName": "c",
+                        "targetVersion": "1.34",
+                        "region": "r",
+                        "upgradeMode": "Forward"
+                    }
+                }]
+            }
+        }))
+        .unwrap();
+
+        let Json(out) = convert(Json(review)).await;
+        let out = serde_json::to_value(out).unwrap();
+        assert_eq!(
+            out["response"]["convertedObjects"][0]["apiVersion"],
+            "kuo.io/v1beta1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serve_missing_certs_fails() {
+        let err = serve(0, Path::new("/nonexistent/kuo-certs"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("webhook certificate"));
+    }
+}
@@ -11,6 +11,7 @@ import type {
   ListResponse,
   ReportMeta,
   ReportType,
+  SeverityTrendPoint,
   Stats,
   StatusResponse,
   TokenInfo,
@@ -106,6 +107,14 @@ export function getDashboardTrends(
   return fetchApi(`/api/v1/dashboard/trends?${params}`)
 }
 
+export function getSeverityTrend(
+  cluster: string,
+  days: number,
+): Promise<SeverityTrendPoint[]> {
+  const params = new URLSearchParams({ cluster, days: String(days) })
+  return fetchApi(`/api/v1/trends?${params}`)
+}
+
 export async function listTokens(): Promise<{ tokens: TokenInfo[] }> {
   return fetchApi('/api/v1/auth/tokens')
 }
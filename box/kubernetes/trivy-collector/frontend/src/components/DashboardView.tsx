@@ -2,8 +2,8 @@ import { useState, useEffect, useRef, useCallback } from 'react'
 import { useNavigate } from 'react-router-dom'
 import { Chart as ChartJS, CategoryScale, LinearScale, PointElement, LineElement, BarElement, Filler, Legend, Tooltip } from 'chart.js'
 import { Line, Bar } from 'react-chartjs-2'
-import { getDashboardTrends, getStats, getClusters } from '../api'
-import type { TrendResponse, Stats, ClusterInfo } from '../types'
+import { getDashboardTrends, getSeverityTrend, getStats, getClusters } from '../api'
+import type { TrendResponse, SeverityTrendPoint, Stats, ClusterInfo } from '../types'
 import styles from './DashboardView.module.css'
 
 ChartJS.register(CategoryScale, LinearScale, PointElement, LineElement, BarElement, Filler, Legend, Tooltip)
@@ -16,6 +16,7 @@ export default function DashboardView() {
   const [cluster, setCluster] = useState('')
   const [clusters, setClusters] = useState<ClusterInfo[]>([])
   const [trendData, setTrendData] = useState<TrendResponse | null>(null)
+  const [severityTrend, setSeverityTrend] = useState<SeverityTrendPoint[]>([])
   const [statsData, setStatsData] = useState<Stats | null>(null)
   const [autoRefresh, setAutoRefresh] = useState(true)
   const [countdown, setCountdown] = useState(REFRESH_INTERVAL)
@@ -25,12 +26,14 @@ export default function DashboardView() {
 
   const loadData = useCallback(async () => {
     try {
-      const [trends, stats] = await Promise.all([
+      const [trends, stats, history] = await Promise.all([
         getDashboardTrends(range, cluster || undefined),
         getStats(),
+        cluster ? getSeverityTrend(cluster, parseInt(range, 10) || 30) : Promise.resolve([]),
       ])
       setTrendData(trends)
       setStatsData(stats)
+      setSeverityTrend(history)
     } catch { /* silent */ }
   }, [range, cluster])
 
@@ -263,6 +266,25 @@ export default function DashboardView() {
               />
             </div>
           </div>
+          {cluster && (
+            <div className={`${styles.chartSection} ${styles.fullWidth}`}>
+              <div className="section-bar"><h3 className="graph-title">Scan History: {cluster}</h3></div>
+              <div className={styles.chartContainer}>
+                <Line
+                  data={{
+                    labels: severityTrend.map((p) => p.date.substring(5)),
+                    datasets: [
+                      { label: 'Critical', data: severityTrend.map((p) => p.critical), borderColor: '#ef4444', backgroundColor: 'transparent', borderWidth: 2, pointRadius: 2, tension: 0.3 },
+                      { label: 'High', data: severityTrend.map((p) => p.high), borderColor: '#f97316', backgroundColor: 'transparent', borderWidth: 2, pointRadius: 2, tension: 0.3 },
+                      { label: 'Medium', data: severityTrend.map((p) => p.medium), borderColor: '#eab308', backgroundColor: 'transparent', borderWidth: 2, pointRadius: 2, tension: 0.3 },
+                      { label: 'Low', data: severityTrend.map((p) => p.low), borderColor: '#22c55e', backgroundColor: 'transparent', borderWidth: 2, pointRadius: 2, tension: 0.3 },
+                    ],
+                  }}
+                  options={chartOpts}
+                />
+              </div>
+            </div>
+          )}
           <div className={`${styles.chartSection} ${styles.fullWidth}`}>
             <div className="section-bar"><h3 className="graph-title">Vulnerabilities by Severity</h3></div>
             <div className={styles.chartContainer}>
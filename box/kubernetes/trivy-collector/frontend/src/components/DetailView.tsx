@@ -141,7 +141,10 @@ export default function DetailView({ report, reportType, onBack }: DetailViewPro
           </div>
 
           {reportType === 'vulnerabilityreport' && reportData && (
-            <VulnDetail vulnerabilities={(reportData.vulnerabilities as Record<string, unknown>[]) || []} />
+            <VulnDetail
+              vulnerabilities={(reportData.vulnerabilities as Record<string, unknown>[]) || []}
+              suppressed={detail.suppressed_cves}
+            />
           )}
 
           {reportType === 'sbomreport' && reportData && (
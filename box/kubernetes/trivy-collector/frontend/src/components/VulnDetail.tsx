@@ -18,12 +18,14 @@ interface Vuln {
 
 interface VulnDetailProps {
   vulnerabilities: Record<string, unknown>[]
+  suppressed?: string[]
 }
 
 const severityOrder: Record<string, number> = { CRITICAL: 0, HIGH: 1, MEDIUM: 2, LOW: 3, UNKNOWN: 4 }
 const severityLabels: Record<string, string> = { CRITICAL: 'C', HIGH: 'H', MEDIUM: 'M', LOW: 'L', UNKNOWN: 'U' }
 
-export default function VulnDetail({ vulnerabilities }: VulnDetailProps) {
+export default function VulnDetail({ vulnerabilities, suppressed = [] }: VulnDetailProps) {
+  const suppressedIds = new Set(suppressed)
   const vulns = (vulnerabilities as unknown as Vuln[]).sort(
     (a, b) => (severityOrder[a.severity] || 5) - (severityOrder[b.severity] || 5),
   )
@@ -32,6 +34,9 @@ export default function VulnDetail({ vulnerabilities }: VulnDetailProps) {
     <div className="graph-section" style={{ display: 'block' }}>
       <div className="section-bar">
         <h3 className="graph-title">Vulnerabilities <span className="section-count">({vulns.length})</span></h3>
+        {suppressedIds.size > 0 && (
+          <span className={styles.textMuted}>{suppressedIds.size} suppressed, excluded from counts</span>
+        )}
       </div>
       <div className="detail-table-container">
         <table className={styles.table}>
@@ -57,8 +62,9 @@ export default function VulnDetail({ vulnerabilities }: VulnDetailProps) {
                 const sev = (vuln.severity || '').toUpperCase()
                 const label = severityLabels[sev] || '?'
                 const score = vuln.score != null ? vuln.score.toFixed(1) : '-'
+                const isSuppressed = suppressedIds.has(vulnId)
                 return (
-                  <tr key={`${vulnId}-${index}`}>
+                  <tr key={`${vulnId}-${index}`} className={isSuppressed ? styles.suppressed : undefined}>
                     <td className={styles.colIndex}>{index + 1}</td>
                     <td className={styles.colSeverity}>
                       <span className={`severity-badge severity-${sev.toLowerCase()}`}>{label}</span>
@@ -69,6 +75,7 @@ export default function VulnDetail({ vulnerabilities }: VulnDetailProps) {
                       ) : (
                         escapeHtml(vulnId)
                       )}
+                      {isSuppressed && <span className={styles.suppressedBadge}>Suppressed</span>}
                     </td>
                     <td className={styles.colScore}>{score}</td>
                     <td>{escapeHtml(vuln.resource || '-')}</td>
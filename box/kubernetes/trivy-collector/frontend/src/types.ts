@@ -26,6 +26,7 @@ export interface ReportMeta {
 export interface FullReport {
   meta: ReportMeta
   data: Record<string, unknown>
+  suppressed_cves?: string[]
 }
 
 export interface ClusterInfo {
@@ -51,6 +52,7 @@ export interface Stats {
 export interface ListResponse<T> {
   items: T[]
   total: number
+  next_cursor?: string
 }
 
 export interface WatcherInfo {
@@ -116,6 +118,15 @@ export interface TrendResponse {
   series: TrendDataPoint[]
 }
 
+export interface SeverityTrendPoint {
+  date: string
+  critical: number
+  high: number
+  medium: number
+  low: number
+  unknown: number
+}
+
 export interface TokenInfo {
   id: number
   name: string
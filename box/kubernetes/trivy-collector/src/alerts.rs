@@ -1,6 +1,8 @@
 //! Alert rules: Alertmanager-style schema, ConfigMap-backed storage,
-//! Slack webhook delivery.
+//! Slack webhook delivery. `critical` is a separate, rule-less webhook for
+//! increases in the fleet-wide critical vulnerability count.
 
+pub mod critical;
 pub mod evaluator;
 pub mod expr;
 pub mod notifier;
@@ -8,6 +10,7 @@ pub mod preview;
 pub mod store;
 pub mod types;
 
+pub use critical::CriticalWebhook;
 pub use evaluator::AlertEvaluator;
 pub use store::{AlertStore, AlertStoreError};
 pub use types::{AlertRule, Matchers, Receiver, SlackReceiver};
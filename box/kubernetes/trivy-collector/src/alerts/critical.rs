@@ -0,0 +1,339 @@
+//! Generic webhook fired when the fleet-wide critical vulnerability count
+//! goes up. Independent of the ConfigMap-backed SBOM rules: the server polls
+//! the reports with critical findings every `ALERT_INTERVAL_MINS`, and when
+//! their total is higher than in the previous cycle it POSTs the delta and
+//! the images that gained critical findings to `ALERT_WEBHOOK_URL`. Counts
+//! come from `Database::query_reports`, so suppressed findings never alert.
+//!
+//! The first cycle only records a baseline, so a restart never re-alerts on
+//! findings that already existed. A cycle whose webhook fails keeps the
+//! previous baseline, so the increase is sent again on the next cycle. When
+//! `ALERT_WEBHOOK_SECRET` is set, the body is signed with HMAC-SHA256 and
+//! sent as `X-Trivy-Collector-Signature-256: sha256=<hex>`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use reqwest::Client as HttpClient;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::{debug, info};
+
+use crate::storage::{Database, QueryParams, ReportMeta};
+
+/// Header carrying the HMAC-SHA256 signature of the request body.
+pub const SIGNATURE_HEADER: &str = "X-Trivy-Collector-Signature-256";
+
+/// Upper bound on reports fetched when listing critical images.
+const MAX_CRITICAL_REPORTS: i64 = 10_000;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An image with at least one critical finding, as sent in the payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CriticalImage {
+    pub cluster: String,
+    pub namespace: String,
+    pub image: String,
+    pub critical: i64,
+}
+
+/// Critical findings observed in one polling cycle.
+#[derive(Debug, Clone, Default)]
+pub struct CriticalSnapshot {
+    pub total_critical: i64,
+    pub images: Vec<CriticalImage>,
+}
+
+/// Webhook request body.
+#[derive(Debug, Clone, Serialize)]
+pub struct CriticalAlertPayload {
+    pub event: &'static str,
+    pub previous_total: i64,
+    pub current_total: i64,
+    pub delta: i64,
+    /// Images that are new to the critical set or whose critical count grew.
+    pub images: Vec<CriticalImage>,
+    pub timestamp: String,
+}
+
+/// Compare two snapshots and build the alert, if the critical count grew.
+pub fn diff_snapshots(
+    previous: &CriticalSnapshot,
+    current: &CriticalSnapshot,
+) -> Option<CriticalAlertPayload> {
+    let delta = current.total_critical - previous.total_critical;
+    if delta <= 0 {
+        return None;
+    }
+
+    let before: HashMap<(&str, &str, &str), i64> = previous
+        .images
+        .iter()
+        .map(|i| {
+            (
+                (i.cluster.as_str(), i.namespace.as_str(), i.image.as_str()),
+                i.critical,
+            )
+        })
+        .collect();
+    let images = current
+        .images
+        .iter()
+        .filter(|i| {
+            before
+                .get(&(i.cluster.as_str(), i.namespace.as_str(), i.image.as_str()))
+                .is_none_or(|&prev| i.critical > prev)
+        })
+        .cloned()
+        .collect();
+
+    Some(CriticalAlertPayload {
+        event: "critical_vulnerabilities_increased",
+        previous_total: previous.total_critical,
+        current_total: current.total_critical,
+        delta,
+        images,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Hex-encoded HMAC-SHA256 of `body` keyed with `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Polls critical totals and posts to the webhook when they increase.
+pub struct CriticalWebhook {
+    http: HttpClient,
+    url: String,
+    secret: String,
+    previous: Option<CriticalSnapshot>,
+}
+
+impl CriticalWebhook {
+    pub fn new(url: String, secret: String) -> Self {
+        Self {
+            http: HttpClient::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            url,
+            secret,
+            previous: None,
+        }
+    }
+
+    /// Run one polling cycle. Returns the delta that was sent, if any.
+    ///
+    /// The new snapshot only replaces the baseline once the webhook accepted
+    /// the alert; on a send error the increase is retried next cycle.
+    pub async fn check(&mut self, db: &Database) -> Result<Option<i64>> {
+        let current = snapshot(db).await?;
+        let Some(previous) = self.previous.as_ref() else {
+            debug!(
+                total_critical = current.total_critical,
+                "Critical webhook baseline recorded"
+            );
+            self.previous = Some(current);
+            return Ok(None);
+        };
+        let Some(payload) = diff_snapshots(previous, &current) else {
+            self.previous = Some(current);
+            return Ok(None);
+        };
+
+        self.send(&payload).await?;
+        self.previous = Some(current);
+        info!(
+            delta = payload.delta,
+            total_critical = payload.current_total,
+            images = payload.images.len(),
+            "Critical vulnerability webhook sent"
+        );
+        Ok(Some(payload.delta))
+    }
+
+    async fn send(&self, payload: &CriticalAlertPayload) -> Result<()> {
+        let body = serde_json::to_vec(payload)?;
+        let mut request = self
+            .http
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if !self.secret.is_empty() {
+            request = request.header(
+                SIGNATURE_HEADER,
+                format!("sha256={}", sign(&self.secret, &body)),
+            );
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .context("Failed to send critical vulnerability webhook")?
+            .error_for_status()
+            .context("Critical vulnerability webhook rejected")?;
+        Ok(())
+    }
+}
+
+/// Read the reports that carry criticals and their total.
+///
+/// `query_reports` subtracts active suppressions, so the total and the image
+/// list exclude suppressed findings.
+async fn snapshot(db: &Database) -> Result<CriticalSnapshot> {
+    let params = QueryParams {
+        severity: Some(vec!["critical".to_string()]),
+        limit: Some(MAX_CRITICAL_REPORTS),
+        ..Default::default()
+    };
+    let (reports, _) = db.query_reports("vulnerabilityreport", &params).await?;
+    let images: Vec<CriticalImage> = reports.iter().map(critical_image).collect();
+
+    Ok(CriticalSnapshot {
+        total_critical: images.iter().map(|i| i.critical).sum(),
+        images,
+    })
+}
+
+fn critical_image(report: &ReportMeta) -> CriticalImage {
+    CriticalImage {
+        cluster: report.cluster.clone(),
+        namespace: report.namespace.clone(),
+        image: report.image.clone(),
+        critical: report.summary.as_ref().map_or(0, |s| s.critical),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(name: &str, critical: i64) -> CriticalImage {
+        CriticalImage {
+            cluster: "prod".to_string(),
+            namespace: "default".to_string(),
+            image: name.to_string(),
+            critical,
+        }
+    }
+
+    fn snapshot_of(images: Vec<CriticalImage>) -> CriticalSnapshot {
+        CriticalSnapshot {
+            total_critical: images.iter().map(|i| i.critical).sum(),
+            images,
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots_no_increase() {
+        let previous = snapshot_of(vec![image("nginx:1.25", 3)]);
+        assert!(diff_snapshots(&previous, &previous).is_none());
+
+        let fixed = snapshot_of(vec![image("nginx:1.25", 1)]);
+        assert!(diff_snapshots(&previous, &fixed).is_none());
+    }
+
+    #[test]
+    fn test_diff_snapshots_reports_new_and_grown_images() {
+        let previous = snapshot_of(vec![image("nginx:1.25", 3), image("redis:7", 1)]);
+        let current = snapshot_of(vec![
+            image("nginx:1.25", 3),
+            image("redis:7", 2),
+            image("postgres:16", 4),
+        ]);
+
+        let payload = diff_snapshots(&previous, &current).unwrap();
+        assert_eq!(payload.previous_total, 4);
+        assert_eq!(payload.current_total, 9);
+        assert_eq!(payload.delta, 5);
+        assert_eq!(
+            payload.images,
+            vec![image("redis:7", 2), image("postgres:16", 4)]
+        );
+    }
+
+    #[test]
+    fn test_sign_rfc4231_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_records_baseline_first() {
+        let db = Database::new(":memory:").await.unwrap();
+        let mut webhook =
+            CriticalWebhook::new("http://127.0.0.1:1/hook".to_string(), String::new());
+
+        // No request is attempted on the first cycle or without an increase.
+        assert_eq!(webhook.check(&db).await.unwrap(), None);
+        assert_eq!(webhook.check(&db).await.unwrap(), None);
+    }
+
+    fn critical_report(name: &str, cves: &[&str]) -> crate::collector::types::ReportPayload {
+        let vulnerabilities: Vec<_> = cves
+            .iter()
+            .map(|cve| serde_json::json!({"vulnerabilityID": cve, "severity": "CRITICAL"}))
+            .collect();
+        crate::collector::types::ReportPayload {
+            cluster: "prod".to_string(),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            report_type: "vulnerabilityreport".to_string(),
+            data_json: serde_json::json!({
+                "report": {
+                    "artifact": {"repository": "nginx", "tag": "1.25"},
+                    "summary": {"criticalCount": cves.len()},
+                    "vulnerabilities": vulnerabilities
+                }
+            })
+            .to_string(),
+            received_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_excludes_suppressed_findings() {
+        let db = Database::new(":memory:").await.unwrap();
+        db.upsert_report(&critical_report(
+            "app1",
+            &["CVE-2023-44487", "CVE-2024-0001"],
+        ))
+        .await
+        .unwrap();
+        assert_eq!(snapshot(&db).await.unwrap().total_critical, 2);
+
+        db.create_suppression("CVE-2023-44487", "nginx:*", "", None, "alice", None)
+            .await
+            .unwrap();
+        let current = snapshot(&db).await.unwrap();
+        assert_eq!(current.total_critical, 1);
+        assert_eq!(current.images[0].critical, 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_keeps_baseline_when_send_fails() {
+        let db = Database::new(":memory:").await.unwrap();
+        let mut webhook =
+            CriticalWebhook::new("http://127.0.0.1:1/hook".to_string(), String::new());
+        assert_eq!(webhook.check(&db).await.unwrap(), None);
+
+        db.upsert_report(&critical_report("app1", &["CVE-2023-44487"]))
+            .await
+            .unwrap();
+        // Nothing listens on port 1, so the send fails and the increase is
+        // retried on the next cycle instead of being absorbed.
+        assert!(webhook.check(&db).await.is_err());
+        assert_eq!(webhook.previous.as_ref().unwrap().total_critical, 0);
+        assert!(webhook.check(&db).await.is_err());
+    }
+}
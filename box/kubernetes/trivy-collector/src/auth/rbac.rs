@@ -116,6 +116,7 @@ p, role:readonly, stats, get, allow
 p, role:readonly, tokens, get, allow
 p, role:readonly, tokens, create, allow
 p, role:readonly, alerts, get, allow
+p, role:readonly, suppressions, get, allow
 p, role:admin, *, *, allow"#
     }
 
@@ -267,7 +268,12 @@ pub fn resolve_endpoint(method: &str, path: &str) -> Option<(&'static str, &'sta
 
 fn resolve_get(path: &str) -> Option<(&'static str, &'static str)> {
     // Reports
-    if path.starts_with("/api/v1/vulnerabilityreports") || path.starts_with("/api/v1/sbomreports") {
+    if path.starts_with("/api/v1/vulnerabilityreports")
+        || path.starts_with("/api/v1/sbomreports")
+        || path == "/api/v1/reports/export"
+        || path == "/api/v1/notes/export"
+        || path == "/api/v1/sbom/diff"
+    {
         return Some(("reports", "get"));
     }
     // Clusters & namespaces
@@ -276,7 +282,11 @@ fn resolve_get(path: &str) -> Option<(&'static str, &'static str)> {
     }
     // Stats & system info
     if path == "/api/v1/stats"
+        || path == "/api/v1/stats/registries"
         || path.starts_with("/api/v1/dashboard/trends")
+        || path == "/api/v1/trends"
+        || path == "/api/v1/top-images"
+        || (path.starts_with("/api/v1/namespaces/") && path.ends_with("/summary"))
         || path == "/api/v1/watcher/status"
         || path == "/api/v1/version"
         || path == "/api/v1/status"
@@ -296,6 +306,10 @@ fn resolve_get(path: &str) -> Option<(&'static str, &'static str)> {
     if path.starts_with("/api/v1/alerts") {
         return Some(("alerts", "get"));
     }
+    // Suppressions
+    if path == "/api/v1/suppressions" {
+        return Some(("suppressions", "get"));
+    }
     None
 }
 
@@ -312,6 +326,12 @@ fn resolve_post(path: &str) -> Option<(&'static str, &'static str)> {
     if path == "/api/v1/alerts" {
         return Some(("alerts", "create"));
     }
+    if path == "/api/v1/notes/import" {
+        return Some(("reports", "update"));
+    }
+    if path == "/api/v1/suppressions" {
+        return Some(("suppressions", "create"));
+    }
     None
 }
 
@@ -338,6 +358,9 @@ fn resolve_delete(path: &str) -> Option<(&'static str, &'static str)> {
     if path.starts_with("/api/v1/alerts/") {
         return Some(("alerts", "delete"));
     }
+    if path.starts_with("/api/v1/suppressions/") {
+        return Some(("suppressions", "delete"));
+    }
     None
 }
 
@@ -353,6 +376,7 @@ p, role:readonly, stats, get, allow
 p, role:readonly, tokens, get, allow
 p, role:readonly, tokens, create, allow
 p, role:readonly, alerts, get, allow
+p, role:readonly, suppressions, get, allow
 p, role:admin, *, *, allow
 
 g, security-team, role:readonly
@@ -636,6 +660,14 @@ g, team-b, role:readonly
             resolve_endpoint("GET", "/api/v1/sbomreports/components/search"),
             Some(("reports", "get"))
         );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/reports/export"),
+            Some(("reports", "get"))
+        );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/sbom/diff"),
+            Some(("reports", "get"))
+        );
     }
 
     #[test]
@@ -660,6 +692,22 @@ g, team-b, role:readonly
             resolve_endpoint("GET", "/api/v1/dashboard/trends"),
             Some(("stats", "get"))
         );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/trends"),
+            Some(("stats", "get"))
+        );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/stats/registries"),
+            Some(("stats", "get"))
+        );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/top-images"),
+            Some(("stats", "get"))
+        );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/namespaces/default/summary"),
+            Some(("stats", "get"))
+        );
         assert_eq!(
             resolve_endpoint("GET", "/api/v1/version"),
             Some(("stats", "get"))
@@ -684,6 +732,10 @@ g, team-b, role:readonly
             resolve_endpoint("GET", "/api/v1/admin/logs/stats"),
             Some(("admin", "get"))
         );
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/admin/audit"),
+            Some(("admin", "get"))
+        );
         assert_eq!(
             resolve_endpoint("GET", "/api/v1/admin/info"),
             Some(("admin", "get"))
@@ -706,6 +758,22 @@ g, team-b, role:readonly
         );
     }
 
+    #[test]
+    fn test_resolve_suppressions() {
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/suppressions"),
+            Some(("suppressions", "get"))
+        );
+        assert_eq!(
+            resolve_endpoint("POST", "/api/v1/suppressions"),
+            Some(("suppressions", "create"))
+        );
+        assert_eq!(
+            resolve_endpoint("DELETE", "/api/v1/suppressions/7"),
+            Some(("suppressions", "delete"))
+        );
+    }
+
     #[test]
     fn test_resolve_delete_reports() {
         assert_eq!(
@@ -717,6 +785,18 @@ g, team-b, role:readonly
         );
     }
 
+    #[test]
+    fn test_resolve_bulk_notes() {
+        assert_eq!(
+            resolve_endpoint("GET", "/api/v1/notes/export"),
+            Some(("reports", "get"))
+        );
+        assert_eq!(
+            resolve_endpoint("POST", "/api/v1/notes/import"),
+            Some(("reports", "update"))
+        );
+    }
+
     #[test]
     fn test_resolve_put_notes() {
         assert_eq!(
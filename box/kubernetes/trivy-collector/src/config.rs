@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +30,16 @@ pub mod env {
     // External base URL used by notification deep links (server-mode only).
     pub const EXTERNAL_URL: &str = "EXTERNAL_URL";
 
+    // Critical vulnerability webhook (server-mode only).
+    pub const ALERT_WEBHOOK_URL: &str = "ALERT_WEBHOOK_URL";
+    pub const ALERT_WEBHOOK_SECRET: &str = "ALERT_WEBHOOK_SECRET";
+    pub const ALERT_INTERVAL_MINS: &str = "ALERT_INTERVAL_MINS";
+
+    // Report age-based pruning (server-mode only).
+    pub const PRUNE_AFTER_DAYS: &str = "PRUNE_AFTER_DAYS";
+    pub const PRUNE_INTERVAL_HOURS: &str = "PRUNE_INTERVAL_HOURS";
+    pub const HISTORY_RETENTION_DAYS: &str = "HISTORY_RETENTION_DAYS";
+
     // Authentication
     pub use crate::auth::config::env::*;
 }
@@ -62,6 +74,21 @@ impl std::fmt::Display for Mode {
 pub enum Command {
     /// Show version information
     Version,
+    /// Grafana dashboard utilities
+    Dashboard {
+        #[command(subcommand)]
+        command: DashboardCommand,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DashboardCommand {
+    /// Write the pre-built Grafana dashboard JSON
+    Export {
+        /// Output file path
+        #[arg(long, short, default_value = "grafana-dashboard.json")]
+        output: PathBuf,
+    },
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -150,6 +177,35 @@ pub struct Config {
     #[arg(long, env = env::EXTERNAL_URL, default_value = "")]
     pub external_url: String,
 
+    /// Webhook URL that receives a POST when the fleet-wide critical
+    /// vulnerability count increases. Empty = disabled.
+    #[arg(long, env = env::ALERT_WEBHOOK_URL, default_value = "")]
+    pub alert_webhook_url: String,
+
+    /// Shared secret for the `X-Trivy-Collector-Signature-256` HMAC header on
+    /// webhook requests. Empty = requests are unsigned.
+    #[arg(long, env = env::ALERT_WEBHOOK_SECRET, default_value = "")]
+    pub alert_webhook_secret: String,
+
+    /// Minutes between critical vulnerability checks
+    #[arg(long, env = env::ALERT_INTERVAL_MINS, default_value = "5")]
+    pub alert_interval_mins: u64,
+
+    /// Delete reports not updated for this many days. 0 = keep forever.
+    #[arg(long, env = env::PRUNE_AFTER_DAYS, default_value = "0")]
+    pub prune_after_days: u32,
+
+    /// Hours between pruning runs
+    #[arg(long, env = env::PRUNE_INTERVAL_HOURS, default_value = "24")]
+    pub prune_interval_hours: u64,
+
+    /// Delete `report_history` snapshots older than this many days.
+    /// 0 = keep forever. The severity trend (up to 365 days) only reaches
+    /// back as far as this retention; the latest snapshot of each resource
+    /// is always kept.
+    #[arg(long, env = env::HISTORY_RETENTION_DAYS, default_value = "90")]
+    pub history_retention_days: u32,
+
     // ============================================
     // Authentication settings (server mode only)
     // ============================================
@@ -256,6 +312,12 @@ mod tests {
             watch_local: true,
             hub_secret_namespace: String::new(),
             external_url: String::new(),
+            alert_webhook_url: String::new(),
+            alert_webhook_secret: String::new(),
+            alert_interval_mins: 5,
+            prune_after_days: 0,
+            prune_interval_hours: 24,
+            history_retention_days: 90,
             auth_mode: "none".to_string(),
             oidc_issuer_url: None,
             oidc_client_id: None,
@@ -312,6 +374,49 @@ mod tests {
         assert_eq!(Mode::Server.to_string(), "server");
     }
 
+    #[test]
+    fn test_parse_dashboard_export() {
+        let config =
+            Config::try_parse_from(["trivy-collector", "dashboard", "export", "-o", "out.json"])
+                .unwrap();
+        match config.command {
+            Some(Command::Dashboard {
+                command: DashboardCommand::Export { output },
+            }) => assert_eq!(output, std::path::Path::new("out.json")),
+            other => panic!("unexpected command: {other:?}"),
+        }
+
+        let config = Config::try_parse_from(["trivy-collector", "dashboard", "export"]).unwrap();
+        assert!(matches!(
+            config.command,
+            Some(Command::Dashboard {
+                command: DashboardCommand::Export { output },
+            }) if output == std::path::Path::new("grafana-dashboard.json")
+        ));
+    }
+
+    #[test]
+    fn test_parse_prune_flags() {
+        let config = Config::try_parse_from(["trivy-collector"]).unwrap();
+        assert_eq!(config.prune_after_days, 0);
+        assert_eq!(config.prune_interval_hours, 24);
+        assert_eq!(config.history_retention_days, 90);
+
+        let config = Config::try_parse_from([
+            "trivy-collector",
+            "--prune-after-days",
+            "30",
+            "--prune-interval-hours",
+            "6",
+            "--history-retention-days",
+            "0",
+        ])
+        .unwrap();
+        assert_eq!(config.prune_after_days, 30);
+        assert_eq!(config.prune_interval_hours, 6);
+        assert_eq!(config.history_retention_days, 0);
+    }
+
     #[test]
     fn test_get_cluster_name() {
         let config = default_config(Mode::Scraper);
@@ -0,0 +1,277 @@
+//! Pre-built Grafana dashboard for the server-mode Prometheus metrics.
+//!
+//! `trivy-collector dashboard export --output grafana-dashboard.json` writes
+//! the dashboard so it can be imported as-is or shipped in a ConfigMap for
+//! the Grafana sidecar. Panels read the `trivy_collector_vulnerabilities` and
+//! `trivy_collector_image_vulnerabilities` gauges, which the server refreshes
+//! from the database every 60 seconds.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+/// Stable UID so re-importing replaces the existing dashboard.
+pub const DASHBOARD_UID: &str = "trivy-collector-overview";
+
+const DATASOURCE: &str = "${datasource}";
+
+const SEVERITIES: [(&str, &str); 5] = [
+    ("critical", "dark-red"),
+    ("high", "orange"),
+    ("medium", "yellow"),
+    ("low", "blue"),
+    ("unknown", "text"),
+];
+
+/// Build the dashboard model.
+pub fn dashboard() -> Value {
+    json!({
+        "uid": DASHBOARD_UID,
+        "title": "Trivy Collector",
+        "description": "Vulnerability overview from trivy-collector server metrics",
+        "tags": ["trivy", "security", "vulnerabilities"],
+        "editable": true,
+        "schemaVersion": 39,
+        "refresh": "5m",
+        "time": { "from": "now-7d", "to": "now" },
+        "templating": { "list": [
+            {
+                "name": "datasource",
+                "label": "Data source",
+                "type": "datasource",
+                "query": "prometheus",
+            },
+            {
+                "name": "cluster",
+                "label": "Cluster",
+                "type": "query",
+                "datasource": datasource(),
+                "query": "label_values(trivy_collector_vulnerabilities, cluster)",
+                "refresh": 2,
+                "multi": true,
+                "includeAll": true,
+                "current": { "text": "All", "value": "$__all" },
+            },
+        ]},
+        "panels": [
+            severity_totals_panel(1),
+            top_images_panel(2),
+            cluster_comparison_panel(3),
+            trend_panel(4),
+        ],
+    })
+}
+
+/// Write the dashboard as pretty-printed JSON to `output`.
+pub fn export(output: &Path) -> Result<()> {
+    let mut body = serde_json::to_string_pretty(&dashboard())?;
+    body.push('\n');
+    std::fs::write(output, body)
+        .with_context(|| format!("Failed to write dashboard to {}", output.display()))
+}
+
+fn datasource() -> Value {
+    json!({ "type": "prometheus", "uid": DATASOURCE })
+}
+
+fn target(ref_id: &str, expr: &str, legend: &str, instant: bool) -> Value {
+    let format = if instant { "table" } else { "time_series" };
+    json!({
+        "refId": ref_id,
+        "datasource": datasource(),
+        "expr": expr,
+        "legendFormat": legend,
+        "instant": instant,
+        "range": !instant,
+        "format": format,
+    })
+}
+
+/// Fixed colors per severity, matched on series name.
+fn severity_overrides() -> Value {
+    SEVERITIES
+        .iter()
+        .map(|(severity, color)| {
+            json!({
+                "matcher": { "id": "byName", "options": severity },
+                "properties": [{ "id": "color", "value": { "mode": "fixed", "fixedColor": color } }],
+            })
+        })
+        .collect()
+}
+
+fn severity_totals_panel(id: u32) -> Value {
+    json!({
+        "id": id,
+        "type": "stat",
+        "title": "Vulnerabilities by severity",
+        "datasource": datasource(),
+        "gridPos": { "h": 5, "w": 24, "x": 0, "y": 0 },
+        "targets": [target(
+            "A",
+            r#"sum by (severity) (trivy_collector_vulnerabilities{cluster=~"$cluster"})"#,
+            "{{severity}}",
+            false,
+        )],
+        "options": {
+            "reduceOptions": { "calcs": ["lastNotNull"], "fields": "", "values": false },
+            "colorMode": "background",
+            "textMode": "value_and_name",
+        },
+        "fieldConfig": { "defaults": { "unit": "none" }, "overrides": severity_overrides() },
+    })
+}
+
+fn top_images_panel(id: u32) -> Value {
+    json!({
+        "id": id,
+        "type": "bargauge",
+        "title": "Top 10 images by critical vulnerabilities",
+        "datasource": datasource(),
+        "gridPos": { "h": 10, "w": 12, "x": 0, "y": 5 },
+        "targets": [target(
+            "A",
+            r#"topk(10, trivy_collector_image_vulnerabilities{severity="critical"})"#,
+            "{{image}}",
+            true,
+        )],
+        "options": {
+            "orientation": "horizontal",
+            "displayMode": "basic",
+            "reduceOptions": { "calcs": ["lastNotNull"], "fields": "", "values": false },
+        },
+        "fieldConfig": {
+            "defaults": { "color": { "mode": "fixed", "fixedColor": "dark-red" }, "min": 0 },
+            "overrides": [],
+        },
+    })
+}
+
+fn cluster_comparison_panel(id: u32) -> Value {
+    let targets: Vec<Value> = SEVERITIES
+        .iter()
+        .zip(["A", "B", "C", "D", "E"])
+        .map(|((severity, _), ref_id)| {
+            target(
+                ref_id,
+                &format!(
+                    r#"sum by (cluster) (trivy_collector_vulnerabilities{{cluster=~"$cluster",severity="{severity}"}})"#
+                ),
+                severity,
+                true,
+            )
+        })
+        .collect();
+    let rename: serde_json::Map<String, Value> = SEVERITIES
+        .iter()
+        .zip(["A", "B", "C", "D", "E"])
+        .map(|((severity, _), ref_id)| (format!("Value #{ref_id}"), json!(severity)))
+        .collect();
+
+    json!({
+        "id": id,
+        "type": "barchart",
+        "title": "Vulnerabilities by cluster",
+        "datasource": datasource(),
+        "gridPos": { "h": 10, "w": 12, "x": 12, "y": 5 },
+        "targets": targets,
+        "transformations": [
+            { "id": "merge", "options": {} },
+            {
+                "id": "organize",
+                "options": { "excludeByName": { "Time": true }, "renameByName": rename },
+            },
+        ],
+        "options": { "xField": "cluster", "stacking": "normal", "orientation": "auto" },
+        "fieldConfig": { "defaults": {}, "overrides": severity_overrides() },
+    })
+}
+
+fn trend_panel(id: u32) -> Value {
+    json!({
+        "id": id,
+        "type": "timeseries",
+        "title": "Vulnerability trend",
+        "datasource": datasource(),
+        "gridPos": { "h": 10, "w": 24, "x": 0, "y": 15 },
+        "targets": [target(
+            "A",
+            r#"sum by (severity) (trivy_collector_vulnerabilities{cluster=~"$cluster"})"#,
+            "{{severity}}",
+            false,
+        )],
+        "options": { "legend": { "displayMode": "table", "placement": "right", "calcs": ["lastNotNull"] } },
+        "fieldConfig": {
+            "defaults": { "custom": { "lineWidth": 2, "fillOpacity": 10 } },
+            "overrides": severity_overrides(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Mode;
+    use crate::metrics::Metrics;
+    use prometheus_client::encoding::text::encode;
+    use prometheus_client::registry::Registry;
+
+    fn exprs(dashboard: &Value) -> Vec<String> {
+        dashboard["panels"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|p| p["targets"].as_array().unwrap().clone())
+            .map(|t| t["expr"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_dashboard_panels() {
+        let dashboard = dashboard();
+        let panels = dashboard["panels"].as_array().unwrap();
+        let types: Vec<&str> = panels.iter().map(|p| p["type"].as_str().unwrap()).collect();
+        assert_eq!(types, vec!["stat", "bargauge", "barchart", "timeseries"]);
+
+        let mut ids: Vec<u64> = panels.iter().map(|p| p["id"].as_u64().unwrap()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), panels.len());
+        assert_eq!(dashboard["uid"], DASHBOARD_UID);
+    }
+
+    #[test]
+    fn test_dashboard_queries_use_server_metrics() {
+        let mut registry = Registry::default();
+        let _metrics = Metrics::new(&mut registry, Mode::Server);
+        let mut exposition = String::new();
+        encode(&mut exposition, &registry).unwrap();
+
+        for expr in exprs(&dashboard()) {
+            let start = expr.find("trivy_collector_").unwrap();
+            let name: String = expr[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            assert!(
+                exposition.contains(&format!("# TYPE {name} ")),
+                "{expr} queries unregistered metric {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_export_writes_json() {
+        let path = std::env::temp_dir().join(format!(
+            "trivy-collector-dashboard-{}.json",
+            std::process::id()
+        ));
+        export(&path).unwrap();
+
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, dashboard());
+    }
+}
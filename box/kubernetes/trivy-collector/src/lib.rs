@@ -2,6 +2,7 @@ pub mod alerts;
 pub mod auth;
 pub mod collector;
 pub mod config;
+pub mod grafana;
 pub mod health;
 pub mod hub;
 pub mod logging;
@@ -3,10 +3,10 @@ use prometheus_client::registry::Registry;
 use std::sync::Arc;
 use tracing::{error, info};
 
-use trivy_collector::config::{Command, Config, Mode};
+use trivy_collector::config::{Command, Config, DashboardCommand, Mode};
 use trivy_collector::health::HealthServer;
 use trivy_collector::metrics::Metrics;
-use trivy_collector::{collector, logging, web};
+use trivy_collector::{collector, grafana, logging, web};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -23,6 +23,16 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle dashboard export subcommand
+    if let Some(Command::Dashboard {
+        command: DashboardCommand::Export { output },
+    }) = &config.command
+    {
+        grafana::export(output)?;
+        println!("Grafana dashboard written to {}", output.display());
+        return Ok(());
+    }
+
     // Initialize logging
     logging::init(&config.log_format, &config.log_level);
 
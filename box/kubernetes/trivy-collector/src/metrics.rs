@@ -10,6 +10,7 @@ use prometheus_client::metrics::histogram::Histogram;
 use prometheus_client::registry::Registry;
 
 use crate::config::Mode;
+use crate::storage::{TopImage, VulnSummary};
 
 // ============================================
 // Label types
@@ -60,6 +61,18 @@ pub struct CleanupResultLabels {
     pub result: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct VulnerabilityLabels {
+    pub cluster: String,
+    pub severity: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ImageVulnerabilityLabels {
+    pub image: String,
+    pub severity: String,
+}
+
 // ============================================
 // Histogram bucket constants
 // ============================================
@@ -92,6 +105,9 @@ pub struct Metrics {
     pub api_logs_total: Option<Gauge>,
     pub api_logs_cleanup_runs_total: Option<Family<CleanupResultLabels, Counter>>,
     pub api_logs_cleanup_deleted_total: Option<Counter>,
+    pub pruned_reports_total: Option<Counter>,
+    pub vulnerabilities: Option<Family<VulnerabilityLabels, Gauge>>,
+    pub image_vulnerabilities: Option<Family<ImageVulnerabilityLabels, Gauge>>,
 
     // -- Collector mode --
     pub reports_sent_total: Option<Family<SendLabels, Counter>>,
@@ -126,6 +142,9 @@ impl Metrics {
             api_logs_total: None,
             api_logs_cleanup_runs_total: None,
             api_logs_cleanup_deleted_total: None,
+            pruned_reports_total: None,
+            vulnerabilities: None,
+            image_vulnerabilities: None,
             reports_sent_total: None,
             reports_send_duration_seconds: None,
             watcher_events_total: None,
@@ -154,6 +173,46 @@ impl Metrics {
         self.registered_count
     }
 
+    /// Replace the vulnerability gauges with the latest database totals.
+    ///
+    /// Both families are cleared first so clusters and images that are gone
+    /// from the database stop being exported.
+    pub fn set_vulnerabilities(&self, by_cluster: &[(String, VulnSummary)], images: &[TopImage]) {
+        if let Some(ref family) = self.vulnerabilities {
+            family.clear();
+            for (cluster, summary) in by_cluster {
+                for (severity, count) in [
+                    ("critical", summary.critical),
+                    ("high", summary.high),
+                    ("medium", summary.medium),
+                    ("low", summary.low),
+                    ("unknown", summary.unknown),
+                ] {
+                    family
+                        .get_or_create(&VulnerabilityLabels {
+                            cluster: cluster.clone(),
+                            severity: severity.to_string(),
+                        })
+                        .set(count);
+                }
+            }
+        }
+
+        if let Some(ref family) = self.image_vulnerabilities {
+            family.clear();
+            for image in images {
+                for (severity, count) in [("critical", image.critical), ("high", image.high)] {
+                    family
+                        .get_or_create(&ImageVulnerabilityLabels {
+                            image: image.image.clone(),
+                            severity: severity.to_string(),
+                        })
+                        .set(count);
+                }
+            }
+        }
+    }
+
     /// Returns the number of metrics registered for server mode.
     fn register_server(&mut self, registry: &mut Registry) -> usize {
         let mut count = 0;
@@ -233,6 +292,33 @@ impl Metrics {
         self.api_logs_cleanup_deleted_total = Some(api_logs_cleanup_deleted_total);
         count += 1;
 
+        let pruned_reports_total = Counter::default();
+        registry.register(
+            "trivy_collector_pruned_reports",
+            "Total reports deleted by age-based pruning",
+            pruned_reports_total.clone(),
+        );
+        self.pruned_reports_total = Some(pruned_reports_total);
+        count += 1;
+
+        let vulnerabilities = Family::<VulnerabilityLabels, Gauge>::default();
+        registry.register(
+            "trivy_collector_vulnerabilities",
+            "Vulnerabilities in stored reports per cluster and severity",
+            vulnerabilities.clone(),
+        );
+        self.vulnerabilities = Some(vulnerabilities);
+        count += 1;
+
+        let image_vulnerabilities = Family::<ImageVulnerabilityLabels, Gauge>::default();
+        registry.register(
+            "trivy_collector_image_vulnerabilities",
+            "Critical and high vulnerabilities of the most vulnerable images",
+            image_vulnerabilities.clone(),
+        );
+        self.image_vulnerabilities = Some(image_vulnerabilities);
+        count += 1;
+
         count
     }
 
@@ -370,6 +456,7 @@ mod tests {
         assert!(metrics.reports_received_total.is_some());
         assert!(metrics.db_size_bytes.is_some());
         assert!(metrics.api_logs_total.is_some());
+        assert!(metrics.pruned_reports_total.is_some());
         // Collector-only fields should be None
         assert!(metrics.reports_sent_total.is_none());
         assert!(metrics.server_up.is_none());
@@ -487,6 +574,41 @@ mod tests {
         assert!(buf.contains("trivy_collector_http_request_duration_seconds_count{"));
     }
 
+    #[test]
+    fn test_set_vulnerabilities_replaces_series() {
+        let mut registry = Registry::default();
+        let metrics = Metrics::new(&mut registry, Mode::Server);
+
+        let summary = |critical| VulnSummary {
+            critical,
+            high: 1,
+            medium: 0,
+            low: 0,
+            unknown: 0,
+        };
+        let image = TopImage {
+            image: "nginx:1.25".to_string(),
+            critical: 3,
+            high: 1,
+            report_count: 1,
+            locations: vec![],
+        };
+        metrics.set_vulnerabilities(&[("old".to_string(), summary(9))], &[]);
+        metrics.set_vulnerabilities(&[("prod".to_string(), summary(3))], &[image]);
+
+        let mut buf = String::new();
+        encode(&mut buf, &registry).unwrap();
+        assert!(
+            buf.contains(
+                r#"trivy_collector_vulnerabilities{cluster="prod",severity="critical"} 3"#
+            )
+        );
+        assert!(buf.contains(
+            r#"trivy_collector_image_vulnerabilities{image="nginx:1.25",severity="critical"} 3"#
+        ));
+        assert!(!buf.contains(r#"cluster="old""#));
+    }
+
     #[test]
     fn test_encoding_has_eof() {
         let mut registry = Registry::default();
@@ -8,21 +8,35 @@
 //! - `schema`: Database schema initialization and migrations
 //! - `operations`: CRUD and query operations
 //! - `extractors`: JSON metadata extraction helpers
+//! - `sbom_diff`: Component diff between two SBOM history snapshots
+//! - `notes`: Bulk notes import/export
+//! - `audit`: Append-only audit trail of mutating API operations
 
 mod api_logs;
+mod audit;
 mod dashboard;
 mod database;
 mod extractors;
 mod models;
+mod notes;
 mod operations;
+mod sbom_diff;
 mod schema;
+mod suppressions;
 mod tokens;
 
 // Re-export public types
-pub use dashboard::{TrendDataPoint, TrendMeta, TrendResponse};
+pub use audit::{AuditCaller, AuditEntry, report_target, suppression_target};
+pub use dashboard::{
+    ImageLocation, NamespaceSummary, RegistryStat, TopImage, TrendDataPoint, TrendMeta, TrendPoint,
+    TrendResponse,
+};
 pub use database::Database;
 pub use models::{
     ApiLogEntry, ApiLogQuery, ApiLogStats, CleanupHistoryEntry, ClusterInfo, ComponentSearchResult,
-    FullReport, QueryParams, ReportMeta, SbomComponentMatch, Stats, TokenInfo, VulnSearchResult,
-    VulnSummary,
+    CveReportMatch, FullReport, QueryParams, ReportMeta, SbomComponentMatch, Stats, TokenInfo,
+    VulnSearchResult, VulnSummary, decode_cursor, encode_cursor,
 };
+pub use notes::{NoteEntry, NotesImportError};
+pub use sbom_diff::{ComponentUpdate, DiffComponent, SbomDiff};
+pub use suppressions::Suppression;
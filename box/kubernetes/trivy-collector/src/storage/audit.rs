@@ -0,0 +1,168 @@
+//! Append-only audit trail of mutating API operations
+//!
+//! Rows are only ever inserted; nothing in the server updates or deletes
+//! them, so the table answers "who changed what" for a shared instance.
+//! Each record is written in the same transaction as the mutation it
+//! describes, so a mutation is never applied without its audit record.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::{Executor, QueryBuilder, Row, Sqlite};
+use utoipa::ToSchema;
+
+use super::database::Database;
+
+/// One audited operation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    #[schema(example = "delete_report")]
+    pub operation: String,
+    /// Report key as `cluster/report_type/namespace/name`, or
+    /// `suppression/<id>`
+    #[schema(
+        example = "prod-cluster/vulnerabilityreport/default/replicaset-nginx-6d4cf56db6-nginx"
+    )]
+    pub target: String,
+    /// Authenticated user or API token owner, or `anonymous`
+    #[schema(example = "alice@example.com")]
+    pub caller: String,
+    /// Unverified `X-Audit-Caller` header sent with the request
+    #[schema(example = "ci-bot")]
+    pub claimed_caller: Option<String>,
+    pub created_at: String,
+}
+
+/// Who performed an audited operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditCaller {
+    /// Authenticated user or API token owner, `anonymous` otherwise
+    pub identity: String,
+    /// `X-Audit-Caller` header value. Any client can set it, so it is
+    /// recorded next to the identity and never in its place.
+    pub claimed: Option<String>,
+}
+
+/// Audit key of a report
+pub fn report_target(cluster: &str, report_type: &str, namespace: &str, name: &str) -> String {
+    format!("{cluster}/{report_type}/{namespace}/{name}")
+}
+
+/// Audit key of a suppression
+pub fn suppression_target(id: i64) -> String {
+    format!("suppression/{id}")
+}
+
+/// Append an audit record using `executor`, so callers can write it in the
+/// transaction of the audited mutation.
+pub(super) async fn insert_audit<'e, E>(
+    executor: E,
+    operation: &str,
+    target: &str,
+    caller: &AuditCaller,
+) -> sqlx::Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    let created_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    sqlx::query(
+        "INSERT INTO audit_log (operation, target, caller, claimed_caller, created_at) VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(operation)
+    .bind(target)
+    .bind(&caller.identity)
+    .bind(caller.claimed.as_deref())
+    .bind(&created_at)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+impl Database {
+    /// Append an audit record on its own
+    pub async fn record_audit(
+        &self,
+        operation: &str,
+        target: &str,
+        caller: &AuditCaller,
+    ) -> Result<()> {
+        insert_audit(&self.pool, operation, target, caller)
+            .await
+            .context("Failed to insert audit record")
+    }
+
+    /// Most recent audit records first, optionally limited to one operation
+    pub async fn list_audit_log(
+        &self,
+        operation: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT id, operation, target, caller, claimed_caller, created_at FROM audit_log",
+        );
+        if let Some(operation) = operation {
+            query.push(" WHERE operation = ");
+            query.push_bind(operation);
+        }
+        query.push(" ORDER BY id DESC LIMIT ");
+        query.push_bind(limit);
+
+        let rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list audit records")?;
+
+        Ok(rows
+            .iter()
+            .map(|row| AuditEntry {
+                id: row.get(0),
+                operation: row.get(1),
+                target: row.get(2),
+                caller: row.get(3),
+                claimed_caller: row.get(4),
+                created_at: row.get(5),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caller(identity: &str, claimed: Option<&str>) -> AuditCaller {
+        AuditCaller {
+            identity: identity.to_string(),
+            claimed: claimed.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_list_audit_log() {
+        let db = Database::new(":memory:").await.unwrap();
+        let target = report_target("prod", "vulnerabilityreport", "default", "app");
+        assert_eq!(target, "prod/vulnerabilityreport/default/app");
+
+        db.record_audit("update_notes", &target, &caller("alice@example.com", None))
+            .await
+            .unwrap();
+        db.record_audit("delete_report", &target, &caller("anonymous", Some("bob")))
+            .await
+            .unwrap();
+
+        let entries = db.list_audit_log(None, 10).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation, "delete_report");
+        assert_eq!(entries[0].caller, "anonymous");
+        assert_eq!(entries[0].claimed_caller.as_deref(), Some("bob"));
+        assert_eq!(entries[1].target, target);
+        assert_eq!(entries[1].claimed_caller, None);
+
+        let notes = db.list_audit_log(Some("update_notes"), 10).await.unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].caller, "alice@example.com");
+
+        assert_eq!(db.list_audit_log(None, 1).await.unwrap().len(), 1);
+    }
+}
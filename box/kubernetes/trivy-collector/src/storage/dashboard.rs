@@ -1,11 +1,14 @@
 //! Dashboard statistics and trend operations
 
+use std::collections::HashMap;
+
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use utoipa::ToSchema;
 
 use super::database::Database;
+use super::models::VulnSummary;
 
 /// Aggregated trend data point
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -44,7 +47,366 @@ pub struct TrendResponse {
     pub series: Vec<TrendDataPoint>,
 }
 
+/// Daily severity totals for one cluster, built from `report_history`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TrendPoint {
+    pub date: String,
+    pub critical: i64,
+    pub high: i64,
+    pub medium: i64,
+    pub low: i64,
+    pub unknown: i64,
+}
+
+/// Cluster and namespace where an image is deployed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ImageLocation {
+    #[schema(example = "prod-cluster")]
+    pub cluster: String,
+    #[schema(example = "default")]
+    pub namespace: String,
+}
+
+/// Fleet-wide critical and high totals for one image
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TopImage {
+    #[schema(example = "nginx:1.25")]
+    pub image: String,
+    pub critical: i64,
+    pub high: i64,
+    /// Number of vulnerability reports for this image
+    pub report_count: i64,
+    pub locations: Vec<ImageLocation>,
+}
+
+/// Critical and high totals for the images pulled from one registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RegistryStat {
+    /// Registry server, empty when the report does not name one
+    #[schema(example = "docker.io")]
+    pub registry: String,
+    /// Number of distinct images from this registry
+    pub image_count: i64,
+    pub critical_count: i64,
+    pub high_count: i64,
+}
+
+/// Number of images listed in `NamespaceSummary::top_images_by_critical`
+const NAMESPACE_TOP_IMAGES: i64 = 5;
+
+/// Aggregated vulnerability counts for one namespace of a cluster
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NamespaceSummary {
+    #[schema(example = "prod-cluster")]
+    pub cluster: String,
+    #[schema(example = "default")]
+    pub namespace: String,
+    /// Number of vulnerability reports in the namespace
+    pub report_count: i64,
+    pub summary: VulnSummary,
+    /// Images with the most critical findings, worst first
+    pub top_images_by_critical: Vec<String>,
+}
+
 impl Database {
+    /// Get severity totals and the worst images for a namespace.
+    ///
+    /// A namespace without reports returns zero counts rather than an error.
+    /// Suppressed findings are excluded from the counts.
+    pub async fn get_namespace_summary(
+        &self,
+        cluster: &str,
+        namespace: &str,
+    ) -> Result<NamespaceSummary> {
+        let columns = self.severity_columns().await?;
+        let sql = format!(
+            r#"
+            WITH ns AS (
+                SELECT
+                    image,
+                    {critical} AS critical_count,
+                    {high} AS high_count,
+                    {medium} AS medium_count,
+                    {low} AS low_count,
+                    {unknown} AS unknown_count
+                {from}
+                WHERE cluster = $1 AND namespace = $2 AND report_type = 'vulnerabilityreport'
+            ),
+            top AS (
+                SELECT image
+                FROM ns
+                WHERE image != ''
+                GROUP BY image
+                HAVING SUM(critical_count) > 0
+                ORDER BY SUM(critical_count) DESC, image ASC
+                LIMIT $3
+            )
+            SELECT
+                COUNT(*),
+                COALESCE(SUM(critical_count), 0),
+                COALESCE(SUM(high_count), 0),
+                COALESCE(SUM(medium_count), 0),
+                COALESCE(SUM(low_count), 0),
+                COALESCE(SUM(unknown_count), 0),
+                (SELECT json_group_array(image) FROM top)
+            FROM ns
+            "#,
+            critical = columns.count("critical"),
+            high = columns.count("high"),
+            medium = columns.count("medium"),
+            low = columns.count("low"),
+            unknown = columns.count("unknown"),
+            from = columns.from(),
+        );
+        let row = sqlx::query(&sql)
+            .bind(cluster)
+            .bind(namespace)
+            .bind(NAMESPACE_TOP_IMAGES)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let top_images: String = row.get(6);
+        Ok(NamespaceSummary {
+            cluster: cluster.to_string(),
+            namespace: namespace.to_string(),
+            report_count: row.get::<i64, _>(0),
+            summary: VulnSummary {
+                critical: row.get::<i64, _>(1),
+                high: row.get::<i64, _>(2),
+                medium: row.get::<i64, _>(3),
+                low: row.get::<i64, _>(4),
+                unknown: row.get::<i64, _>(5),
+            },
+            top_images_by_critical: serde_json::from_str(&top_images)?,
+        })
+    }
+
+    /// Get current severity totals of vulnerability reports per cluster,
+    /// ordered by cluster name. Suppressed findings are excluded.
+    pub async fn vulnerability_counts_by_cluster(&self) -> Result<Vec<(String, VulnSummary)>> {
+        let columns = self.severity_columns().await?;
+        let sql = format!(
+            r#"
+            SELECT
+                cluster,
+                SUM({critical}),
+                SUM({high}),
+                SUM({medium}),
+                SUM({low}),
+                SUM({unknown})
+            {from}
+            WHERE report_type = 'vulnerabilityreport'
+            GROUP BY cluster
+            ORDER BY cluster
+            "#,
+            critical = columns.count("critical"),
+            high = columns.count("high"),
+            medium = columns.count("medium"),
+            low = columns.count("low"),
+            unknown = columns.count("unknown"),
+            from = columns.from(),
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<String, _>(0),
+                    VulnSummary {
+                        critical: row.get::<i64, _>(1),
+                        high: row.get::<i64, _>(2),
+                        medium: row.get::<i64, _>(3),
+                        low: row.get::<i64, _>(4),
+                        unknown: row.get::<i64, _>(5),
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Get the images with the most critical and high vulnerabilities.
+    ///
+    /// Counts are summed over every vulnerability report of the image, so an
+    /// image running in several clusters or namespaces weighs accordingly.
+    /// Suppressed findings are excluded. Ordered by critical + high, then
+    /// critical, then image name.
+    pub async fn top_vulnerable_images(&self, limit: i64) -> Result<Vec<TopImage>> {
+        let columns = self.severity_columns().await?;
+        let sql = format!(
+            r#"
+            SELECT
+                image,
+                SUM({critical}),
+                SUM({high}),
+                COUNT(*) as report_count
+            {from}
+            WHERE report_type = 'vulnerabilityreport' AND image != ''
+            GROUP BY image
+            HAVING SUM({critical}) + SUM({high}) > 0
+            ORDER BY SUM({critical}) + SUM({high}) DESC, SUM({critical}) DESC, image ASC
+            LIMIT $1
+            "#,
+            critical = columns.count("critical"),
+            high = columns.count("high"),
+            from = columns.from(),
+        );
+        let rows = sqlx::query(&sql).bind(limit).fetch_all(&self.pool).await?;
+
+        let mut images: Vec<TopImage> = rows
+            .iter()
+            .map(|row| TopImage {
+                image: row.get::<String, _>(0),
+                critical: row.get::<i64, _>(1),
+                high: row.get::<i64, _>(2),
+                report_count: row.get::<i64, _>(3),
+                locations: Vec::new(),
+            })
+            .collect();
+        if images.is_empty() {
+            return Ok(images);
+        }
+
+        let placeholders = (1..=images.len())
+            .map(|i| format!("${}", i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            r#"
+            SELECT DISTINCT image, cluster, namespace
+            FROM reports
+            WHERE report_type = 'vulnerabilityreport' AND image IN ({})
+            ORDER BY cluster, namespace
+            "#,
+            placeholders
+        );
+        let mut query = sqlx::query(&sql);
+        for image in &images {
+            query = query.bind(&image.image);
+        }
+        let mut locations: HashMap<String, Vec<ImageLocation>> = HashMap::new();
+        for row in query.fetch_all(&self.pool).await? {
+            locations
+                .entry(row.get::<String, _>(0))
+                .or_default()
+                .push(ImageLocation {
+                    cluster: row.get::<String, _>(1),
+                    namespace: row.get::<String, _>(2),
+                });
+        }
+        for image in &mut images {
+            image.locations = locations.remove(&image.image).unwrap_or_default();
+        }
+
+        Ok(images)
+    }
+
+    /// Get vulnerability totals grouped by image registry.
+    ///
+    /// Ordered by critical count, then high count, then registry name, so the
+    /// registries introducing the most findings come first. Suppressed
+    /// findings are excluded.
+    pub async fn get_registry_stats(&self) -> Result<Vec<RegistryStat>> {
+        let columns = self.severity_columns().await?;
+        let sql = format!(
+            r#"
+            SELECT
+                registry,
+                COUNT(DISTINCT NULLIF(image, '')) as image_count,
+                COALESCE(SUM({critical}), 0) as critical_total,
+                COALESCE(SUM({high}), 0) as high_total
+            {from}
+            WHERE report_type = 'vulnerabilityreport'
+            GROUP BY registry
+            ORDER BY critical_total DESC, high_total DESC, registry ASC
+            "#,
+            critical = columns.count("critical"),
+            high = columns.count("high"),
+            from = columns.from(),
+        );
+        let rows = sqlx::query(&sql).fetch_all(&self.pool).await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| RegistryStat {
+                registry: row.get::<Option<String>, _>(0).unwrap_or_default(),
+                image_count: row.get::<i64, _>(1),
+                critical_count: row.get::<i64, _>(2),
+                high_count: row.get::<i64, _>(3),
+            })
+            .collect())
+    }
+
+    /// Get daily severity totals for a cluster over the last `days` days.
+    ///
+    /// Each day sums the most recent vulnerability report recorded on or
+    /// before that day for every resource, so a workload that was not
+    /// rescanned still counts with its last known result. A resource stops
+    /// counting once its latest row is a deletion tombstone. Days are
+    /// returned oldest first, including days without data.
+    pub async fn get_severity_trend(&self, cluster: &str, days: u32) -> Result<Vec<TrendPoint>> {
+        let days = days.max(1);
+
+        let rows = sqlx::query(
+            r#"
+            WITH RECURSIVE all_days AS (
+                SELECT date('now', 'localtime', '-' || ($2 - 1) || ' days') as day
+                UNION ALL
+                SELECT date(day, '+1 day')
+                FROM all_days
+                WHERE day < date('now', 'localtime')
+            ),
+            snapshots AS (
+                -- Latest history row per resource as of each day
+                SELECT
+                    d.day,
+                    h.critical_count,
+                    h.high_count,
+                    h.medium_count,
+                    h.low_count,
+                    h.unknown_count,
+                    h.deleted,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY d.day, h.namespace, h.name
+                        ORDER BY h.recorded_at DESC, h.id DESC
+                    ) as rn
+                FROM all_days d
+                JOIN report_history h
+                    ON date(h.recorded_at, 'localtime') <= d.day
+                WHERE h.cluster = $1
+                  AND h.report_type = 'vulnerabilityreport'
+            )
+            SELECT
+                d.day,
+                COALESCE(SUM(s.critical_count), 0) as critical,
+                COALESCE(SUM(s.high_count), 0) as high,
+                COALESCE(SUM(s.medium_count), 0) as medium,
+                COALESCE(SUM(s.low_count), 0) as low,
+                COALESCE(SUM(s.unknown_count), 0) as unknown
+            FROM all_days d
+            LEFT JOIN snapshots s ON s.day = d.day AND s.rn = 1 AND s.deleted = 0
+            GROUP BY d.day
+            ORDER BY d.day ASC
+            "#,
+        )
+        .bind(cluster)
+        .bind(i64::from(days))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TrendPoint {
+                date: row.get::<String, _>(0),
+                critical: row.get::<i64, _>(1),
+                high: row.get::<i64, _>(2),
+                medium: row.get::<i64, _>(3),
+                low: row.get::<i64, _>(4),
+                unknown: row.get::<i64, _>(5),
+            })
+            .collect())
+    }
+
     /// Get the date range of stored data from reports table
     pub async fn get_reports_data_range(&self) -> Result<(Option<String>, Option<String>)> {
         let row = sqlx::query(
@@ -379,4 +741,314 @@ mod tests {
         // Should have hourly granularity
         assert_eq!(trends.meta.granularity, "hourly");
     }
+
+    #[tokio::test]
+    async fn test_get_severity_trend() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        // Two scans of the same resource today: only the latest counts
+        let mut payload = create_test_payload("prod", "default", "app1", "vulnerabilityreport");
+        db.upsert_report(&payload).await.unwrap();
+        payload.data_json = json!({
+            "report": { "summary": { "criticalCount": 1, "highCount": 1 } }
+        })
+        .to_string();
+        db.upsert_report(&payload).await.unwrap();
+
+        // Other clusters and SBOM reports are ignored
+        db.upsert_report(&create_test_payload(
+            "staging",
+            "default",
+            "app1",
+            "vulnerabilityreport",
+        ))
+        .await
+        .unwrap();
+        db.upsert_report(&create_test_payload(
+            "prod",
+            "default",
+            "app1",
+            "sbomreport",
+        ))
+        .await
+        .unwrap();
+
+        // A scan recorded two days ago carries forward until it is replaced
+        sqlx::query(
+            r#"
+            INSERT INTO report_history (
+                cluster, namespace, name, report_type, critical_count, high_count,
+                data, received_at, recorded_at
+            ) VALUES ('prod', 'default', 'app2', 'vulnerabilityreport', 4, 0, '{}',
+                datetime('now', '-2 days'), datetime('now', '-2 days'))
+            "#,
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let trend = db.get_severity_trend("prod", 3).await.unwrap();
+        assert_eq!(trend.len(), 3);
+        assert_eq!(trend[0].critical, 4);
+        assert_eq!(trend[1].critical, 4);
+        assert_eq!(trend[2].critical, 5);
+        assert_eq!(trend[2].high, 1);
+        assert_eq!(trend[2].medium, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_severity_trend_stops_at_tombstone() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        // Scanned two days ago, deleted yesterday
+        sqlx::query(
+            r#"
+            INSERT INTO report_history (
+                cluster, namespace, name, report_type, critical_count,
+                data, received_at, recorded_at, deleted
+            ) VALUES
+                ('prod', 'default', 'gone', 'vulnerabilityreport', 4, '{}',
+                    datetime('now', '-2 days'), datetime('now', '-2 days'), 0),
+                ('prod', 'default', 'gone', 'vulnerabilityreport', 0, '',
+                    datetime('now', '-1 days'), datetime('now', '-1 days'), 1)
+            "#,
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let trend = db.get_severity_trend("prod", 3).await.unwrap();
+        let critical: Vec<i64> = trend.iter().map(|p| p.critical).collect();
+        assert_eq!(critical, vec![4, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_get_severity_trend_empty_cluster() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let trend = db.get_severity_trend("unknown", 7).await.unwrap();
+        assert_eq!(trend.len(), 7);
+        assert!(trend.iter().all(|p| p.critical == 0 && p.high == 0));
+
+        // Zero days still returns today
+        assert_eq!(db.get_severity_trend("unknown", 0).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_top_vulnerable_images() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let report = |cluster: &str, namespace: &str, name: &str, repo: &str, c: i64, h: i64| {
+            let mut payload = create_test_payload(cluster, namespace, name, "vulnerabilityreport");
+            payload.data_json = json!({
+                "report": {
+                    "artifact": { "repository": repo, "tag": "1.0" },
+                    "summary": { "criticalCount": c, "highCount": h, "mediumCount": 9 }
+                }
+            })
+            .to_string();
+            payload
+        };
+        for payload in [
+            report("prod", "web", "app1", "nginx", 2, 3),
+            report("staging", "web", "app1", "nginx", 2, 3),
+            report("prod", "db", "app2", "postgres", 4, 0),
+            report("prod", "cache", "app3", "redis", 1, 3),
+            report("prod", "tools", "app4", "busybox", 0, 0),
+        ] {
+            db.upsert_report(&payload).await.unwrap();
+        }
+        // SBOM reports never count toward vulnerability totals
+        db.upsert_report(&create_test_payload("prod", "web", "app1", "sbomreport"))
+            .await
+            .unwrap();
+
+        let top = db.top_vulnerable_images(20).await.unwrap();
+        let images: Vec<&str> = top.iter().map(|t| t.image.as_str()).collect();
+        // nginx sums both clusters; postgres beats redis on critical at 4 total
+        assert_eq!(images, vec!["nginx:1.0", "postgres:1.0", "redis:1.0"]);
+
+        assert_eq!(top[0].critical, 4);
+        assert_eq!(top[0].high, 6);
+        assert_eq!(top[0].report_count, 2);
+        assert_eq!(
+            top[0].locations,
+            vec![
+                ImageLocation {
+                    cluster: "prod".to_string(),
+                    namespace: "web".to_string(),
+                },
+                ImageLocation {
+                    cluster: "staging".to_string(),
+                    namespace: "web".to_string(),
+                },
+            ]
+        );
+        assert_eq!(top[1].locations.len(), 1);
+
+        let top = db.top_vulnerable_images(1).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].image, "nginx:1.0");
+    }
+
+    #[tokio::test]
+    async fn test_top_vulnerable_images_empty() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        assert!(db.top_vulnerable_images(20).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_registry_stats() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let report = |name: &str, registry: &str, repo: &str, c: i64, h: i64| {
+            let mut payload = create_test_payload("prod", "web", name, "vulnerabilityreport");
+            payload.data_json = json!({
+                "report": {
+                    "registry": { "server": registry },
+                    "artifact": { "repository": repo, "tag": "1.0" },
+                    "summary": { "criticalCount": c, "highCount": h }
+                }
+            })
+            .to_string();
+            payload
+        };
+        for payload in [
+            report("app1", "docker.io", "nginx", 2, 3),
+            report("app2", "docker.io", "nginx", 2, 3),
+            report("app3", "docker.io", "redis", 1, 0),
+            report(
+                "app4",
+                "123456789012.dkr.ecr.ap-northeast-2.amazonaws.com",
+                "api",
+                0,
+                7,
+            ),
+            report("app5", "ghcr.io", "tool", 0, 7),
+        ] {
+            db.upsert_report(&payload).await.unwrap();
+        }
+        // SBOM reports never count toward vulnerability totals
+        db.upsert_report(&create_test_payload("prod", "web", "app1", "sbomreport"))
+            .await
+            .unwrap();
+
+        let stats = db.get_registry_stats().await.unwrap();
+        let stat = |registry: &str, image_count, critical_count, high_count| RegistryStat {
+            registry: registry.to_string(),
+            image_count,
+            critical_count,
+            high_count,
+        };
+        assert_eq!(
+            stats,
+            vec![
+                stat("docker.io", 2, 5, 6),
+                stat("123456789012.dkr.ecr.ap-northeast-2.amazonaws.com", 1, 0, 7),
+                stat("ghcr.io", 1, 0, 7),
+            ]
+        );
+
+        let empty = Database::new(":memory:").await.unwrap();
+        assert!(empty.get_registry_stats().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_namespace_summary() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let report = |cluster: &str, name: &str, repo: &str, critical: i64| {
+            let mut payload = create_test_payload(cluster, "web", name, "vulnerabilityreport");
+            payload.data_json = json!({
+                "report": {
+                    "artifact": { "repository": repo, "tag": "1.0" },
+                    "summary": {
+                        "criticalCount": critical,
+                        "highCount": 2,
+                        "mediumCount": 3,
+                        "lowCount": 4,
+                        "unknownCount": 1
+                    }
+                }
+            })
+            .to_string();
+            payload
+        };
+        for payload in [
+            report("prod", "app1", "nginx", 1),
+            report("prod", "app2", "postgres", 3),
+            report("prod", "app3", "busybox", 0),
+            // Other clusters and namespaces are excluded
+            report("staging", "app1", "nginx", 9),
+            create_test_payload("prod", "db", "app4", "vulnerabilityreport"),
+        ] {
+            db.upsert_report(&payload).await.unwrap();
+        }
+
+        let summary = db.get_namespace_summary("prod", "web").await.unwrap();
+        assert_eq!(summary.report_count, 3);
+        assert_eq!(summary.summary.critical, 4);
+        assert_eq!(summary.summary.high, 6);
+        assert_eq!(summary.summary.medium, 9);
+        assert_eq!(summary.summary.low, 12);
+        assert_eq!(summary.summary.unknown, 3);
+        // Images without criticals are not listed
+        assert_eq!(
+            summary.top_images_by_critical,
+            vec!["postgres:1.0", "nginx:1.0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_namespace_summary_empty() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let summary = db.get_namespace_summary("prod", "missing").await.unwrap();
+        assert_eq!(summary.report_count, 0);
+        assert_eq!(summary.summary.critical, 0);
+        assert!(summary.top_images_by_critical.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_vulnerability_counts_by_cluster() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        for (cluster, name, report_type) in [
+            ("prod", "app1", "vulnerabilityreport"),
+            ("prod", "app2", "vulnerabilityreport"),
+            ("dev", "app1", "vulnerabilityreport"),
+            ("dev", "app1", "sbomreport"),
+        ] {
+            db.upsert_report(&create_test_payload(cluster, "default", name, report_type))
+                .await
+                .unwrap();
+        }
+
+        let counts = db.vulnerability_counts_by_cluster().await.unwrap();
+        let clusters: Vec<&str> = counts.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(clusters, vec!["dev", "prod"]);
+        assert_eq!(counts[0].1.critical, 2);
+        assert_eq!(counts[1].1.critical, 4);
+        assert_eq!(counts[1].1.high, 10);
+        assert_eq!(counts[1].1.unknown, 2);
+    }
 }
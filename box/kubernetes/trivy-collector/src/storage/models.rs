@@ -1,5 +1,6 @@
 //! Data models for the storage layer
 
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use utoipa::ToSchema;
 
 /// Query parameters for filtering reports
@@ -12,8 +13,49 @@ pub struct QueryParams {
     pub image: Option<String>,
     pub cve: Option<String>,
     pub component: Option<String>,
+    /// Inclusive lower bound on `components_count`
+    pub min_components: Option<i64>,
+    /// Inclusive upper bound on `components_count`
+    pub max_components: Option<i64>,
     pub limit: Option<i64>,
+    /// Deprecated in favour of `cursor`; ignored when a cursor is set
     pub offset: Option<i64>,
+    /// Opaque keyset cursor. `Some("")` requests the first page in cursor
+    /// order (`id DESC`); otherwise the `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+}
+
+/// Default page size of `Database::query_reports`
+pub const DEFAULT_QUERY_LIMIT: i64 = 1000;
+
+impl QueryParams {
+    /// Cursor for the page after `items`, when paginating by cursor.
+    ///
+    /// A full page may still be the last one; the following request then
+    /// returns no items and no cursor.
+    pub fn next_cursor(&self, items: &[ReportMeta]) -> Option<String> {
+        self.cursor.as_ref()?;
+        let limit = self.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+        if (items.len() as i64) < limit {
+            return None;
+        }
+        items.last().map(|r| encode_cursor(r.id))
+    }
+}
+
+/// Encode a report id as an opaque pagination cursor
+pub fn encode_cursor(id: i64) -> String {
+    URL_SAFE_NO_PAD.encode(id.to_string())
+}
+
+/// Decode a pagination cursor back into a report id
+pub fn decode_cursor(cursor: &str) -> Option<i64> {
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    std::str::from_utf8(&bytes)
+        .ok()?
+        .parse::<i64>()
+        .ok()
+        .filter(|id| *id > 0)
 }
 
 /// Summary of vulnerability counts
@@ -78,6 +120,8 @@ pub struct FullReport {
     /// Full report data as raw JSON string (parsed lazily on serialization)
     #[schema(value_type = serde_json::Value)]
     pub data_json: String,
+    /// CVE IDs covered by an active suppression for this report's image
+    pub suppressed_cves: Vec<String>,
 }
 
 impl serde::Serialize for FullReport {
@@ -86,12 +130,13 @@ impl serde::Serialize for FullReport {
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("FullReport", 2)?;
+        let mut state = serializer.serialize_struct("FullReport", 3)?;
         state.serialize_field("meta", &self.meta)?;
         // Parse JSON string to Value only during serialization (lazy loading)
         let data: serde_json::Value =
             serde_json::from_str(&self.data_json).unwrap_or(serde_json::Value::Null);
         state.serialize_field("data", &data)?;
+        state.serialize_field("suppressed_cves", &self.suppressed_cves)?;
         state.end()
     }
 }
@@ -202,6 +247,16 @@ pub struct SbomComponentMatch {
     pub updated_at: String,
 }
 
+/// Report containing a specific CVE, with the matching vulnerability entries
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+pub struct CveReportMatch {
+    /// Report metadata
+    pub report: ReportMeta,
+    /// Matching entries from `report.vulnerabilities` (one per affected package)
+    #[schema(value_type = Vec<Object>)]
+    pub vulnerabilities: Vec<serde_json::Value>,
+}
+
 /// Vulnerability search result (one row per matching vulnerability)
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
 pub struct VulnSearchResult {
@@ -323,11 +378,13 @@ mod tests {
         let report = FullReport {
             meta: sample_report_meta(),
             data_json: r#"{"key": "value"}"#.to_string(),
+            suppressed_cves: vec!["CVE-2024-1234".to_string()],
         };
 
         let json = serde_json::to_value(&report).expect("Failed to serialize");
         assert_eq!(json["data"]["key"], "value");
         assert_eq!(json["meta"]["cluster"], "prod");
+        assert_eq!(json["suppressed_cves"][0], "CVE-2024-1234");
     }
 
     #[test]
@@ -335,6 +392,7 @@ mod tests {
         let report = FullReport {
             meta: sample_report_meta(),
             data_json: "not valid json".to_string(),
+            suppressed_cves: Vec::new(),
         };
 
         // Should not panic — invalid JSON falls back to null
@@ -347,6 +405,7 @@ mod tests {
         let report = FullReport {
             meta: sample_report_meta(),
             data_json: String::new(),
+            suppressed_cves: Vec::new(),
         };
 
         let json = serde_json::to_value(&report).expect("Failed to serialize");
@@ -369,4 +428,37 @@ mod tests {
         assert_eq!(json["low"], 4);
         assert_eq!(json["unknown"], 5);
     }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = encode_cursor(4242);
+        assert_eq!(decode_cursor(&cursor), Some(4242));
+        assert!(decode_cursor("not-a-cursor!").is_none());
+        assert!(decode_cursor(&encode_cursor(0)).is_none());
+        assert!(decode_cursor(&URL_SAFE_NO_PAD.encode("abc")).is_none());
+    }
+
+    #[test]
+    fn test_next_cursor() {
+        let items = vec![sample_report_meta()];
+        let offset_mode = QueryParams {
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert!(offset_mode.next_cursor(&items).is_none());
+
+        let cursor_mode = QueryParams {
+            limit: Some(1),
+            cursor: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(cursor_mode.next_cursor(&items), Some(encode_cursor(1)));
+
+        // Short page: nothing after it
+        let short_page = QueryParams {
+            limit: Some(2),
+            ..cursor_mode
+        };
+        assert!(short_page.next_cursor(&items).is_none());
+    }
 }
@@ -0,0 +1,299 @@
+//! Bulk import and export of report notes
+//!
+//! The export format is a JSON array of [`NoteEntry`] and is accepted back
+//! as-is by [`Database::import_notes`], so notes can be round-tripped through
+//! a review spreadsheet.
+
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use thiserror::Error;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use super::audit::{AuditCaller, insert_audit, report_target};
+use super::database::Database;
+use super::operations::set_notes;
+
+const REPORT_TYPES: [&str; 2] = ["vulnerabilityreport", "sbomreport"];
+
+/// Notes of one report, identified by its natural key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct NoteEntry {
+    #[schema(example = "prod-cluster")]
+    pub cluster: String,
+    #[schema(example = "default")]
+    pub namespace: String,
+    #[schema(example = "replicaset-nginx-6d4cf56db6-nginx")]
+    pub name: String,
+    #[schema(example = "vulnerabilityreport")]
+    pub report_type: String,
+    #[schema(example = "Reviewed, patch scheduled")]
+    pub notes: String,
+}
+
+#[derive(Debug, Error)]
+pub enum NotesImportError {
+    /// An entry failed validation; nothing was imported
+    #[error("entry {index}: {reason}")]
+    Invalid { index: usize, reason: String },
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+impl Database {
+    /// Every report with non-empty notes, ordered by report key
+    pub async fn export_notes(&self) -> anyhow::Result<Vec<NoteEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT cluster, namespace, name, report_type, notes
+            FROM reports
+            WHERE notes IS NOT NULL AND notes != ''
+            ORDER BY cluster, namespace, name, report_type
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| NoteEntry {
+                cluster: row.get(0),
+                namespace: row.get(1),
+                name: row.get(2),
+                report_type: row.get(3),
+                notes: row.get(4),
+            })
+            .collect())
+    }
+
+    /// Write the notes of every entry in a single transaction.
+    ///
+    /// All entries are validated first, and an entry whose report does not
+    /// exist rolls back the whole import. With `audit`, an `import_notes`
+    /// audit record per entry is part of the same transaction. Returns the
+    /// number of reports updated.
+    pub async fn import_notes(
+        &self,
+        entries: &[NoteEntry],
+        audit: Option<&AuditCaller>,
+    ) -> Result<usize, NotesImportError> {
+        for (index, entry) in entries.iter().enumerate() {
+            validate_entry(entry).map_err(|reason| NotesImportError::Invalid { index, reason })?;
+        }
+
+        // Every statement is a write, so concurrent imports queue on the
+        // SQLite write lock (bounded by busy_timeout) instead of deadlocking
+        // on a read-to-write lock upgrade.
+        let mut tx = self.pool.begin().await?;
+        for (index, entry) in entries.iter().enumerate() {
+            let affected = set_notes(
+                &mut *tx,
+                &entry.cluster,
+                &entry.namespace,
+                &entry.name,
+                &entry.report_type,
+                &entry.notes,
+            )
+            .await?;
+            if affected == 0 {
+                return Err(NotesImportError::Invalid {
+                    index,
+                    reason: format!(
+                        "report not found: {}/{}/{}/{}",
+                        entry.cluster, entry.report_type, entry.namespace, entry.name
+                    ),
+                });
+            }
+            if let Some(caller) = audit {
+                let target = report_target(
+                    &entry.cluster,
+                    &entry.report_type,
+                    &entry.namespace,
+                    &entry.name,
+                );
+                insert_audit(&mut *tx, "import_notes", &target, caller).await?;
+            }
+        }
+        tx.commit().await?;
+
+        debug!(count = entries.len(), "Report notes imported");
+        Ok(entries.len())
+    }
+}
+
+fn validate_entry(entry: &NoteEntry) -> Result<(), String> {
+    for (field, value) in [
+        ("cluster", &entry.cluster),
+        ("namespace", &entry.namespace),
+        ("name", &entry.name),
+    ] {
+        if value.trim().is_empty() {
+            return Err(format!("{field} is required"));
+        }
+    }
+    if !REPORT_TYPES.contains(&entry.report_type.as_str()) {
+        return Err(format!(
+            "report_type must be one of {}",
+            REPORT_TYPES.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::types::ReportPayload;
+
+    fn payload(cluster: &str, name: &str) -> ReportPayload {
+        ReportPayload {
+            cluster: cluster.to_string(),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            report_type: "vulnerabilityreport".to_string(),
+            data_json: "{}".to_string(),
+            received_at: chrono::Utc::now(),
+        }
+    }
+
+    fn entry(cluster: &str, name: &str, notes: &str) -> NoteEntry {
+        NoteEntry {
+            cluster: cluster.to_string(),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            report_type: "vulnerabilityreport".to_string(),
+            notes: notes.to_string(),
+        }
+    }
+
+    async fn seeded_db(path: &str) -> Database {
+        let db = Database::new(path)
+            .await
+            .expect("Failed to create database");
+        for (cluster, name) in [("prod", "app1"), ("prod", "app2"), ("dev", "app1")] {
+            db.upsert_report(&payload(cluster, name)).await.unwrap();
+        }
+        db
+    }
+
+    #[tokio::test]
+    async fn test_import_then_export_round_trip() {
+        let db = seeded_db(":memory:").await;
+
+        let imported = db
+            .import_notes(
+                &[
+                    entry("prod", "app2", "patch scheduled"),
+                    entry("dev", "app1", "accepted risk"),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(imported, 2);
+
+        // Reports without notes are not exported
+        let exported = db.export_notes().await.unwrap();
+        assert_eq!(
+            exported,
+            vec![
+                entry("dev", "app1", "accepted risk"),
+                entry("prod", "app2", "patch scheduled"),
+            ]
+        );
+
+        // The export is accepted back unchanged
+        assert_eq!(db.import_notes(&exported, None).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_rolls_back_on_missing_report() {
+        let db = seeded_db(":memory:").await;
+
+        let caller = AuditCaller {
+            identity: "alice".to_string(),
+            claimed: None,
+        };
+        let err = db
+            .import_notes(
+                &[
+                    entry("prod", "app1", "should not persist"),
+                    entry("prod", "missing", "no such report"),
+                ],
+                Some(&caller),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NotesImportError::Invalid { index: 1, .. }));
+        assert!(db.export_notes().await.unwrap().is_empty());
+        // The audit record of the first entry is rolled back with it
+        assert!(db.list_audit_log(None, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_invalid_entries() {
+        let db = seeded_db(":memory:").await;
+
+        let mut bad_type = entry("prod", "app1", "note");
+        bad_type.report_type = "configauditreport".to_string();
+        let err = db
+            .import_notes(&[entry("prod", "app2", "note"), bad_type], None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NotesImportError::Invalid { index: 1, .. }));
+
+        let err = db
+            .import_notes(&[entry(" ", "app1", "note")], None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cluster is required"));
+        assert!(db.export_notes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_imports_do_not_deadlock() {
+        let path =
+            std::env::temp_dir().join(format!("trivy-collector-notes-{}.db", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let cleanup = || {
+            for suffix in ["", "-wal", "-shm"] {
+                let _ = std::fs::remove_file(format!("{path}{suffix}"));
+            }
+        };
+        cleanup();
+        let db = seeded_db(&path).await;
+
+        let imports = (0..8).map(|i| {
+            let db = db.clone();
+            tokio::spawn(async move {
+                let notes = format!("import {i}");
+                db.import_notes(
+                    &[
+                        entry("prod", "app1", &notes),
+                        entry("prod", "app2", &notes),
+                        entry("dev", "app1", &notes),
+                    ],
+                    None,
+                )
+                .await
+            })
+        });
+        let results = tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            futures::future::join_all(imports),
+        )
+        .await
+        .expect("concurrent imports timed out");
+        for result in results {
+            assert_eq!(result.unwrap().unwrap(), 3);
+        }
+
+        // Each import is atomic: all three reports carry the same note
+        let exported = db.export_notes().await.unwrap();
+        assert_eq!(exported.len(), 3);
+        assert!(exported.iter().all(|e| e.notes == exported[0].notes));
+
+        drop(db);
+        cleanup();
+    }
+}
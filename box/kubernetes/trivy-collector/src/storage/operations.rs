@@ -8,14 +8,17 @@ use tracing::debug;
 
 use crate::collector::types::ReportPayload;
 
+use super::audit::{AuditCaller, insert_audit, report_target};
 use super::database::Database;
 use super::extractors::{
     extract_components_count_from_str, extract_metadata_from_str, extract_vuln_summary_from_str,
 };
 use super::models::{
-    ClusterInfo, ComponentSearchResult, FullReport, QueryParams, ReportMeta, SbomComponentMatch,
-    Stats, VulnSearchResult, VulnSummary,
+    ClusterInfo, ComponentSearchResult, CveReportMatch, DEFAULT_QUERY_LIMIT, FullReport,
+    QueryParams, ReportMeta, SbomComponentMatch, Stats, VulnSearchResult, VulnSummary,
+    decode_cursor,
 };
+use super::suppressions::SeverityColumns;
 
 impl Database {
     /// Insert or update a report
@@ -29,6 +32,10 @@ impl Database {
         let received_at = payload.received_at.to_rfc3339();
         let updated_at = chrono::Utc::now().to_rfc3339();
 
+        // The latest result and its history row are written together so the
+        // trend never disagrees with the current report.
+        let mut tx = self.pool.begin().await?;
+
         sqlx::query(
             r#"
             INSERT INTO reports (
@@ -66,9 +73,39 @@ impl Database {
         .bind(&payload.data_json)
         .bind(&received_at)
         .bind(&updated_at)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        sqlx::query(
+            r#"
+            INSERT INTO report_history (
+                cluster, namespace, name, report_type, app, image, registry,
+                critical_count, high_count, medium_count, low_count, unknown_count,
+                components_count, data, received_at, recorded_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            "#,
+        )
+        .bind(&payload.cluster)
+        .bind(&payload.namespace)
+        .bind(&payload.name)
+        .bind(&payload.report_type)
+        .bind(&app)
+        .bind(&image)
+        .bind(&registry)
+        .bind(critical)
+        .bind(high)
+        .bind(medium)
+        .bind(low)
+        .bind(unknown)
+        .bind(components_count)
+        .bind(&payload.data_json)
+        .bind(&received_at)
+        .bind(&updated_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
         debug!(
             cluster = %payload.cluster,
             namespace = %payload.namespace,
@@ -82,12 +119,20 @@ impl Database {
 
     /// Delete every report for a cluster. Used when a cluster registration
     /// is removed so its data stops showing up in Dashboard / Vulnerabilities
-    /// / SBOM views.
+    /// / SBOM views. The cluster's report history goes with it, in the same
+    /// transaction.
     pub async fn delete_reports_for_cluster(&self, cluster: &str) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM report_history WHERE cluster = $1")
+            .bind(cluster)
+            .execute(&mut *tx)
+            .await?;
         let result = sqlx::query("DELETE FROM reports WHERE cluster = $1")
             .bind(cluster)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
+
         let affected = result.rows_affected();
         debug!(
             cluster = %cluster,
@@ -97,14 +142,20 @@ impl Database {
         Ok(affected)
     }
 
-    /// Delete a report
+    /// Delete a report.
+    ///
+    /// Writes a tombstone to `report_history` in the same transaction, so
+    /// the severity trend stops carrying the resource's last scan forward.
+    /// With `audit`, a `delete_report` audit record is written there too.
     pub async fn delete_report(
         &self,
         cluster: &str,
         namespace: &str,
         name: &str,
         report_type: &str,
+        audit: Option<&AuditCaller>,
     ) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query(
             "DELETE FROM reports WHERE cluster = $1 AND namespace = $2 AND name = $3 AND report_type = $4",
         )
@@ -112,10 +163,18 @@ impl Database {
         .bind(namespace)
         .bind(name)
         .bind(report_type)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
         let affected = result.rows_affected();
+        if affected > 0 {
+            insert_tombstone(&mut *tx, cluster, namespace, name, report_type).await?;
+            if let Some(caller) = audit {
+                let target = report_target(cluster, report_type, namespace, name);
+                insert_audit(&mut *tx, "delete_report", &target, caller).await?;
+            }
+        }
+        tx.commit().await?;
 
         debug!(
             cluster = %cluster,
@@ -129,7 +188,76 @@ impl Database {
         Ok(affected > 0)
     }
 
-    /// Update notes for a report
+    /// Delete reports whose `updated_at` is more than `days` days old.
+    ///
+    /// Removes reports of workloads that disappeared without the watcher
+    /// seeing a delete event. Each pruned report gets a tombstone in
+    /// `report_history`, in the same transaction, like `delete_report`.
+    /// Returns the number of deleted reports.
+    pub async fn prune_old_reports(&self, days: u32) -> Result<u64> {
+        let now = chrono::Utc::now();
+        let cutoff = (now - chrono::Duration::days(days as i64)).to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO report_history (
+                cluster, namespace, name, report_type, data, received_at, recorded_at, deleted
+            )
+            SELECT cluster, namespace, name, report_type, '', $2, $2, 1
+            FROM reports
+            WHERE updated_at < $1
+            "#,
+        )
+        .bind(&cutoff)
+        .bind(now.to_rfc3339())
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query("DELETE FROM reports WHERE updated_at < $1")
+            .bind(&cutoff)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        let deleted = result.rows_affected();
+        debug!(days, deleted, "Old reports pruned");
+        Ok(deleted)
+    }
+
+    /// Delete `report_history` snapshots recorded more than `days` days ago.
+    ///
+    /// The newest snapshot of each resource is kept even when it is older
+    /// than the cutoff, since the severity trend carries it forward until
+    /// the resource is rescanned or deleted. A tombstone past the cutoff is
+    /// removed together with everything before it. Returns the number of
+    /// deleted snapshots.
+    pub async fn prune_report_history(&self, days: u32) -> Result<u64> {
+        let cutoff = (chrono::Utc::now() - chrono::Duration::days(days as i64)).to_rfc3339();
+        let result = sqlx::query(
+            r#"
+            DELETE FROM report_history
+            WHERE recorded_at < $1
+              AND (
+                  deleted = 1
+                  OR EXISTS (
+                      SELECT 1 FROM report_history newer
+                      WHERE newer.cluster = report_history.cluster
+                        AND newer.namespace = report_history.namespace
+                        AND newer.name = report_history.name
+                        AND newer.report_type = report_history.report_type
+                        AND newer.id > report_history.id
+                  )
+              )
+            "#,
+        )
+        .bind(&cutoff)
+        .execute(&self.pool)
+        .await?;
+        let deleted = result.rows_affected();
+        debug!(days, deleted, "Old report history pruned");
+        Ok(deleted)
+    }
+
+    /// Update notes for a report. With `audit`, an `update_notes` audit
+    /// record is written in the same transaction.
     pub async fn update_notes(
         &self,
         cluster: &str,
@@ -137,50 +265,17 @@ impl Database {
         name: &str,
         report_type: &str,
         notes: &str,
+        audit: Option<&AuditCaller>,
     ) -> Result<bool> {
-        let now = chrono::Utc::now().to_rfc3339();
-
-        // Check if notes_created_at already exists (to determine if this is create or update)
-        let existing_created_at: Option<String> = sqlx::query(
-            "SELECT notes_created_at FROM reports WHERE cluster = $1 AND namespace = $2 AND name = $3 AND report_type = $4",
-        )
-        .bind(cluster)
-        .bind(namespace)
-        .bind(name)
-        .bind(report_type)
-        .fetch_optional(&self.pool)
-        .await?
-        .and_then(|row| row.get::<Option<String>, _>(0));
-
-        let affected = if existing_created_at.is_none() {
-            // First time adding notes - set both created_at and updated_at
-            sqlx::query(
-                "UPDATE reports SET notes = $1, notes_created_at = $2, notes_updated_at = $2 WHERE cluster = $3 AND namespace = $4 AND name = $5 AND report_type = $6",
-            )
-            .bind(notes)
-            .bind(&now)
-            .bind(cluster)
-            .bind(namespace)
-            .bind(name)
-            .bind(report_type)
-            .execute(&self.pool)
-            .await?
-            .rows_affected()
-        } else {
-            // Updating existing notes - only update updated_at
-            sqlx::query(
-                "UPDATE reports SET notes = $1, notes_updated_at = $2 WHERE cluster = $3 AND namespace = $4 AND name = $5 AND report_type = $6",
-            )
-            .bind(notes)
-            .bind(&now)
-            .bind(cluster)
-            .bind(namespace)
-            .bind(name)
-            .bind(report_type)
-            .execute(&self.pool)
-            .await?
-            .rows_affected()
-        };
+        let mut tx = self.pool.begin().await?;
+        let affected = set_notes(&mut *tx, cluster, namespace, name, report_type, notes).await?;
+        if affected > 0
+            && let Some(caller) = audit
+        {
+            let target = report_target(cluster, report_type, namespace, name);
+            insert_audit(&mut *tx, "update_notes", &target, caller).await?;
+        }
+        tx.commit().await?;
 
         debug!(
             cluster = %cluster,
@@ -195,14 +290,33 @@ impl Database {
     }
 
     /// Query reports with filters
+    ///
+    /// Without a cursor, results are ordered by `updated_at DESC` and paged
+    /// with `limit`/`offset`. With a cursor they are ordered by `id DESC` and
+    /// start after the cursor's id, which stays stable while reports are
+    /// being inserted; use [`QueryParams::next_cursor`] for the next page.
+    /// `total` always counts every row matching the filters.
+    ///
+    /// For vulnerability reports, findings covered by an active suppression
+    /// are excluded from the severity counts and severity filters.
     pub async fn query_reports(
         &self,
         report_type: &str,
         params: &QueryParams,
     ) -> Result<(Vec<ReportMeta>, i64)> {
+        // Active suppressions are subtracted from the severity counts; the
+        // join is skipped entirely when there are none.
+        let columns = if report_type == "vulnerabilityreport" {
+            self.severity_columns().await?
+        } else {
+            SeverityColumns::default()
+        };
+        let from = columns.from();
+        let severity = |column: &str| columns.count(column);
+
         // COUNT query
         let mut count_builder: QueryBuilder<Sqlite> =
-            QueryBuilder::new("SELECT COUNT(*) FROM reports WHERE report_type = ");
+            QueryBuilder::new(format!("SELECT COUNT(*) {from} WHERE report_type = "));
         count_builder.push_bind(report_type.to_string());
 
         if let Some(cluster) = &params.cluster {
@@ -221,6 +335,11 @@ impl Database {
             count_builder.push(" AND image LIKE ");
             count_builder.push_bind(format!("%{}%", image));
         }
+        if let Some(cve) = &params.cve {
+            count_builder.push(" AND data LIKE '%' || ");
+            count_builder.push_bind(cve.clone());
+            count_builder.push(" || '%'");
+        }
         if report_type == "sbomreport"
             && let Some(component) = &params.component
         {
@@ -230,17 +349,22 @@ impl Database {
             count_builder.push_bind(format!("%{}%", component));
             count_builder.push(")");
         }
+        if let Some(min) = params.min_components {
+            count_builder.push(" AND components_count >= ");
+            count_builder.push_bind(min);
+        }
+        if let Some(max) = params.max_components {
+            count_builder.push(" AND components_count <= ");
+            count_builder.push_bind(max);
+        }
         if report_type == "vulnerabilityreport"
             && let Some(severities) = &params.severity
         {
             let mut severity_conditions = Vec::new();
-            for severity in severities {
-                match severity.to_lowercase().as_str() {
-                    "critical" => severity_conditions.push("critical_count > 0"),
-                    "high" => severity_conditions.push("high_count > 0"),
-                    "medium" => severity_conditions.push("medium_count > 0"),
-                    "low" => severity_conditions.push("low_count > 0"),
-                    _ => {}
+            for level in severities {
+                let level = level.to_lowercase();
+                if matches!(level.as_str(), "critical" | "high" | "medium" | "low") {
+                    severity_conditions.push(format!("{} > 0", severity(&level)));
                 }
             }
             if !severity_conditions.is_empty() {
@@ -251,12 +375,17 @@ impl Database {
         let (total,): (i64,) = count_builder.build_query_as().fetch_one(&self.pool).await?;
 
         // Data query with the same WHERE conditions
-        let mut data_builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+        let mut data_builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
             r#"SELECT id, cluster, namespace, name, app, image, report_type,
-                   critical_count, high_count, medium_count, low_count, unknown_count,
+                   {}, {}, {}, {}, {},
                    components_count, received_at, updated_at, notes, notes_created_at, notes_updated_at
-            FROM reports WHERE report_type = "#,
-        );
+            {from} WHERE report_type = "#,
+            severity("critical"),
+            severity("high"),
+            severity("medium"),
+            severity("low"),
+            severity("unknown"),
+        ));
         data_builder.push_bind(report_type.to_string());
 
         if let Some(cluster) = &params.cluster {
@@ -275,6 +404,11 @@ impl Database {
             data_builder.push(" AND image LIKE ");
             data_builder.push_bind(format!("%{}%", image));
         }
+        if let Some(cve) = &params.cve {
+            data_builder.push(" AND data LIKE '%' || ");
+            data_builder.push_bind(cve.clone());
+            data_builder.push(" || '%'");
+        }
         if report_type == "sbomreport"
             && let Some(component) = &params.component
         {
@@ -284,17 +418,22 @@ impl Database {
             data_builder.push_bind(format!("%{}%", component));
             data_builder.push(")");
         }
+        if let Some(min) = params.min_components {
+            data_builder.push(" AND components_count >= ");
+            data_builder.push_bind(min);
+        }
+        if let Some(max) = params.max_components {
+            data_builder.push(" AND components_count <= ");
+            data_builder.push_bind(max);
+        }
         if report_type == "vulnerabilityreport"
             && let Some(severities) = &params.severity
         {
             let mut severity_conditions = Vec::new();
-            for severity in severities {
-                match severity.to_lowercase().as_str() {
-                    "critical" => severity_conditions.push("critical_count > 0"),
-                    "high" => severity_conditions.push("high_count > 0"),
-                    "medium" => severity_conditions.push("medium_count > 0"),
-                    "low" => severity_conditions.push("low_count > 0"),
-                    _ => {}
+            for level in severities {
+                let level = level.to_lowercase();
+                if matches!(level.as_str(), "critical" | "high" | "medium" | "low") {
+                    severity_conditions.push(format!("{} > 0", severity(&level)));
                 }
             }
             if !severity_conditions.is_empty() {
@@ -302,15 +441,24 @@ impl Database {
             }
         }
 
-        data_builder.push(" ORDER BY updated_at DESC");
-
-        let limit = params.limit.unwrap_or(1000);
-        data_builder.push(" LIMIT ");
-        data_builder.push_bind(limit);
+        let limit = params.limit.unwrap_or(DEFAULT_QUERY_LIMIT);
+        if let Some(cursor) = &params.cursor {
+            if !cursor.is_empty() {
+                let cursor_id = decode_cursor(cursor)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid pagination cursor"))?;
+                data_builder.push(" AND id < ");
+                data_builder.push_bind(cursor_id);
+            }
+            data_builder.push(" ORDER BY id DESC LIMIT ");
+            data_builder.push_bind(limit);
+        } else {
+            data_builder.push(" ORDER BY updated_at DESC LIMIT ");
+            data_builder.push_bind(limit);
 
-        if let Some(offset) = params.offset {
-            data_builder.push(" OFFSET ");
-            data_builder.push_bind(offset);
+            if let Some(offset) = params.offset {
+                data_builder.push(" OFFSET ");
+                data_builder.push_bind(offset);
+            }
         }
 
         let rows = data_builder.build().fetch_all(&self.pool).await?;
@@ -372,6 +520,12 @@ impl Database {
             Some(row) => {
                 // Store raw JSON string - parsing deferred to serialization time (lazy loading)
                 let data_json: String = row.get::<String, _>(15);
+                let image = row.get::<String, _>(5);
+                let suppressed_cves = if report_type == "vulnerabilityreport" {
+                    self.suppressed_cves_for_image(&image).await?
+                } else {
+                    Vec::new()
+                };
 
                 Ok(Some(FullReport {
                     meta: ReportMeta {
@@ -380,7 +534,7 @@ impl Database {
                         namespace: row.get::<String, _>(2),
                         name: row.get::<String, _>(3),
                         app: row.get::<String, _>(4),
-                        image: row.get::<String, _>(5),
+                        image,
                         report_type: row.get::<String, _>(6),
                         summary: Some(VulnSummary {
                             critical: row.get::<i64, _>(7),
@@ -397,6 +551,7 @@ impl Database {
                         notes_updated_at: row.get::<Option<String>, _>(18),
                     },
                     data_json,
+                    suppressed_cves,
                 }))
             }
             None => Ok(None),
@@ -475,22 +630,29 @@ impl Database {
             .await?;
         let sqlite_version: String = version_row.get::<String, _>(0);
 
-        let row = sqlx::query(
+        // Severity totals exclude suppressed findings, like `query_reports`
+        let columns = self.severity_columns().await?;
+        let sql = format!(
             r#"
             SELECT
                 COUNT(DISTINCT cluster) as total_clusters,
                 COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN 1 ELSE 0 END), 0) as total_vuln,
                 COALESCE(SUM(CASE WHEN report_type = 'sbomreport' THEN 1 ELSE 0 END), 0) as total_sbom,
-                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN critical_count ELSE 0 END), 0) as total_critical,
-                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN high_count ELSE 0 END), 0) as total_high,
-                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN medium_count ELSE 0 END), 0) as total_medium,
-                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN low_count ELSE 0 END), 0) as total_low,
-                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN unknown_count ELSE 0 END), 0) as total_unknown
-            FROM reports
+                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN {critical} ELSE 0 END), 0) as total_critical,
+                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN {high} ELSE 0 END), 0) as total_high,
+                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN {medium} ELSE 0 END), 0) as total_medium,
+                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN {low} ELSE 0 END), 0) as total_low,
+                COALESCE(SUM(CASE WHEN report_type = 'vulnerabilityreport' THEN {unknown} ELSE 0 END), 0) as total_unknown
+            {from}
             "#,
-        )
-        .fetch_one(&self.pool)
-        .await?;
+            critical = columns.count("critical"),
+            high = columns.count("high"),
+            medium = columns.count("medium"),
+            low = columns.count("low"),
+            unknown = columns.count("unknown"),
+            from = columns.from(),
+        );
+        let row = sqlx::query(&sql).fetch_one(&self.pool).await?;
 
         let stats = Stats {
             total_clusters: row.get::<i64, _>(0),
@@ -747,6 +909,63 @@ impl Database {
         Ok((results, total))
     }
 
+    /// Find every vulnerability report containing a CVE ID (case-insensitive
+    /// exact match), with the matching vulnerability entries from the report data
+    pub async fn search_by_cve(&self, cve_id: &str) -> Result<Vec<CveReportMatch>> {
+        // The LIKE on the raw JSON skips json_each for reports that cannot match
+        let rows = sqlx::query(
+            r#"
+            SELECT r.id, r.cluster, r.namespace, r.name, r.app, r.image, r.report_type,
+                   r.critical_count, r.high_count, r.medium_count, r.low_count, r.unknown_count,
+                   r.components_count, r.received_at, r.updated_at, r.notes,
+                   r.notes_created_at, r.notes_updated_at,
+                   json_group_array(json(j.value)) AS vulnerabilities
+            FROM reports r,
+                 json_each(json_extract(r.data, '$.report.vulnerabilities')) j
+            WHERE r.report_type = 'vulnerabilityreport'
+              AND r.data LIKE '%' || $1 || '%'
+              AND UPPER(json_extract(j.value, '$.vulnerabilityID')) = UPPER($1)
+            GROUP BY r.id
+            ORDER BY r.updated_at DESC
+            "#,
+        )
+        .bind(cve_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let results = rows
+            .iter()
+            .map(|row| CveReportMatch {
+                report: ReportMeta {
+                    id: row.get::<i64, _>(0),
+                    cluster: row.get::<String, _>(1),
+                    namespace: row.get::<String, _>(2),
+                    name: row.get::<String, _>(3),
+                    app: row.get::<String, _>(4),
+                    image: row.get::<String, _>(5),
+                    report_type: row.get::<String, _>(6),
+                    summary: Some(VulnSummary {
+                        critical: row.get::<i64, _>(7),
+                        high: row.get::<i64, _>(8),
+                        medium: row.get::<i64, _>(9),
+                        low: row.get::<i64, _>(10),
+                        unknown: row.get::<i64, _>(11),
+                    }),
+                    components_count: row.get::<Option<i64>, _>(12),
+                    received_at: row.get::<String, _>(13),
+                    updated_at: row.get::<String, _>(14),
+                    notes: row.get::<Option<String>, _>(15).unwrap_or_default(),
+                    notes_created_at: row.get::<Option<String>, _>(16),
+                    notes_updated_at: row.get::<Option<String>, _>(17),
+                },
+                vulnerabilities: serde_json::from_str(&row.get::<String, _>(18))
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(results)
+    }
+
     /// Suggest distinct vulnerability IDs matching a substring
     pub async fn suggest_vulnerability_ids(&self, query: &str, limit: i64) -> Result<Vec<String>> {
         let pattern = format!("%{}%", query);
@@ -798,6 +1017,70 @@ impl Database {
     }
 }
 
+/// Write a report's notes, returning the number of rows updated.
+///
+/// `notes_created_at` is only set the first time notes are written, while
+/// `notes_updated_at` moves on every write. Being a single `UPDATE`, it takes
+/// the write lock straight away when run inside a transaction.
+pub(super) async fn set_notes<'e, E>(
+    executor: E,
+    cluster: &str,
+    namespace: &str,
+    name: &str,
+    report_type: &str,
+    notes: &str,
+) -> sqlx::Result<u64>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let now = chrono::Utc::now().to_rfc3339();
+    let result = sqlx::query(
+        "UPDATE reports SET notes = $1, notes_created_at = COALESCE(notes_created_at, $2), notes_updated_at = $2 WHERE cluster = $3 AND namespace = $4 AND name = $5 AND report_type = $6",
+    )
+    .bind(notes)
+    .bind(&now)
+    .bind(cluster)
+    .bind(namespace)
+    .bind(name)
+    .bind(report_type)
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Record in `report_history` that a report was deleted.
+///
+/// The tombstone has zero counts and no data. History readers take the
+/// latest row per resource, so the resource stops counting from here on.
+pub(super) async fn insert_tombstone<'e, E>(
+    executor: E,
+    cluster: &str,
+    namespace: &str,
+    name: &str,
+    report_type: &str,
+) -> sqlx::Result<()>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO report_history (
+            cluster, namespace, name, report_type, data, received_at, recorded_at, deleted
+        ) VALUES ($1, $2, $3, $4, '', $5, $5, 1)
+        "#,
+    )
+    .bind(cluster)
+    .bind(namespace)
+    .bind(name)
+    .bind(report_type)
+    .bind(&now)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -924,7 +1207,7 @@ mod tests {
         db.upsert_report(&payload).await.expect("Failed to insert");
 
         let deleted = db
-            .delete_report("prod", "default", "nginx-vuln", "vulnerabilityreport")
+            .delete_report("prod", "default", "nginx-vuln", "vulnerabilityreport", None)
             .await
             .expect("Failed to delete");
         assert!(deleted);
@@ -936,6 +1219,73 @@ mod tests {
         assert!(report.is_none());
     }
 
+    #[tokio::test]
+    async fn test_delete_report_writes_tombstone() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        db.upsert_report(&create_test_payload(
+            "prod",
+            "default",
+            "nginx-vuln",
+            "vulnerabilityreport",
+        ))
+        .await
+        .unwrap();
+
+        assert!(
+            db.delete_report("prod", "default", "nginx-vuln", "vulnerabilityreport", None)
+                .await
+                .unwrap()
+        );
+        let rows: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT deleted, critical_count FROM report_history WHERE name = 'nginx-vuln' ORDER BY id",
+        )
+        .fetch_all(&db.pool)
+        .await
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, 0);
+        assert_eq!(rows[1], (1, 0));
+
+        // Deleting a missing report leaves no tombstone
+        assert!(
+            !db.delete_report("prod", "default", "nginx-vuln", "vulnerabilityreport", None)
+                .await
+                .unwrap()
+        );
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM report_history")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_delete_reports_for_cluster_removes_history() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        for cluster in ["prod", "staging"] {
+            db.upsert_report(&create_test_payload(
+                cluster,
+                "default",
+                "app1",
+                "vulnerabilityreport",
+            ))
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(db.delete_reports_for_cluster("prod").await.unwrap(), 1);
+        let clusters: Vec<String> =
+            sqlx::query_scalar("SELECT DISTINCT cluster FROM report_history")
+                .fetch_all(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(clusters, vec!["staging".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_delete_report_not_found() {
         let db = Database::new(":memory:")
@@ -943,12 +1293,118 @@ mod tests {
             .expect("Failed to create database");
 
         let deleted = db
-            .delete_report("prod", "default", "nonexistent", "vulnerabilityreport")
+            .delete_report(
+                "prod",
+                "default",
+                "nonexistent",
+                "vulnerabilityreport",
+                None,
+            )
             .await
             .expect("Failed to delete");
         assert!(!deleted);
     }
 
+    #[tokio::test]
+    async fn test_prune_old_reports_and_history() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        for name in ["stale", "fresh"] {
+            db.upsert_report(&create_test_payload(
+                "prod",
+                "default",
+                name,
+                "vulnerabilityreport",
+            ))
+            .await
+            .unwrap();
+        }
+        let old = (chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        sqlx::query("UPDATE reports SET updated_at = $1 WHERE name = 'stale'")
+            .bind(&old)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE report_history SET recorded_at = $1 WHERE name = 'stale'")
+            .bind(&old)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        assert_eq!(db.prune_old_reports(60).await.unwrap(), 0);
+        assert_eq!(db.prune_old_reports(30).await.unwrap(), 1);
+        assert!(
+            db.get_report("prod", "default", "stale", "vulnerabilityreport")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            db.get_report("prod", "default", "fresh", "vulnerabilityreport")
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // The pruned report leaves a tombstone; history is kept until its
+        // own retention expires
+        async fn history_count(db: &Database) -> i64 {
+            sqlx::query_scalar("SELECT COUNT(*) FROM report_history")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap()
+        }
+        let tombstones: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM report_history WHERE name = 'stale' AND deleted = 1",
+        )
+        .fetch_one(&db.pool)
+        .await
+        .unwrap();
+        assert_eq!(tombstones, 1);
+        assert_eq!(history_count(&db).await, 3);
+        assert_eq!(db.prune_report_history(90).await.unwrap(), 0);
+        // Only the superseded snapshot of "stale" is old enough to go
+        assert_eq!(db.prune_report_history(30).await.unwrap(), 1);
+        assert_eq!(history_count(&db).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_prune_report_history_keeps_latest_snapshot() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        let payload = create_test_payload("prod", "default", "app", "vulnerabilityreport");
+        db.upsert_report(&payload).await.unwrap();
+        db.upsert_report(&payload).await.unwrap();
+        let old = (chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        sqlx::query("UPDATE report_history SET recorded_at = $1")
+            .bind(&old)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+
+        // An unchanged workload is not rescanned, so its only snapshots are
+        // old; the newest one must survive for the trend
+        assert_eq!(db.prune_report_history(30).await.unwrap(), 1);
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM report_history")
+            .fetch_one(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 1);
+
+        // Once deleted, the tombstone and the snapshot go after retention
+        db.delete_report("prod", "default", "app", "vulnerabilityreport", None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE report_history SET recorded_at = $1")
+            .bind(&old)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        assert_eq!(db.prune_report_history(30).await.unwrap(), 2);
+    }
+
     #[tokio::test]
     async fn test_query_reports_by_cluster() {
         let db = Database::new(":memory:")
@@ -1044,6 +1500,7 @@ mod tests {
                 "app1",
                 "vulnerabilityreport",
                 "This is a test note",
+                None,
             )
             .await
             .expect("Failed to update notes");
@@ -1262,6 +1719,72 @@ mod tests {
         assert_eq!(results.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_query_reports_with_components_range() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        for (cluster, name, count) in [
+            ("prod", "small-sbom", 10),
+            ("prod", "medium-sbom", 500),
+            ("prod", "large-sbom", 4000),
+            ("dev", "large-sbom", 4000),
+        ] {
+            let mut payload = create_test_payload(cluster, "default", name, "sbomreport");
+            payload.data_json = json!({
+                "report": { "summary": { "componentsCount": count } }
+            })
+            .to_string();
+            db.upsert_report(&payload).await.unwrap();
+        }
+
+        let names = |results: &[ReportMeta]| {
+            let mut names: Vec<String> = results
+                .iter()
+                .map(|r| format!("{}/{}", r.cluster, r.name))
+                .collect();
+            names.sort();
+            names
+        };
+
+        let params = QueryParams {
+            min_components: Some(1000),
+            ..Default::default()
+        };
+        let (results, total) = db.query_reports("sbomreport", &params).await.unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(names(&results), vec!["dev/large-sbom", "prod/large-sbom"]);
+
+        // Bounds are inclusive
+        let params = QueryParams {
+            min_components: Some(10),
+            max_components: Some(500),
+            ..Default::default()
+        };
+        let (results, total) = db.query_reports("sbomreport", &params).await.unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(names(&results), vec!["prod/medium-sbom", "prod/small-sbom"]);
+
+        // Composes with the other filters
+        let params = QueryParams {
+            cluster: Some("prod".to_string()),
+            min_components: Some(1000),
+            ..Default::default()
+        };
+        let (results, total) = db.query_reports("sbomreport", &params).await.unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(names(&results), vec!["prod/large-sbom"]);
+
+        let params = QueryParams {
+            min_components: Some(5000),
+            ..Default::default()
+        };
+        let (results, total) = db.query_reports("sbomreport", &params).await.unwrap();
+        assert_eq!(total, 0);
+        assert!(results.is_empty());
+    }
+
     #[tokio::test]
     async fn test_query_reports_with_limit_offset() {
         let db = Database::new(":memory:")
@@ -1301,6 +1824,165 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    async fn seed_reports(db: &Database, count: usize) {
+        for i in 0..count {
+            db.upsert_report(&create_test_payload(
+                "prod",
+                &format!("ns{}", i % 7),
+                &format!("app{}", i),
+                "vulnerabilityreport",
+            ))
+            .await
+            .unwrap();
+        }
+    }
+
+    /// Follow `next_cursor` until exhausted, returning every page's ids
+    async fn collect_cursor_pages(db: &Database, params: QueryParams) -> Vec<Vec<i64>> {
+        let mut params = QueryParams {
+            cursor: Some(String::new()),
+            ..params
+        };
+        let mut pages = Vec::new();
+        loop {
+            let (results, total) = db
+                .query_reports("vulnerabilityreport", &params)
+                .await
+                .expect("Failed to query");
+            assert!(total >= results.len() as i64);
+            let next = params.next_cursor(&results);
+            pages.push(results.iter().map(|r| r.id).collect());
+            match next {
+                Some(cursor) => params.cursor = Some(cursor),
+                None => return pages,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_cursor_pagination_1000_rows() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        seed_reports(&db, 1000).await;
+
+        let pages = collect_cursor_pages(
+            &db,
+            QueryParams {
+                limit: Some(300),
+                ..Default::default()
+            },
+        )
+        .await;
+        let sizes: Vec<usize> = pages.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![300, 300, 300, 100]);
+
+        let ids: Vec<i64> = pages.into_iter().flatten().collect();
+        assert_eq!(ids.len(), 1000);
+        assert!(
+            ids.windows(2).all(|w| w[0] > w[1]),
+            "ids strictly descending"
+        );
+
+        // An exact multiple of the page size ends with an empty page
+        let pages = collect_cursor_pages(
+            &db,
+            QueryParams {
+                limit: Some(250),
+                ..Default::default()
+            },
+        )
+        .await;
+        let sizes: Vec<usize> = pages.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![250, 250, 250, 250, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_cursor_with_filter() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        seed_reports(&db, 1000).await;
+
+        let pages = collect_cursor_pages(
+            &db,
+            QueryParams {
+                namespace: Some("ns3".to_string()),
+                limit: Some(50),
+                ..Default::default()
+            },
+        )
+        .await;
+        // 1000 rows over 7 namespaces: ns3 gets indices 3, 10, ..., 997
+        let ids: Vec<i64> = pages.into_iter().flatten().collect();
+        assert_eq!(ids.len(), 143);
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_cursor_stable_under_inserts() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        seed_reports(&db, 1000).await;
+
+        let mut params = QueryParams {
+            limit: Some(100),
+            cursor: Some(String::new()),
+            ..Default::default()
+        };
+        let (first, _) = db
+            .query_reports("vulnerabilityreport", &params)
+            .await
+            .unwrap();
+        params.cursor = params.next_cursor(&first);
+
+        // New reports arriving mid-pagination would shift an offset window;
+        // the cursor keeps walking the original rows.
+        for i in 0..10 {
+            db.upsert_report(&create_test_payload(
+                "prod",
+                "default",
+                &format!("late{}", i),
+                "vulnerabilityreport",
+            ))
+            .await
+            .unwrap();
+        }
+
+        let mut seen: Vec<i64> = first.iter().map(|r| r.id).collect();
+        while params.cursor.is_some() {
+            let (page, total) = db
+                .query_reports("vulnerabilityreport", &params)
+                .await
+                .unwrap();
+            assert_eq!(total, 1010);
+            seen.extend(page.iter().map(|r| r.id));
+            params.cursor = params.next_cursor(&page);
+        }
+        assert_eq!(seen.len(), 1000);
+        assert_eq!(
+            seen.iter().collect::<std::collections::HashSet<_>>().len(),
+            1000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_invalid_cursor() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let params = QueryParams {
+            cursor: Some("%%%".to_string()),
+            ..Default::default()
+        };
+        assert!(
+            db.query_reports("vulnerabilityreport", &params)
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_list_clusters_with_data() {
         let db = Database::new(":memory:")
@@ -1359,6 +2041,7 @@ mod tests {
             "app1",
             "vulnerabilityreport",
             "first note",
+            None,
         )
         .await
         .expect("Failed to update notes");
@@ -1379,6 +2062,7 @@ mod tests {
             "app1",
             "vulnerabilityreport",
             "updated note",
+            None,
         )
         .await
         .expect("Failed to update notes");
@@ -1405,6 +2089,7 @@ mod tests {
                 "nonexistent",
                 "vulnerabilityreport",
                 "note",
+                None,
             )
             .await
             .expect("Failed to update notes");
@@ -1622,4 +2307,111 @@ mod tests {
         // 2 from node-app + 5 from noise-* = 7
         assert_eq!(rows_all.len(), 7);
     }
+
+    fn vuln_payload_with_cves(cluster: &str, name: &str, cves: &[(&str, &str)]) -> ReportPayload {
+        let vulnerabilities: Vec<serde_json::Value> = cves
+            .iter()
+            .map(|(id, resource)| {
+                json!({
+                    "vulnerabilityID": id,
+                    "severity": "HIGH",
+                    "resource": resource,
+                    "installedVersion": "1.0.0",
+                    "fixedVersion": "1.0.1",
+                })
+            })
+            .collect();
+        ReportPayload {
+            cluster: cluster.to_string(),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            report_type: "vulnerabilityreport".to_string(),
+            data_json: json!({
+                "metadata": {"labels": {}},
+                "report": {
+                    "artifact": {"repository": "app", "tag": "v1"},
+                    "registry": {"server": "ghcr.io"},
+                    "summary": {"highCount": cves.len()},
+                    "vulnerabilities": vulnerabilities,
+                }
+            })
+            .to_string(),
+            received_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_by_cve() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        db.upsert_report(&vuln_payload_with_cves(
+            "prod",
+            "app1",
+            &[("CVE-2024-1234", "openssl")],
+        ))
+        .await
+        .unwrap();
+        db.upsert_report(&vuln_payload_with_cves(
+            "prod",
+            "app2",
+            &[("CVE-2023-9999", "zlib")],
+        ))
+        .await
+        .unwrap();
+
+        let params = QueryParams {
+            cve: Some("CVE-2024-1234".to_string()),
+            ..Default::default()
+        };
+        let (results, total) = db
+            .query_reports("vulnerabilityreport", &params)
+            .await
+            .expect("Failed to query");
+        assert_eq!(total, 1);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "app1");
+    }
+
+    #[tokio::test]
+    async fn test_search_by_cve_returns_matching_excerpt() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        db.upsert_report(&vuln_payload_with_cves(
+            "prod",
+            "app1",
+            &[
+                ("CVE-2024-1234", "openssl"),
+                ("CVE-2024-1234", "libssl3"),
+                ("CVE-2023-9999", "zlib"),
+            ],
+        ))
+        .await
+        .unwrap();
+        db.upsert_report(&vuln_payload_with_cves(
+            "staging",
+            "app2",
+            &[("CVE-2024-12345", "curl")],
+        ))
+        .await
+        .unwrap();
+
+        let matches = db.search_by_cve("cve-2024-1234").await.unwrap();
+        // CVE-2024-12345 contains the ID as a substring but is not a match
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].report.name, "app1");
+        assert_eq!(matches[0].vulnerabilities.len(), 2);
+        let resources: Vec<&str> = matches[0]
+            .vulnerabilities
+            .iter()
+            .map(|v| v["resource"].as_str().unwrap())
+            .collect();
+        assert!(resources.contains(&"openssl"));
+        assert!(resources.contains(&"libssl3"));
+
+        assert!(db.search_by_cve("CVE-2000-0001").await.unwrap().is_empty());
+    }
 }
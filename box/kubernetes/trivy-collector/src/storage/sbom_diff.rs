@@ -0,0 +1,407 @@
+//! SBOM diff between two points in a report's scan history
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use utoipa::ToSchema;
+
+use super::database::Database;
+use crate::collector::types::{SbomComponent, SbomReportData};
+
+/// Component present in only one of the two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiffComponent {
+    #[schema(example = "openssl")]
+    pub name: String,
+    #[schema(example = "3.0.13")]
+    pub version: String,
+    #[schema(example = "library")]
+    pub component_type: String,
+}
+
+/// Component whose version changed between the two snapshots
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ComponentUpdate {
+    #[schema(example = "openssl")]
+    pub name: String,
+    #[schema(example = "library")]
+    pub component_type: String,
+    #[schema(example = "3.0.11")]
+    pub from_version: String,
+    #[schema(example = "3.0.13")]
+    pub to_version: String,
+}
+
+/// Added, removed, and version-bumped components between two scans
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct SbomDiff {
+    /// `recorded_at` of the snapshot used as the baseline (None = no scan yet)
+    pub from_recorded_at: Option<String>,
+    /// `recorded_at` of the snapshot compared against the baseline
+    pub to_recorded_at: Option<String>,
+    pub added: Vec<DiffComponent>,
+    pub removed: Vec<DiffComponent>,
+    pub updated: Vec<ComponentUpdate>,
+}
+
+#[derive(Deserialize)]
+struct SbomEnvelope {
+    #[serde(default)]
+    report: SbomReportData,
+}
+
+impl Database {
+    /// Compare the SBOM of a report as of `from_ts` with the one as of `to_ts`.
+    ///
+    /// Each side uses the latest `report_history` snapshot recorded at or
+    /// before the given timestamp. A side without a snapshot, or whose latest
+    /// snapshot is a deletion tombstone, is treated as an empty SBOM, so
+    /// diffing from before the first scan lists every component as added.
+    /// A snapshot whose JSON cannot be decoded is an error.
+    pub async fn diff_sbom_reports(
+        &self,
+        cluster: &str,
+        namespace: &str,
+        name: &str,
+        from_ts: DateTime<Utc>,
+        to_ts: DateTime<Utc>,
+    ) -> Result<SbomDiff> {
+        let (from_recorded_at, from) = self
+            .sbom_snapshot_at(cluster, namespace, name, from_ts)
+            .await?;
+        let (to_recorded_at, to) = self
+            .sbom_snapshot_at(cluster, namespace, name, to_ts)
+            .await?;
+
+        let mut diff = diff_components(&from, &to);
+        diff.from_recorded_at = from_recorded_at;
+        diff.to_recorded_at = to_recorded_at;
+        Ok(diff)
+    }
+
+    /// Latest SBOM history snapshot recorded at or before `ts`
+    async fn sbom_snapshot_at(
+        &self,
+        cluster: &str,
+        namespace: &str,
+        name: &str,
+        ts: DateTime<Utc>,
+    ) -> Result<(Option<String>, Vec<SbomComponent>)> {
+        let row = sqlx::query(
+            r#"
+            SELECT recorded_at, data, deleted
+            FROM report_history
+            WHERE cluster = $1 AND namespace = $2 AND name = $3
+              AND report_type = 'sbomreport'
+              AND datetime(recorded_at) <= datetime($4)
+            ORDER BY datetime(recorded_at) DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(cluster)
+        .bind(namespace)
+        .bind(name)
+        .bind(ts.to_rfc3339())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok((None, Vec::new()));
+        };
+        let recorded_at: String = row.get(0);
+        if row.get::<bool, _>(2) {
+            return Ok((Some(recorded_at), Vec::new()));
+        }
+        let data: String = row.get(1);
+        let envelope = serde_json::from_str::<SbomEnvelope>(&data).with_context(|| {
+            format!(
+                "Failed to decode SBOM snapshot of {namespace}/{name} recorded at {recorded_at}"
+            )
+        })?;
+        Ok((Some(recorded_at), envelope.report.components.components))
+    }
+}
+
+/// Diff two component lists, keyed by (type, name).
+///
+/// When a component's versions differ on both sides by exactly one version,
+/// it is reported as updated; any other change is reported as the
+/// individual versions added and removed.
+pub(crate) fn diff_components(from: &[SbomComponent], to: &[SbomComponent]) -> SbomDiff {
+    fn index(components: &[SbomComponent]) -> BTreeMap<(&str, &str), BTreeSet<&str>> {
+        let mut out: BTreeMap<(&str, &str), BTreeSet<&str>> = BTreeMap::new();
+        for c in components {
+            out.entry((c.name.as_str(), c.component_type.as_str()))
+                .or_default()
+                .insert(c.version.as_str());
+        }
+        out
+    }
+
+    let before = index(from);
+    let after = index(to);
+    let keys: BTreeSet<_> = before.keys().chain(after.keys()).copied().collect();
+    let empty = BTreeSet::new();
+
+    let mut diff = SbomDiff::default();
+    for key @ (name, component_type) in keys {
+        let old = before.get(&key).unwrap_or(&empty);
+        let new = after.get(&key).unwrap_or(&empty);
+        let removed: Vec<&str> = old.difference(new).copied().collect();
+        let added: Vec<&str> = new.difference(old).copied().collect();
+
+        let component = |version: &str| DiffComponent {
+            name: name.to_string(),
+            version: version.to_string(),
+            component_type: component_type.to_string(),
+        };
+        match (removed.as_slice(), added.as_slice()) {
+            ([from_version], [to_version]) => diff.updated.push(ComponentUpdate {
+                name: name.to_string(),
+                component_type: component_type.to_string(),
+                from_version: from_version.to_string(),
+                to_version: to_version.to_string(),
+            }),
+            _ => {
+                diff.removed.extend(removed.iter().map(|v| component(v)));
+                diff.added.extend(added.iter().map(|v| component(v)));
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::types::ReportPayload;
+    use serde_json::json;
+
+    fn component(name: &str, version: &str) -> SbomComponent {
+        SbomComponent {
+            component_type: "library".to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn sbom_json(components: &[(&str, &str)]) -> String {
+        let components: Vec<_> = components
+            .iter()
+            .map(|(name, version)| json!({"type": "library", "name": name, "version": version}))
+            .collect();
+        json!({ "report": { "components": { "components": components } } }).to_string()
+    }
+
+    #[test]
+    fn test_diff_components() {
+        let from = [
+            component("openssl", "3.0.11"),
+            component("zlib", "1.2.13"),
+            component("libc", "2.36"),
+        ];
+        let to = [
+            component("openssl", "3.0.13"),
+            component("libc", "2.36"),
+            component("curl", "8.5.0"),
+        ];
+
+        let diff = diff_components(&from, &to);
+        assert_eq!(
+            diff.added,
+            vec![DiffComponent {
+                name: "curl".to_string(),
+                version: "8.5.0".to_string(),
+                component_type: "library".to_string(),
+            }]
+        );
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "zlib");
+        assert_eq!(
+            diff.updated,
+            vec![ComponentUpdate {
+                name: "openssl".to_string(),
+                component_type: "library".to_string(),
+                from_version: "3.0.11".to_string(),
+                to_version: "3.0.13".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_components_multiple_versions() {
+        // Two versions of the same module become one: not a simple bump
+        let from = [
+            component("golang.org/x/net", "0.17.0"),
+            component("golang.org/x/net", "0.19.0"),
+        ];
+        let to = [component("golang.org/x/net", "0.23.0")];
+
+        let diff = diff_components(&from, &to);
+        assert!(diff.updated.is_empty());
+        assert_eq!(diff.removed.len(), 2);
+        assert_eq!(diff.added.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_diff_sbom_reports() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        for (recorded_at, data) in [
+            (
+                "2026-01-01T00:00:00+00:00",
+                sbom_json(&[("openssl", "3.0.11")]),
+            ),
+            (
+                "2026-01-05T00:00:00+00:00",
+                sbom_json(&[("openssl", "3.0.13"), ("curl", "8.5.0")]),
+            ),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO report_history (
+                    cluster, namespace, name, report_type, data, received_at, recorded_at
+                ) VALUES ('prod', 'default', 'nginx-sbom', 'sbomreport', $1, $2, $2)
+                "#,
+            )
+            .bind(&data)
+            .bind(recorded_at)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        }
+
+        let diff = db
+            .diff_sbom_reports(
+                "prod",
+                "default",
+                "nginx-sbom",
+                utc("2026-01-02T00:00:00Z"),
+                utc("2026-01-06T00:00:00Z"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            diff.from_recorded_at.as_deref(),
+            Some("2026-01-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            diff.to_recorded_at.as_deref(),
+            Some("2026-01-05T00:00:00+00:00")
+        );
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.updated.len(), 1);
+        assert!(diff.removed.is_empty());
+
+        // Before the first scan everything counts as added
+        let diff = db
+            .diff_sbom_reports(
+                "prod",
+                "default",
+                "nginx-sbom",
+                utc("2025-12-01T00:00:00Z"),
+                utc("2026-01-01T00:00:00Z"),
+            )
+            .await
+            .unwrap();
+        assert!(diff.from_recorded_at.is_none());
+        assert_eq!(diff.added.len(), 1);
+    }
+
+    fn utc(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn test_diff_sbom_reports_surfaces_decode_errors() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        sqlx::query(
+            r#"
+            INSERT INTO report_history (
+                cluster, namespace, name, report_type, data, received_at, recorded_at
+            ) VALUES ('prod', 'default', 'nginx-sbom', 'sbomreport', '{"report": 1}',
+                '2026-01-01T00:00:00+00:00', '2026-01-01T00:00:00+00:00')
+            "#,
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let ts = utc("2026-01-02T00:00:00Z");
+        let err = db
+            .diff_sbom_reports("prod", "default", "nginx-sbom", ts, ts)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to decode SBOM snapshot"));
+    }
+
+    #[tokio::test]
+    async fn test_diff_sbom_reports_after_delete_lists_removed() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        let payload = ReportPayload {
+            cluster: "prod".to_string(),
+            namespace: "default".to_string(),
+            name: "nginx-sbom".to_string(),
+            report_type: "sbomreport".to_string(),
+            data_json: sbom_json(&[("openssl", "3.0.11")]),
+            received_at: chrono::Utc::now(),
+        };
+        db.upsert_report(&payload).await.unwrap();
+        // SQLite compares at second precision, so move the scan an hour back
+        let scanned = chrono::Utc::now() - chrono::Duration::hours(1);
+        sqlx::query("UPDATE report_history SET recorded_at = $1")
+            .bind(scanned.to_rfc3339())
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let before = scanned + chrono::Duration::minutes(30);
+        db.delete_report("prod", "default", "nginx-sbom", "sbomreport", None)
+            .await
+            .unwrap();
+
+        let diff = db
+            .diff_sbom_reports("prod", "default", "nginx-sbom", before, chrono::Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(diff.removed.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_diff_sbom_reports_uses_upserted_history() {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+
+        let mut payload = ReportPayload {
+            cluster: "prod".to_string(),
+            namespace: "default".to_string(),
+            name: "nginx-sbom".to_string(),
+            report_type: "sbomreport".to_string(),
+            data_json: sbom_json(&[("openssl", "3.0.11")]),
+            received_at: chrono::Utc::now(),
+        };
+        db.upsert_report(&payload).await.unwrap();
+        payload.data_json = sbom_json(&[("openssl", "3.0.13")]);
+        db.upsert_report(&payload).await.unwrap();
+
+        let now = chrono::Utc::now();
+        let diff = db
+            .diff_sbom_reports("prod", "default", "nginx-sbom", now, now)
+            .await
+            .unwrap();
+        // Both sides resolve to the latest snapshot
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.updated.is_empty());
+    }
+}
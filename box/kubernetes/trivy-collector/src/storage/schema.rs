@@ -68,6 +68,48 @@ pub async fn init_schema(pool: &SqlitePool) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_reports_type_updated
             ON reports(report_type, updated_at);
 
+        -- Report history table: one row per received scan result. Unlike
+        -- reports, rows are never overwritten, so severity trends can be
+        -- reconstructed per day. A deleted report leaves a tombstone row
+        -- (deleted = 1) so the trend stops counting it from then on.
+        CREATE TABLE IF NOT EXISTS report_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            cluster TEXT NOT NULL,
+            namespace TEXT NOT NULL,
+            name TEXT NOT NULL,
+            report_type TEXT NOT NULL,
+            app TEXT DEFAULT '',
+            image TEXT DEFAULT '',
+            registry TEXT DEFAULT '',
+            critical_count INTEGER DEFAULT 0,
+            high_count INTEGER DEFAULT 0,
+            medium_count INTEGER DEFAULT 0,
+            low_count INTEGER DEFAULT 0,
+            unknown_count INTEGER DEFAULT 0,
+            components_count INTEGER DEFAULT 0,
+            data TEXT NOT NULL,
+            received_at TEXT NOT NULL,
+            recorded_at TEXT NOT NULL,
+            deleted INTEGER NOT NULL DEFAULT 0
+        );
+        CREATE INDEX IF NOT EXISTS idx_report_history_cluster_recorded
+            ON report_history(cluster, report_type, recorded_at);
+        CREATE INDEX IF NOT EXISTS idx_report_history_resource
+            ON report_history(cluster, namespace, name, report_type, id);
+
+        -- Suppression allowlist: CVEs acknowledged for images matching a
+        -- GLOB pattern, excluded from severity counts until expires_at.
+        CREATE TABLE IF NOT EXISTS suppressions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            cve_id TEXT NOT NULL,
+            image_pattern TEXT NOT NULL DEFAULT '*',
+            reason TEXT NOT NULL DEFAULT '',
+            expires_at TEXT,
+            created_by TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_suppressions_cve_id ON suppressions(cve_id);
+
         -- API tokens table
         CREATE TABLE IF NOT EXISTS api_tokens (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -111,6 +153,17 @@ pub async fn init_schema(pool: &SqlitePool) -> Result<()> {
         );
         CREATE INDEX IF NOT EXISTS idx_cleanup_history_cleaned_at ON cleanup_history(cleaned_at);
 
+        -- Append-only audit trail of mutating API operations
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            operation TEXT NOT NULL,
+            target TEXT NOT NULL,
+            caller TEXT NOT NULL,
+            claimed_caller TEXT,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_audit_log_operation ON audit_log(operation);
+
         -- Clusters view for quick cluster listing
         CREATE VIEW IF NOT EXISTS clusters_view AS
         SELECT
@@ -282,6 +335,119 @@ async fn run_migrations(pool: &SqlitePool) -> Result<()> {
         .context("Failed to create api_logs table")?;
     }
 
+    // Migration: Create report_history table if it doesn't exist
+    if !table_exists_check(pool, "report_history").await? {
+        info!("Migrating database: creating report_history table");
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE IF NOT EXISTS report_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                cluster TEXT NOT NULL,
+                namespace TEXT NOT NULL,
+                name TEXT NOT NULL,
+                report_type TEXT NOT NULL,
+                app TEXT DEFAULT '',
+                image TEXT DEFAULT '',
+                registry TEXT DEFAULT '',
+                critical_count INTEGER DEFAULT 0,
+                high_count INTEGER DEFAULT 0,
+                medium_count INTEGER DEFAULT 0,
+                low_count INTEGER DEFAULT 0,
+                unknown_count INTEGER DEFAULT 0,
+                components_count INTEGER DEFAULT 0,
+                data TEXT NOT NULL,
+                received_at TEXT NOT NULL,
+                recorded_at TEXT NOT NULL,
+                deleted INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE INDEX IF NOT EXISTS idx_report_history_cluster_recorded
+                ON report_history(cluster, report_type, recorded_at);
+            CREATE INDEX IF NOT EXISTS idx_report_history_resource
+                ON report_history(cluster, namespace, name, report_type, id);
+            "#,
+        )
+        .execute(pool)
+        .await
+        .context("Failed to create report_history table")?;
+    }
+
+    // Migration: Add the per-resource history index used by the nightly
+    // prune, SBOM snapshot lookups and per-resource trends
+    if !index_exists(pool, "idx_report_history_resource").await? {
+        info!("Migrating database: adding idx_report_history_resource index");
+        sqlx::raw_sql(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_report_history_resource
+                ON report_history(cluster, namespace, name, report_type, id);
+            ANALYZE report_history;
+            "#,
+        )
+        .execute(pool)
+        .await
+        .context("Failed to add idx_report_history_resource index")?;
+    }
+
+    // Migration: Add deleted (tombstone) column to report_history if it doesn't exist
+    if !column_exists(pool, "report_history", "deleted").await? {
+        info!("Migrating database: adding deleted column to report_history");
+        sqlx::query("ALTER TABLE report_history ADD COLUMN deleted INTEGER NOT NULL DEFAULT 0")
+            .execute(pool)
+            .await
+            .context("Failed to add deleted column to report_history")?;
+    }
+
+    // Migration: Create suppressions table if it doesn't exist
+    if !table_exists_check(pool, "suppressions").await? {
+        info!("Migrating database: creating suppressions table");
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE IF NOT EXISTS suppressions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                cve_id TEXT NOT NULL,
+                image_pattern TEXT NOT NULL DEFAULT '*',
+                reason TEXT NOT NULL DEFAULT '',
+                expires_at TEXT,
+                created_by TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_suppressions_cve_id ON suppressions(cve_id);
+            "#,
+        )
+        .execute(pool)
+        .await
+        .context("Failed to create suppressions table")?;
+    }
+
+    // Migration: Create audit_log table if it doesn't exist
+    if !table_exists_check(pool, "audit_log").await? {
+        info!("Migrating database: creating audit_log table");
+        sqlx::raw_sql(
+            r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                operation TEXT NOT NULL,
+                target TEXT NOT NULL,
+                caller TEXT NOT NULL,
+                claimed_caller TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_audit_log_operation ON audit_log(operation);
+            "#,
+        )
+        .execute(pool)
+        .await
+        .context("Failed to create audit_log table")?;
+    }
+
+    // Migration: Add claimed_caller column to audit_log if it doesn't exist
+    if !column_exists(pool, "audit_log", "claimed_caller").await? {
+        info!("Migrating database: adding claimed_caller column to audit_log");
+        sqlx::query("ALTER TABLE audit_log ADD COLUMN claimed_caller TEXT")
+            .execute(pool)
+            .await
+            .context("Failed to add claimed_caller column to audit_log")?;
+    }
+
     Ok(())
 }
 
@@ -344,6 +510,9 @@ mod tests {
         assert!(table_exists_check(&pool, "api_tokens").await.unwrap());
         assert!(table_exists_check(&pool, "api_logs").await.unwrap());
         assert!(table_exists_check(&pool, "cleanup_history").await.unwrap());
+        assert!(table_exists_check(&pool, "report_history").await.unwrap());
+        assert!(table_exists_check(&pool, "suppressions").await.unwrap());
+        assert!(table_exists_check(&pool, "audit_log").await.unwrap());
     }
 
     #[tokio::test]
@@ -456,11 +625,24 @@ mod tests {
         assert!(table_exists_check(&pool, "api_tokens").await.unwrap());
         assert!(table_exists_check(&pool, "api_logs").await.unwrap());
         assert!(table_exists_check(&pool, "cleanup_history").await.unwrap());
+        assert!(table_exists_check(&pool, "report_history").await.unwrap());
+        assert!(table_exists_check(&pool, "suppressions").await.unwrap());
+        assert!(table_exists_check(&pool, "audit_log").await.unwrap());
         assert!(
             column_exists(&pool, "api_tokens", "description")
                 .await
                 .unwrap()
         );
+        assert!(
+            column_exists(&pool, "audit_log", "claimed_caller")
+                .await
+                .unwrap()
+        );
+        assert!(
+            index_exists(&pool, "idx_report_history_resource")
+                .await
+                .unwrap()
+        );
     }
 
     #[tokio::test]
@@ -475,5 +657,10 @@ mod tests {
                 .unwrap();
         // Should have at least the report indexes
         assert!(index_count > 0);
+        assert!(
+            index_exists(&pool, "idx_report_history_resource")
+                .await
+                .unwrap()
+        );
     }
 }
@@ -0,0 +1,487 @@
+//! Vulnerability suppression allowlist
+//!
+//! A suppression acknowledges a CVE as accepted or a false positive for images
+//! matching a glob pattern (SQLite `GLOB`, e.g. `nginx:*`). Active suppressions
+//! are subtracted from every severity count the server reports (report lists,
+//! stats, dashboard aggregates and metrics), and stop applying once
+//! `expires_at` has passed; expired rows are kept for audit.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sqlx::Row;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use super::audit::{AuditCaller, insert_audit, suppression_target};
+use super::database::Database;
+
+/// SQL predicate that holds while suppression `s` has not expired
+pub(super) const ACTIVE_SUPPRESSION: &str =
+    "(s.expires_at IS NULL OR datetime(s.expires_at) > datetime('now'))";
+
+/// Per-report counts of findings covered by an active suppression, joined as
+/// `sup` onto `reports`. Only reports whose JSON mentions a suppressed CVE for
+/// a matching image are expanded with `json_each`.
+pub(super) fn suppressed_counts_join() -> String {
+    format!(
+        r#" LEFT JOIN (
+            SELECT r.id AS report_id,
+                SUM(json_extract(v.value, '$.severity') = 'CRITICAL') AS critical,
+                SUM(json_extract(v.value, '$.severity') = 'HIGH') AS high,
+                SUM(json_extract(v.value, '$.severity') = 'MEDIUM') AS medium,
+                SUM(json_extract(v.value, '$.severity') = 'LOW') AS low,
+                SUM(json_extract(v.value, '$.severity') = 'UNKNOWN') AS unknown
+            FROM (
+                SELECT id, image, data FROM reports
+                WHERE report_type = 'vulnerabilityreport'
+                  AND EXISTS (
+                      SELECT 1 FROM suppressions s
+                      WHERE {active} AND image GLOB s.image_pattern
+                        AND instr(data, s.cve_id) > 0
+                  )
+            ) r, json_each(r.data, '$.report.vulnerabilities') v
+            WHERE EXISTS (
+                SELECT 1 FROM suppressions s
+                WHERE {active}
+                  AND s.cve_id = json_extract(v.value, '$.vulnerabilityID')
+                  AND r.image GLOB s.image_pattern
+            )
+            GROUP BY r.id
+        ) sup ON sup.report_id = reports.id"#,
+        active = ACTIVE_SUPPRESSION
+    )
+}
+
+/// Severity count expressions over `reports` with active suppressions
+/// subtracted. When no suppression is active the join is skipped and the
+/// stored counts are used as-is.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct SeverityColumns {
+    suppressed: bool,
+}
+
+impl SeverityColumns {
+    /// `FROM reports`, joined with the per-report suppressed counts as `sup`
+    pub(super) fn from(self) -> String {
+        if self.suppressed {
+            format!("FROM reports{}", suppressed_counts_join())
+        } else {
+            "FROM reports".to_string()
+        }
+    }
+
+    /// Count of `column` (`critical`, `high`, ...) for the current report
+    pub(super) fn count(self, column: &str) -> String {
+        if self.suppressed {
+            format!("(reports.{column}_count - COALESCE(sup.{column}, 0))")
+        } else {
+            format!("reports.{column}_count")
+        }
+    }
+}
+
+/// Suppression allowlist entry
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Suppression {
+    pub id: i64,
+    #[schema(example = "CVE-2023-44487")]
+    pub cve_id: String,
+    /// Glob matched against the report image (`*` matches every image)
+    #[schema(example = "nginx:*")]
+    pub image_pattern: String,
+    #[schema(example = "Not exploitable: HTTP/2 disabled")]
+    pub reason: String,
+    /// RFC 3339 expiry; `None` never expires
+    pub expires_at: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+    /// False once `expires_at` has passed
+    pub active: bool,
+}
+
+impl Database {
+    /// Add a suppression for `cve_id` on images matching `image_pattern`.
+    /// With `audit`, a `create_suppression` audit record is written in the
+    /// same transaction.
+    pub async fn create_suppression(
+        &self,
+        cve_id: &str,
+        image_pattern: &str,
+        reason: &str,
+        expires_at: Option<&str>,
+        created_by: &str,
+        audit: Option<&AuditCaller>,
+    ) -> Result<Suppression> {
+        let created_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query(
+            "INSERT INTO suppressions (cve_id, image_pattern, reason, expires_at, created_by, created_at) VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(cve_id)
+        .bind(image_pattern)
+        .bind(reason)
+        .bind(expires_at)
+        .bind(created_by)
+        .bind(&created_at)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to insert suppression")?;
+
+        let id = result.last_insert_rowid();
+        if let Some(caller) = audit {
+            insert_audit(
+                &mut *tx,
+                "create_suppression",
+                &suppression_target(id),
+                caller,
+            )
+            .await
+            .context("Failed to insert audit record")?;
+        }
+        tx.commit().await?;
+        debug!(suppression_id = id, cve_id = %cve_id, image_pattern = %image_pattern, created_by = %created_by, "Suppression created");
+
+        self.get_suppression(id)
+            .await?
+            .context("Suppression missing after insert")
+    }
+
+    /// Look up a suppression by ID
+    pub async fn get_suppression(&self, id: i64) -> Result<Option<Suppression>> {
+        let row = sqlx::query(&format!(
+            "SELECT s.id, s.cve_id, s.image_pattern, s.reason, s.expires_at, s.created_by, s.created_at, {ACTIVE_SUPPRESSION} FROM suppressions s WHERE s.id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to execute get_suppression query")?;
+
+        Ok(row.as_ref().map(suppression_from_row))
+    }
+
+    /// List suppressions, newest first. Expired entries are included only
+    /// when `include_expired` is set.
+    pub async fn list_suppressions(&self, include_expired: bool) -> Result<Vec<Suppression>> {
+        let filter = if include_expired {
+            String::new()
+        } else {
+            format!("WHERE {ACTIVE_SUPPRESSION}")
+        };
+        let rows = sqlx::query(&format!(
+            "SELECT s.id, s.cve_id, s.image_pattern, s.reason, s.expires_at, s.created_by, s.created_at, {ACTIVE_SUPPRESSION} FROM suppressions s {filter} ORDER BY s.id DESC"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to execute list_suppressions query")?;
+
+        Ok(rows.iter().map(suppression_from_row).collect())
+    }
+
+    /// Delete a suppression by ID. Returns true if a row was deleted. With
+    /// `audit`, a `delete_suppression` audit record is written in the same
+    /// transaction.
+    pub async fn delete_suppression(&self, id: i64, audit: Option<&AuditCaller>) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query("DELETE FROM suppressions WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to delete suppression")?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted && let Some(caller) = audit {
+            insert_audit(
+                &mut *tx,
+                "delete_suppression",
+                &suppression_target(id),
+                caller,
+            )
+            .await
+            .context("Failed to insert audit record")?;
+        }
+        tx.commit().await?;
+        if deleted {
+            debug!(suppression_id = id, "Suppression deleted");
+        }
+        Ok(deleted)
+    }
+
+    /// CVE IDs currently suppressed for an image
+    pub async fn suppressed_cves_for_image(&self, image: &str) -> Result<Vec<String>> {
+        let rows = sqlx::query(&format!(
+            "SELECT DISTINCT s.cve_id FROM suppressions s WHERE {ACTIVE_SUPPRESSION} AND $1 GLOB s.image_pattern ORDER BY s.cve_id"
+        ))
+        .bind(image)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to execute suppressed_cves_for_image query")?;
+
+        Ok(rows.iter().map(|row| row.get::<String, _>(0)).collect())
+    }
+
+    /// Severity count expressions for the suppressions active right now
+    pub(super) async fn severity_columns(&self) -> Result<SeverityColumns> {
+        Ok(SeverityColumns {
+            suppressed: self.has_active_suppressions().await?,
+        })
+    }
+
+    /// Whether any suppression is currently active. Lets severity queries
+    /// skip the suppression join entirely in the common case.
+    async fn has_active_suppressions(&self) -> Result<bool> {
+        let (exists,): (bool,) = sqlx::query_as(&format!(
+            "SELECT EXISTS (SELECT 1 FROM suppressions s WHERE {ACTIVE_SUPPRESSION})"
+        ))
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check active suppressions")?;
+        Ok(exists)
+    }
+}
+
+fn suppression_from_row(row: &sqlx::sqlite::SqliteRow) -> Suppression {
+    Suppression {
+        id: row.get::<i64, _>(0),
+        cve_id: row.get::<String, _>(1),
+        image_pattern: row.get::<String, _>(2),
+        reason: row.get::<String, _>(3),
+        expires_at: row.get::<Option<String>, _>(4),
+        created_by: row.get::<String, _>(5),
+        created_at: row.get::<String, _>(6),
+        active: row.get::<bool, _>(7),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::types::ReportPayload;
+    use crate::storage::QueryParams;
+    use serde_json::json;
+
+    fn vuln_payload(name: &str, image_tag: &str, vulns: &[(&str, &str)]) -> ReportPayload {
+        let vulnerabilities: Vec<_> = vulns
+            .iter()
+            .map(|(id, severity)| json!({"vulnerabilityID": id, "severity": severity}))
+            .collect();
+        let count = |s: &str| vulns.iter().filter(|(_, sev)| *sev == s).count();
+        ReportPayload {
+            cluster: "prod".to_string(),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            report_type: "vulnerabilityreport".to_string(),
+            data_json: json!({
+                "report": {
+                    "artifact": {"repository": "nginx", "tag": image_tag},
+                    "summary": {
+                        "criticalCount": count("CRITICAL"),
+                        "highCount": count("HIGH"),
+                        "mediumCount": count("MEDIUM"),
+                        "lowCount": count("LOW"),
+                        "unknownCount": count("UNKNOWN")
+                    },
+                    "vulnerabilities": vulnerabilities
+                }
+            })
+            .to_string(),
+            received_at: chrono::Utc::now(),
+        }
+    }
+
+    async fn seeded_db() -> Database {
+        let db = Database::new(":memory:")
+            .await
+            .expect("Failed to create database");
+        db.upsert_report(&vuln_payload(
+            "nginx-old",
+            "1.25",
+            &[
+                ("CVE-2023-44487", "CRITICAL"),
+                ("CVE-2023-44487", "CRITICAL"),
+                ("CVE-2024-0001", "HIGH"),
+            ],
+        ))
+        .await
+        .unwrap();
+        db.upsert_report(&vuln_payload(
+            "nginx-new",
+            "1.27",
+            &[("CVE-2023-44487", "CRITICAL")],
+        ))
+        .await
+        .unwrap();
+        db
+    }
+
+    async fn counts(db: &Database, params: &QueryParams) -> Vec<(String, i64, i64)> {
+        let (reports, _) = db
+            .query_reports("vulnerabilityreport", params)
+            .await
+            .unwrap();
+        let mut out: Vec<_> = reports
+            .iter()
+            .map(|r| {
+                let s = r.summary.as_ref().unwrap();
+                (r.name.clone(), s.critical, s.high)
+            })
+            .collect();
+        out.sort();
+        out
+    }
+
+    #[tokio::test]
+    async fn test_suppression_crud() {
+        let db = Database::new(":memory:").await.unwrap();
+
+        let created = db
+            .create_suppression(
+                "CVE-2023-44487",
+                "nginx:*",
+                "HTTP/2 disabled",
+                None,
+                "alice",
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(created.active);
+        assert_eq!(created.created_by, "alice");
+
+        let listed = db.list_suppressions(false).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].cve_id, "CVE-2023-44487");
+
+        assert!(db.delete_suppression(created.id, None).await.unwrap());
+        assert!(!db.delete_suppression(created.id, None).await.unwrap());
+        assert!(db.list_suppressions(true).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_reports_excludes_suppressed_findings() {
+        let db = seeded_db().await;
+        let all = QueryParams::default();
+        assert_eq!(
+            counts(&db, &all).await,
+            vec![
+                ("nginx-new".to_string(), 1, 0),
+                ("nginx-old".to_string(), 2, 1),
+            ]
+        );
+
+        db.create_suppression("CVE-2023-44487", "nginx:1.25", "", None, "alice", None)
+            .await
+            .unwrap();
+        // Both findings of the CVE in the matching image are dropped;
+        // the other image is untouched.
+        assert_eq!(
+            counts(&db, &all).await,
+            vec![
+                ("nginx-new".to_string(), 1, 0),
+                ("nginx-old".to_string(), 0, 1),
+            ]
+        );
+
+        // Severity filters use the adjusted counts
+        let critical = QueryParams {
+            severity: Some(vec!["critical".to_string()]),
+            ..Default::default()
+        };
+        let (reports, total) = db
+            .query_reports("vulnerabilityreport", &critical)
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(reports[0].name, "nginx-new");
+
+        // Report detail lists the suppressed CVEs for the UI
+        let report = db
+            .get_report("prod", "default", "nginx-old", "vulnerabilityreport")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.suppressed_cves, vec!["CVE-2023-44487".to_string()]);
+        let report = db
+            .get_report("prod", "default", "nginx-new", "vulnerabilityreport")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(report.suppressed_cves.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_exclude_suppressed_findings() {
+        let db = seeded_db().await;
+        db.create_suppression("CVE-2023-44487", "nginx:*", "", None, "alice", None)
+            .await
+            .unwrap();
+
+        let stats = db.get_stats().await.unwrap();
+        assert_eq!((stats.total_critical, stats.total_high), (0, 1));
+
+        let summary = db.get_namespace_summary("prod", "default").await.unwrap();
+        assert_eq!((summary.summary.critical, summary.summary.high), (0, 1));
+
+        let by_cluster = db.vulnerability_counts_by_cluster().await.unwrap();
+        assert_eq!(by_cluster.len(), 1);
+        assert_eq!((by_cluster[0].1.critical, by_cluster[0].1.high), (0, 1));
+
+        // nginx:1.27 only had the suppressed CVE and drops out of the ranking
+        let top = db.top_vulnerable_images(10).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!((top[0].image.as_str(), top[0].critical), ("nginx:1.25", 0));
+
+        let registries = db.get_registry_stats().await.unwrap();
+        assert_eq!(registries.len(), 1);
+        assert_eq!(
+            (registries[0].critical_count, registries[0].high_count),
+            (0, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expired_suppression_is_ignored() {
+        let db = seeded_db().await;
+        let expired = db
+            .create_suppression(
+                "CVE-2023-44487",
+                "*",
+                "",
+                Some("2020-01-01T00:00:00Z"),
+                "alice",
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(!expired.active);
+        assert!(db.list_suppressions(false).await.unwrap().is_empty());
+        assert_eq!(db.list_suppressions(true).await.unwrap().len(), 1);
+        assert!(
+            db.suppressed_cves_for_image("nginx:1.25")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            counts(&db, &QueryParams::default()).await,
+            vec![
+                ("nginx-new".to_string(), 1, 0),
+                ("nginx-old".to_string(), 2, 1),
+            ]
+        );
+
+        db.create_suppression(
+            "CVE-2023-44487",
+            "*",
+            "",
+            Some("2999-01-01T00:00:00Z"),
+            "alice",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            db.suppressed_cves_for_image("nginx:1.27").await.unwrap(),
+            vec!["CVE-2023-44487".to_string()]
+        );
+    }
+}
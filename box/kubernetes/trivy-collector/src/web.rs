@@ -8,30 +8,38 @@
 //! - `types`: Request and response types
 //! - `watcher`: Local Kubernetes watcher
 //! - `admin_handlers`: Admin API handlers
+//! - `export_handlers`: CSV and XLSX report export
+//! - `suppression_handlers`: CVE suppression allowlist
+//! - `notes_handlers`: Bulk notes import/export
 //! - `logging_middleware`: API request logging
 
 mod admin_handlers;
 mod alert_handlers;
 mod cluster_handlers;
+mod export_handlers;
 mod handlers;
 mod logging_middleware;
+mod notes_handlers;
 pub mod state;
+mod suppression_handlers;
 mod types;
 mod watcher;
 
 // Re-export public types
 pub use handlers::{
-    delete_report, get_config, get_dashboard_trends, get_sbom_report, get_stats, get_status,
+    delete_report, diff_sbom_reports, get_config, get_dashboard_trends, get_namespace_summary,
+    get_registry_stats, get_sbom_report, get_severity_trend, get_stats, get_status, get_top_images,
     get_version, get_vulnerability_report, get_watcher_status, healthz, list_clusters,
     list_namespaces, list_sbom_reports, list_vulnerability_reports, receive_report,
-    search_sbom_components, search_vulnerabilities, suggest_sbom_components,
+    search_reports_by_cve, search_sbom_components, search_vulnerabilities, suggest_sbom_components,
     suggest_vulnerabilities, update_notes,
 };
 pub use state::{AppState, RuntimeInfo, WatcherStatus};
 pub use types::{
     ComponentSearchQuery, ComponentSuggestQuery, ConfigItem, ConfigResponse, ErrorResponse,
-    HealthResponse, ListQuery, ListResponse, StatusResponse, TrendQuery, UpdateNotesRequest,
-    VersionResponse, VulnSearchQuery, VulnSuggestQuery, WatcherInfo, WatcherStatusResponse,
+    HealthResponse, ListQuery, ListResponse, SbomDiffQuery, SeverityTrendQuery, StatusResponse,
+    TrendQuery, UpdateNotesRequest, VersionResponse, VulnSearchQuery, VulnSuggestQuery,
+    WatcherInfo, WatcherStatusResponse,
 };
 pub use watcher::LocalWatcher;
 
@@ -40,8 +48,10 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::collector::types::{ReportEvent, ReportEventType, ReportPayload};
 use crate::storage::{
-    CleanupHistoryEntry, ClusterInfo, ComponentSearchResult, FullReport, ReportMeta, Stats,
-    TrendDataPoint, TrendMeta, TrendResponse, VulnSearchResult, VulnSummary,
+    AuditEntry, CleanupHistoryEntry, ClusterInfo, ComponentSearchResult, ComponentUpdate,
+    CveReportMatch, DiffComponent, FullReport, ImageLocation, NamespaceSummary, NoteEntry,
+    RegistryStat, ReportMeta, SbomDiff, Stats, Suppression, TopImage, TrendDataPoint, TrendMeta,
+    TrendPoint, TrendResponse, VulnSearchResult, VulnSummary,
 };
 
 /// OpenAPI documentation
@@ -58,15 +68,18 @@ use crate::storage::{
         handlers::receive_report,
         handlers::list_vulnerability_reports,
         handlers::search_vulnerabilities,
+        handlers::search_reports_by_cve,
         handlers::suggest_vulnerabilities,
         handlers::get_vulnerability_report,
         handlers::list_sbom_reports,
         handlers::search_sbom_components,
         handlers::suggest_sbom_components,
         handlers::get_sbom_report,
+        handlers::diff_sbom_reports,
         handlers::list_clusters,
         handlers::get_stats,
         handlers::list_namespaces,
+        handlers::get_namespace_summary,
         handlers::delete_report,
         handlers::update_notes,
         handlers::get_watcher_status,
@@ -74,9 +87,16 @@ use crate::storage::{
         handlers::get_status,
         handlers::get_config,
         handlers::get_dashboard_trends,
+        handlers::get_severity_trend,
+        handlers::get_top_images,
+        handlers::get_registry_stats,
+        export_handlers::export_reports,
+        notes_handlers::export_notes,
+        notes_handlers::import_notes,
         admin_handlers::list_api_logs,
         admin_handlers::get_api_log_stats,
         admin_handlers::cleanup_api_logs,
+        admin_handlers::list_audit_log,
         admin_handlers::admin_info,
         alert_handlers::list_alerts,
         alert_handlers::get_alert,
@@ -85,6 +105,9 @@ use crate::storage::{
         alert_handlers::delete_alert,
         alert_handlers::preview_alert,
         alert_handlers::test_alert_draft,
+        suppression_handlers::list_suppressions,
+        suppression_handlers::create_suppression,
+        suppression_handlers::delete_suppression,
         cluster_handlers::list_registered_clusters,
         cluster_handlers::register_cluster,
         cluster_handlers::delete_registered_cluster,
@@ -108,6 +131,10 @@ use crate::storage::{
         FullReport,
         ComponentSearchResult,
         VulnSearchResult,
+        CveReportMatch,
+        SbomDiff,
+        DiffComponent,
+        ComponentUpdate,
         ClusterInfo,
         Stats,
         VulnSummary,
@@ -117,6 +144,11 @@ use crate::storage::{
         TrendResponse,
         TrendMeta,
         TrendDataPoint,
+        TrendPoint,
+        TopImage,
+        RegistryStat,
+        ImageLocation,
+        NamespaceSummary,
         CleanupHistoryEntry,
         cluster_handlers::RegisterClusterRequest,
         cluster_handlers::RegisteredCluster,
@@ -130,6 +162,10 @@ use crate::storage::{
         crate::alerts::types::Matchers,
         crate::alerts::types::Receiver,
         crate::alerts::types::SlackReceiver,
+        NoteEntry,
+        AuditEntry,
+        Suppression,
+        suppression_handlers::CreateSuppressionRequest,
     )),
     tags(
         (name = "Health", description = "Health check endpoints"),
@@ -144,10 +180,11 @@ use crate::storage::{
         (name = "Status", description = "Server runtime status endpoints"),
         (name = "Config", description = "Configuration endpoints"),
         (name = "Dashboard", description = "Dashboard trend analysis endpoints"),
-        (name = "Admin", description = "Admin API log management endpoints"),
+        (name = "Admin", description = "Admin API log and audit log endpoints"),
         (name = "Auth", description = "Authentication and token management endpoints"),
         (name = "Hub", description = "Cluster registration endpoints for hub-pull mode"),
         (name = "Alerts", description = "Alert rule management (ConfigMap-backed)"),
+        (name = "Suppressions", description = "CVE suppression allowlist"),
     )
 )]
 pub struct ApiDoc;
@@ -178,6 +215,9 @@ use crate::storage::Database;
 #[folder = "static/"]
 struct StaticAssets;
 
+/// Number of images exported in `trivy_collector_image_vulnerabilities`
+const METRICS_TOP_IMAGES: i64 = 50;
+
 pub async fn run(
     config: Config,
     health_server: HealthServer,
@@ -367,6 +407,55 @@ pub async fn run(
         }
     });
 
+    // Start background report pruning task (disabled when both retentions are 0)
+    if (config.prune_after_days > 0 || config.history_retention_days > 0)
+        && config.prune_interval_hours > 0
+    {
+        let db_prune = db.clone();
+        let metrics_prune = metrics.clone();
+        let prune_after_days = config.prune_after_days;
+        let history_retention_days = config.history_retention_days;
+        let mut shutdown_prune = shutdown.clone();
+        info!(
+            prune_after_days,
+            history_retention_days,
+            interval_hours = config.prune_interval_hours,
+            "Report pruning enabled"
+        );
+        let interval_secs = config.prune_interval_hours * 3600;
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if prune_after_days > 0 {
+                            match db_prune.prune_old_reports(prune_after_days).await {
+                                Ok(deleted) => {
+                                    if let Some(ref counter) = metrics_prune.pruned_reports_total {
+                                        counter.inc_by(deleted);
+                                    }
+                                    info!(deleted, days = prune_after_days, "Old reports pruned");
+                                }
+                                Err(e) => warn!(error = %e, "Report pruning failed"),
+                            }
+                        }
+                        if history_retention_days > 0 {
+                            match db_prune.prune_report_history(history_retention_days).await {
+                                Ok(deleted) => {
+                                    info!(deleted, days = history_retention_days, "Old report history pruned");
+                                }
+                                Err(e) => warn!(error = %e, "Report history pruning failed"),
+                            }
+                        }
+                    }
+                    _ = shutdown_prune.changed() => {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
     // Start background DB metrics refresh task (every 60 seconds)
     let db_metrics_refresh = db.clone();
     let metrics_refresh = metrics.clone();
@@ -400,6 +489,13 @@ pub async fn run(
                         && let Ok(count) = db_metrics_refresh.count_api_logs().await {
                             gauge.set(count);
                     }
+                    // Update vulnerability gauges used by the Grafana dashboard
+                    if let (Ok(by_cluster), Ok(images)) = (
+                        db_metrics_refresh.vulnerability_counts_by_cluster().await,
+                        db_metrics_refresh.top_vulnerable_images(METRICS_TOP_IMAGES).await,
+                    ) {
+                        metrics_refresh.set_vulnerabilities(&by_cluster, &images);
+                    }
                 }
                 _ = shutdown_metrics.changed() => {
                     break;
@@ -408,6 +504,37 @@ pub async fn run(
         }
     });
 
+    // Start critical vulnerability webhook task (disabled when no URL is set)
+    if !config.alert_webhook_url.is_empty() && config.alert_interval_mins > 0 {
+        let db_alerts = db.clone();
+        let mut webhook = crate::alerts::CriticalWebhook::new(
+            config.alert_webhook_url.clone(),
+            config.alert_webhook_secret.clone(),
+        );
+        let interval_secs = config.alert_interval_mins * 60;
+        let mut shutdown_alerts = shutdown.clone();
+        info!(
+            interval_mins = config.alert_interval_mins,
+            signed = !config.alert_webhook_secret.is_empty(),
+            "Critical vulnerability webhook enabled"
+        );
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = webhook.check(&db_alerts).await {
+                            warn!(error = %e, "Critical vulnerability webhook check failed");
+                        }
+                    }
+                    _ = shutdown_alerts.changed() => {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
     // Run server with graceful shutdown (with ConnectInfo for remote addr logging)
     axum::serve(
         listener,
@@ -465,6 +592,10 @@ fn build_router(state: AppState, auth_mode: auth::AuthMode) -> Router {
             "/api/v1/vulnerabilityreports/vulnerabilities/suggest",
             get(suggest_vulnerabilities),
         )
+        .route(
+            "/api/v1/vulnerabilityreports/vulnerabilities/{cve_id}/reports",
+            get(search_reports_by_cve),
+        )
         .route(
             "/api/v1/vulnerabilityreports/{cluster}/{namespace}/{name}",
             get(get_vulnerability_report),
@@ -484,12 +615,24 @@ fn build_router(state: AppState, auth_mode: auth::AuthMode) -> Router {
         )
         .route("/api/v1/clusters", get(list_clusters))
         .route("/api/v1/stats", get(get_stats))
+        .route("/api/v1/stats/registries", get(get_registry_stats))
         .route("/api/v1/namespaces", get(list_namespaces))
+        .route(
+            "/api/v1/namespaces/{namespace}/summary",
+            get(get_namespace_summary),
+        )
         .route("/api/v1/watcher/status", get(get_watcher_status))
         .route("/api/v1/version", get(get_version))
         .route("/api/v1/status", get(get_status))
         .route("/api/v1/config", get(get_config))
         .route("/api/v1/dashboard/trends", get(get_dashboard_trends))
+        .route("/api/v1/trends", get(get_severity_trend))
+        .route("/api/v1/top-images", get(get_top_images))
+        .route("/api/v1/sbom/diff", get(diff_sbom_reports))
+        .route(
+            "/api/v1/reports/export",
+            get(export_handlers::export_reports),
+        )
         .route(
             "/api/v1/reports/{cluster}/{report_type}/{namespace}/{name}",
             delete(delete_report),
@@ -515,6 +658,7 @@ fn build_router(state: AppState, auth_mode: auth::AuthMode) -> Router {
             "/api/v1/admin/logs/stats",
             get(admin_handlers::get_api_log_stats),
         )
+        .route("/api/v1/admin/audit", get(admin_handlers::list_audit_log))
         .route("/api/v1/admin/info", get(admin_handlers::admin_info))
         // Alert rules
         .route(
@@ -535,6 +679,19 @@ fn build_router(state: AppState, auth_mode: auth::AuthMode) -> Router {
                 .put(alert_handlers::update_alert)
                 .delete(alert_handlers::delete_alert),
         )
+        // Bulk notes import/export
+        .route("/api/v1/notes/export", get(notes_handlers::export_notes))
+        .route("/api/v1/notes/import", post(notes_handlers::import_notes))
+        // Suppression allowlist
+        .route(
+            "/api/v1/suppressions",
+            get(suppression_handlers::list_suppressions)
+                .post(suppression_handlers::create_suppression),
+        )
+        .route(
+            "/api/v1/suppressions/{id}",
+            delete(suppression_handlers::delete_suppression),
+        )
         // Hub-pull cluster registration
         .route(
             "/api/v1/hub/clusters",
@@ -810,6 +967,199 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_build_router_vuln_reports_cursor() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/vulnerabilityreports?cursor=&limit=10")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 0);
+        assert!(json.get("next_cursor").is_none());
+
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/vulnerabilityreports?cursor=not-a-cursor")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_build_router_notes_import_export() {
+        let app = create_router_no_auth().await;
+        let import = |body: &'static str| {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/api/v1/notes/import")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        // Empty import succeeds
+        let resp = app.clone().oneshot(import("[]")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Unknown report rolls back with 400
+        let resp = app
+            .clone()
+            .oneshot(import(
+                r#"[{"cluster":"prod","namespace":"default","name":"missing","report_type":"vulnerabilityreport","notes":"x"}]"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["index"], 0);
+
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/notes/export")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json, serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_build_router_suppressions_crud() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/suppressions")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"cve_id":"CVE-2023-44487","image_pattern":"nginx:*","reason":"HTTP/2 disabled"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(created["created_by"], "anonymous");
+        let id = created["id"].as_i64().unwrap();
+
+        let resp = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/suppressions")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 1);
+
+        let resp = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/api/v1/suppressions/{id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/api/v1/suppressions/{id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Create and the successful delete are audited; the 404 is not
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/admin/audit")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let operations: Vec<_> = json["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| {
+                (
+                    e["operation"].as_str().unwrap(),
+                    e["target"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        let target = format!("suppression/{id}");
+        assert_eq!(
+            operations,
+            vec![
+                ("delete_suppression", target.as_str()),
+                ("create_suppression", target.as_str()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_router_suppressions_rejects_invalid() {
+        let app = create_router_no_auth().await;
+        for body in [
+            r#"{"cve_id":"  "}"#,
+            r#"{"cve_id":"CVE-2023-44487","expires_at":"2020-01-01T00:00:00Z"}"#,
+        ] {
+            let resp = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .method("POST")
+                        .uri("/api/v1/suppressions")
+                        .header("content-type", "application/json")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{body}");
+        }
+    }
+
     #[tokio::test]
     async fn test_build_router_sbom_reports() {
         let app = create_router_no_auth().await;
@@ -855,6 +1205,21 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_build_router_admin_audit() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/admin/audit")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_build_router_admin_log_stats() {
         let app = create_router_no_auth().await;
@@ -885,6 +1250,85 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_build_router_severity_trend() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/trends?cluster=prod&days=7")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_build_router_top_images() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/top-images?limit=20")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_build_router_export_reports() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/reports/export?format=csv&report_type=vulnerabilityreport")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/csv; charset=utf-8"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_router_export_reports_invalid_format() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/reports/export?format=pdf")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_build_router_sbom_diff() {
+        let app = create_router_no_auth().await;
+        let resp = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/sbom/diff?cluster=prod&namespace=default&name=nginx&from=2026-01-01T00:00:00Z&to=2026-02-01T00:00:00Z")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_build_router_receive_report() {
         let app = create_router_no_auth().await;
@@ -11,7 +11,7 @@ use serde::Deserialize;
 use tracing::{error, info};
 
 use crate::auth::session::{AuthSession, SESSION_COOKIE_NAME};
-use crate::storage::ApiLogQuery;
+use crate::storage::{ApiLogQuery, AuditEntry};
 use crate::web::AppState;
 
 #[derive(Deserialize)]
@@ -167,6 +167,52 @@ pub async fn cleanup_api_logs(
     }
 }
 
+#[derive(Deserialize)]
+pub struct AuditQuery {
+    pub operation: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/v1/admin/audit — List recent audit records
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/audit",
+    tag = "Admin",
+    params(
+        ("operation" = Option<String>, Query, description = "Filter by operation (delete_report, update_notes, import_notes)"),
+        ("limit" = Option<i64>, Query, description = "Number of records (default 100, max 1000)"),
+    ),
+    responses(
+        (status = 200, description = "Audit records, newest first", body = Vec<AuditEntry>),
+        (status = 500, description = "Internal server error"),
+    )
+)]
+pub async fn list_audit_log(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    match state
+        .db
+        .list_audit_log(query.operation.as_deref(), limit)
+        .await
+    {
+        Ok(items) => Json(serde_json::json!({
+            "items": items,
+            "total": items.len(),
+        }))
+        .into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to list audit log");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Failed to list audit log"})),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// GET /api/v1/admin/info — Admin info summary
 #[utoipa::path(
     get,
@@ -203,7 +249,7 @@ mod tests {
     use tower::ServiceExt;
 
     use crate::auth::rbac::RbacPolicy;
-    use crate::storage::Database;
+    use crate::storage::{AuditCaller, Database};
     use crate::web::state::{AppState, ConfigInfo, RuntimeInfo, WatcherStatus};
 
     async fn create_test_state() -> AppState {
@@ -308,6 +354,53 @@ mod tests {
         assert_eq!(json["total"], 1);
     }
 
+    #[tokio::test]
+    async fn test_list_audit_log_handler() {
+        let state = create_test_state().await;
+        let caller = |identity: &str| AuditCaller {
+            identity: identity.to_string(),
+            claimed: None,
+        };
+        state
+            .db
+            .record_audit(
+                "delete_report",
+                "prod/vulnerabilityreport/default/a",
+                &caller("alice"),
+            )
+            .await
+            .unwrap();
+        state
+            .db
+            .record_audit(
+                "update_notes",
+                "prod/vulnerabilityreport/default/b",
+                &caller("bob"),
+            )
+            .await
+            .unwrap();
+
+        let app = Router::new()
+            .route("/api/v1/admin/audit", get(list_audit_log))
+            .with_state(state);
+
+        let resp = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/v1/admin/audit?operation=update_notes")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["total"], 1);
+        assert_eq!(json["items"][0]["caller"], "bob");
+    }
+
     #[tokio::test]
     async fn test_get_api_log_stats_handler() {
         let state = create_test_state().await;
@@ -372,7 +372,7 @@ fn unavailable() -> axum::response::Response {
         .into_response()
 }
 
-fn current_user(jar: &PrivateCookieJar) -> String {
+pub(super) fn current_user(jar: &PrivateCookieJar) -> String {
     jar.get(SESSION_COOKIE_NAME)
         .and_then(|c| serde_json::from_str::<AuthSession>(c.value()).ok())
         .and_then(|s| {
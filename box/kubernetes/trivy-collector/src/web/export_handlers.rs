@@ -0,0 +1,412 @@
+//! Report export handlers (CSV and XLSX)
+
+use std::borrow::Cow;
+
+use anyhow::Result;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use serde::Deserialize;
+use tracing::{debug, error};
+use utoipa::IntoParams;
+
+use super::types::ListQuery;
+use crate::storage::{QueryParams, ReportMeta};
+use crate::web::AppState;
+
+/// Default row limit when `limit` is not given
+const DEFAULT_EXPORT_LIMIT: i64 = 100_000;
+
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+const XLSX_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// Column headers shared by CSV and XLSX exports
+const COLUMNS: [&str; 15] = [
+    "cluster",
+    "namespace",
+    "name",
+    "app",
+    "image",
+    "report_type",
+    "critical",
+    "high",
+    "medium",
+    "low",
+    "unknown",
+    "components_count",
+    "received_at",
+    "updated_at",
+    "notes",
+];
+
+/// Query parameters for the export endpoint
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// Output format: "csv" or "xlsx" (default: csv)
+    #[param(example = "csv")]
+    pub format: Option<String>,
+    /// Report type: "vulnerabilityreport" or "sbomreport" (default: vulnerabilityreport)
+    #[param(example = "vulnerabilityreport")]
+    pub report_type: Option<String>,
+    /// Same filters as the list endpoints. `limit` defaults to 100000 here.
+    #[serde(flatten)]
+    pub filters: ListQuery,
+}
+
+impl ExportQuery {
+    fn to_query_params(&self) -> QueryParams {
+        let mut params = self.filters.to_query_params();
+        params.limit = Some(params.limit.unwrap_or(DEFAULT_EXPORT_LIMIT));
+        params
+    }
+}
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    fn parse(value: Option<&str>) -> Option<Self> {
+        match value.unwrap_or("csv").to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => CSV_CONTENT_TYPE,
+            Self::Xlsx => XLSX_CONTENT_TYPE,
+        }
+    }
+}
+
+/// GET /api/v1/reports/export — Download reports as CSV or XLSX
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/export",
+    tag = "Reports",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "Report export file (text/csv or xlsx)"),
+        (status = 400, description = "Invalid format or report type"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn export_reports(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let Some(format) = ExportFormat::parse(query.format.as_deref()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "format must be 'csv' or 'xlsx'"})),
+        )
+            .into_response();
+    };
+    let report_type = query
+        .report_type
+        .as_deref()
+        .unwrap_or("vulnerabilityreport");
+    if report_type != "vulnerabilityreport" && report_type != "sbomreport" {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "report_type must be 'vulnerabilityreport' or 'sbomreport'"
+            })),
+        )
+            .into_response();
+    }
+
+    let params = query.to_query_params();
+    let reports = match state.db.query_reports(report_type, &params).await {
+        Ok((reports, _)) => reports,
+        Err(e) => {
+            error!(error = %e, "Failed to query reports for export");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    let body = match format {
+        ExportFormat::Csv => reports_to_csv(&reports),
+        ExportFormat::Xlsx => reports_to_xlsx(&reports, report_type),
+    };
+    let body = match body {
+        Ok(body) => body,
+        Err(e) => {
+            error!(error = %e, "Failed to render report export");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    debug!(
+        report_type = %report_type,
+        format = format.extension(),
+        rows = reports.len(),
+        "Reports exported"
+    );
+
+    let filename = format!(
+        "{}s-{}.{}",
+        report_type,
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Severity counts of a report, zero when absent
+fn severity_counts(report: &ReportMeta) -> [i64; 5] {
+    report
+        .summary
+        .as_ref()
+        .map_or([0; 5], |s| [s.critical, s.high, s.medium, s.low, s.unknown])
+}
+
+/// Neutralize a text cell that a spreadsheet would evaluate as a formula.
+///
+/// Cluster, image and notes values come from workloads and users, so a
+/// value such as `=HYPERLINK(...)` is prefixed with `'` to be shown as text.
+fn escape_formula(value: &str) -> Cow<'_, str> {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        Cow::Owned(format!("'{value}"))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Render reports as CSV, one row per report
+fn reports_to_csv(reports: &[ReportMeta]) -> Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(COLUMNS)?;
+
+    for report in reports {
+        let counts = severity_counts(report).map(|c| c.to_string());
+        let components = report.components_count.unwrap_or(0).to_string();
+        writer.write_record([
+            escape_formula(&report.cluster).as_ref(),
+            escape_formula(&report.namespace).as_ref(),
+            escape_formula(&report.name).as_ref(),
+            escape_formula(&report.app).as_ref(),
+            escape_formula(&report.image).as_ref(),
+            escape_formula(&report.report_type).as_ref(),
+            counts[0].as_str(),
+            counts[1].as_str(),
+            counts[2].as_str(),
+            counts[3].as_str(),
+            counts[4].as_str(),
+            components.as_str(),
+            escape_formula(&report.received_at).as_ref(),
+            escape_formula(&report.updated_at).as_ref(),
+            escape_formula(&report.notes).as_ref(),
+        ])?;
+    }
+
+    writer.into_inner().map_err(|e| e.into_error().into())
+}
+
+/// Render reports as an XLSX workbook.
+///
+/// Vulnerability reports get one sheet per severity level listing the
+/// reports with at least one finding of that severity. SBOM reports are
+/// written to a single sheet.
+fn reports_to_xlsx(reports: &[ReportMeta], report_type: &str) -> Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold();
+
+    if report_type == "vulnerabilityreport" {
+        for (index, sheet) in ["Critical", "High", "Medium", "Low", "Unknown"]
+            .into_iter()
+            .enumerate()
+        {
+            let rows = reports.iter().filter(|r| severity_counts(r)[index] > 0);
+            write_sheet(workbook.add_worksheet(), sheet, &header, rows)?;
+        }
+    } else {
+        write_sheet(workbook.add_worksheet(), "SBOM", &header, reports.iter())?;
+    }
+
+    Ok(workbook.save_to_buffer()?)
+}
+
+/// Write a header row followed by one row per report
+fn write_sheet<'a>(
+    worksheet: &mut Worksheet,
+    name: &str,
+    header: &Format,
+    reports: impl Iterator<Item = &'a ReportMeta>,
+) -> Result<()> {
+    worksheet.set_name(name)?;
+    for (col, title) in COLUMNS.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *title, header)?;
+    }
+
+    for (index, report) in reports.enumerate() {
+        let row = index as u32 + 1;
+        worksheet.write_string(row, 0, escape_formula(&report.cluster))?;
+        worksheet.write_string(row, 1, escape_formula(&report.namespace))?;
+        worksheet.write_string(row, 2, escape_formula(&report.name))?;
+        worksheet.write_string(row, 3, escape_formula(&report.app))?;
+        worksheet.write_string(row, 4, escape_formula(&report.image))?;
+        worksheet.write_string(row, 5, escape_formula(&report.report_type))?;
+        for (offset, count) in severity_counts(report).into_iter().enumerate() {
+            worksheet.write_number(row, 6 + offset as u16, count as f64)?;
+        }
+        worksheet.write_number(row, 11, report.components_count.unwrap_or(0) as f64)?;
+        worksheet.write_string(row, 12, escape_formula(&report.received_at))?;
+        worksheet.write_string(row, 13, escape_formula(&report.updated_at))?;
+        worksheet.write_string(row, 14, escape_formula(&report.notes))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::VulnSummary;
+
+    fn report(name: &str, critical: i64, notes: &str) -> ReportMeta {
+        ReportMeta {
+            id: 1,
+            cluster: "prod".to_string(),
+            namespace: "default".to_string(),
+            name: name.to_string(),
+            app: "nginx".to_string(),
+            image: "nginx:1.25".to_string(),
+            report_type: "vulnerabilityreport".to_string(),
+            summary: Some(VulnSummary {
+                critical,
+                high: 2,
+                medium: 0,
+                low: 1,
+                unknown: 0,
+            }),
+            components_count: None,
+            received_at: "2026-01-01T00:00:00+00:00".to_string(),
+            updated_at: "2026-01-02T00:00:00+00:00".to_string(),
+            notes: notes.to_string(),
+            notes_created_at: None,
+            notes_updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_export_format_parse() {
+        assert_eq!(ExportFormat::parse(None), Some(ExportFormat::Csv));
+        assert_eq!(ExportFormat::parse(Some("XLSX")), Some(ExportFormat::Xlsx));
+        assert_eq!(ExportFormat::parse(Some("pdf")), None);
+    }
+
+    #[test]
+    fn test_reports_to_csv() {
+        let csv = reports_to_csv(&[report("nginx-vuln", 3, "tracked, see JIRA-1")]).unwrap();
+        let text = String::from_utf8(csv).unwrap();
+        let mut lines = text.lines();
+
+        assert_eq!(lines.next().unwrap(), COLUMNS.join(","));
+        assert_eq!(
+            lines.next().unwrap(),
+            "prod,default,nginx-vuln,nginx,nginx:1.25,vulnerabilityreport,3,2,0,1,0,0,\
+             2026-01-01T00:00:00+00:00,2026-01-02T00:00:00+00:00,\"tracked, see JIRA-1\""
+        );
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn test_reports_to_xlsx() {
+        let reports = [report("a", 1, ""), report("b", 0, "")];
+        let xlsx = reports_to_xlsx(&reports, "vulnerabilityreport").unwrap();
+        // XLSX files are ZIP archives
+        assert!(xlsx.starts_with(b"PK"));
+
+        let xlsx = reports_to_xlsx(&reports, "sbomreport").unwrap();
+        assert!(xlsx.starts_with(b"PK"));
+    }
+
+    #[test]
+    fn test_escape_formula() {
+        for value in ["=1+1", "+1", "-1", "@SUM(A1)", "\tx", "\rx"] {
+            assert_eq!(escape_formula(value), format!("'{value}"));
+        }
+        assert_eq!(escape_formula("nginx:1.25"), "nginx:1.25");
+        assert_eq!(escape_formula(""), "");
+    }
+
+    #[test]
+    fn test_reports_to_csv_escapes_formulas() {
+        let csv = reports_to_csv(&[report("=1+1", 0, "@SUM(A1)")]).unwrap();
+        let text = String::from_utf8(csv).unwrap();
+        let row = text.lines().nth(1).unwrap();
+        assert!(row.starts_with("prod,default,'=1+1,"));
+        assert!(row.ends_with(",'@SUM(A1)"));
+    }
+
+    #[test]
+    fn test_export_query_default_limit() {
+        let uri: axum::http::Uri =
+            "/api/v1/reports/export?format=xlsx&cluster=prod&severity=critical,%20high"
+                .parse()
+                .unwrap();
+        let Query(query) = Query::<ExportQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.format.as_deref(), Some("xlsx"));
+        let params = query.to_query_params();
+        assert_eq!(params.limit, Some(DEFAULT_EXPORT_LIMIT));
+        assert_eq!(params.cluster.as_deref(), Some("prod"));
+        assert_eq!(
+            params.severity,
+            Some(vec!["critical".to_string(), "high".to_string()])
+        );
+
+        let uri: axum::http::Uri = "/api/v1/reports/export?limit=50".parse().unwrap();
+        let Query(query) = Query::<ExportQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.to_query_params().limit, Some(50));
+    }
+
+    #[test]
+    fn test_export_query_forwards_component_filters() {
+        let uri: axum::http::Uri =
+            "/api/v1/reports/export?report_type=sbomreport&min_components=10&max_components=20&component=log4j"
+                .parse()
+                .unwrap();
+        let Query(query) = Query::<ExportQuery>::try_from_uri(&uri).unwrap();
+        let params = query.to_query_params();
+        assert_eq!(params.min_components, Some(10));
+        assert_eq!(params.max_components, Some(20));
+        assert_eq!(params.component.as_deref(), Some("log4j"));
+    }
+}
@@ -1,29 +1,52 @@
 //! HTTP request handlers for API endpoints
 
 use axum::{
-    Json,
+    Extension, Json,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
 };
 use std::sync::atomic::Ordering;
 use tracing::{debug, error, info};
 
+use crate::auth::session::AuthSession;
 use crate::collector::types::{ReportEvent, ReportEventType};
 use crate::config::env;
 use crate::metrics::ReportReceivedLabels;
 use crate::storage::{
-    ClusterInfo, ComponentSearchResult, FullReport, ReportMeta, Stats, TrendResponse,
-    VulnSearchResult,
+    AuditCaller, ClusterInfo, ComponentSearchResult, CveReportMatch, FullReport, NamespaceSummary,
+    RegistryStat, ReportMeta, SbomDiff, Stats, TopImage, TrendPoint, TrendResponse,
+    VulnSearchResult, decode_cursor, report_target,
 };
 
 use super::state::AppState;
 use super::types::{
     ComponentSearchQuery, ComponentSuggestQuery, ConfigItem, ConfigResponse, ErrorResponse,
-    HealthResponse, ListQuery, ListResponse, StatusResponse, TrendQuery, UpdateNotesRequest,
+    HealthResponse, ListQuery, ListResponse, NamespaceSummaryQuery, SbomDiffQuery,
+    SeverityTrendQuery, StatusResponse, TopImagesQuery, TrendQuery, UpdateNotesRequest,
     VersionResponse, VulnSearchQuery, VulnSuggestQuery, WatcherInfo, WatcherStatusResponse,
 };
 
+/// Header a client may send to name who is behind the request
+const AUDIT_CALLER_HEADER: &str = "x-audit-caller";
+
+/// Caller recorded in the audit log. The identity is the authenticated user
+/// or token owner, or `anonymous`. The `X-Audit-Caller` header is client
+/// supplied, so it is only kept as an unverified claim next to it.
+pub(super) fn audit_caller(session: Option<&AuthSession>, headers: &HeaderMap) -> AuditCaller {
+    AuditCaller {
+        identity: session
+            .map(|s| s.email.clone().unwrap_or_else(|| s.sub.clone()))
+            .unwrap_or_else(|| "anonymous".to_string()),
+        claimed: headers
+            .get(AUDIT_CALLER_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string),
+    }
+}
+
 /// Health check endpoint for collectors
 #[utoipa::path(
     get,
@@ -150,6 +173,7 @@ pub async fn receive_report(
                     &event.payload.namespace,
                     &event.payload.name,
                     &event.payload.report_type,
+                    None,
                 )
                 .await
             {
@@ -187,6 +211,7 @@ pub async fn receive_report(
     params(ListQuery),
     responses(
         (status = 200, description = "List of vulnerability reports", body = ListResponse<ReportMeta>),
+        (status = 400, description = "Invalid cursor"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -194,25 +219,44 @@ pub async fn list_vulnerability_reports(
     State(state): State<AppState>,
     Query(query): Query<ListQuery>,
 ) -> impl IntoResponse {
+    list_reports(&state, "vulnerabilityreport", &query).await
+}
+
+/// Shared body of the report list endpoints
+async fn list_reports(state: &AppState, report_type: &str, query: &ListQuery) -> Response {
     let params = query.to_query_params();
+    if let Some(cursor) = params.cursor.as_deref()
+        && !cursor.is_empty()
+        && decode_cursor(cursor).is_none()
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "invalid cursor"})),
+        )
+            .into_response();
+    }
 
-    match state.db.query_reports("vulnerabilityreport", &params).await {
+    match state.db.query_reports(report_type, &params).await {
         Ok((reports, total)) => (
             StatusCode::OK,
             Json(ListResponse {
+                next_cursor: params.next_cursor(&reports),
                 items: reports,
                 total: total as usize,
             }),
-        ),
+        )
+            .into_response(),
         Err(e) => {
-            error!(error = %e, "Failed to query vulnerability reports");
+            error!(error = %e, report_type = %report_type, "Failed to query reports");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ListResponse {
+                Json(ListResponse::<ReportMeta> {
                     items: vec![],
                     total: 0,
+                    next_cursor: None,
                 }),
             )
+                .into_response()
         }
     }
 }
@@ -311,6 +355,44 @@ pub async fn search_vulnerabilities(
     }
 }
 
+/// List vulnerability reports affected by a CVE, with the matching vulnerability entries
+#[utoipa::path(
+    get,
+    path = "/api/v1/vulnerabilityreports/vulnerabilities/{cve_id}/reports",
+    tag = "Vulnerability Reports",
+    params(
+        ("cve_id" = String, Path, description = "CVE ID (case-insensitive exact match)", example = "CVE-2024-1234")
+    ),
+    responses(
+        (status = 200, description = "Reports containing the CVE", body = ListResponse<CveReportMatch>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn search_reports_by_cve(
+    State(state): State<AppState>,
+    Path(cve_id): Path<String>,
+) -> impl IntoResponse {
+    match state.db.search_by_cve(cve_id.trim()).await {
+        Ok(results) => {
+            let total = results.len();
+            (
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "items": results,
+                    "total": total,
+                })),
+            )
+        }
+        Err(e) => {
+            error!(error = %e, cve_id = %cve_id, "Failed to search reports by CVE");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
 /// Suggest vulnerability IDs (autocomplete)
 #[utoipa::path(
     get,
@@ -353,6 +435,7 @@ pub async fn suggest_vulnerabilities(
     params(ListQuery),
     responses(
         (status = 200, description = "List of SBOM reports", body = ListResponse<ReportMeta>),
+        (status = 400, description = "Invalid cursor"),
         (status = 500, description = "Internal server error")
     )
 )]
@@ -360,27 +443,7 @@ pub async fn list_sbom_reports(
     State(state): State<AppState>,
     Query(query): Query<ListQuery>,
 ) -> impl IntoResponse {
-    let params = query.to_query_params();
-
-    match state.db.query_reports("sbomreport", &params).await {
-        Ok((reports, total)) => (
-            StatusCode::OK,
-            Json(ListResponse {
-                items: reports,
-                total: total as usize,
-            }),
-        ),
-        Err(e) => {
-            error!(error = %e, "Failed to query SBOM reports");
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ListResponse {
-                    items: vec![],
-                    total: 0,
-                }),
-            )
-        }
-    }
+    list_reports(&state, "sbomreport", &query).await
 }
 
 /// Search SBOM components across all reports
@@ -535,6 +598,7 @@ pub async fn list_clusters(State(state): State<AppState>) -> impl IntoResponse {
                 Json(ListResponse {
                     items: clusters,
                     total,
+                    next_cursor: None,
                 }),
             )
         }
@@ -545,6 +609,7 @@ pub async fn list_clusters(State(state): State<AppState>) -> impl IntoResponse {
                 Json(ListResponse {
                     items: vec![],
                     total: 0,
+                    next_cursor: None,
                 }),
             )
         }
@@ -611,6 +676,7 @@ pub async fn list_namespaces(
                 Json(ListResponse {
                     items: namespaces,
                     total,
+                    next_cursor: None,
                 }),
             )
         }
@@ -621,12 +687,53 @@ pub async fn list_namespaces(
                 Json(ListResponse {
                     items: vec![],
                     total: 0,
+                    next_cursor: None,
                 }),
             )
         }
     }
 }
 
+/// Get aggregated vulnerability counts for a namespace
+#[utoipa::path(
+    get,
+    path = "/api/v1/namespaces/{namespace}/summary",
+    tag = "Namespaces",
+    params(
+        ("namespace" = String, Path, description = "Namespace name"),
+        NamespaceSummaryQuery
+    ),
+    responses(
+        (status = 200, description = "Severity totals and worst images for the namespace", body = NamespaceSummary),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_namespace_summary(
+    State(state): State<AppState>,
+    Path(namespace): Path<String>,
+    Query(query): Query<NamespaceSummaryQuery>,
+) -> impl IntoResponse {
+    match state
+        .db
+        .get_namespace_summary(&query.cluster, &namespace)
+        .await
+    {
+        Ok(summary) => (StatusCode::OK, Json(serde_json::json!(summary))),
+        Err(e) => {
+            error!(
+                error = %e,
+                cluster = %query.cluster,
+                namespace = %namespace,
+                "Failed to get namespace summary"
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
 /// Delete report
 #[utoipa::path(
     delete,
@@ -646,15 +753,20 @@ pub async fn list_namespaces(
 )]
 pub async fn delete_report(
     State(state): State<AppState>,
+    session: Option<Extension<AuthSession>>,
+    headers: HeaderMap,
     Path((cluster, report_type, namespace, name)): Path<(String, String, String, String)>,
 ) -> impl IntoResponse {
+    let caller = audit_caller(session.as_deref(), &headers);
     match state
         .db
-        .delete_report(&cluster, &namespace, &name, &report_type)
+        .delete_report(&cluster, &namespace, &name, &report_type, Some(&caller))
         .await
     {
         Ok(deleted) => {
             if deleted {
+                let target = report_target(&cluster, &report_type, &namespace, &name);
+                info!(report = %target, by = %caller.identity, "Report deleted");
                 (
                     StatusCode::OK,
                     Json(serde_json::json!({"status": "deleted"})),
@@ -696,12 +808,22 @@ pub async fn delete_report(
 )]
 pub async fn update_notes(
     State(state): State<AppState>,
+    session: Option<Extension<AuthSession>>,
+    headers: HeaderMap,
     Path((cluster, report_type, namespace, name)): Path<(String, String, String, String)>,
     Json(request): Json<UpdateNotesRequest>,
 ) -> impl IntoResponse {
+    let caller = audit_caller(session.as_deref(), &headers);
     match state
         .db
-        .update_notes(&cluster, &namespace, &name, &report_type, &request.notes)
+        .update_notes(
+            &cluster,
+            &namespace,
+            &name,
+            &report_type,
+            &request.notes,
+            Some(&caller),
+        )
         .await
     {
         Ok(updated) => {
@@ -711,6 +833,7 @@ pub async fn update_notes(
                     report_type = %report_type,
                     namespace = %namespace,
                     name = %name,
+                    by = %caller.identity,
                     "Report notes updated"
                 );
                 (
@@ -931,6 +1054,138 @@ pub async fn get_dashboard_trends(
     }
 }
 
+/// Diff the SBOM of a report between two scan timestamps
+#[utoipa::path(
+    get,
+    path = "/api/v1/sbom/diff",
+    tag = "SBOM Reports",
+    params(SbomDiffQuery),
+    responses(
+        (status = 200, description = "Components added, removed, and updated between the two scans", body = SbomDiff),
+        (status = 400, description = "Invalid or reversed timestamps", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn diff_sbom_reports(
+    State(state): State<AppState>,
+    Query(query): Query<SbomDiffQuery>,
+) -> impl IntoResponse {
+    let parse =
+        |ts: &str| chrono::DateTime::parse_from_rfc3339(ts).map(|t| t.with_timezone(&chrono::Utc));
+    let (Ok(from), Ok(to)) = (parse(&query.from), parse(&query.to)) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "from and to must be RFC 3339 timestamps"})),
+        );
+    };
+    if from > to {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "from must not be later than to"})),
+        );
+    }
+
+    match state
+        .db
+        .diff_sbom_reports(&query.cluster, &query.namespace, &query.name, from, to)
+        .await
+    {
+        Ok(diff) => (StatusCode::OK, Json(serde_json::json!(diff))),
+        Err(e) => {
+            error!(
+                error = %e,
+                cluster = %query.cluster,
+                namespace = %query.namespace,
+                name = %query.name,
+                "Failed to diff SBOM reports"
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
+/// Get daily severity totals for a cluster
+#[utoipa::path(
+    get,
+    path = "/api/v1/trends",
+    tag = "Dashboard",
+    params(SeverityTrendQuery),
+    responses(
+        (status = 200, description = "Daily severity totals, oldest first", body = Vec<TrendPoint>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_severity_trend(
+    State(state): State<AppState>,
+    Query(query): Query<SeverityTrendQuery>,
+) -> impl IntoResponse {
+    let days = query.get_days();
+
+    match state.db.get_severity_trend(&query.cluster, days).await {
+        Ok(points) => (StatusCode::OK, Json(serde_json::json!(points))),
+        Err(e) => {
+            error!(error = %e, cluster = %query.cluster, "Failed to get severity trend");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
+/// Get the images with the most critical and high vulnerabilities across all clusters
+#[utoipa::path(
+    get,
+    path = "/api/v1/top-images",
+    tag = "Dashboard",
+    params(TopImagesQuery),
+    responses(
+        (status = 200, description = "Images ordered by critical + high count, highest first", body = Vec<TopImage>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_top_images(
+    State(state): State<AppState>,
+    Query(query): Query<TopImagesQuery>,
+) -> impl IntoResponse {
+    match state.db.top_vulnerable_images(query.get_limit()).await {
+        Ok(images) => (StatusCode::OK, Json(serde_json::json!(images))),
+        Err(e) => {
+            error!(error = %e, "Failed to get top vulnerable images");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
+/// Get vulnerability totals grouped by image registry
+#[utoipa::path(
+    get,
+    path = "/api/v1/stats/registries",
+    tag = "Dashboard",
+    responses(
+        (status = 200, description = "Registries ordered by critical count, highest first", body = Vec<RegistryStat>),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+pub async fn get_registry_stats(State(state): State<AppState>) -> impl IntoResponse {
+    match state.db.get_registry_stats().await {
+        Ok(stats) => (StatusCode::OK, Json(serde_json::json!(stats))),
+        Err(e) => {
+            error!(error = %e, "Failed to get registry stats");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1007,12 +1262,19 @@ mod tests {
             )
             .route("/api/v1/clusters", get(list_clusters))
             .route("/api/v1/stats", get(get_stats))
+            .route("/api/v1/stats/registries", get(get_registry_stats))
             .route("/api/v1/namespaces", get(list_namespaces))
+            .route(
+                "/api/v1/namespaces/{namespace}/summary",
+                get(get_namespace_summary),
+            )
             .route("/api/v1/watcher/status", get(get_watcher_status))
             .route("/api/v1/version", get(get_version))
             .route("/api/v1/status", get(get_status))
             .route("/api/v1/config", get(get_config))
             .route("/api/v1/dashboard/trends", get(get_dashboard_trends))
+            .route("/api/v1/trends", get(get_severity_trend))
+            .route("/api/v1/top-images", get(get_top_images))
             .route(
                 "/api/v1/reports/{cluster}/{report_type}/{namespace}/{name}",
                 delete(delete_report),
@@ -1592,6 +1854,60 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn test_delete_report_writes_one_audit_record() {
+        let state = create_test_state().await;
+        seed_test_data(&state).await;
+        let app = create_test_router(state.clone());
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("DELETE")
+                    .uri("/api/v1/reports/prod/vulnerabilityreport/default/nginx-vuln")
+                    .header(AUDIT_CALLER_HEADER, "alice@example.com")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let entries = state.db.list_audit_log(None, 10).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].operation, "delete_report");
+        assert_eq!(
+            entries[0].target,
+            "prod/vulnerabilityreport/default/nginx-vuln"
+        );
+        // The header is only a claim; the request was not authenticated
+        assert_eq!(entries[0].caller, "anonymous");
+        assert_eq!(
+            entries[0].claimed_caller.as_deref(),
+            Some("alice@example.com")
+        );
+        assert!(chrono::DateTime::parse_from_rfc3339(&entries[0].created_at).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_report_not_found_is_not_audited() {
+        let state = create_test_state().await;
+        let app = create_test_router(state.clone());
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("DELETE")
+                    .uri("/api/v1/reports/prod/vulnerabilityreport/default/nonexistent")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(state.db.list_audit_log(None, 10).await.unwrap().is_empty());
+    }
+
     // ===== update_notes =====
 
     #[tokio::test]
@@ -1621,6 +1937,63 @@ mod tests {
         assert_eq!(json["status"], "updated");
     }
 
+    #[tokio::test]
+    async fn test_update_notes_writes_one_audit_record() {
+        let state = create_test_state().await;
+        seed_test_data(&state).await;
+        let app = create_test_router(state.clone());
+
+        let body = serde_json::json!({"notes": "Reviewed"});
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .method("PUT")
+                    .uri("/api/v1/reports/prod/vulnerabilityreport/default/nginx-vuln/notes")
+                    .header("content-type", "application/json")
+                    .body(axum::body::Body::from(
+                        serde_json::to_string(&body).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Without a session or X-Audit-Caller header the caller is anonymous
+        let entries = state.db.list_audit_log(None, 10).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].operation, "update_notes");
+        assert_eq!(
+            entries[0].target,
+            "prod/vulnerabilityreport/default/nginx-vuln"
+        );
+        assert_eq!(entries[0].caller, "anonymous");
+    }
+
+    #[test]
+    fn test_audit_caller_keeps_header_as_claim() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUDIT_CALLER_HEADER, "ci-bot".parse().unwrap());
+        let session = AuthSession {
+            sub: "sub-1".to_string(),
+            email: Some("alice@example.com".to_string()),
+            name: None,
+            preferred_username: None,
+            groups: vec![],
+            expires_at: i64::MAX,
+        };
+
+        let caller = audit_caller(Some(&session), &headers);
+        assert_eq!(caller.identity, "alice@example.com");
+        assert_eq!(caller.claimed.as_deref(), Some("ci-bot"));
+
+        // The header never stands in for an authenticated identity
+        let caller = audit_caller(None, &headers);
+        assert_eq!(caller.identity, "anonymous");
+        assert_eq!(caller.claimed.as_deref(), Some("ci-bot"));
+        assert_eq!(audit_caller(None, &HeaderMap::new()).claimed, None);
+    }
+
     #[tokio::test]
     async fn test_update_notes_not_found() {
         let state = create_test_state().await;
@@ -1789,4 +2162,132 @@ mod tests {
         assert!(json["meta"].is_object());
         assert!(json["series"].is_array());
     }
+
+    // ===== get_registry_stats =====
+
+    #[tokio::test]
+    async fn test_get_registry_stats() {
+        let state = create_test_state().await;
+        seed_test_data(&state).await;
+        let app = create_test_router(state);
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/stats/registries")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = response_json(response).await;
+        // Three nginx:1.25 vulnerability reports, all from docker.io
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "registry": "docker.io",
+                "image_count": 1,
+                "critical_count": 6,
+                "high_count": 15
+            }])
+        );
+    }
+
+    // ===== get_top_images =====
+
+    #[tokio::test]
+    async fn test_get_top_images() {
+        let state = create_test_state().await;
+        seed_test_data(&state).await;
+        let app = create_test_router(state);
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/top-images?limit=20")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = response_json(response).await;
+        let images = json.as_array().unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0]["image"], "nginx:1.25");
+        assert_eq!(images[0]["critical"], 6);
+        assert_eq!(images[0]["high"], 15);
+        assert_eq!(images[0]["report_count"], 3);
+        assert_eq!(images[0]["locations"].as_array().unwrap().len(), 3);
+    }
+
+    // ===== get_namespace_summary =====
+
+    #[tokio::test]
+    async fn test_get_namespace_summary() {
+        let state = create_test_state().await;
+        seed_test_data(&state).await;
+        let app = create_test_router(state);
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/namespaces/default/summary?cluster=prod")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = response_json(response).await;
+        assert_eq!(json["report_count"], 1);
+        assert_eq!(json["summary"]["critical"], 2);
+        assert_eq!(json["summary"]["high"], 5);
+        assert_eq!(json["top_images_by_critical"][0], "nginx:1.25");
+
+        // cluster is required
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/api/v1/namespaces/default/summary")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // ===== diff_sbom_reports =====
+
+    #[tokio::test]
+    async fn test_diff_sbom_reports_rejects_bad_ranges() {
+        let state = create_test_state().await;
+        let app = create_test_router(state);
+
+        for range in [
+            "from=yesterday&to=2026-02-01T00:00:00Z",
+            "from=2026-01-01T00:00:00Z&to=2026-02-01",
+            "from=2026-02-01T00:00:00Z&to=2026-01-01T00:00:00Z",
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .uri(format!(
+                            "/api/v1/sbom/diff?cluster=prod&namespace=default&name=nginx&{range}"
+                        ))
+                        .body(axum::body::Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{range}");
+        }
+    }
 }
@@ -0,0 +1,79 @@
+//! HTTP handlers for bulk report notes import/export (`/api/v1/notes/*`).
+
+use axum::{
+    Extension, Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tracing::{error, info, warn};
+
+use super::handlers::audit_caller;
+use crate::auth::session::AuthSession;
+use crate::storage::{NoteEntry, NotesImportError};
+use crate::web::AppState;
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/notes/export",
+    tag = "Reports",
+    responses(
+        (status = 200, description = "Every report with non-empty notes", body = Vec<NoteEntry>),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn export_notes(State(state): State<AppState>) -> Response {
+    match state.db.export_notes().await {
+        Ok(entries) => Json(entries).into_response(),
+        Err(e) => {
+            error!(error = %e, "Failed to export notes");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/notes/import",
+    tag = "Reports",
+    request_body = Vec<NoteEntry>,
+    responses(
+        (status = 200, description = "All notes imported"),
+        (status = 400, description = "An entry is invalid or its report does not exist; nothing was imported"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn import_notes(
+    State(state): State<AppState>,
+    session: Option<Extension<AuthSession>>,
+    headers: HeaderMap,
+    Json(entries): Json<Vec<NoteEntry>>,
+) -> Response {
+    let caller = audit_caller(session.as_deref(), &headers);
+    match state.db.import_notes(&entries, Some(&caller)).await {
+        Ok(imported) => {
+            info!(imported, by = %caller.identity, "Report notes imported");
+            Json(serde_json::json!({"imported": imported})).into_response()
+        }
+        Err(NotesImportError::Invalid { index, reason }) => {
+            warn!(index, reason = %reason, "Notes import rejected");
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": reason, "index": index})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to import notes");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+}
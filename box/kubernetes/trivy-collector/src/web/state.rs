@@ -235,6 +235,12 @@ mod tests {
             watch_local: true,
             hub_secret_namespace: String::new(),
             external_url: String::new(),
+            alert_webhook_url: String::new(),
+            alert_webhook_secret: String::new(),
+            alert_interval_mins: 5,
+            prune_after_days: 0,
+            prune_interval_hours: 24,
+            history_retention_days: 90,
             auth_mode: "keycloak".to_string(),
             oidc_issuer_url: None,
             oidc_client_id: None,
@@ -278,6 +284,12 @@ mod tests {
             watch_local: false,
             hub_secret_namespace: String::new(),
             external_url: String::new(),
+            alert_webhook_url: String::new(),
+            alert_webhook_secret: String::new(),
+            alert_interval_mins: 5,
+            prune_after_days: 0,
+            prune_interval_hours: 24,
+            history_retention_days: 90,
             auth_mode: "none".to_string(),
             oidc_issuer_url: None,
             oidc_client_id: None,
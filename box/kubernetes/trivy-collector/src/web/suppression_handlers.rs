@@ -0,0 +1,199 @@
+//! HTTP handlers for `/api/v1/suppressions` (CVE suppression allowlist CRUD).
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::PrivateCookieJar;
+use serde::Deserialize;
+use tracing::{error, info};
+use utoipa::{IntoParams, ToSchema};
+
+use super::alert_handlers::current_user;
+use super::handlers::audit_caller;
+use crate::auth::session::AuthSession;
+use crate::storage::Suppression;
+use crate::web::AppState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSuppressionRequest {
+    /// CVE (or other vulnerability) ID to suppress
+    #[schema(example = "CVE-2023-44487")]
+    pub cve_id: String,
+    /// Image glob the suppression applies to (default: every image)
+    #[schema(example = "nginx:*")]
+    pub image_pattern: Option<String>,
+    /// Why the finding is accepted
+    #[serde(default)]
+    pub reason: String,
+    /// RFC 3339 expiry; omit for a permanent suppression
+    #[schema(example = "2026-12-31T00:00:00Z")]
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SuppressionListQuery {
+    /// Include suppressions whose `expires_at` has passed
+    #[serde(default)]
+    pub include_expired: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/suppressions",
+    tag = "Suppressions",
+    params(SuppressionListQuery),
+    responses((status = 200, description = "Suppression allowlist", body = Vec<Suppression>))
+)]
+pub async fn list_suppressions(
+    State(state): State<AppState>,
+    Query(query): Query<SuppressionListQuery>,
+) -> impl IntoResponse {
+    match state.db.list_suppressions(query.include_expired).await {
+        Ok(items) => Json(serde_json::json!({
+            "total": items.len(),
+            "items": items,
+        }))
+        .into_response(),
+        Err(e) => internal_error("Failed to list suppressions", e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/suppressions",
+    tag = "Suppressions",
+    request_body = CreateSuppressionRequest,
+    responses(
+        (status = 201, description = "Created", body = Suppression),
+        (status = 400, description = "Invalid input")
+    )
+)]
+pub async fn create_suppression(
+    State(state): State<AppState>,
+    cookie_jar: PrivateCookieJar,
+    session: Option<Extension<AuthSession>>,
+    headers: HeaderMap,
+    Json(body): Json<CreateSuppressionRequest>,
+) -> impl IntoResponse {
+    let cve_id = body.cve_id.trim();
+    if cve_id.is_empty() {
+        return bad_request("cve_id is required");
+    }
+    let image_pattern = body
+        .image_pattern
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .unwrap_or("*");
+    let expires_at = match body.expires_at.as_deref().map(parse_expires_at) {
+        None => None,
+        Some(Ok(ts)) => Some(ts),
+        Some(Err(msg)) => return bad_request(msg),
+    };
+
+    let user = current_user(&cookie_jar);
+    let caller = audit_caller(session.as_deref(), &headers);
+    match state
+        .db
+        .create_suppression(
+            cve_id,
+            image_pattern,
+            body.reason.trim(),
+            expires_at.as_deref(),
+            &user,
+            Some(&caller),
+        )
+        .await
+    {
+        Ok(suppression) => {
+            info!(
+                suppression_id = suppression.id,
+                cve_id = %suppression.cve_id,
+                image_pattern = %suppression.image_pattern,
+                by = %user,
+                "Suppression created"
+            );
+            (StatusCode::CREATED, Json(suppression)).into_response()
+        }
+        Err(e) => internal_error("Failed to create suppression", e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/suppressions/{id}",
+    tag = "Suppressions",
+    params(("id" = i64, Path, description = "Suppression ID")),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn delete_suppression(
+    State(state): State<AppState>,
+    cookie_jar: PrivateCookieJar,
+    session: Option<Extension<AuthSession>>,
+    headers: HeaderMap,
+    Path(id): Path<i64>,
+) -> impl IntoResponse {
+    let caller = audit_caller(session.as_deref(), &headers);
+    match state.db.delete_suppression(id, Some(&caller)).await {
+        Ok(true) => {
+            info!(suppression_id = id, by = %current_user(&cookie_jar), "Suppression deleted");
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("suppression {} not found", id)})),
+        )
+            .into_response(),
+        Err(e) => internal_error("Failed to delete suppression", e),
+    }
+}
+
+/// Normalize an RFC 3339 expiry to UTC; expiries in the past are rejected.
+fn parse_expires_at(raw: &str) -> Result<String, &'static str> {
+    let ts = chrono::DateTime::parse_from_rfc3339(raw.trim())
+        .map_err(|_| "expires_at must be an RFC 3339 timestamp")?
+        .with_timezone(&chrono::Utc);
+    if ts <= chrono::Utc::now() {
+        return Err("expires_at must be in the future");
+    }
+    Ok(ts.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+}
+
+fn bad_request(msg: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({"error": msg})),
+    )
+        .into_response()
+}
+
+fn internal_error(context: &str, e: anyhow::Error) -> Response {
+    error!(error = %e, "{}", context);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": e.to_string()})),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expires_at() {
+        assert_eq!(
+            parse_expires_at("2999-01-01T09:00:00+09:00").unwrap(),
+            "2999-01-01T00:00:00Z"
+        );
+        assert!(parse_expires_at("2020-01-01T00:00:00Z").is_err());
+        assert!(parse_expires_at("tomorrow").is_err());
+    }
+}
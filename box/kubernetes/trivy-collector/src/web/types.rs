@@ -30,12 +30,50 @@ pub struct ListQuery {
     /// Filter by SBOM component name (partial match, searches within report JSON)
     #[param(example = "log4j")]
     pub component: Option<String>,
+    /// Only reports with at least this many SBOM components
+    #[param(example = 1000)]
+    #[serde(default, deserialize_with = "optional_i64")]
+    pub min_components: Option<i64>,
+    /// Only reports with at most this many SBOM components
+    #[param(example = 5000)]
+    #[serde(default, deserialize_with = "optional_i64")]
+    pub max_components: Option<i64>,
     /// Limit results (default: 1000)
     #[param(example = 100)]
+    #[serde(default, deserialize_with = "optional_i64")]
     pub limit: Option<i64>,
-    /// Pagination offset
+    /// Pagination offset. Deprecated: use `cursor`; ignored when `cursor` is set
     #[param(example = 0)]
+    #[serde(default, deserialize_with = "optional_i64")]
     pub offset: Option<i64>,
+    /// Pagination cursor: empty for the first page, then the previous
+    /// response's `next_cursor`. Results are ordered by report id, newest first
+    #[param(example = "")]
+    pub cursor: Option<String>,
+}
+
+/// Deserialize an optional integer query parameter.
+///
+/// Query strings carry every value as text. Once `ListQuery` is flattened
+/// into another query struct, serde no longer parses those values as
+/// numbers, so both forms are accepted. An empty value means unset.
+fn optional_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Number(i64),
+        Text(String),
+    }
+
+    match Option::<Value>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Value::Number(n)) => Ok(Some(n)),
+        Some(Value::Text(s)) if s.is_empty() => Ok(None),
+        Some(Value::Text(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
+    }
 }
 
 impl ListQuery {
@@ -51,8 +89,11 @@ impl ListQuery {
             image: self.image.clone(),
             cve: self.cve.clone(),
             component: self.component.clone(),
+            min_components: self.min_components,
+            max_components: self.max_components,
             limit: self.limit,
             offset: self.offset,
+            cursor: self.cursor.clone(),
         }
     }
 }
@@ -118,6 +159,9 @@ pub struct ListResponse<T: ToSchema> {
     pub items: Vec<T>,
     /// Total count
     pub total: usize,
+    /// Cursor for the next page; only set when paginating with `cursor`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Error response
@@ -311,6 +355,72 @@ impl TrendQuery {
     }
 }
 
+/// Query parameters for the SBOM diff endpoint
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SbomDiffQuery {
+    /// Cluster name
+    #[param(example = "prod-cluster")]
+    pub cluster: String,
+    /// Kubernetes namespace
+    #[param(example = "default")]
+    pub namespace: String,
+    /// SBOM report name
+    #[param(example = "replicaset-nginx-6d4cf56db6-nginx")]
+    pub name: String,
+    /// Baseline timestamp (RFC 3339); the latest scan at or before it is used
+    #[param(example = "2026-01-01T00:00:00Z")]
+    pub from: String,
+    /// Comparison timestamp (RFC 3339); the latest scan at or before it is used
+    #[param(example = "2026-02-01T00:00:00Z")]
+    pub to: String,
+}
+
+/// Query parameters for the severity trend endpoint
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SeverityTrendQuery {
+    /// Cluster name
+    #[param(example = "prod-cluster")]
+    pub cluster: String,
+    /// Number of days to return, ending today (default: 30, max: 365).
+    /// Days older than the server's history retention have incomplete data.
+    #[param(example = 30)]
+    pub days: Option<u32>,
+}
+
+impl SeverityTrendQuery {
+    /// Get the day count with default and upper bound applied
+    pub fn get_days(&self) -> u32 {
+        self.days.unwrap_or(30).clamp(1, 365)
+    }
+}
+
+/// Query parameters for the namespace summary endpoint
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NamespaceSummaryQuery {
+    /// Cluster name
+    #[param(example = "prod-cluster")]
+    pub cluster: String,
+}
+
+/// Query parameters for the top vulnerable images endpoint
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TopImagesQuery {
+    /// Number of images to return (default: 20, max: 100)
+    #[param(example = 20)]
+    pub limit: Option<i64>,
+}
+
+impl TopImagesQuery {
+    /// Get the image count with default and upper bound applied
+    pub fn get_limit(&self) -> i64 {
+        self.limit.unwrap_or(20).clamp(1, 100)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,8 +435,11 @@ mod tests {
             image: None,
             cve: None,
             component: None,
+            min_components: None,
+            max_components: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
 
         let params = query.to_query_params();
@@ -338,6 +451,7 @@ mod tests {
         assert!(params.cve.is_none());
         assert!(params.limit.is_none());
         assert!(params.offset.is_none());
+        assert!(params.cursor.is_none());
     }
 
     #[test]
@@ -350,8 +464,11 @@ mod tests {
             image: Some("nginx:1.25".to_string()),
             cve: Some("CVE-2024-1234".to_string()),
             component: None,
+            min_components: Some(10),
+            max_components: Some(500),
             limit: Some(100),
             offset: Some(50),
+            cursor: Some("MTA".to_string()),
         };
 
         let params = query.to_query_params();
@@ -360,8 +477,11 @@ mod tests {
         assert_eq!(params.app, Some("nginx".to_string()));
         assert_eq!(params.image, Some("nginx:1.25".to_string()));
         assert_eq!(params.cve, Some("CVE-2024-1234".to_string()));
+        assert_eq!(params.min_components, Some(10));
+        assert_eq!(params.max_components, Some(500));
         assert_eq!(params.limit, Some(100));
         assert_eq!(params.offset, Some(50));
+        assert_eq!(params.cursor.as_deref(), Some("MTA"));
     }
 
     #[test]
@@ -374,8 +494,11 @@ mod tests {
             image: None,
             cve: None,
             component: None,
+            min_components: None,
+            max_components: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
 
         let params = query.to_query_params();
@@ -396,8 +519,11 @@ mod tests {
             image: None,
             cve: None,
             component: None,
+            min_components: None,
+            max_components: None,
             limit: None,
             offset: None,
+            cursor: None,
         };
 
         let params = query.to_query_params();
@@ -517,4 +643,25 @@ mod tests {
         let item = ConfigItem::sensitive("PIN", "1234");
         assert_eq!(item.value, "****");
     }
+
+    #[test]
+    fn test_severity_trend_query_days() {
+        let query = |days| SeverityTrendQuery {
+            cluster: "prod".to_string(),
+            days,
+        };
+        assert_eq!(query(None).get_days(), 30);
+        assert_eq!(query(Some(7)).get_days(), 7);
+        assert_eq!(query(Some(0)).get_days(), 1);
+        assert_eq!(query(Some(1000)).get_days(), 365);
+    }
+
+    #[test]
+    fn test_top_images_query_limit() {
+        let query = |limit| TopImagesQuery { limit };
+        assert_eq!(query(None).get_limit(), 20);
+        assert_eq!(query(Some(5)).get_limit(), 5);
+        assert_eq!(query(Some(0)).get_limit(), 1);
+        assert_eq!(query(Some(1000)).get_limit(), 100);
+    }
 }
@@ -299,7 +299,7 @@ async fn handle_vuln_event(
                 return Ok(());
             }
 
-            db.delete_report(cluster_name, namespace, name, "vulnerabilityreport")
+            db.delete_report(cluster_name, namespace, name, "vulnerabilityreport", None)
                 .await?;
 
             info!(
@@ -375,7 +375,7 @@ async fn handle_sbom_event(
                 return Ok(());
             }
 
-            db.delete_report(cluster_name, namespace, name, "sbomreport")
+            db.delete_report(cluster_name, namespace, name, "sbomreport", None)
                 .await?;
 
             info!(
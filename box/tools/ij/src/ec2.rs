@@ -1,5 +1,8 @@
 //! EC2 instance discovery and management.
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aws_sdk_ec2::primitives::DateTime;
 use aws_sdk_ec2::types::Filter;
 use tabled::Tabled;
 use tracing::{debug, warn};
@@ -43,6 +46,19 @@ pub fn format_age(secs: u64) -> String {
     }
 }
 
+/// Age of an instance from its EC2 `LaunchTime`, or "-" when unknown.
+///
+/// A launch time ahead of `now` (clock skew) is shown as "0s".
+pub fn launch_age(launch_time: Option<&DateTime>, now: SystemTime) -> String {
+    launch_time
+        .and_then(|lt| {
+            let now = now.duration_since(UNIX_EPOCH).ok()?.as_secs();
+            let launched = u64::try_from(lt.secs()).ok()?;
+            Some(format_age(now.saturating_sub(launched)))
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
 impl Instance {
     /// Extract region from AZ (e.g., "ap-northeast-2a" → "ap-northeast-2").
     pub fn region(&self) -> &str {
@@ -252,6 +268,7 @@ async fn fetch_region_instances_with_config(
         }
     };
 
+    let now = SystemTime::now();
     let instances = resp
         .reservations()
         .iter()
@@ -276,17 +293,7 @@ async fn fetch_region_instances_with_config(
                 .unwrap_or(region)
                 .to_string(),
             private_ip: i.private_ip_address().unwrap_or("N/A").to_string(),
-            age: i
-                .launch_time()
-                .and_then(|lt| {
-                    let now = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .ok()?
-                        .as_secs();
-                    let launched = u64::try_from(lt.secs()).ok()?;
-                    Some(format_age(now.saturating_sub(launched)))
-                })
-                .unwrap_or_else(|| "-".to_string()),
+            age: launch_age(i.launch_time(), now),
             platform: i
                 .platform()
                 .map(|p| p.as_str())
@@ -733,4 +740,23 @@ mod tests {
         assert_eq!(format_age(86400), "1d");
         assert_eq!(format_age(86400 * 365), "365d");
     }
+
+    // --- launch_age tests ---
+
+    #[test]
+    fn launch_age_from_launch_time() {
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let just_launched = DateTime::from_secs(1_700_000_000 - 90);
+        let days_ago = DateTime::from_secs(1_700_000_000 - 3 * 86400 - 7200);
+        assert_eq!(launch_age(Some(&just_launched), now), "1m");
+        assert_eq!(launch_age(Some(&days_ago), now), "3d");
+    }
+
+    #[test]
+    fn launch_age_unknown_or_in_future() {
+        let now = UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let future = DateTime::from_secs(1_700_000_060);
+        assert_eq!(launch_age(Some(&future), now), "0s");
+        assert_eq!(launch_age(None, now), "-");
+    }
 }
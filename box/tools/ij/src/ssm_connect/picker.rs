@@ -82,6 +82,57 @@ impl PickerState {
     }
 }
 
+/// One rendered row of the picker list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PickerRow<'a> {
+    /// Non-selectable region separator with its instance count.
+    Region(&'a str, usize),
+    /// Selectable instance, as a position in `filtered_indices`.
+    Item(usize),
+}
+
+/// Build the rendered rows for the current filter state.
+///
+/// Without a query the instances are listed in scan order (region, then
+/// name), so a region header is inserted before each region group when more
+/// than one region is present. Fuzzy results are ranked by score and are
+/// listed without headers.
+pub(crate) fn build_rows<'a>(state: &PickerState, instances: &'a [Instance]) -> Vec<PickerRow<'a>> {
+    let items = (0..state.filtered_indices.len()).map(PickerRow::Item);
+    let first_region = state
+        .filtered_indices
+        .first()
+        .map(|&(idx, _)| instances[idx].region());
+    let grouped = state.query.is_empty()
+        && state
+            .filtered_indices
+            .iter()
+            .any(|&(idx, _)| Some(instances[idx].region()) != first_region);
+    if !grouped {
+        return items.collect();
+    }
+
+    let mut rows = Vec::with_capacity(state.filtered_indices.len() + 8);
+    let mut pos = 0;
+    while pos < state.filtered_indices.len() {
+        let region = instances[state.filtered_indices[pos].0].region();
+        let len = state.filtered_indices[pos..]
+            .iter()
+            .take_while(|&&(idx, _)| instances[idx].region() == region)
+            .count();
+        rows.push(PickerRow::Region(region, len));
+        rows.extend((pos..pos + len).map(PickerRow::Item));
+        pos += len;
+    }
+    rows
+}
+
+/// Position of the selected instance within `rows`, skipping header rows.
+pub(crate) fn selected_row(rows: &[PickerRow], selected: usize) -> Option<usize> {
+    rows.iter()
+        .position(|row| matches!(row, PickerRow::Item(pos) if *pos == selected))
+}
+
 /// Update filtered indices based on current query.
 pub(crate) fn update_filter(items: &[String], state: &mut PickerState, matcher: &mut Matcher) {
     if state.query.is_empty() {
@@ -175,27 +226,34 @@ pub(crate) fn draw_picker(
     frame.render_widget(Paragraph::new(header), chunks[1]);
 
     // List
-    let list_items: Vec<ListItem> = state
-        .filtered_indices
+    let rows = build_rows(state, instances);
+    let list_items: Vec<ListItem> = rows
         .iter()
-        .enumerate()
-        .map(|(i, &(idx, _))| {
-            let content = &items[idx];
-            let style = if i == state.selected {
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-            } else {
+        .map(|row| match *row {
+            PickerRow::Region(region, count) => ListItem::new(Line::from(Span::styled(
+                format!("── {region} ({count})"),
                 Style::default()
-            };
-            ListItem::new(Line::from(Span::styled(content.clone(), style)))
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::DIM),
+            ))),
+            PickerRow::Item(pos) => {
+                let content = &items[state.filtered_indices[pos].0];
+                let style = if pos == state.selected {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(Span::styled(content.clone(), style)))
+            }
         })
         .collect();
 
     let list = List::new(list_items).highlight_symbol("> ");
 
     let mut list_state = ListState::default();
-    list_state.select(Some(state.selected));
+    list_state.select(selected_row(&rows, state.selected));
 
     frame.render_stateful_widget(list, chunks[2], &mut list_state);
 
@@ -625,6 +683,108 @@ mod tests {
         }
     }
 
+    // --- build_rows / selected_row tests ---
+
+    fn instance_in(name: &str, az: &str) -> Instance {
+        Instance {
+            name: name.into(),
+            instance_id: format!("i-{name}"),
+            instance_type: "t3.micro".into(),
+            state: "running".into(),
+            az: az.into(),
+            private_ip: "10.0.0.1".into(),
+            platform: "Linux".into(),
+            age: "1d".into(),
+        }
+    }
+
+    /// Instances in scan order: sorted by AZ, then name.
+    fn multi_region_instances() -> Vec<Instance> {
+        vec![
+            instance_in("bastion", "ap-northeast-2a"),
+            instance_in("web", "ap-northeast-2c"),
+            instance_in("batch", "eu-west-1b"),
+            instance_in("api", "us-east-1a"),
+            instance_in("worker", "us-east-1d"),
+        ]
+    }
+
+    #[test]
+    fn build_rows_interleaves_region_headers() {
+        let instances = multi_region_instances();
+        let state = PickerState::new(instances.len());
+
+        let rows = build_rows(&state, &instances);
+        assert_eq!(
+            rows,
+            vec![
+                PickerRow::Region("ap-northeast-2", 2),
+                PickerRow::Item(0),
+                PickerRow::Item(1),
+                PickerRow::Region("eu-west-1", 1),
+                PickerRow::Item(2),
+                PickerRow::Region("us-east-1", 2),
+                PickerRow::Item(3),
+                PickerRow::Item(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn selected_row_maps_back_to_instance() {
+        let instances = multi_region_instances();
+        let mut state = PickerState::new(instances.len());
+        let rows = build_rows(&state, &instances);
+
+        // Every selectable position lands on its own instance, never a header
+        for selected in 0..instances.len() {
+            let row = selected_row(&rows, selected).unwrap();
+            let PickerRow::Item(pos) = rows[row] else {
+                panic!("selected row {row} is a header");
+            };
+            let (idx, _) = state.filtered_indices[pos];
+            assert_eq!(idx, selected);
+        }
+        assert_eq!(selected_row(&rows, 2), Some(4));
+        assert_eq!(selected_row(&rows, 4), Some(7));
+
+        // Navigation skips headers: moving down from the last ap-northeast-2
+        // instance selects the first eu-west-1 instance
+        state.selected = 1;
+        state.move_down();
+        assert_eq!(
+            instances[state.filtered_indices[state.selected].0].name,
+            "batch"
+        );
+    }
+
+    #[test]
+    fn build_rows_without_headers_for_query_or_single_region() {
+        let instances = multi_region_instances();
+        let mut state = PickerState::new(instances.len());
+        let (items, _) = make_items(&instances);
+        let mut matcher = new_matcher();
+        state.query = "b".to_string();
+        update_filter(&items, &mut state, &mut matcher);
+        let rows = build_rows(&state, &instances);
+        assert!(rows.iter().all(|r| matches!(r, PickerRow::Item(_))));
+        assert_eq!(rows.len(), state.filtered_indices.len());
+
+        let single = vec![
+            instance_in("a", "us-east-1a"),
+            instance_in("b", "us-east-1b"),
+        ];
+        let state = PickerState::new(single.len());
+        assert_eq!(
+            build_rows(&state, &single),
+            vec![PickerRow::Item(0), PickerRow::Item(1)]
+        );
+        assert_eq!(
+            selected_row(&build_rows(&PickerState::new(0), &[]), 0),
+            None
+        );
+    }
+
     // --- draw tests ---
 
     #[test]